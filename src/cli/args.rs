@@ -1,10 +1,341 @@
+use std::path::PathBuf;
+
+use crate::models::config::{CommandCosts, GameConfig};
+use crate::models::constants::{SymbolSet, TORPEDO_SPLASH_DAMAGE};
+use crate::models::difficulty::Difficulty;
+use crate::models::director::DirectorBounds;
+use crate::models::galaxy::{GalaxyOptions, MirrorAxis, SeedConstraints};
+use crate::models::ruleset::{AutoShieldPolicy, MissionDuration, Ruleset};
+use crate::models::scenarios::{Scenario, SCENARIOS};
+
+#[cfg(feature = "scenario-files")]
+use super::scenario_file::ScenarioFile;
+use super::simulate::{SimulateOptions, DEFAULT_GAMES};
+
 pub struct Args {
     pub seed: Option<u64>,
+    /// Reflection applied to the galaxy, for racing on mirrored pairs.
+    pub mirror: MirrorAxis,
+    /// Mission duration rule.
+    pub mission_duration: MissionDuration,
+    /// Start with the fleet HQ long range sensor upgrade.
+    pub extended_lrs: bool,
+    /// Raise shields automatically on entering hostile quadrants.
+    pub auto_shields: bool,
+    /// Klingons migrate toward starbases.
+    pub migrating_klingons: bool,
+    /// Klingon reinforcements arrive on a timer.
+    pub reinforcement_waves: bool,
+    /// Klingons attack starbases.
+    pub starbase_attacks: bool,
+    /// Torpedo blasts damage Klingons next to the target.
+    pub torpedo_splash: bool,
+    /// Starbases offer full repairs on docking.
+    pub docking_repairs: bool,
+    /// A few Klingon groups are led by commanders.
+    pub klingon_commanders: bool,
+    /// Cloaked Romulans lurk in a few quadrants.
+    pub romulans: bool,
+    /// Black holes form in some quadrants.
+    pub black_holes: bool,
+    /// Tholians web off some quadrants.
+    pub tholians: bool,
+    /// Escape a destroyed ship in the shuttlecraft.
+    pub shuttlecraft: bool,
+    /// Mines are sown in some quadrants.
+    pub minefields: bool,
+    /// Heavy casualties slow repairs and weaken phasers.
+    pub crew_morale: bool,
+    /// Distant Klingons dodge torpedoes.
+    pub torpedo_dodging: bool,
+    /// Phaser fire heats the banks.
+    pub phaser_heat: bool,
+    /// Raised shields drain main power.
+    pub shield_drain: bool,
+    /// Steering into a Klingon rams it.
+    pub ramming: bool,
+    /// Destroyed Klingons leave debris.
+    pub debris: bool,
+    /// Quadrants keep their layout between visits.
+    pub persistent_quadrants: bool,
+    /// Klingon shields regenerate over time.
+    pub klingon_regeneration: bool,
+    /// Scans, combat, and shield changes take time too.
+    pub strict_clock: bool,
+    /// Adaptive difficulty director.
+    pub adaptive: bool,
+    /// Difficulty level; asked for at startup when not given.
+    pub difficulty: Option<Difficulty>,
+    /// Allow UNDO of the last move or shield change with no enemy ships present.
+    pub undo: bool,
+    /// Use the original game's wording.
+    pub classic: bool,
+    /// Never color the output, even on a terminal.
+    pub no_color: bool,
+    /// Symbols the short range scan draws sectors with.
+    pub symbols: SymbolSet,
+    /// Record every RNG draw and write the log when the game ends.
+    pub dev_rng_log: bool,
+    /// Write a replay of the game to this file when it ends.
+    pub record: Option<PathBuf>,
+    /// Play back a replay file before handing over to the keyboard.
+    pub replay: Option<PathBuf>,
+    /// Write the game's events to this file as JSON Lines when it ends.
+    pub export_events: Option<PathBuf>,
+    /// Read every command and answer from this file instead of the keyboard.
+    pub script: Option<PathBuf>,
+    /// Write the game's output to this file instead of the terminal.
+    pub output: Option<PathBuf>,
+    /// Play back a replay even if it was recorded with incompatible logic.
+    pub force: bool,
+    /// Play a sealed tournament game under the classic rules.
+    pub tournament: bool,
+    /// Play a curated starting setup instead of a generated galaxy.
+    pub scenario: Option<&'static Scenario>,
+    /// Play a mission loaded from a TOML or JSON file.
+    #[cfg(feature = "scenario-files")]
+    pub scenario_file: Option<ScenarioFile>,
+    /// Print the local high score table instead of playing.
+    pub scores: bool,
+    /// Let the autoplayer play this many games and print its results.
+    pub autoplay: Option<u32>,
+    /// Run a balance simulation (`simulate`) instead of playing.
+    pub simulate: Option<SimulateOptions>,
+    /// Search for a seed whose galaxy meets these constraints.
+    pub find_seed: Option<SeedConstraints>,
+    /// Play on the full-screen terminal frontend.
+    #[cfg(feature = "tui")]
+    pub tui: bool,
+    /// Leaderboard URL that end-of-game results are posted to.
+    #[cfg(feature = "net")]
+    pub submit_url: Option<String>,
+    /// Address to serve the REST API on (`serve --http`) instead of playing.
+    #[cfg(feature = "net")]
+    pub serve_http: Option<String>,
+}
+
+impl Args {
+    /// The variant rules selected by the flags.
+    pub fn ruleset(&self) -> Ruleset {
+        Ruleset {
+            mission_duration: self.mission_duration,
+            extended_long_range_sensors: self.extended_lrs,
+            auto_shields: self.auto_shields.then(AutoShieldPolicy::default),
+            klingon_migration: self.migrating_klingons,
+            reinforcement_waves: self.reinforcement_waves,
+            starbase_attacks: self.starbase_attacks,
+            torpedo_splash: self.torpedo_splash.then_some(TORPEDO_SPLASH_DAMAGE),
+            docking_repairs: self.docking_repairs,
+            klingon_commanders: self.klingon_commanders,
+            romulans: self.romulans,
+            black_holes: self.black_holes,
+            tholians: self.tholians,
+            shuttlecraft: self.shuttlecraft,
+            minefields: self.minefields,
+            crew_morale: self.crew_morale,
+            torpedo_dodging: self.torpedo_dodging,
+            phaser_heat: self.phaser_heat,
+            shield_drain: self.shield_drain,
+            ramming: self.ramming,
+            debris: self.debris,
+            persistent_quadrants: self.persistent_quadrants,
+            klingon_regeneration: self.klingon_regeneration,
+            director: self.adaptive.then(DirectorBounds::default),
+            difficulty: self.difficulty.unwrap_or_default(),
+        }
+    }
+
+    pub fn galaxy_options(&self) -> GalaxyOptions {
+        GalaxyOptions {
+            mirror: self.mirror,
+            ruleset: self.ruleset(),
+            config: GameConfig {
+                command_costs: if self.strict_clock { CommandCosts::STRICT } else { CommandCosts::FREE },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// The flags that change how the game plays, as they would be typed.
+    /// Replays store these so the game can be rebuilt.
+    pub fn logic_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        match self.mirror {
+            MirrorAxis::None => {}
+            MirrorAxis::Horizontal => flags.extend(["--mirror", "h"]),
+            MirrorAxis::Vertical => flags.extend(["--mirror", "v"]),
+            MirrorAxis::Both => flags.extend(["--mirror", "hv"]),
+        }
+        if self.mission_duration == MissionDuration::SCALED {
+            flags.push("--scaled-duration");
+        }
+        if self.extended_lrs {
+            flags.push("--extended-lrs");
+        }
+        if self.auto_shields {
+            flags.push("--auto-shields");
+        }
+        if self.migrating_klingons {
+            flags.push("--migrating-klingons");
+        }
+        if self.reinforcement_waves {
+            flags.push("--reinforcement-waves");
+        }
+        if self.starbase_attacks {
+            flags.push("--starbase-attacks");
+        }
+        if self.torpedo_splash {
+            flags.push("--torpedo-splash");
+        }
+        if self.docking_repairs {
+            flags.push("--docking-repairs");
+        }
+        if self.klingon_commanders {
+            flags.push("--klingon-commanders");
+        }
+        if self.romulans {
+            flags.push("--romulans");
+        }
+        if self.black_holes {
+            flags.push("--black-holes");
+        }
+        if self.tholians {
+            flags.push("--tholians");
+        }
+        if self.shuttlecraft {
+            flags.push("--shuttlecraft");
+        }
+        if self.minefields {
+            flags.push("--minefields");
+        }
+        if self.crew_morale {
+            flags.push("--crew-morale");
+        }
+        if self.torpedo_dodging {
+            flags.push("--torpedo-dodging");
+        }
+        if self.phaser_heat {
+            flags.push("--phaser-heat");
+        }
+        if self.shield_drain {
+            flags.push("--shield-drain");
+        }
+        if self.ramming {
+            flags.push("--ramming");
+        }
+        if self.debris {
+            flags.push("--debris");
+        }
+        if self.persistent_quadrants {
+            flags.push("--persistent-quadrants");
+        }
+        if self.klingon_regeneration {
+            flags.push("--klingon-regeneration");
+        }
+        if self.strict_clock {
+            flags.push("--strict-clock");
+        }
+        if self.adaptive {
+            flags.push("--adaptive");
+        }
+        let difficulty = self.difficulty.unwrap_or_default();
+        if difficulty != Difficulty::default() {
+            flags.extend(["--difficulty", difficulty.label()]);
+        }
+        if self.undo {
+            flags.push("--undo");
+        }
+        if self.tournament {
+            flags.push("--tournament");
+        }
+        let mut flags: Vec<String> = flags.into_iter().map(str::to_string).collect();
+        if let Some(scenario) = self.scenario {
+            flags.extend(["--scenario".to_string(), scenario.key()]);
+        }
+        #[cfg(feature = "scenario-files")]
+        if let Some(file) = &self.scenario_file {
+            flags.extend(["--scenario-file".to_string(), file.path.display().to_string()]);
+        }
+        flags
+    }
+
+    /// Whether a scenario, built in or from a file, is played instead of
+    /// a generated galaxy.
+    pub fn plays_scenario(&self) -> bool {
+        #[cfg(feature = "scenario-files")]
+        if self.scenario_file.is_some() {
+            return true;
+        }
+        self.scenario.is_some()
+    }
 }
 
 pub fn parse() -> Args {
-    let mut args = Args { seed: None };
-    let mut iter = std::env::args().skip(1);
+    parse_from(std::env::args().skip(1))
+}
+
+/// Parse arguments (without the program name). Prints usage errors and
+/// exits, like the command line does.
+pub fn parse_from(arguments: impl IntoIterator<Item = String>) -> Args {
+    let mut args = Args {
+        seed: None,
+        mirror: MirrorAxis::None,
+        mission_duration: MissionDuration::default(),
+        extended_lrs: false,
+        auto_shields: false,
+        migrating_klingons: false,
+        reinforcement_waves: false,
+        starbase_attacks: false,
+        torpedo_splash: false,
+        docking_repairs: false,
+        klingon_commanders: false,
+        romulans: false,
+        black_holes: false,
+        tholians: false,
+        shuttlecraft: false,
+        minefields: false,
+        crew_morale: false,
+        torpedo_dodging: false,
+        phaser_heat: false,
+        shield_drain: false,
+        ramming: false,
+        debris: false,
+        persistent_quadrants: false,
+        klingon_regeneration: false,
+        strict_clock: false,
+        adaptive: false,
+        difficulty: None,
+        undo: false,
+        classic: false,
+        no_color: false,
+        symbols: SymbolSet::Classic,
+        dev_rng_log: false,
+        record: None,
+        replay: None,
+        export_events: None,
+        script: None,
+        output: None,
+        force: false,
+        tournament: false,
+        scenario: None,
+        #[cfg(feature = "scenario-files")]
+        scenario_file: None,
+        scores: false,
+        autoplay: None,
+        simulate: None,
+        find_seed: None,
+        #[cfg(feature = "tui")]
+        tui: false,
+        #[cfg(feature = "net")]
+        submit_url: None,
+        #[cfg(feature = "net")]
+        serve_http: None,
+    };
+    #[cfg(feature = "net")]
+    let (mut serve, mut http) = (false, false);
+    let (mut simulate, mut games, mut threads, mut csv) = (false, None, None, false);
+    let mut iter = arguments.into_iter();
 
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -19,11 +350,217 @@ pub fn parse() -> Args {
                     std::process::exit(1);
                 }
             }
+            "--mirror" => {
+                args.mirror = match iter.next().as_deref() {
+                    Some("h") => MirrorAxis::Horizontal,
+                    Some("v") => MirrorAxis::Vertical,
+                    Some("hv") => MirrorAxis::Both,
+                    _ => {
+                        eprintln!("Error: --mirror requires h, v, or hv");
+                        std::process::exit(1);
+                    }
+                };
+            }
+            "--scaled-duration" => args.mission_duration = MissionDuration::SCALED,
+            "--extended-lrs" => args.extended_lrs = true,
+            "--auto-shields" => args.auto_shields = true,
+            "--migrating-klingons" => args.migrating_klingons = true,
+            "--reinforcement-waves" => args.reinforcement_waves = true,
+            "--starbase-attacks" => args.starbase_attacks = true,
+            "--torpedo-splash" => args.torpedo_splash = true,
+            "--docking-repairs" => args.docking_repairs = true,
+            "--klingon-commanders" => args.klingon_commanders = true,
+            "--romulans" => args.romulans = true,
+            "--black-holes" => args.black_holes = true,
+            "--tholians" => args.tholians = true,
+            "--shuttlecraft" => args.shuttlecraft = true,
+            "--minefields" => args.minefields = true,
+            "--crew-morale" => args.crew_morale = true,
+            "--torpedo-dodging" => args.torpedo_dodging = true,
+            "--phaser-heat" => args.phaser_heat = true,
+            "--shield-drain" => args.shield_drain = true,
+            "--ramming" => args.ramming = true,
+            "--debris" => args.debris = true,
+            "--persistent-quadrants" => args.persistent_quadrants = true,
+            "--klingon-regeneration" => args.klingon_regeneration = true,
+            "--strict-clock" => args.strict_clock = true,
+            "--adaptive" => args.adaptive = true,
+            "--difficulty" => {
+                match iter.next().as_deref().and_then(Difficulty::parse) {
+                    Some(level) => args.difficulty = Some(level),
+                    None => {
+                        eprintln!("Error: --difficulty requires novice, captain, or admiral");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--undo" => args.undo = true,
+            "--classic" => args.classic = true,
+            "--no-color" => args.no_color = true,
+            "--symbols" => {
+                match iter.next().as_deref().and_then(SymbolSet::parse) {
+                    Some(symbols) => args.symbols = symbols,
+                    None => {
+                        eprintln!("Error: --symbols requires classic, unicode, or compact");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--dev-rng-log" => args.dev_rng_log = true,
+            "--record" | "--replay" | "--export-events" | "--script" | "--output" => {
+                let Some(val) = iter.next() else {
+                    eprintln!("Error: {} requires a file", arg);
+                    std::process::exit(1);
+                };
+                let path = Some(PathBuf::from(val));
+                match arg.as_str() {
+                    "--record" => args.record = path,
+                    "--replay" => args.replay = path,
+                    "--export-events" => args.export_events = path,
+                    "--script" => args.script = path,
+                    _ => args.output = path,
+                }
+            }
+            "--force" => args.force = true,
+            "--tournament" => args.tournament = true,
+            "--scenario" => match iter.next().as_deref().and_then(Scenario::find) {
+                Some(scenario) => args.scenario = Some(scenario),
+                None => {
+                    let names: Vec<String> = SCENARIOS.iter().map(Scenario::key).collect();
+                    eprintln!("Error: --scenario requires one of {}", names.join(", "));
+                    std::process::exit(1);
+                }
+            },
+            #[cfg(feature = "scenario-files")]
+            "--scenario-file" => match iter.next() {
+                Some(path) => match ScenarioFile::load(std::path::Path::new(&path)) {
+                    Ok(file) => args.scenario_file = Some(file),
+                    Err(e) => {
+                        eprintln!("Error: cannot load scenario file {}: {}", path, e);
+                        std::process::exit(1);
+                    }
+                },
+                None => {
+                    eprintln!("Error: --scenario-file requires a path");
+                    std::process::exit(1);
+                }
+            },
+            "--scores" => args.scores = true,
+            "--autoplay" => match iter.next().map(|val| val.parse::<u32>()) {
+                Some(Ok(games)) if games > 0 => args.autoplay = Some(games),
+                _ => {
+                    eprintln!("Error: --autoplay requires a number of games");
+                    std::process::exit(1);
+                }
+            },
+            "--find-seed" => match iter.next().as_deref().and_then(SeedConstraints::parse) {
+                Some(constraints) => args.find_seed = Some(constraints),
+                None => {
+                    eprintln!("Error: --find-seed requires constraints such as klingons=15-20,starbases=3+");
+                    std::process::exit(1);
+                }
+            },
+            "simulate" => simulate = true,
+            "--games" => match iter.next().map(|val| val.parse::<u32>()) {
+                Some(Ok(n)) if n > 0 => games = Some(n),
+                _ => {
+                    eprintln!("Error: --games requires a number of games");
+                    std::process::exit(1);
+                }
+            },
+            "--threads" => match iter.next().map(|val| val.parse::<usize>()) {
+                Some(Ok(n)) if n > 0 => threads = Some(n),
+                _ => {
+                    eprintln!("Error: --threads requires a number of threads");
+                    std::process::exit(1);
+                }
+            },
+            "--csv" => csv = true,
+            #[cfg(feature = "tui")]
+            "--tui" => args.tui = true,
+            #[cfg(feature = "net")]
+            "--submit-url" => {
+                if let Some(val) = iter.next() {
+                    args.submit_url = Some(val);
+                } else {
+                    eprintln!("Error: --submit-url requires a value");
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(feature = "net")]
+            "serve" => serve = true,
+            #[cfg(feature = "net")]
+            "--http" => http = true,
+            #[cfg(feature = "net")]
+            "--listen" => {
+                if let Some(val) = iter.next() {
+                    args.serve_http = Some(val);
+                } else {
+                    eprintln!("Error: --listen requires an address");
+                    std::process::exit(1);
+                }
+            }
             "--help" | "-h" => {
                 println!("Usage: startrek [OPTIONS]");
+                #[cfg(feature = "net")]
+                println!("       startrek serve --http [--listen <ADDR>]");
+                println!("       startrek simulate [--games <N>] [--threads <N>] [--csv] [OPTIONS]");
                 println!();
                 println!("Options:");
                 println!("  -s, --seed <INT>  Seed for the random number generator");
+                println!("  --mirror <h|v|hv> Reflect the galaxy (racers share a seed, not a map)");
+                println!("  --scaled-duration Mission lasts max(Klingons + 1, 30) stardates");
+                println!("  --extended-lrs    Start with 5x5 long range scans");
+                println!("  --auto-shields    Raise shields on entering hostile quadrants");
+                println!("  --migrating-klingons Klingons close in on starbases over time");
+                println!("  --reinforcement-waves A new Klingon warps in every 5 stardates");
+                println!("  --starbase-attacks Klingons besiege starbases you must relieve");
+                println!("  --torpedo-splash  Torpedo blasts damage Klingons next to the target");
+                println!("  --docking-repairs Starbases offer to repair every device on docking");
+                println!("  --klingon-commanders Heavily shielded commanders lead some Klingon groups");
+                println!("  --romulans        Cloaked Romulans lurk unseen until they fire");
+                println!("  --black-holes     Black holes swallow torpedoes and fling the ship");
+                println!("  --tholians        Tholians spin a web around the quadrant's edge");
+                println!("  --shuttlecraft    Escape a destroyed ship in the shuttlecraft");
+                println!("  --minefields      Hidden mines drain the shields of ships that hit them");
+                println!("  --crew-morale     Heavy casualties slow repairs and weaken phasers");
+                println!("  --torpedo-dodging Distant Klingons may dodge torpedoes");
+                println!("  --phaser-heat     Phasers lose power as they heat and may overload");
+                println!("  --shield-drain    Raised shields draw on main power over time");
+                println!("  --ramming         Steer into a Klingon to ram it, at great cost");
+                println!("  --debris          Destroyed Klingons leave debris that slows ships and torpedoes");
+                println!("  --persistent-quadrants Quadrants look the same when you come back");
+                println!("  --klingon-regeneration Klingon shields recover as time passes");
+                println!("  --strict-clock    Scans, combat, and shield changes take time");
+                println!("  --adaptive        Reinforcements and Klingon fire follow your pace");
+                println!("  --difficulty <novice|captain|admiral> Difficulty level (asked at startup if omitted)");
+                println!("  --undo            Allow UNDO of the last move or shield change");
+                println!("  --classic         Use the original game's wording");
+                println!("  --no-color        Plain text even on a color terminal");
+                println!("  --symbols <classic|unicode|compact> Sector symbols on the short range scan");
+                println!("  --dev-rng-log     Write every RNG draw to rng-<seed>.log");
+                println!("  --record <FILE>   Save a replay of the game to FILE");
+                println!("  --replay <FILE>   Play back a replay, then continue the game");
+                println!("  --export-events <FILE> Write the game's events to FILE as JSON Lines");
+                println!("  --script <FILE>   Read every command from FILE; the game ends with it (needs --seed)");
+                println!("  --output <FILE>   Write the game's output to FILE");
+                println!("  --force           Play back a replay from an incompatible build");
+                println!("  --tournament      Sealed game under the classic rules, no dev tools");
+                println!("  --scenario <NAME> Play a curated setup: training-cruise, outnumbered, last-starbase");
+                #[cfg(feature = "scenario-files")]
+                println!("  --scenario-file <FILE> Play a mission described in a TOML or JSON file");
+                println!("  --scores          Print the local high score table and exit");
+                println!("  --autoplay <N>    Let the computer play N games from the seed and print its win rate");
+                println!("  --find-seed <CONSTRAINTS> Find a seed, such as klingons=15-20,starbases=3+");
+                println!("  --games <N>       Games for simulate to play (default {})", DEFAULT_GAMES);
+                println!("  --threads <N>     Threads for simulate (default one per core)");
+                println!("  --csv             Print the simulate summary as CSV");
+                #[cfg(feature = "tui")]
+                println!("  --tui             Play on a full-screen display (no replays)");
+                #[cfg(feature = "net")]
+                println!("  --submit-url <URL> Post end-of-game results to a leaderboard");
+                #[cfg(feature = "net")]
+                println!("  --listen <ADDR>   Address for serve --http (default 127.0.0.1:8080)");
                 println!("  -h, --help        Print help");
                 std::process::exit(0);
             }
@@ -34,5 +571,231 @@ pub fn parse() -> Args {
         }
     }
 
+    #[cfg(feature = "net")]
+    match (serve, http) {
+        (true, true) => {
+            let addr = args.serve_http.take();
+            args.serve_http = Some(addr.unwrap_or_else(|| crate::net::server::DEFAULT_LISTEN_ADDR.to_string()));
+        }
+        (true, false) => {
+            eprintln!("Error: serve requires --http");
+            std::process::exit(1);
+        }
+        (false, _) => {
+            if args.serve_http.is_some() || http {
+                eprintln!("Error: --http and --listen are only used with serve");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if simulate {
+        args.simulate = Some(SimulateOptions { games: games.unwrap_or(DEFAULT_GAMES), threads, csv });
+    } else if games.is_some() || threads.is_some() || csv {
+        eprintln!("Error: --games, --threads, and --csv are only used with simulate");
+        std::process::exit(1);
+    }
+
+    // A scenario made from a seed is played on that seed, and under the
+    // rules that generate the galaxy its briefing describes
+    if let Some(seed) = args.scenario.and_then(Scenario::seed) {
+        let key = args.scenario.unwrap().key();
+        if args.seed.is_some_and(|s| s != seed) {
+            eprintln!("Error: the {} scenario is played on seed {}", key, seed);
+            std::process::exit(1);
+        }
+        if args.ruleset() != Ruleset::default() || args.mirror != MirrorAxis::None {
+            eprintln!("Error: the {} scenario cannot be combined with variant rules or --mirror", key);
+            std::process::exit(1);
+        }
+        args.seed = Some(seed);
+    }
+
+    #[cfg(feature = "scenario-files")]
+    if let Some(file) = args.scenario_file.take() {
+        if args.scenario.is_some() {
+            eprintln!("Error: --scenario and --scenario-file cannot be combined");
+            std::process::exit(1);
+        }
+        if let Some(seed) = file.seed {
+            if args.seed.is_some_and(|s| s != seed) {
+                eprintln!("Error: the {} scenario is played on seed {}", file.name, seed);
+                std::process::exit(1);
+            }
+            args.seed = Some(seed);
+        }
+        file.rules.apply(&mut args);
+        args.scenario_file = Some(file);
+    }
+
+    if args.script.is_some() && (args.seed.is_none() || args.replay.is_some()) {
+        eprintln!("Error: --script requires --seed and cannot be combined with --replay");
+        std::process::exit(1);
+    }
+    #[cfg(feature = "tui")]
+    if args.tui && (args.script.is_some() || args.output.is_some()) {
+        eprintln!("Error: --script and --output cannot be used with --tui");
+        std::process::exit(1);
+    }
+
+    if args.tournament
+        && (args.ruleset() != Ruleset::default()
+            || args.mirror != MirrorAxis::None
+            || args.strict_clock
+            || args.dev_rng_log
+            || args.undo
+            || args.plays_scenario())
+    {
+        eprintln!(
+            "Error: --tournament cannot be combined with variant rules, --mirror, --strict-clock, --undo, a scenario or --dev-rng-log"
+        );
+        std::process::exit(1);
+    }
     args
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(arguments: &[&str]) -> Args {
+        parse_from(arguments.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn logic_flags_rebuild_the_same_rules() {
+        let args = parse_strs(&[
+            "--seed", "5", "--mirror", "hv", "--scaled-duration", "--auto-shields",
+            "--classic", "--no-color", "--symbols", "compact", "--record", "game.replay", "--export-events",
+            "game.jsonl",
+        ]);
+        assert_eq!(args.record, Some(PathBuf::from("game.replay")));
+        assert_eq!(args.export_events, Some(PathBuf::from("game.jsonl")));
+        assert!(args.no_color);
+        assert_eq!(args.symbols, SymbolSet::Compact);
+
+        let flags = args.logic_flags();
+        assert_eq!(flags, vec!["--mirror", "hv", "--scaled-duration", "--auto-shields"]);
+        let rebuilt = parse_from(flags);
+        assert_eq!(rebuilt.ruleset(), args.ruleset());
+        assert_eq!(rebuilt.mirror, args.mirror);
+    }
+
+    #[test]
+    fn difficulty_is_replayed_unless_it_is_the_default() {
+        let args = parse_strs(&["--difficulty", "admiral"]);
+        assert_eq!(args.ruleset().difficulty, Difficulty::Admiral);
+        let flags = args.logic_flags();
+        assert_eq!(flags, vec!["--difficulty", "ADMIRAL"]);
+        assert_eq!(parse_from(flags).ruleset(), args.ruleset());
+        assert!(parse_strs(&["--difficulty", "captain"]).logic_flags().is_empty());
+    }
+
+    #[test]
+    fn tournament_flag_is_replayed() {
+        let args = parse_strs(&["--tournament", "--classic"]);
+        assert_eq!(args.logic_flags(), vec!["--tournament"]);
+        assert!(parse_from(args.logic_flags()).tournament);
+    }
+
+    #[test]
+    fn undo_flag_is_replayed() {
+        let args = parse_strs(&["--undo"]);
+        assert_eq!(args.logic_flags(), vec!["--undo"]);
+        assert!(parse_from(args.logic_flags()).undo);
+    }
+
+    #[test]
+    fn strict_clock_sets_command_costs_and_is_replayed() {
+        let args = parse_strs(&["--strict-clock"]);
+        assert_eq!(args.galaxy_options().config.command_costs, CommandCosts::STRICT);
+        assert_eq!(args.logic_flags(), vec!["--strict-clock"]);
+        assert!(parse_from(args.logic_flags()).strict_clock);
+        assert_eq!(parse_strs(&[]).galaxy_options().config.command_costs, CommandCosts::FREE);
+    }
+
+    #[test]
+    fn autoplay_is_not_a_logic_flag() {
+        let args = parse_strs(&["--autoplay", "20", "--seed", "3"]);
+        assert_eq!(args.autoplay, Some(20));
+        assert!(args.logic_flags().is_empty());
+    }
+
+    #[test]
+    fn simulate_takes_its_options_and_the_rules() {
+        let args = parse_strs(&["simulate", "--games", "50", "--csv", "--strict-clock"]);
+        assert_eq!(args.simulate, Some(SimulateOptions { games: 50, threads: None, csv: true }));
+        assert!(args.strict_clock);
+        let args = parse_strs(&["simulate", "--threads", "2"]);
+        assert_eq!(args.simulate, Some(SimulateOptions { games: DEFAULT_GAMES, threads: Some(2), csv: false }));
+        assert_eq!(parse_strs(&[]).simulate, None);
+    }
+
+    #[test]
+    fn scenario_is_replayed_and_may_fix_the_seed() {
+        let args = parse_strs(&["--scenario", "Outnumbered"]);
+        assert_eq!(args.scenario.unwrap().name, "Outnumbered");
+        assert_eq!(args.seed, None);
+        assert_eq!(args.logic_flags(), vec!["--scenario", "outnumbered"]);
+        assert_eq!(parse_from(args.logic_flags()).scenario.unwrap().name, "Outnumbered");
+
+        let args = parse_strs(&["--scenario", "training-cruise"]);
+        assert_eq!(args.seed, args.scenario.unwrap().seed());
+        assert!(args.seed.is_some());
+    }
+
+    #[cfg(feature = "scenario-files")]
+    #[test]
+    fn scenario_file_turns_its_rules_on_and_is_replayed() {
+        let dir = std::env::temp_dir().join(format!("startrek-scenario-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mission.json");
+        std::fs::write(
+            &path,
+            r#"{"name": "Ambush", "seed": 12, "rules": {"romulans": true, "difficulty": "novice"},
+                "quadrants": [{"position": [4, 4], "klingons": 2}]}"#,
+        )
+        .unwrap();
+
+        let args = parse_strs(&["--scenario-file", path.to_str().unwrap(), "--tholians"]);
+        assert_eq!(args.scenario_file.as_ref().unwrap().name, "Ambush");
+        assert_eq!(args.seed, Some(12));
+        assert!(args.romulans && args.tholians);
+        assert_eq!(args.ruleset().difficulty, Difficulty::Novice);
+        assert!(args.plays_scenario());
+
+        let rebuilt = parse_from(args.logic_flags());
+        assert_eq!(rebuilt.ruleset(), args.ruleset());
+        assert_eq!(rebuilt.scenario_file, args.scenario_file);
+
+        // Replaying against an edited file reads a different digest
+        std::fs::write(&path, r#"{"name": "Ambush", "seed": 12}"#).unwrap();
+        let edited = parse_from(args.logic_flags());
+        assert_ne!(edited.scenario_file.unwrap().digest, args.scenario_file.unwrap().digest);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_seed_takes_constraints() {
+        let args = parse_strs(&["--find-seed", "klingons=15-20,starbases=3+", "--seed", "100"]);
+        let constraints = args.find_seed.clone().unwrap();
+        assert_eq!(constraints.klingons, Some(15..=20));
+        assert_eq!(constraints.starbases, Some(3..=i32::MAX));
+        assert!(args.logic_flags().is_empty());
+    }
+
+    #[test]
+    fn script_and_output_are_not_logic_flags() {
+        let args = parse_strs(&["--seed", "7", "--script", "game.txt", "--output", "game.out"]);
+        assert_eq!(args.script, Some(PathBuf::from("game.txt")));
+        assert_eq!(args.output, Some(PathBuf::from("game.out")));
+        assert!(args.logic_flags().is_empty());
+    }
+
+    #[test]
+    fn defaults_have_no_logic_flags() {
+        let args = parse_strs(&[]);
+        assert!(args.logic_flags().is_empty());
+        assert_eq!(args.ruleset(), Ruleset::default());
+    }
+}
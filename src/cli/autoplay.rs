@@ -0,0 +1,40 @@
+//! Autoplay runs
+//!
+//! `--autoplay N` lets the built-in captain play N games, one per seed
+//! counting up from `--seed`, under the rules given on the command line,
+//! and prints each outcome and the win rate.
+
+use crate::game_engine::GameEngine;
+use crate::services::ai::AUTOPLAY_MAX_TURNS;
+use crate::services::simulation::{self, SimulationSummary};
+
+use super::args::Args;
+
+/// Play the games and print the results.
+pub fn run(args: &Args, games: u32) -> SimulationSummary {
+    let first = args.seed.unwrap_or(1);
+    println!("AUTOPLAY: {} GAMES FROM SEED {}", games, first);
+    let mut summary = SimulationSummary::default();
+    for seed in first..first.saturating_add(games as u64) {
+        let mut engine = GameEngine::with_options(seed, args.galaxy_options());
+        let sample = simulation::simulate(seed, &mut engine, AUTOPLAY_MAX_TURNS);
+        let outcome = match sample.outcome {
+            Some(_) if sample.victory => format!("VICTORY, RATING {}", sample.rating.unwrap_or_default()),
+            Some(outcome) => outcome.to_string(),
+            None => "UNFINISHED".to_string(),
+        };
+        println!("SEED {}: {} IN {} TURNS", seed, outcome, sample.turns);
+        summary.add(&sample);
+    }
+    println!();
+    println!(
+        "WON {} OF {} FINISHED ({:.0}%), {} UNFINISHED, {:.1} TURNS PER FINISHED GAME, {} KLINGONS DESTROYED",
+        summary.victories,
+        summary.finished(),
+        summary.win_rate(),
+        summary.unfinished,
+        summary.average_turns(),
+        summary.klingons_destroyed
+    );
+    summary
+}
@@ -0,0 +1,170 @@
+//! Crash reports
+//!
+//! When the game panics, the binary writes a `crash-<timestamp>.json` bundle
+//! with the seed, version, recent commands, and a full state dump, so a bug
+//! report can be reproduced from the file alone.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::services::game::Game;
+
+/// Message and location of the most recent panic, recorded by the hook.
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Install a panic hook that records the panic for the crash report, then
+/// runs the default hook so the usual message is still printed.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info
+            .location()
+            .map(|l| format!(" at {}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+        if let Ok(mut last) = LAST_PANIC.lock() {
+            *last = Some(format!("{}{}", payload_message(info.payload()), location));
+        }
+        default_hook(info);
+    }));
+}
+
+fn payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Everything needed to reproduce a crash.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub version: &'static str,
+    pub seed: u64,
+    pub panic_message: String,
+    pub commands: Vec<String>,
+    /// `Galaxy::state_dump` at the time of the crash.
+    pub state_dump: String,
+}
+
+impl CrashReport {
+    /// Build a report for a game that panicked with the given payload.
+    pub fn from_game(game: &Game, payload: &(dyn std::any::Any + Send)) -> Self {
+        let panic_message = LAST_PANIC
+            .lock()
+            .ok()
+            .and_then(|mut last| last.take())
+            .unwrap_or_else(|| payload_message(payload));
+        let galaxy = game.engine().galaxy();
+        CrashReport {
+            version: env!("CARGO_PKG_VERSION"),
+            seed: galaxy.seed(),
+            panic_message,
+            commands: game.command_history().map(str::to_string).collect(),
+            state_dump: galaxy.state_dump(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let commands: Vec<String> = self.commands.iter().map(|c| json_string(c)).collect();
+        format!(
+            "{{\n  \"version\": {},\n  \"seed\": {},\n  \"panic\": {},\n  \"commands\": [{}],\n  \"state\": {}\n}}\n",
+            json_string(self.version),
+            self.seed,
+            json_string(&self.panic_message),
+            commands.join(", "),
+            self.state_dump.replace('\n', "\n  "),
+        )
+    }
+
+    /// Write the bundle into `dir` as `crash-<unix seconds>.json`.
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = dir.join(format!("crash-{}.json", timestamp));
+        File::create(&path)?.write_all(self.to_json().as_bytes())?;
+        Ok(path)
+    }
+}
+
+/// Quote and escape a string for JSON.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CrashReport {
+        CrashReport {
+            version: "0.1.0",
+            seed: 42,
+            panic_message: "index out of bounds at src/x.rs:1".to_string(),
+            commands: vec!["0".to_string(), "say \"hi\"".to_string()],
+            state_dump: "{\n  \"seed\": 42\n}".to_string(),
+        }
+    }
+
+    #[test]
+    fn json_string_escapes_control_and_quote_characters() {
+        assert_eq!(json_string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+    }
+
+    #[test]
+    fn report_json_nests_state_dump() {
+        assert_eq!(
+            sample().to_json(),
+            "{\n  \"version\": \"0.1.0\",\n  \"seed\": 42,\n  \
+             \"panic\": \"index out of bounds at src/x.rs:1\",\n  \
+             \"commands\": [\"0\", \"say \\\"hi\\\"\"],\n  \
+             \"state\": {\n    \"seed\": 42\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn report_from_game_uses_payload_without_hook() {
+        let game = Game::new(7);
+        let payload: Box<dyn std::any::Any + Send> = Box::new("boom");
+        let report = CrashReport::from_game(&game, payload.as_ref());
+
+        assert_eq!(report.seed, 7);
+        assert_eq!(report.panic_message, "boom");
+        assert!(report.commands.is_empty());
+        assert_eq!(report.state_dump, game.engine().galaxy().state_dump());
+    }
+
+    #[test]
+    fn bundle_is_written_to_directory() {
+        let dir = std::env::temp_dir().join(format!("startrek-crash-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = sample().write_to(&dir).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("crash-") && name.ends_with(".json"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), sample().to_json());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+//! Event export
+//!
+//! Writes a game's battle log as JSON Lines for `--export-events`: one
+//! object per event, each starting with its `type`, `turn`, and `stardate`.
+//! Field names are part of the format; add fields rather than rename them.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::models::journal::QuadrantEvent;
+use crate::services::battle_log::GameEvent;
+
+/// The exported shape of a [`GameEvent`]. Kept apart from the models so the
+/// file format doesn't change when they do.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record<'a> {
+    MissionStart {
+        turn: u32,
+        stardate: f64,
+        seed: u64,
+        mission_duration: f64,
+        klingons: i32,
+        starbases: i32,
+    },
+    Quadrant {
+        turn: u32,
+        stardate: f64,
+        quadrant: [i32; 2],
+        event: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        klingons: Option<i32>,
+    },
+    Turn {
+        turn: u32,
+        stardate: f64,
+        command: &'a str,
+        energy_delta: f64,
+        damage_delta: f64,
+        hull_delta: f64,
+        klingons_destroyed: i32,
+        time_delta: f64,
+    },
+    GameOver {
+        turn: u32,
+        stardate: f64,
+        outcome: &'static str,
+        rating: Option<i32>,
+        klingons_destroyed: i32,
+        klingons_remaining: i32,
+        starbases_lost: i32,
+        hull: f64,
+        stardates_used: f64,
+        achievements: Vec<&'static str>,
+    },
+}
+
+impl<'a> Record<'a> {
+    fn from_event(event: &'a GameEvent) -> Self {
+        let (turn, stardate) = (event.turn(), event.stardate());
+        match event {
+            GameEvent::MissionStart { seed, mission_duration, klingons, starbases, .. } => Record::MissionStart {
+                turn,
+                stardate,
+                seed: *seed,
+                mission_duration: *mission_duration,
+                klingons: *klingons,
+                starbases: *starbases,
+            },
+            GameEvent::Quadrant { quadrant, event, .. } => Record::Quadrant {
+                turn,
+                stardate,
+                quadrant: [quadrant.x, quadrant.y],
+                event: quadrant_event_type(event),
+                klingons: match event {
+                    QuadrantEvent::KlingonsSighted(count) => Some(*count),
+                    _ => None,
+                },
+            },
+            GameEvent::Turn(summary) => Record::Turn {
+                turn,
+                stardate,
+                command: &summary.command,
+                energy_delta: summary.energy_delta,
+                damage_delta: summary.damage_delta,
+                hull_delta: summary.hull_delta,
+                klingons_destroyed: summary.klingons_destroyed,
+                time_delta: summary.time_delta,
+            },
+            GameEvent::GameOver { report, achievements } => Record::GameOver {
+                turn,
+                stardate,
+                outcome: report.outcome_label(),
+                rating: report.rating,
+                klingons_destroyed: report.klingons_destroyed(),
+                klingons_remaining: report.klingons_remaining,
+                starbases_lost: report.starbases_lost,
+                hull: report.hull,
+                stardates_used: report.stardates_used,
+                achievements: achievements.iter().map(|a| a.label()).collect(),
+            },
+        }
+    }
+}
+
+/// One event as a single line of JSON, without the newline.
+pub fn event_json(event: &GameEvent) -> String {
+    serde_json::to_string(&Record::from_event(event)).expect("event records always serialize")
+}
+
+fn quadrant_event_type(event: &QuadrantEvent) -> &'static str {
+    match event {
+        QuadrantEvent::KlingonsSighted(_) => "klingons_sighted",
+        QuadrantEvent::QuadrantCleared => "quadrant_cleared",
+        QuadrantEvent::StarbaseDestroyed => "starbase_destroyed",
+        QuadrantEvent::StarbaseConstructed => "starbase_constructed",
+    }
+}
+
+/// Write `events` to `path`, one JSON object per line.
+pub fn export(path: &Path, events: &[GameEvent]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for event in events {
+        writeln!(file, "{}", event_json(event))?;
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::position::QuadrantPosition;
+    use crate::models::turn::TurnSummary;
+
+    #[test]
+    fn events_serialize_to_single_line_objects() {
+        let sighting = GameEvent::Quadrant {
+            turn: 0,
+            stardate: 2500.0,
+            quadrant: QuadrantPosition { x: 3, y: 5 },
+            event: QuadrantEvent::KlingonsSighted(2),
+        };
+        assert_eq!(
+            event_json(&sighting),
+            "{\"type\":\"quadrant\",\"turn\":0,\"stardate\":2500.0,\"quadrant\":[3,5],\
+             \"event\":\"klingons_sighted\",\"klingons\":2}"
+        );
+
+        let turn = GameEvent::Turn(TurnSummary {
+            turn: 4,
+            command: "SAY \"HI\"\n".to_string(),
+            stardate: 2501.5,
+            energy_delta: -200.0,
+            damage_delta: 0.0,
+            hull_delta: -2.5,
+            klingons_destroyed: 1,
+            time_delta: 0.0,
+        });
+        assert_eq!(
+            event_json(&turn),
+            "{\"type\":\"turn\",\"turn\":4,\"stardate\":2501.5,\"command\":\"SAY \\\"HI\\\"\\n\",\
+             \"energy_delta\":-200.0,\"damage_delta\":0.0,\"hull_delta\":-2.5,\
+             \"klingons_destroyed\":1,\"time_delta\":0.0}"
+        );
+    }
+}
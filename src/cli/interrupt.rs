@@ -0,0 +1,20 @@
+//! Ctrl-C handling for the terminal game
+
+use crate::io;
+
+/// Catch Ctrl-C so it pauses the game at the next prompt instead of
+/// killing it mid-command. A second Ctrl-C before the game notices the
+/// first quits as usual.
+pub fn install_handler() {
+    let result = ctrlc::set_handler(|| {
+        if io::interrupt() {
+            println!();
+            println!("INTERRUPTED: PRESS RETURN TO PAUSE THE GAME, OR CTRL-C AGAIN TO QUIT");
+        } else {
+            std::process::exit(130);
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: could not catch Ctrl-C: {}", e);
+    }
+}
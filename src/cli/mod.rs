@@ -1,5 +1,17 @@
 //! Command-line interface
 //!
-//! Handles command-line argument parsing.
+//! Handles command-line argument parsing, crash reporting, Ctrl-C, replays,
+//! event export, autoplay and simulation runs, seed search, scenario files,
+//! and the local high score table.
 
 pub mod args;
+pub mod autoplay;
+pub mod crash;
+pub mod events;
+pub mod interrupt;
+pub mod replay;
+#[cfg(feature = "scenario-files")]
+pub mod scenario_file;
+pub mod scores;
+pub mod seeds;
+pub mod simulate;
@@ -0,0 +1,289 @@
+//! Replays
+//!
+//! A replay file is a header describing the build and rules a game was
+//! played under, followed by every line of input. Playing it back on the
+//! same seed reproduces the game exactly, as long as the game logic has not
+//! changed since; the header lets a newer build detect that and refuse.
+
+use std::fmt;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::Path;
+
+use crate::models::digest::Fnv1a;
+use crate::models::errors::{GameError, GameResult};
+use crate::models::galaxy::GalaxyOptions;
+use crate::models::rng::RNG_STREAM_ID;
+
+/// Version of the game logic. Bump it whenever a change makes the same seed
+/// and input play out differently, e.g. a new random draw or a formula change.
+///
+/// * 2 - the impulse engines and subspace radio can be damaged, which
+///   changes the device damage rolls and the damage report
+/// * 3 - Romulans fire without Klingons present, and decloaked ones take
+///   a share of each phaser volley
+pub const LOGIC_VERSION: u32 = 3;
+
+const MAGIC: &str = "STARTREK REPLAY";
+
+/// Everything besides the input that a replay depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayHeader {
+    /// Crate version that recorded the replay (informational).
+    pub version: String,
+    pub logic_version: u32,
+    pub rng_stream: String,
+    /// The resolved ruleset, as this build describes it.
+    pub ruleset: String,
+    /// Digest of the ruleset, galaxy reflection, game config, and any
+    /// scenario file.
+    pub config_hash: u64,
+    pub seed: u64,
+    /// Command-line flags that shaped the game, replayed to rebuild it.
+    pub flags: Vec<String>,
+}
+
+impl ReplayHeader {
+    /// Header for a game of this build played under `options`.
+    pub fn new(seed: u64, flags: Vec<String>, options: &GalaxyOptions) -> Self {
+        let ruleset = format!("{:?}", options.ruleset);
+        let mut hasher = Fnv1a::default();
+        hasher.write(ruleset.as_bytes());
+        hasher.write(format!("{:?}", options.mirror).as_bytes());
+        hasher.write(format!("{:?}", options.config).as_bytes());
+        ReplayHeader {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            logic_version: LOGIC_VERSION,
+            rng_stream: RNG_STREAM_ID.to_string(),
+            ruleset,
+            config_hash: hasher.finish(),
+            seed,
+            flags,
+        }
+    }
+
+    /// Mix the digest of the scenario file the game was played from into
+    /// the config hash, so an edited file is caught like changed rules.
+    pub fn with_scenario_digest(mut self, digest: u64) -> Self {
+        let mut hasher = Fnv1a::default();
+        hasher.write_u64(self.config_hash);
+        hasher.write_u64(digest);
+        self.config_hash = hasher.finish();
+        self
+    }
+
+    /// Check that a game recorded with this header plays out the same way
+    /// under `current`, the header this build produces for the same seed
+    /// and flags. The crate version alone does not matter.
+    pub fn check_compatible(&self, current: &ReplayHeader) -> Result<(), Incompatibility> {
+        if self.logic_version != current.logic_version {
+            return Err(Incompatibility::LogicVersion {
+                recorded: self.logic_version,
+                current: current.logic_version,
+            });
+        }
+        if self.rng_stream != current.rng_stream {
+            return Err(Incompatibility::RngStream {
+                recorded: self.rng_stream.clone(),
+                current: current.rng_stream.clone(),
+            });
+        }
+        if self.ruleset != current.ruleset {
+            return Err(Incompatibility::Ruleset {
+                recorded: self.ruleset.clone(),
+                current: current.ruleset.clone(),
+            });
+        }
+        if self.config_hash != current.config_hash {
+            return Err(Incompatibility::ConfigHash {
+                recorded: self.config_hash,
+                current: current.config_hash,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why a replay cannot be trusted to play out as recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incompatibility {
+    LogicVersion { recorded: u32, current: u32 },
+    RngStream { recorded: String, current: String },
+    Ruleset { recorded: String, current: String },
+    ConfigHash { recorded: u64, current: u64 },
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Incompatibility::LogicVersion { recorded, current } => write!(
+                f,
+                "replay was recorded with game logic version {}, this build has version {}",
+                recorded, current
+            ),
+            Incompatibility::RngStream { recorded, current } => write!(
+                f,
+                "replay was recorded with random stream {}, this build uses {}",
+                recorded, current
+            ),
+            Incompatibility::Ruleset { recorded, current } => write!(
+                f,
+                "replay was recorded under {}, its flags now give {}",
+                recorded, current
+            ),
+            Incompatibility::ConfigHash { recorded, current } => write!(
+                f,
+                "replay configuration digest {:016x} does not match {:016x}",
+                recorded, current
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub inputs: Vec<String>,
+    /// `GameEngine::tournament_seal` of the finished game, for tournament
+    /// games only.
+    pub seal: Option<u64>,
+}
+
+impl Replay {
+    pub fn to_text(&self) -> String {
+        let h = &self.header;
+        let mut out = format!(
+            "{}\nversion: {}\nlogic: {}\nrng: {}\nruleset: {}\nconfig: {:016x}\nseed: {}\nflags: {}\n",
+            MAGIC,
+            h.version,
+            h.logic_version,
+            h.rng_stream,
+            h.ruleset,
+            h.config_hash,
+            h.seed,
+            h.flags.join(" "),
+        );
+        if let Some(seal) = self.seal {
+            out.push_str(&format!("seal: {:016x}\n", seal));
+        }
+        out.push_str("input:\n");
+        for line in &self.inputs {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn parse(text: &str) -> GameResult<Replay> {
+        let mut lines = text.lines().peekable();
+        if lines.next() != Some(MAGIC) {
+            return Err(GameError::InvalidInput("not a replay file".to_string()));
+        }
+        let field = |line: Option<&str>, name: &str| -> GameResult<String> {
+            line.and_then(|line| line.strip_prefix(name))
+                .and_then(|rest| rest.strip_prefix(':'))
+                .map(|value| value.trim().to_string())
+                .ok_or_else(|| GameError::InvalidInput(format!("replay is missing {}", name)))
+        };
+
+        let version = field(lines.next(), "version")?;
+        let logic_version = field(lines.next(), "logic")?.parse()?;
+        let rng_stream = field(lines.next(), "rng")?;
+        let ruleset = field(lines.next(), "ruleset")?;
+        let config_hash = u64::from_str_radix(&field(lines.next(), "config")?, 16)?;
+        let seed = field(lines.next(), "seed")?.parse()?;
+        let flags = field(lines.next(), "flags")?.split_whitespace().map(str::to_string).collect();
+        let seal = match lines.next_if(|line| line.starts_with("seal:")) {
+            Some(line) => Some(u64::from_str_radix(&field(Some(line), "seal")?, 16)?),
+            None => None,
+        };
+        field(lines.next(), "input")?;
+
+        Ok(Replay {
+            header: ReplayHeader {
+                version,
+                logic_version,
+                rng_stream,
+                ruleset,
+                config_hash,
+                seed,
+                flags,
+            },
+            inputs: lines.map(str::to_string).collect(),
+            seal,
+        })
+    }
+
+    pub fn load(path: &Path) -> GameResult<Replay> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::{CommandCosts, GameConfig};
+    use crate::models::galaxy::MirrorAxis;
+    use crate::models::ruleset::{MissionDuration, Ruleset};
+
+    fn sample() -> Replay {
+        let ruleset = Ruleset { mission_duration: MissionDuration::SCALED, ..Default::default() };
+        let options = GalaxyOptions { ruleset, mirror: MirrorAxis::Horizontal, ..Default::default() };
+        Replay {
+            header: ReplayHeader::new(
+                42,
+                vec!["--scaled-duration".to_string(), "--mirror".to_string(), "h".to_string()],
+                &options,
+            ),
+            inputs: vec!["0".to_string(), "".to_string(), "SHIELDS UP".to_string()],
+            seal: None,
+        }
+    }
+
+    #[test]
+    fn text_round_trips() {
+        let replay = sample();
+        let text = replay.to_text();
+        assert!(text.starts_with("STARTREK REPLAY\nversion: "));
+        assert!(text.contains("\nflags: --scaled-duration --mirror h\ninput:\n0\n\nSHIELDS UP\n"));
+        assert_eq!(Replay::parse(&text).unwrap(), replay);
+    }
+
+    #[test]
+    fn sealed_replays_round_trip() {
+        let replay = Replay { seal: Some(0xfeed), ..sample() };
+        let text = replay.to_text();
+        assert!(text.contains("\nseal: 000000000000feed\ninput:\n"));
+        assert_eq!(Replay::parse(&text).unwrap(), replay);
+        assert!(Replay::parse(&text.replace("seal: 000000000000feed", "seal: nope")).is_err());
+    }
+
+    #[test]
+    fn malformed_files_are_rejected() {
+        assert!(Replay::parse("hello").is_err());
+        assert!(Replay::parse("STARTREK REPLAY\nversion: 0.1.0\n").is_err());
+        let bad_seed = sample().to_text().replace("seed: 42", "seed: many");
+        assert!(Replay::parse(&bad_seed).is_err());
+    }
+
+    #[test]
+    fn config_hash_covers_ruleset_mirror_and_config() {
+        let hash = |options: GalaxyOptions| ReplayHeader::new(1, Vec::new(), &options).config_hash;
+        let base = hash(GalaxyOptions::default());
+        assert_eq!(hash(GalaxyOptions::default()), base);
+        let scaled = Ruleset { mission_duration: MissionDuration::SCALED, ..Default::default() };
+        assert_ne!(hash(GalaxyOptions { ruleset: scaled, ..Default::default() }), base);
+        assert_ne!(hash(GalaxyOptions { mirror: MirrorAxis::Vertical, ..Default::default() }), base);
+        let strict = GameConfig { command_costs: CommandCosts::STRICT, ..Default::default() };
+        assert_ne!(hash(GalaxyOptions { config: strict, ..Default::default() }), base);
+
+        let edited = |digest| ReplayHeader::new(1, Vec::new(), &GalaxyOptions::default()).with_scenario_digest(digest);
+        assert_ne!(edited(1).config_hash, base);
+        assert!(edited(1).check_compatible(&edited(2)).is_err());
+    }
+}
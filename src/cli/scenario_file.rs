@@ -0,0 +1,394 @@
+//! Scenario files
+//!
+//! `--scenario-file` plays a mission described in a TOML or JSON file
+//! rather than one built into the game, so new missions can be shared
+//! without recompiling. A file lays the quadrants out for a
+//! [`GalaxyBuilder`], and may set the Enterprise's supplies and turn
+//! variant rules on. Files ending in `.json` are read as JSON, anything
+//! else as TOML:
+//!
+//! ```toml
+//! name = "Kobayashi Maru"
+//! briefing = "A FREIGHTER IS STRANDED BEYOND THE NEUTRAL ZONE."
+//! mission_duration = 20
+//!
+//! [start]
+//! quadrant = [1, 1]
+//! sector = [4, 4]
+//!
+//! [enterprise]
+//! energy = 2000
+//! torpedoes = 5
+//!
+//! [rules]
+//! difficulty = "admiral"
+//! klingon_commanders = true
+//!
+//! [[quadrants]]
+//! position = [2, 1]
+//! klingons = 3
+//! stars = 2
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::models::difficulty::Difficulty;
+use crate::models::digest::Fnv1a;
+use crate::models::galaxy::{BuildError, Galaxy, GalaxyBuilder, GalaxyOptions};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::quadrant::QuadrantData;
+use crate::models::ruleset::MissionDuration;
+
+use super::args::Args;
+
+/// Why a scenario file could not be used.
+#[derive(Debug)]
+pub enum ScenarioFileError {
+    Io(io::Error),
+    /// Not valid TOML or JSON, or not a scenario
+    Format(String),
+    /// A difficulty other than novice, captain, or admiral
+    Difficulty(String),
+    /// Starting energy that is not a positive number
+    Energy(f64),
+    /// A negative number of starting torpedoes
+    Torpedoes(i32),
+    /// A layout the galaxy builder refused
+    Build(BuildError),
+}
+
+impl fmt::Display for ScenarioFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioFileError::Io(e) => write!(f, "{}", e),
+            ScenarioFileError::Format(message) => write!(f, "{}", message.trim_end()),
+            ScenarioFileError::Difficulty(name) => write!(f, "unknown difficulty \"{}\"", name),
+            ScenarioFileError::Energy(energy) => write!(f, "starting energy {} is not a positive number", energy),
+            ScenarioFileError::Torpedoes(torpedoes) => write!(f, "starting torpedoes {} is negative", torpedoes),
+            ScenarioFileError::Build(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioFileError {}
+
+impl From<io::Error> for ScenarioFileError {
+    fn from(e: io::Error) -> Self {
+        ScenarioFileError::Io(e)
+    }
+}
+
+impl From<BuildError> for ScenarioFileError {
+    fn from(e: BuildError) -> Self {
+        ScenarioFileError::Build(e)
+    }
+}
+
+/// A mission as written in a scenario file. Everything but the name may
+/// be left out; quadrants not listed are empty.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    pub name: String,
+    /// Shown to the player before the mission starts.
+    #[serde(default)]
+    pub briefing: String,
+    /// The seed the mission has to be played on; any seed if left out.
+    pub seed: Option<u64>,
+    pub stardate: Option<f64>,
+    /// Stardates the mission lasts, in place of the ruleset's formula.
+    pub mission_duration: Option<f64>,
+    /// Where the Enterprise starts; the middle of the galaxy if left out.
+    pub start: Option<StartSetup>,
+    #[serde(default)]
+    pub enterprise: EnterpriseSetup,
+    #[serde(default)]
+    pub rules: RuleToggles,
+    #[serde(default)]
+    pub quadrants: Vec<QuadrantSetup>,
+    /// Where the file was read from, so replays can load it again.
+    #[serde(skip)]
+    pub path: PathBuf,
+    /// Digest of the file's text, so a replay can tell if the file it
+    /// loads again has been edited since.
+    #[serde(skip)]
+    pub digest: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StartSetup {
+    pub quadrant: [i32; 2],
+    pub sector: [i32; 2],
+}
+
+/// The Enterprise's supplies, which starbases also restock to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnterpriseSetup {
+    pub energy: Option<f64>,
+    pub torpedoes: Option<i32>,
+}
+
+/// One quadrant's contents; anything left out is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuadrantSetup {
+    pub position: [i32; 2],
+    #[serde(default)]
+    pub klingons: i32,
+    #[serde(default)]
+    pub starbases: i32,
+    #[serde(default)]
+    pub stars: i32,
+    #[serde(default)]
+    pub commanders: i32,
+    #[serde(default)]
+    pub romulans: i32,
+    #[serde(default)]
+    pub mines: i32,
+}
+
+/// Variant rules the mission turns on, named after their command-line
+/// flags with underscores for hyphens.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleToggles {
+    /// `novice`, `captain`, or `admiral`
+    pub difficulty: Option<String>,
+    pub scaled_duration: bool,
+    pub extended_lrs: bool,
+    pub auto_shields: bool,
+    pub migrating_klingons: bool,
+    pub reinforcement_waves: bool,
+    pub starbase_attacks: bool,
+    pub torpedo_splash: bool,
+    pub docking_repairs: bool,
+    pub klingon_commanders: bool,
+    pub romulans: bool,
+    pub black_holes: bool,
+    pub tholians: bool,
+    pub shuttlecraft: bool,
+    pub minefields: bool,
+    pub crew_morale: bool,
+    pub torpedo_dodging: bool,
+    pub phaser_heat: bool,
+    pub shield_drain: bool,
+    pub ramming: bool,
+    pub debris: bool,
+    pub persistent_quadrants: bool,
+    pub klingon_regeneration: bool,
+    pub strict_clock: bool,
+    pub adaptive: bool,
+}
+
+impl RuleToggles {
+    /// Turn the mission's rules on in `args`. Rules given on the command
+    /// line stay on, and a `--difficulty` given there wins.
+    pub fn apply(&self, args: &mut Args) {
+        if self.scaled_duration {
+            args.mission_duration = MissionDuration::SCALED;
+        }
+        args.extended_lrs |= self.extended_lrs;
+        args.auto_shields |= self.auto_shields;
+        args.migrating_klingons |= self.migrating_klingons;
+        args.reinforcement_waves |= self.reinforcement_waves;
+        args.starbase_attacks |= self.starbase_attacks;
+        args.torpedo_splash |= self.torpedo_splash;
+        args.docking_repairs |= self.docking_repairs;
+        args.klingon_commanders |= self.klingon_commanders;
+        args.romulans |= self.romulans;
+        args.black_holes |= self.black_holes;
+        args.tholians |= self.tholians;
+        args.shuttlecraft |= self.shuttlecraft;
+        args.minefields |= self.minefields;
+        args.crew_morale |= self.crew_morale;
+        args.torpedo_dodging |= self.torpedo_dodging;
+        args.phaser_heat |= self.phaser_heat;
+        args.shield_drain |= self.shield_drain;
+        args.ramming |= self.ramming;
+        args.debris |= self.debris;
+        args.persistent_quadrants |= self.persistent_quadrants;
+        args.klingon_regeneration |= self.klingon_regeneration;
+        args.strict_clock |= self.strict_clock;
+        args.adaptive |= self.adaptive;
+        args.difficulty = args.difficulty.or(self.difficulty.as_deref().and_then(Difficulty::parse));
+    }
+}
+
+impl ScenarioFile {
+    /// Read and check a scenario file.
+    pub fn load(path: &Path) -> Result<ScenarioFile, ScenarioFileError> {
+        let text = fs::read_to_string(path)?;
+        let mut file = if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+            ScenarioFile::from_json(&text)?
+        } else {
+            ScenarioFile::from_toml(&text)?
+        };
+        let mut digest = Fnv1a::default();
+        digest.write(text.as_bytes());
+        file.path = path.to_path_buf();
+        file.digest = digest.finish();
+        Ok(file)
+    }
+
+    pub fn from_toml(text: &str) -> Result<ScenarioFile, ScenarioFileError> {
+        toml::from_str::<ScenarioFile>(text)
+            .map_err(|e| ScenarioFileError::Format(e.to_string()))?
+            .checked()
+    }
+
+    pub fn from_json(text: &str) -> Result<ScenarioFile, ScenarioFileError> {
+        serde_json::from_str::<ScenarioFile>(text)
+            .map_err(|e| ScenarioFileError::Format(e.to_string()))?
+            .checked()
+    }
+
+    /// Refuse anything the game could not be set up with, so that
+    /// [`ScenarioFile::galaxy`] cannot fail later.
+    fn checked(self) -> Result<ScenarioFile, ScenarioFileError> {
+        if let Some(name) = self.rules.difficulty.as_ref().filter(|d| Difficulty::parse(d).is_none()) {
+            return Err(ScenarioFileError::Difficulty(name.clone()));
+        }
+        if let Some(energy) = self.enterprise.energy.filter(|e| !(e.is_finite() && *e > 0.0)) {
+            return Err(ScenarioFileError::Energy(energy));
+        }
+        if let Some(torpedoes) = self.enterprise.torpedoes.filter(|t| *t < 0) {
+            return Err(ScenarioFileError::Torpedoes(torpedoes));
+        }
+        self.builder(self.seed.unwrap_or(0), GalaxyOptions::default()).build()?;
+        Ok(self)
+    }
+
+    /// The mission's layout, with the Enterprise's supplies set in
+    /// `options`.
+    pub fn builder(&self, seed: u64, mut options: GalaxyOptions) -> GalaxyBuilder {
+        if let Some(energy) = self.enterprise.energy {
+            options.config.initial_energy = energy;
+        }
+        if let Some(torpedoes) = self.enterprise.torpedoes {
+            options.config.initial_torpedoes = torpedoes;
+        }
+        let mut builder = GalaxyBuilder::new(seed).options(options);
+        for q in &self.quadrants {
+            let data = QuadrantData {
+                klingons: q.klingons,
+                starbases: q.starbases,
+                stars: q.stars,
+                commanders: q.commanders,
+                romulans: q.romulans,
+                mines: q.mines,
+            };
+            builder = builder.quadrant_data(QuadrantPosition { x: q.position[0], y: q.position[1] }, data);
+        }
+        if let Some(start) = self.start {
+            builder = builder.start(
+                QuadrantPosition { x: start.quadrant[0], y: start.quadrant[1] },
+                SectorPosition { x: start.sector[0], y: start.sector[1] },
+            );
+        }
+        if let Some(stardate) = self.stardate {
+            builder = builder.stardate(stardate);
+        }
+        if let Some(stardates) = self.mission_duration {
+            builder = builder.mission_duration(stardates);
+        }
+        builder
+    }
+
+    /// Set the mission's galaxy up under `options`.
+    pub fn galaxy(&self, seed: u64, options: GalaxyOptions) -> Galaxy {
+        // The layout was checked when the file was read
+        self.builder(seed, options).build().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KOBAYASHI_MARU: &str = r#"
+name = "Kobayashi Maru"
+briefing = "A FREIGHTER IS STRANDED BEYOND THE NEUTRAL ZONE."
+mission_duration = 20
+
+[start]
+quadrant = [1, 1]
+sector = [4, 4]
+
+[enterprise]
+energy = 2000
+torpedoes = 5
+
+[rules]
+difficulty = "admiral"
+klingon_commanders = true
+
+[[quadrants]]
+position = [2, 1]
+klingons = 3
+stars = 2
+
+[[quadrants]]
+position = [1, 1]
+starbases = 1
+"#;
+
+    #[test]
+    fn toml_file_sets_up_its_mission() {
+        let file = ScenarioFile::from_toml(KOBAYASHI_MARU).unwrap();
+        assert_eq!(file.name, "Kobayashi Maru");
+        assert!(file.rules.klingon_commanders);
+
+        let galaxy = file.galaxy(7, GalaxyOptions::default());
+        assert_eq!((galaxy.total_klingons(), galaxy.total_starbases()), (3, 1));
+        assert_eq!(galaxy.enterprise().quadrant(), QuadrantPosition { x: 1, y: 1 });
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 4, y: 4 });
+        assert_eq!((galaxy.enterprise().energy(), galaxy.enterprise().torpedoes()), (2000.0, 5));
+        assert_eq!(galaxy.mission_duration(), 20.0);
+    }
+
+    #[test]
+    fn json_file_reads_the_same_as_toml() {
+        let json = r#"{
+            "name": "Kobayashi Maru",
+            "briefing": "A FREIGHTER IS STRANDED BEYOND THE NEUTRAL ZONE.",
+            "mission_duration": 20,
+            "start": { "quadrant": [1, 1], "sector": [4, 4] },
+            "enterprise": { "energy": 2000, "torpedoes": 5 },
+            "rules": { "difficulty": "admiral", "klingon_commanders": true },
+            "quadrants": [
+                { "position": [2, 1], "klingons": 3, "stars": 2 },
+                { "position": [1, 1], "starbases": 1 }
+            ]
+        }"#;
+        assert_eq!(ScenarioFile::from_json(json).unwrap(), ScenarioFile::from_toml(KOBAYASHI_MARU).unwrap());
+    }
+
+    #[test]
+    fn unplayable_files_are_refused() {
+        let error = |text: &str| ScenarioFile::from_toml(text).unwrap_err().to_string();
+        assert_eq!(
+            error("name = \"X\"\n[[quadrants]]\nposition = [9, 1]\nklingons = 1"),
+            "quadrant 9,1 is outside the galaxy"
+        );
+        assert_eq!(
+            error("name = \"X\"\n[[quadrants]]\nposition = [1, 1]\nmines = 70"),
+            "quadrant 1,1 cannot hold 0 klingons, 0 starbases, 0 stars, 0 romulans, and 70 mines"
+        );
+        assert_eq!(error("name = \"X\"\n[rules]\ndifficulty = \"ensign\""), "unknown difficulty \"ensign\"");
+        assert_eq!(error("name = \"X\"\n[enterprise]\nenergy = 0"), "starting energy 0 is not a positive number");
+        assert_eq!(error("name = \"X\"\n[enterprise]\ntorpedoes = -1"), "starting torpedoes -1 is negative");
+        assert!(matches!(
+            ScenarioFile::from_toml("name = \"X\"\nwarbirds = 3"),
+            Err(ScenarioFileError::Format(_))
+        ));
+        assert!(matches!(ScenarioFile::from_json("{}"), Err(ScenarioFileError::Format(_))));
+    }
+}
@@ -0,0 +1,109 @@
+//! Local high score storage
+//!
+//! Keeps the high score table in `~/.startrek/scores.txt` so victories are
+//! remembered between games. A missing file is an empty table.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::models::errors::GameResult;
+use crate::services::scoring::HighScoreTable;
+
+const SCORES_DIR: &str = ".startrek";
+const SCORES_FILE: &str = "scores.txt";
+
+/// `$HOME/.startrek/scores.txt`, or under the working directory without a home.
+pub fn default_path() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    home.join(SCORES_DIR).join(SCORES_FILE)
+}
+
+pub fn load(path: &Path) -> GameResult<HighScoreTable> {
+    match fs::read_to_string(path) {
+        Ok(text) => HighScoreTable::parse(&text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HighScoreTable::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// [`load`], but a file that cannot be read or parsed only earns a
+/// warning, and the game carries on with an empty table.
+pub fn load_or_empty(path: &Path) -> HighScoreTable {
+    load(path).unwrap_or_else(|e| {
+        eprintln!("Warning: could not read high scores from {}: {}; starting an empty table", path.display(), e);
+        HighScoreTable::default()
+    })
+}
+
+pub fn save(table: &HighScoreTable, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, table.to_text())
+}
+
+/// Today's date (UTC) as YYYY-MM-DD.
+pub fn today() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    civil_date(secs / 86_400)
+}
+
+/// The calendar date `days` after 1970-01-01, from Howard Hinnant's
+/// days-to-civil algorithm.
+fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::scoring::{HighScore, Rank};
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(59), "1970-03-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(20_742), "2026-10-16");
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_table_and_saves_create_the_directory() {
+        let dir = std::env::temp_dir().join(format!("startrek-scores-{}", std::process::id()));
+        let path = dir.join(SCORES_DIR).join(SCORES_FILE);
+        assert_eq!(load(&path).unwrap(), HighScoreTable::default());
+
+        let mut table = HighScoreTable::default();
+        table.insert(HighScore {
+            name: "KIRK".to_string(),
+            seed: 7,
+            rating: 812,
+            rank: Rank::Captain,
+            date: "2026-10-16".to_string(),
+            turns: 88,
+        });
+        save(&table, &path).unwrap();
+        assert_eq!(load(&path).unwrap(), table);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_file_falls_back_to_an_empty_table() {
+        let path = std::env::temp_dir().join(format!("startrek-corrupt-scores-{}.txt", std::process::id()));
+        fs::write(&path, "not a score table").unwrap();
+        assert!(load(&path).is_err());
+        assert_eq!(load_or_empty(&path), HighScoreTable::default());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,35 @@
+//! Seed search
+//!
+//! `--find-seed` looks for the first seed, counting up from `--seed`, whose
+//! galaxy under the rules given on the command line meets the constraints.
+
+use crate::models::galaxy::{Galaxy, GalaxyProfile, SeedConstraints};
+
+use super::args::Args;
+
+/// Seeds searched before giving up.
+pub const FIND_SEED_LIMIT: u64 = 1_000_000;
+
+/// Search for a matching seed and print it.
+pub fn find_seed(args: &Args, constraints: &SeedConstraints) -> Option<GalaxyProfile> {
+    let first = args.seed.unwrap_or(1);
+    let last = first.saturating_add(FIND_SEED_LIMIT);
+    let found = Galaxy::find_seed(first..last, &args.galaxy_options(), constraints);
+    match &found {
+        Some(profile) => {
+            println!(
+                "SEED {}: {} KLINGONS, {} STARBASES, {} STARS",
+                profile.seed, profile.klingons, profile.starbases, profile.stars
+            );
+            let starbase = match profile.starbase_distance {
+                Some(0) => "A STARBASE IN IT".to_string(),
+                Some(d) => format!("NEAREST STARBASE {} QUADRANT{} AWAY", d, if d == 1 { "" } else { "S" }),
+                None => "NO STARBASES".to_string(),
+            };
+            println!("  {} KLINGONS IN THE STARTING QUADRANT, {}", profile.klingons_at_start, starbase);
+            println!("  {:.0} STARDATES FOR THE MISSION", profile.mission_duration);
+        }
+        None => println!("NO SEED FROM {} TO {} MATCHES", first, last - 1),
+    }
+    found
+}
@@ -0,0 +1,137 @@
+//! Balance simulation runs
+//!
+//! `startrek simulate` plays a batch of seeds with the autoplayer, spread
+//! over several threads, under the rules given on the command line, and
+//! prints the summary as a table or, with `--csv`, as CSV.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::game_engine::GameEngine;
+use crate::models::constants::Device;
+use crate::services::ai::AUTOPLAY_MAX_TURNS;
+use crate::services::simulation::{self, GameSample, SimulationSummary};
+
+use super::args::Args;
+
+/// Games played when `--games` is not given.
+pub const DEFAULT_GAMES: u32 = 100;
+
+/// What `startrek simulate` was asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulateOptions {
+    pub games: u32,
+    /// Worker threads; `None` for one per available core.
+    pub threads: Option<usize>,
+    pub csv: bool,
+}
+
+/// Play the games and print the summary.
+pub fn run(args: &Args, options: &SimulateOptions) -> SimulationSummary {
+    let first = args.seed.unwrap_or(1);
+    let threads = options
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let samples = play_seeds(first..first.saturating_add(options.games as u64), threads, |seed| {
+        GameEngine::with_options(seed, args.galaxy_options())
+    });
+    let summary: SimulationSummary = samples.iter().collect();
+    if options.csv {
+        print!("{}", format_csv(&summary));
+    } else {
+        let plural = if threads == 1 { "" } else { "S" };
+        println!("SIMULATION: {} GAMES FROM SEED {} ON {} THREAD{}", options.games, first, threads, plural);
+        println!();
+        print!("{}", format_table(&summary));
+    }
+    summary
+}
+
+/// Autoplay a game for each seed on `threads` threads, each game built by
+/// `new_game`. Threads take the next seed as they come free; the samples
+/// come back in seed order, the same whatever the thread count.
+pub fn play_seeds(
+    seeds: std::ops::Range<u64>,
+    threads: usize,
+    new_game: impl Fn(u64) -> GameEngine + Sync,
+) -> Vec<GameSample> {
+    let next = AtomicU64::new(seeds.start);
+    let samples = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let seed = next.fetch_add(1, Ordering::Relaxed);
+                if seed >= seeds.end {
+                    break;
+                }
+                let sample = simulation::simulate(seed, &mut new_game(seed), AUTOPLAY_MAX_TURNS);
+                samples.lock().unwrap().push(sample);
+            });
+        }
+    });
+    let mut samples = samples.into_inner().unwrap();
+    samples.sort_by_key(|s| s.seed);
+    samples
+}
+
+/// The summary as a table. Unfinished games are counted on their own; the
+/// figures below them are over the finished games.
+pub fn format_table(summary: &SimulationSummary) -> String {
+    let mut table = String::new();
+    table.push_str(&format!("GAMES                {}\n", summary.games));
+    table.push_str(&format!("UNFINISHED           {}\n", summary.unfinished));
+    table.push_str(&format!("WIN RATE             {:.1}%\n", summary.win_rate()));
+    table.push_str(&format!("AVERAGE RATING       {:.0}\n", summary.average_rating()));
+    table.push_str(&format!("AVERAGE STARDATES    {:.1}\n", summary.average_stardates()));
+    table.push('\n');
+    table.push_str("DEVICE          BREAKDOWNS PER GAME  GAMES DAMAGED\n");
+    for device in Device::ALL {
+        table.push_str(&format!(
+            "{:<16}{:>19.2}{:>14.0}%\n",
+            device.name(),
+            summary.breakdowns_per_game(device),
+            summary.damaged_share(device)
+        ));
+    }
+    table
+}
+
+/// One `statistic,device,value` row per figure; the device is empty for
+/// figures about the whole game.
+pub fn format_csv(summary: &SimulationSummary) -> String {
+    let mut csv = String::from("statistic,device,value\n");
+    csv.push_str(&format!("games,,{}\n", summary.games));
+    csv.push_str(&format!("win_rate,,{:.2}\n", summary.win_rate()));
+    csv.push_str(&format!("unfinished,,{}\n", summary.unfinished));
+    csv.push_str(&format!("average_rating,,{:.2}\n", summary.average_rating()));
+    csv.push_str(&format!("average_stardates,,{:.2}\n", summary.average_stardates()));
+    for device in Device::ALL {
+        csv.push_str(&format!("breakdowns_per_game,{},{:.3}\n", device.name(), summary.breakdowns_per_game(device)));
+        csv.push_str(&format!("games_damaged,{},{:.2}\n", device.name(), summary.damaged_share(device)));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_do_not_depend_on_the_thread_count() {
+        let serial = play_seeds(1..5, 1, GameEngine::new);
+        let parallel = play_seeds(1..5, 3, GameEngine::new);
+        assert_eq!(serial, parallel);
+        assert_eq!(serial.iter().map(|s| s.seed).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn csv_has_a_row_per_figure() {
+        let summary = SimulationSummary::default();
+        let csv = format_csv(&summary);
+        assert_eq!(csv.lines().next(), Some("statistic,device,value"));
+        assert_eq!(csv.lines().count(), 1 + 5 + 2 * Device::ALL.len());
+        assert!(csv.contains("breakdowns_per_game,WARP ENGINES,0.000"));
+        assert_eq!(format_table(&summary).lines().count(), 7 + Device::ALL.len());
+    }
+}
@@ -3,27 +3,139 @@
 //! Manages the overall game state, checking for victory and defeat conditions.
 //! The GameEngine owns the Galaxy and tracks whether the game is still being played.
 
-use crate::models::galaxy::Galaxy;
+use std::future::Future;
+use std::hash::Hasher;
+
+use crate::io::{self, EventQueue, InputReader, OutputWriter, PendingInput};
+use crate::models::config::GameConfig;
+use crate::models::constants::{Condition, COURT_MARTIAL_STARBASES, DEFAULT_QUICK_SHIELD_UNITS};
+use crate::models::digest::Fnv1a;
+use crate::models::errors::GameResult;
+use crate::models::galaxy::{Galaxy, GalaxyOptions, MirrorAxis};
+use crate::models::hooks::ModRegistry;
+use crate::models::output_event::OutputEvent;
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::turn::{TurnSnapshot, TurnSummary};
+use crate::services::battle_log::{BattleLog, GameEvent};
+use crate::services::commands::{self, CommandId};
+use crate::services::preview::{self, Command, PreviewReport};
+use crate::services::repair;
+use crate::services::turn::{self, PlayerAction};
+use crate::services::scoring::{Rank, ScoreCard};
+use crate::ui::presenters::Emit;
 
 /// Core game engine that manages game state and victory/defeat conditions
 pub struct GameEngine {
     galaxy: Galaxy,
     state: GameState,
+    tournament: bool,
+    events: EventQueue,
 }
 
+/// How far [`GameEngine::try_execute`] got with a command
 #[derive(Debug, Clone, PartialEq)]
+pub enum Execution {
+    /// The command ran to completion
+    Complete,
+    /// The command asked a question it had no answer for. The game is
+    /// unchanged; run the command again with the answer added.
+    NeedsInput { prompt: String },
+}
+
+/// Where a turn began, from [`GameEngine::begin_turn`], for
+/// [`GameEngine::finish_turn`] to take the rest of the turn from
+#[derive(Debug, Clone)]
+pub struct TurnStart {
+    before: TurnSnapshot,
+    quadrant: QuadrantPosition,
+    sector: SectorPosition,
+    docked: bool,
+}
+
+/// Key mixed into tournament seals. Not a secret, only a guard against
+/// results from ordinary games being passed off as tournament games.
+const TOURNAMENT_KEY: &[u8] = b"startrek-tournament-v1";
+
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameState {
     Playing,
-    Victory { rating: i32 },
+    Victory { rating: i32, rank: Rank },
     Defeat { reason: DefeatReason },
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DefeatReason {
     ShipDestroyed,
     TimeExpired,
-    #[allow(dead_code)]
+    /// Out of energy and shields; survivors are demoted to private
     DeadInSpace,
+    /// The player ran out of thinking time in head-to-head play
+    ClockExpired,
+    /// The Enterprise destroyed a second starbase, or the last one, with
+    /// its own torpedoes
+    CourtMartial,
+}
+
+impl DefeatReason {
+    /// Short description shown in the epilogue and sent with scores
+    pub fn label(&self) -> &'static str {
+        match self {
+            DefeatReason::ShipDestroyed => "SHIP DESTROYED",
+            DefeatReason::TimeExpired => "TIME EXPIRED",
+            DefeatReason::DeadInSpace => "DEAD IN SPACE",
+            DefeatReason::ClockExpired => "CLOCK EXPIRED",
+            DefeatReason::CourtMartial => "COURT MARTIAL",
+        }
+    }
+}
+
+/// Everything an end screen needs, computed once by the engine so every
+/// frontend shows the same epilogue
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameOverReport {
+    /// `Victory` or `Defeat`; never `Playing`
+    pub state: GameState,
+    /// Efficiency rating after mods; `None` for a defeat
+    pub rating: Option<i32>,
+    /// Rank earned; `None` for a defeat
+    pub rank: Option<Rank>,
+    pub klingons_remaining: i32,
+    /// Klingons faced, reinforcements included
+    pub klingons_initial: i32,
+    /// Stardate the game ended on
+    pub stardate: f64,
+    pub stardates_used: f64,
+    pub starbases_remaining: i32,
+    /// Starbases destroyed during the game
+    pub starbases_lost: i32,
+    /// Hull integrity left, in percent
+    pub hull: f64,
+    pub torpedoes_used: i32,
+    /// Crew lost over the whole game
+    pub casualties: i32,
+    pub turns: u32,
+}
+
+impl GameOverReport {
+    pub fn is_victory(&self) -> bool {
+        matches!(self.state, GameState::Victory { .. })
+    }
+
+    /// "VICTORY", or the defeat reason
+    pub fn outcome_label(&self) -> &'static str {
+        match &self.state {
+            GameState::Defeat { reason } => reason.label(),
+            _ => "VICTORY",
+        }
+    }
+
+    pub fn klingons_destroyed(&self) -> i32 {
+        self.klingons_initial - self.klingons_remaining
+    }
 }
 
 impl GameEngine {
@@ -40,7 +152,138 @@ impl GameEngine {
         Self {
             galaxy: Galaxy::new(seed),
             state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine whose galaxy is adjusted by registered mods
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Random number generator seed for galaxy generation
+    /// * `mods` - Mods applied to generation, random events, and scoring
+    pub fn new_with_mods(seed: u64, mods: ModRegistry) -> Self {
+        Self {
+            galaxy: Galaxy::new_with_mods(seed, mods),
+            state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine on the mirror twin of the seed's galaxy
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Random number generator seed for galaxy generation
+    /// * `axis` - Reflection applied to the generated galaxy
+    pub fn new_mirrored(seed: u64, axis: MirrorAxis) -> Self {
+        Self {
+            galaxy: Galaxy::new_mirrored(seed, axis),
+            state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine with its balance numbers taken from `config`
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Random number generator seed for galaxy generation
+    /// * `config` - Starting supplies, Klingon shields, and event chances
+    pub fn new_with_config(seed: u64, config: GameConfig) -> Self {
+        Self {
+            galaxy: Galaxy::new_with_config(seed, config),
+            state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine with every generation option spelled out
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Random number generator seed for galaxy generation
+    /// * `options` - Mods, mirroring, the variant ruleset, and the config
+    pub fn with_options(seed: u64, options: GalaxyOptions) -> Self {
+        Self {
+            galaxy: Galaxy::with_options(seed, options),
+            state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine around a galaxy set up elsewhere, such
+    /// as a scenario or a `GalaxyBuilder` layout
+    ///
+    /// # Arguments
+    ///
+    /// * `galaxy` - The galaxy to play, before its first turn
+    pub fn from_galaxy(galaxy: Galaxy) -> Self {
+        Self {
+            galaxy,
+            state: GameState::Playing,
+            tournament: false,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Creates a new game engine for tournament play
+    ///
+    /// Tournament games are always played under the classic rules on an
+    /// unmirrored galaxy with no mods, and never log their RNG draws,
+    /// whatever the frontend asks for. Only tournament games can seal
+    /// their results (see `tournament_seal`).
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Random number generator seed for galaxy generation
+    pub fn new_tournament(seed: u64) -> Self {
+        let mut galaxy = Galaxy::new(seed);
+        galaxy.rng_mut().stop_logging();
+        Self {
+            galaxy,
+            state: GameState::Playing,
+            tournament: true,
+            events: EventQueue::default(),
+        }
+    }
+
+    /// Returns true if the engine was created with `new_tournament`
+    pub fn is_tournament(&self) -> bool {
+        self.tournament
+    }
+
+    /// Seals a tournament game's result
+    ///
+    /// The seal is a keyed digest of the seed, final state, turn count and
+    /// input digest, so a leaderboard can check a submitted score or replay
+    /// by playing the input back on a tournament engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `replay_hash` - Digest of every line of input entered
+    ///
+    /// # Returns
+    ///
+    /// * `Some(seal)` for a tournament game
+    /// * `None` for any other game
+    pub fn tournament_seal(&self, replay_hash: u64) -> Option<u64> {
+        if !self.tournament {
+            return None;
         }
+        let mut hasher = Fnv1a::default();
+        hasher.write(TOURNAMENT_KEY);
+        hasher.write_u64(self.galaxy.seed());
+        hasher.write_u64(self.galaxy.state_digest());
+        hasher.write_u32(self.galaxy.turns());
+        hasher.write_u64(replay_hash);
+        hasher.write(TOURNAMENT_KEY);
+        Some(hasher.finish())
     }
 
     /// Returns an immutable reference to the galaxy
@@ -59,6 +302,245 @@ impl GameEngine {
         &self.state
     }
 
+    /// Projects the outcome of a command without running it. Random
+    /// factors are taken at their means and the RNG is left untouched.
+    pub fn preview(&self, command: Command) -> PreviewReport {
+        preview::preview(&self.galaxy, command)
+    }
+
+    /// Runs a built-in command, queueing its output as events
+    ///
+    /// Frontends that draw their own display run commands through the
+    /// engine and take the events afterwards, instead of passing an
+    /// `OutputWriter` to the services.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Command to run; Quit does nothing
+    /// * `input` - Answers to the command's prompts
+    pub fn execute(&mut self, id: CommandId, input: &mut dyn InputReader) -> GameResult<()> {
+        commands::execute(id, &mut self.galaxy, DEFAULT_QUICK_SHIELD_UNITS, input, &mut self.events)
+    }
+
+    /// Runs a built-in command without blocking for input
+    ///
+    /// The command is answered from `answers`. If it asks for more, the
+    /// galaxy and the event queue are put back as they were and the prompt
+    /// is returned, so a frontend that cannot block, such as a browser,
+    /// can get the answer from the player and try again with it added.
+    /// The game is deterministic, so the retry retraces the same prompts.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Command to run; Quit does nothing
+    /// * `answers` - Answers to the command's prompts known so far
+    pub fn try_execute(&mut self, id: CommandId, answers: &[String]) -> GameResult<Execution> {
+        let saved = self.galaxy.clone();
+        let queued = self.events.events().len();
+        let mut input = PendingInput::new(answers.iter().cloned());
+        match self.execute(id, &mut input) {
+            Err(e) if io::is_pending(&e) => {
+                self.galaxy = saved;
+                self.events.truncate(queued);
+                Ok(Execution::NeedsInput {
+                    prompt: input.awaiting().unwrap_or_default().to_string(),
+                })
+            }
+            result => result.map(|()| Execution::Complete),
+        }
+    }
+
+    /// Runs a built-in command, awaiting each answer from `ask`
+    ///
+    /// The async counterpart of `execute` for frontends whose input
+    /// arrives as futures, built on `try_execute`. No runtime is needed;
+    /// any executor, including a browser's event loop, can drive it.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Command to run; Quit does nothing
+    /// * `ask` - Shows a prompt and resolves to the player's answer
+    pub async fn execute_async<F, Fut>(&mut self, id: CommandId, mut ask: F) -> GameResult<()>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = String>,
+    {
+        let mut answers = Vec::new();
+        while let Execution::NeedsInput { prompt } = self.try_execute(id, &answers)? {
+            answers.push(ask(prompt).await);
+        }
+        Ok(())
+    }
+
+    /// Plays one whole turn
+    ///
+    /// The player's action runs first, with the Klingons returning fire
+    /// before or after it as the action calls for, then the enemy phase,
+    /// then end-of-turn upkeep, and finally the check for the end of the
+    /// game. The turn's output is queued as for `execute`. An action whose
+    /// command is unavailable takes no turn. Offers that need an answer,
+    /// such as docking repairs, are left to the interactive frontends. If
+    /// a `Command` action runs out of answers the game is put back as it
+    /// was and the error returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - What the player does this turn, with its answers
+    ///
+    /// # Returns
+    ///
+    /// The turn as the battle log records it: journal entries, the turn
+    /// summary, and the ending if the game is over
+    pub fn step(&mut self, action: PlayerAction) -> GameResult<Vec<GameEvent>> {
+        let spec = action.command().spec();
+        if let Err(message) = spec.availability(&self.galaxy) {
+            self.events.writeln(&message);
+            return Ok(Vec::new());
+        }
+
+        let saved = self.galaxy.clone();
+        let queued = self.events.events().len();
+        let start = self.begin_turn();
+        let mut log = BattleLog::resume(&self.galaxy);
+        if let Err(e) = turn::act(&mut self.galaxy, &action, DEFAULT_QUICK_SHIELD_UNITS, &mut self.events) {
+            self.galaxy = saved;
+            self.events.truncate(queued);
+            return Err(e);
+        }
+        let mut events = std::mem::take(&mut self.events);
+        let summary = self.finish_turn(&start, spec.name, None, &mut events);
+        self.events = events;
+        log.record_turn(&summary?, &self.galaxy);
+        if let Some(report) = self.game_over_report() {
+            log.finish(&report, &self.galaxy);
+        }
+        Ok(log.into_events())
+    }
+
+    /// Notes where the Enterprise is before the player's command runs, for
+    /// `finish_turn`
+    pub fn begin_turn(&self) -> TurnStart {
+        let enterprise = self.galaxy.enterprise();
+        TurnStart {
+            before: TurnSnapshot::capture(&self.galaxy),
+            quadrant: enterprise.quadrant(),
+            sector: enterprise.sector(),
+            docked: self.galaxy.evaluate_condition() == Condition::Docked,
+        }
+    }
+
+    /// Plays the rest of a turn once the player's command has run
+    ///
+    /// The enemy phase, then end-of-turn upkeep, then, if the Enterprise
+    /// has just docked and `offers` can answer, the starbase's offer of
+    /// repairs; then the turn is summarized and the end of the game checked
+    /// for. Every frontend finishes its turns here, so the order is the
+    /// same everywhere. A command that did not run takes no turn and should
+    /// not come here.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - From `begin_turn`, before the command ran
+    /// * `command` - Name of the command the player issued
+    /// * `offers` - Answers offers made at the end of the turn; `None` to
+    ///   make none
+    /// * `output` - Where the rest of the turn is shown
+    ///
+    /// # Returns
+    ///
+    /// The turn's summary, or the error if the player pressed Ctrl-C at an
+    /// offer, leaving the caller to take the turn back
+    pub fn finish_turn(
+        &mut self,
+        start: &TurnStart,
+        command: &str,
+        offers: Option<&mut dyn InputReader>,
+        output: &mut dyn OutputWriter,
+    ) -> GameResult<TurnSummary> {
+        turn::enemy_phase(&mut self.galaxy, start.quadrant, start.sector, output);
+        turn::upkeep(&mut self.galaxy, output);
+        if let Some(offers) = offers.filter(|_| !start.docked) {
+            if let Err(e) = repair::offer_docking_repairs(&mut self.galaxy, offers, output) {
+                if io::is_interrupted(&e) {
+                    return Err(e);
+                }
+                output.writeln(&format!("Error: {}", e));
+            }
+        }
+        let summary = self.end_turn(command, &start.before);
+        self.check_game_over();
+        Ok(summary)
+    }
+
+    /// Takes the events queued by `execute`, oldest first
+    pub fn take_events(&mut self) -> Vec<OutputEvent> {
+        self.events.drain()
+    }
+
+    /// Returns the number of turns completed so far
+    pub fn turns(&self) -> u32 {
+        self.galaxy.turns()
+    }
+
+    /// Completes a turn and summarizes it
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Name of the command the player issued
+    /// * `before` - Snapshot captured before the command ran
+    ///
+    /// # Returns
+    ///
+    /// One consolidated record of what the turn changed
+    pub fn end_turn(&mut self, command: &str, before: &TurnSnapshot) -> TurnSummary {
+        let turn = self.galaxy.complete_turn();
+        TurnSummary::between(turn, command, before, &self.galaxy)
+    }
+
+    /// Builds the epilogue for a finished game
+    ///
+    /// # Returns
+    ///
+    /// * `Some(GameOverReport)` once `check_game_over` has ended the game
+    /// * `None` while the game is still being played
+    pub fn game_over_report(&self) -> Option<GameOverReport> {
+        let (rating, rank) = match &self.state {
+            GameState::Playing => return None,
+            GameState::Victory { rating, rank } => (Some(*rating), Some(*rank)),
+            GameState::Defeat { .. } => (None, None),
+        };
+        let galaxy = &self.galaxy;
+        Some(GameOverReport {
+            state: self.state.clone(),
+            rating,
+            rank,
+            klingons_remaining: galaxy.total_klingons(),
+            klingons_initial: galaxy.klingons_faced(),
+            stardate: galaxy.stardate(),
+            stardates_used: galaxy.stardate() - galaxy.starting_stardate(),
+            starbases_remaining: galaxy.total_starbases(),
+            starbases_lost: galaxy.starbases_lost(),
+            hull: galaxy.enterprise().hull(),
+            torpedoes_used: galaxy.enterprise().torpedoes_fired(),
+            casualties: galaxy.enterprise().casualties(),
+            turns: self.galaxy.turns(),
+        })
+    }
+
+    /// Ends a game in progress because the player's chess clock ran out
+    ///
+    /// # Returns
+    ///
+    /// The game state afterwards; a game already over is left as it was
+    pub fn forfeit_on_time(&mut self) -> GameState {
+        if self.state == GameState::Playing {
+            self.state = GameState::Defeat {
+                reason: DefeatReason::ClockExpired,
+            };
+        }
+        self.state.clone()
+    }
+
     /// Checks for game over conditions and updates the game state
     ///
     /// # Returns
@@ -69,12 +551,15 @@ impl GameEngine {
     /// # Victory Conditions
     ///
     /// The player wins when all Klingon battle cruisers are destroyed.
-    /// An efficiency rating is calculated based on time remaining and losses.
+    /// An efficiency rating is calculated based on time remaining and losses,
+    /// then adjusted by any registered mods.
     ///
     /// # Defeat Conditions
     ///
     /// The player loses if:
-    /// - The Enterprise is destroyed (shields fall below 0)
+    /// - The Enterprise is destroyed (hull integrity reaches 0), unless the
+    ///   shuttlecraft rule lets the crew escape; the escape is queued as an
+    ///   event for `take_events`
     /// - Time expires before all Klingons are destroyed
     pub fn check_game_over(&mut self) -> Option<GameState> {
         if self.state != GameState::Playing {
@@ -83,19 +568,48 @@ impl GameEngine {
 
         // Victory: all Klingons destroyed
         if self.galaxy.all_klingons_destroyed() {
-            let rating = self.galaxy.efficiency_rating();
-            self.state = GameState::Victory { rating };
+            let rating = self
+                .galaxy
+                .mods()
+                .adjust_score(self.galaxy.efficiency_rating());
+            let rank = ScoreCard::tally(&self.galaxy, rating).rank;
+            self.state = GameState::Victory { rating, rank };
             return Some(self.state.clone());
         }
 
-        // Defeat: ship destroyed (shields < 0)
-        if self.galaxy.enterprise().shields() < 0.0 {
+        // The crew may escape a destroyed ship in the shuttlecraft
+        let enterprise = self.galaxy.enterprise();
+        if enterprise.is_destroyed() && self.galaxy.ruleset().shuttlecraft && enterprise.can_abandon_ship() {
+            self.galaxy.enterprise_mut().abandon_ship();
+            let energy = self.galaxy.enterprise().energy();
+            self.events.emit(OutputEvent::ShipAbandoned { energy });
+        }
+
+        // Defeat: ship destroyed (hull breached)
+        if self.galaxy.enterprise().is_destroyed() {
             self.state = GameState::Defeat {
                 reason: DefeatReason::ShipDestroyed,
             };
             return Some(self.state.clone());
         }
 
+        // Defeat: survived dead in space, demoted
+        if self.galaxy.is_dead_in_space() {
+            self.state = GameState::Defeat {
+                reason: DefeatReason::DeadInSpace,
+            };
+            return Some(self.state.clone());
+        }
+
+        // Defeat: court-martialed for destroying starbases
+        let destroyed = self.galaxy.starbases_destroyed_by_enterprise();
+        if destroyed >= COURT_MARTIAL_STARBASES || (destroyed > 0 && self.galaxy.total_starbases() == 0) {
+            self.state = GameState::Defeat {
+                reason: DefeatReason::CourtMartial,
+            };
+            return Some(self.state.clone());
+        }
+
         // Defeat: time expired
         if self.galaxy.is_time_expired() {
             self.state = GameState::Defeat {
@@ -0,0 +1,127 @@
+//! ANSI color output
+//!
+//! Colors the teletype output on terminals that support it: condition
+//! codes in their own colors, Klingons on the short range scan in red,
+//! and phaser and torpedo hits highlighted. Writers report whether they
+//! show color, and the presenters color each element as they write it.
+//! The game text itself is unchanged, so output without color reads
+//! exactly as before.
+
+use crate::models::constants::Condition;
+
+const RESET: &str = "\x1b[0m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+const BOLD_RED: &str = "\x1b[1;31m";
+
+fn condition_color(condition: Condition) -> &'static str {
+    match condition {
+        Condition::Green => GREEN,
+        Condition::Yellow => YELLOW,
+        Condition::Red => RED,
+        Condition::Docked => CYAN,
+    }
+}
+
+fn paint(text: &str, color: &str) -> String {
+    format!("{}{}{}", color, text, RESET)
+}
+
+/// What an element of the output is, for the color it is shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// A condition code, in its own color
+    Condition(Condition),
+    /// A Klingon on the short range scan
+    Klingon,
+    /// A hit on the Enterprise
+    EnterpriseHit,
+    /// A hit on an enemy ship
+    EnemyHit,
+}
+
+impl Highlight {
+    fn color(self) -> &'static str {
+        match self {
+            Highlight::Condition(condition) => condition_color(condition),
+            Highlight::Klingon => RED,
+            Highlight::EnterpriseHit => BOLD_RED,
+            Highlight::EnemyHit => YELLOW,
+        }
+    }
+}
+
+/// `text` in the color for `highlight` when `color` is on, for writers
+/// that report [`OutputWriter::color`](super::OutputWriter::color), and
+/// unchanged otherwise.
+pub fn highlight(text: &str, highlight: Highlight, color: bool) -> String {
+    if color {
+        paint(text, highlight.color())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Terminal I/O that colors its output when `color` is on, and otherwise
+/// behaves exactly like [`TerminalIO`](super::TerminalIO).
+#[cfg(feature = "terminal")]
+#[derive(Debug, Clone, Copy)]
+pub struct AnsiTerminalIO {
+    color: bool,
+}
+
+#[cfg(feature = "terminal")]
+impl AnsiTerminalIO {
+    pub fn new(color: bool) -> Self {
+        AnsiTerminalIO { color }
+    }
+
+    /// Color only when stdout is a terminal, the player has not asked for
+    /// plain text with `no_color`, and `NO_COLOR` is not set.
+    pub fn detect(no_color: bool) -> Self {
+        use std::io::IsTerminal;
+
+        let color = !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+        Self::new(color)
+    }
+
+}
+
+#[cfg(feature = "terminal")]
+impl super::InputReader for AnsiTerminalIO {
+    fn read_line(&mut self, prompt: &str) -> Result<String, std::io::Error> {
+        super::TerminalIO.read_line(prompt)
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl super::OutputWriter for AnsiTerminalIO {
+    fn write(&mut self, message: &str) {
+        print!("{}", message);
+    }
+
+    fn writeln(&mut self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn color(&self) -> bool {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_are_painted_only_with_color_on() {
+        assert_eq!(highlight("RED", Highlight::Condition(Condition::Red), true), "\x1b[31mRED\x1b[0m");
+        assert_eq!(highlight("DOCKED", Highlight::Condition(Condition::Docked), true), "\x1b[36mDOCKED\x1b[0m");
+        assert_eq!(highlight("+K+", Highlight::Klingon, true), "\x1b[31m+K+\x1b[0m");
+        assert_eq!(highlight("150 UNIT HIT", Highlight::EnterpriseHit, true), "\x1b[1;31m150 UNIT HIT\x1b[0m");
+        assert_eq!(highlight("300 UNIT HIT", Highlight::EnemyHit, true), "\x1b[33m300 UNIT HIT\x1b[0m");
+        assert_eq!(highlight("300 UNIT HIT", Highlight::EnemyHit, false), "300 UNIT HIT");
+    }
+}
@@ -3,12 +3,98 @@
 //! Provides traits for input and output operations, enabling testing
 //! by allowing mock implementations.
 
-use std::io::{self, Write};
+use std::collections::VecDeque;
+use std::hash::Hasher;
+use std::io;
+#[cfg(feature = "terminal")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::models::compat::CompatMode;
+use crate::models::digest::Fnv1a;
+use crate::models::errors::{GameError, GameResult};
+use crate::models::output_event::OutputEvent;
+
+pub mod ansi;
+pub mod testing;
+
+#[cfg(feature = "terminal")]
+pub use ansi::AnsiTerminalIO;
 
 /// Trait for reading user input
 pub trait InputReader {
     /// Read a line of input from the user with a prompt
     fn read_line(&mut self, prompt: &str) -> Result<String, io::Error>;
+
+    /// Ask a yes/no question using modern answer parsing.
+    /// See [`confirm_with`] for the rules.
+    fn confirm(&mut self, prompt: &str) -> GameResult<bool> {
+        confirm_with(self, prompt, CompatMode::Modern)
+    }
+}
+
+/// Ask a yes/no question, re-prompting until the answer parses.
+/// An empty answer (or end of input) counts as no.
+pub fn confirm_with<R: InputReader + ?Sized>(
+    reader: &mut R,
+    prompt: &str,
+    mode: CompatMode,
+) -> GameResult<bool> {
+    loop {
+        let answer = match reader.read_line(prompt) {
+            Ok(answer) => answer,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        if answer.trim().is_empty() {
+            return Ok(false);
+        }
+        if let Some(yes) = parse_yes_no(&answer, mode) {
+            return Ok(yes);
+        }
+    }
+}
+
+/// Parse a yes/no answer. Y/YES and N/NO are always accepted, in any case;
+/// classic mode also takes the naval AYE and NAY.
+pub fn parse_yes_no(answer: &str, mode: CompatMode) -> Option<bool> {
+    match (answer.trim().to_uppercase().as_str(), mode) {
+        ("Y" | "YES", _) => Some(true),
+        ("N" | "NO", _) => Some(false),
+        ("AYE", CompatMode::Classic) => Some(true),
+        ("NAY", CompatMode::Classic) => Some(false),
+        _ => None,
+    }
+}
+
+impl<R: InputReader + ?Sized> InputReader for Box<R> {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        (**self).read_line(prompt)
+    }
+}
+
+/// Whether an error is the player's input running out, e.g. the end of a
+/// `--script` file. The game ends quietly rather than reporting it.
+pub fn is_end_of_input(error: &GameError) -> bool {
+    matches!(error, GameError::IoError(e) if e.kind() == io::ErrorKind::UnexpectedEof)
+}
+
+/// Whether an error is the player pressing Ctrl-C at a prompt (see
+/// [`interrupt`]). The game loop takes the turn back and pauses.
+pub fn is_interrupted(error: &GameError) -> bool {
+    matches!(error, GameError::IoError(e) if e.kind() == io::ErrorKind::Interrupted)
+}
+
+/// Set by [`interrupt`] until the terminal next reads a line.
+#[cfg(feature = "terminal")]
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Note a Ctrl-C from the player, for a signal handler to call. The line
+/// [`TerminalIO`] is reading is then discarded and fails with
+/// `ErrorKind::Interrupted`. Returns false if an earlier Ctrl-C has not
+/// been noticed yet, so a second press can end the program.
+#[cfg(feature = "terminal")]
+pub fn interrupt() -> bool {
+    !INTERRUPTED.swap(true, Ordering::SeqCst)
 }
 
 /// Trait for writing output to the user
@@ -18,21 +104,61 @@ pub trait OutputWriter {
     fn write(&mut self, message: &str);
     /// Write a message with a newline
     fn writeln(&mut self, message: &str);
+
+    /// Whether this writer shows ANSI color. Presenters color what they
+    /// write with [`ansi::highlight`] when it does.
+    fn color(&self) -> bool {
+        false
+    }
+
+    /// Take a structured event to keep as it is, as [`EventQueue`] does
+    /// for frontends that draw their own display. Writers that show text
+    /// hand it back, and it is written to them as lines instead; services
+    /// report events through `ui::presenters::Emit`, which does both.
+    fn accept(&mut self, event: OutputEvent) -> Option<OutputEvent> {
+        Some(event)
+    }
+}
+
+impl<W: OutputWriter + ?Sized> OutputWriter for Box<W> {
+    fn write(&mut self, message: &str) {
+        (**self).write(message)
+    }
+
+    fn writeln(&mut self, message: &str) {
+        (**self).writeln(message)
+    }
+
+    fn accept(&mut self, event: OutputEvent) -> Option<OutputEvent> {
+        (**self).accept(event)
+    }
+
+    fn color(&self) -> bool {
+        (**self).color()
+    }
 }
 
 /// Terminal I/O implementation using stdin/stdout
+#[cfg(feature = "terminal")]
 pub struct TerminalIO;
 
+#[cfg(feature = "terminal")]
 impl InputReader for TerminalIO {
     fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        use std::io::Write;
+
         print!("{} ", prompt);
         io::stdout().flush()?;
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
+        if INTERRUPTED.swap(false, Ordering::SeqCst) {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
         Ok(input)
     }
 }
 
+#[cfg(feature = "terminal")]
 impl OutputWriter for TerminalIO {
     fn write(&mut self, message: &str) {
         print!("{}", message);
@@ -43,60 +169,480 @@ impl OutputWriter for TerminalIO {
     }
 }
 
-#[cfg(test)]
-pub mod test_utils {
-    use super::*;
-    use std::collections::VecDeque;
+/// Output writer that collects everything written, for frontends that
+/// send game output somewhere other than a terminal.
+#[derive(Debug, Default)]
+pub struct BufferedOutput {
+    text: String,
+}
 
-    /// Mock input reader for testing
-    #[allow(dead_code)]
-    pub struct MockInput {
-        responses: VecDeque<String>,
+impl BufferedOutput {
+    /// Take everything written so far, leaving the buffer empty.
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.text)
+    }
+}
+
+impl OutputWriter for BufferedOutput {
+    fn write(&mut self, message: &str) {
+        self.text.push_str(message);
     }
 
-    impl MockInput {
-        #[allow(dead_code)]
-        pub fn new(responses: Vec<&str>) -> Self {
-            Self {
-                responses: responses.into_iter().map(|s| s.to_string()).collect(),
-            }
+    fn writeln(&mut self, message: &str) {
+        self.text.push_str(message);
+        self.text.push('\n');
+    }
+}
+
+/// Output writer that queues events instead of showing them, for
+/// frontends that draw their own display. Plain text written through it is
+/// queued as [`OutputEvent::Message`] and [`OutputEvent::Text`].
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    events: Vec<OutputEvent>,
+}
+
+impl EventQueue {
+    /// Events queued so far, oldest first.
+    pub fn events(&self) -> &[OutputEvent] {
+        &self.events
+    }
+
+    /// Take every queued event, leaving the queue empty.
+    pub fn drain(&mut self) -> Vec<OutputEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Drop events queued after the first `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.events.truncate(len);
+    }
+}
+
+/// Input reader for frontends that cannot block waiting for the player,
+/// such as a browser page awaiting a JavaScript promise. It answers from
+/// the lines it was given and, once they run out, fails with
+/// `ErrorKind::WouldBlock` and remembers the prompt it was asked, so the
+/// frontend can get the answer and run the command again with it.
+/// See `GameEngine::try_execute`.
+#[derive(Debug, Default)]
+pub struct PendingInput {
+    lines: VecDeque<String>,
+    awaiting: Option<String>,
+}
+
+impl PendingInput {
+    pub fn new<S: Into<String>>(lines: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            lines: lines.into_iter().map(Into::into).collect(),
+            awaiting: None,
+        }
+    }
+
+    /// The prompt that found no answer, if any did.
+    pub fn awaiting(&self) -> Option<&str> {
+        self.awaiting.as_deref()
+    }
+}
+
+impl InputReader for PendingInput {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        self.lines.pop_front().ok_or_else(|| {
+            self.awaiting = Some(prompt.to_string());
+            io::Error::new(io::ErrorKind::WouldBlock, format!("waiting for {}", prompt))
+        })
+    }
+}
+
+/// Whether an error is [`PendingInput`] running out of answers.
+pub fn is_pending(error: &GameError) -> bool {
+    matches!(error, GameError::IoError(e) if e.kind() == io::ErrorKind::WouldBlock)
+}
+
+impl OutputWriter for EventQueue {
+    fn write(&mut self, message: &str) {
+        self.events.push(OutputEvent::Text(message.to_string()));
+    }
+
+    fn writeln(&mut self, message: &str) {
+        self.events.push(OutputEvent::Message(message.to_string()));
+    }
+
+    fn accept(&mut self, event: OutputEvent) -> Option<OutputEvent> {
+        self.events.push(event);
+        None
+    }
+}
+
+/// Number of input lines kept by [`RecordingReader::history`].
+pub const HISTORY_LEN: usize = 50;
+
+/// Input reader that digests every line read through it, including the
+/// answers to sub-prompts, so a game's full input can be fingerprinted.
+/// The most recent lines are kept for crash reports, and every line for
+/// replays.
+pub struct RecordingReader<R> {
+    inner: R,
+    digest: Fnv1a,
+    history: VecDeque<String>,
+    transcript: Vec<String>,
+}
+
+impl<R: InputReader> RecordingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            digest: Fnv1a::default(),
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            transcript: Vec::new(),
+        }
+    }
+
+    /// The last [`HISTORY_LEN`] lines read, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    /// Every line read so far, oldest first.
+    pub fn transcript(&self) -> &[String] {
+        &self.transcript
+    }
+
+    /// Digest of all input read so far.
+    pub fn digest(&self) -> u64 {
+        self.digest.finish()
+    }
+
+    /// Forget every line after the first `len`, as if never read.
+    pub fn rewind(&mut self, len: usize) {
+        self.transcript.truncate(len);
+        self.digest = Fnv1a::default();
+        for line in &self.transcript {
+            self.digest.write(line.as_bytes());
+            self.digest.write_u8(b'\n');
+        }
+        let kept = self.transcript.len().saturating_sub(HISTORY_LEN);
+        self.history = self.transcript[kept..].iter().cloned().collect();
+    }
+
+    /// Record a line the player entered some other way, such as a key
+    /// press standing for a command, as if it had been read.
+    pub fn record(&mut self, line: &str) {
+        self.digest.write(line.as_bytes());
+        self.digest.write_u8(b'\n');
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(line.to_string());
+        self.transcript.push(line.to_string());
+    }
+
+    /// The reader underneath, for input that should not be recorded.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: InputReader> InputReader for RecordingReader<R> {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        let input = self.inner.read_line(prompt)?;
+        self.record(input.trim_end_matches(['\r', '\n']));
+        Ok(input)
+    }
+}
+
+/// Input reader that answers a command's first prompt with what the player
+/// typed after the command, e.g. "3 4" from "AUTO 3 4", and reads every
+/// later prompt from `inner`. The typed-ahead answer is part of the command
+/// line, so it is not read through `inner` again.
+pub struct TypeAhead<'a> {
+    answer: Option<String>,
+    inner: &'a mut dyn InputReader,
+}
+
+impl<'a> TypeAhead<'a> {
+    /// A blank `answer` leaves every prompt to `inner`.
+    pub fn new(answer: &str, inner: &'a mut dyn InputReader) -> Self {
+        let answer = answer.trim();
+        Self {
+            answer: (!answer.is_empty()).then(|| answer.to_string()),
+            inner,
+        }
+    }
+}
+
+impl InputReader for TypeAhead<'_> {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        match self.answer.take() {
+            Some(answer) => Ok(answer),
+            None => self.inner.read_line(prompt),
         }
     }
+}
+
+/// Input reader that answers prompts from a script, echoing each line after
+/// its prompt as if typed, then hands over to `inner` once the script runs
+/// out. Used to play back replays.
+#[cfg(feature = "terminal")]
+pub struct ScriptedReader<R> {
+    script: VecDeque<String>,
+    inner: R,
+}
 
-    impl InputReader for MockInput {
-        fn read_line(&mut self, _prompt: &str) -> Result<String, io::Error> {
-            self.responses
-                .pop_front()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "No more mock responses"))
+#[cfg(feature = "terminal")]
+impl<R: InputReader> ScriptedReader<R> {
+    pub fn new(script: Vec<String>, inner: R) -> Self {
+        Self {
+            script: script.into(),
+            inner,
         }
     }
+}
 
-    /// Mock output writer for testing
-    pub struct MockOutput {
-        pub messages: Vec<String>,
+#[cfg(feature = "terminal")]
+impl<R: InputReader> InputReader for ScriptedReader<R> {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        match self.script.pop_front() {
+            Some(line) => {
+                println!("{} {}", prompt, line);
+                Ok(line)
+            }
+            None => self.inner.read_line(prompt),
+        }
     }
+}
 
-    impl Default for MockOutput {
-        fn default() -> Self {
-            Self::new()
+/// Input reader that takes every line from a script, such as a `--script`
+/// file, echoing each after its prompt as if typed. Unlike
+/// [`ScriptedReader`] it never falls back to the keyboard: once the script
+/// runs out it fails with `ErrorKind::UnexpectedEof` (see
+/// [`is_end_of_input`]).
+#[cfg(feature = "terminal")]
+pub struct FileInput<R, W> {
+    lines: io::Lines<R>,
+    echo: W,
+}
+
+#[cfg(feature = "terminal")]
+impl<R: io::BufRead, W: io::Write> FileInput<R, W> {
+    pub fn new(script: R, echo: W) -> Self {
+        Self {
+            lines: script.lines(),
+            echo,
         }
     }
+}
+
+#[cfg(feature = "terminal")]
+impl<W: io::Write> FileInput<io::BufReader<std::fs::File>, W> {
+    pub fn open(path: &std::path::Path, echo: W) -> io::Result<Self> {
+        Ok(Self::new(io::BufReader::new(std::fs::File::open(path)?), echo))
+    }
+}
 
-    impl MockOutput {
-        pub fn new() -> Self {
-            Self {
-                messages: Vec::new(),
+#[cfg(feature = "terminal")]
+impl<R: io::BufRead, W: io::Write> InputReader for FileInput<R, W> {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        match self.lines.next() {
+            Some(line) => {
+                let line = line?;
+                writeln!(self.echo, "{} {}", prompt, line)?;
+                Ok(line)
+            }
+            None => {
+                writeln!(self.echo, "{}", prompt)?;
+                Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of script"))
             }
         }
     }
+}
+
+/// Output writer that writes to a file (`--output`) or any other stream,
+/// so a scripted game's transcript can be compared with a golden copy.
+/// Write errors are ignored, as they are on the terminal.
+#[cfg(feature = "terminal")]
+pub struct FileOutput<W> {
+    inner: W,
+}
+
+#[cfg(feature = "terminal")]
+impl<W: io::Write> FileOutput<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl<W: io::Write> OutputWriter for FileOutput<W> {
+    fn write(&mut self, message: &str) {
+        let _ = self.inner.write_all(message.as_bytes());
+    }
+
+    fn writeln(&mut self, message: &str) {
+        let _ = writeln!(self.inner, "{}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::MockInput;
+    use super::*;
+
+    #[test]
+    fn parse_yes_no_accepts_mode_specific_answers() {
+        assert_eq!(parse_yes_no("y", CompatMode::Modern), Some(true));
+        assert_eq!(parse_yes_no(" Yes \n", CompatMode::Modern), Some(true));
+        assert_eq!(parse_yes_no("NO", CompatMode::Modern), Some(false));
+        assert_eq!(parse_yes_no("aye", CompatMode::Modern), None);
+        assert_eq!(parse_yes_no("aye", CompatMode::Classic), Some(true));
+        assert_eq!(parse_yes_no("NAY", CompatMode::Classic), Some(false));
+        assert_eq!(parse_yes_no("maybe", CompatMode::Classic), None);
+    }
+
+    #[test]
+    fn confirm_reprompts_until_answer_parses() {
+        let mut input = MockInput::new(vec!["maybe", "aye", "yes"]);
+        assert!(input.confirm("SURE").unwrap());
+
+        let mut input = MockInput::new(vec!["aye"]);
+        assert!(confirm_with(&mut input, "SURE", CompatMode::Classic).unwrap());
+    }
+
+    #[test]
+    fn confirm_treats_empty_answer_and_end_of_input_as_no() {
+        assert!(!MockInput::new(vec![""]).confirm("SURE").unwrap());
+        assert!(!MockInput::new(vec![]).confirm("SURE").unwrap());
+        assert!(!MockInput::new(vec!["maybe"]).confirm("SURE").unwrap());
+    }
+
+    #[test]
+    fn event_queue_keeps_events_that_text_writers_hand_back() {
+        let hit = OutputEvent::TorpedoTrack { x: 2, y: 7 };
+        let mut queue = EventQueue::default();
+        assert_eq!(queue.accept(hit.clone()), None);
+        queue.writeln("DONE");
+        assert_eq!(queue.drain(), vec![hit.clone(), OutputEvent::Message("DONE".to_string())]);
+        assert!(queue.events().is_empty());
+
+        let mut text = BufferedOutput::default();
+        assert_eq!(text.accept(hit.clone()), Some(hit));
+        assert_eq!(text.take(), "");
+    }
+
+    #[test]
+    fn pending_input_reports_the_unanswered_prompt() {
+        let mut input = PendingInput::new(["3"]);
+        assert_eq!(input.read_line("COURSE (1-9)").unwrap(), "3");
+        assert_eq!(input.awaiting(), None);
+
+        let error = GameError::from(input.read_line("WARP FACTOR (0-8)").unwrap_err());
+        assert!(is_pending(&error));
+        assert_eq!(input.awaiting(), Some("WARP FACTOR (0-8)"));
+        assert!(!is_pending(&GameError::from(io::Error::from(io::ErrorKind::UnexpectedEof))));
+    }
+
+    #[test]
+    fn recording_reader_passes_input_through() {
+        let mut reader = RecordingReader::new(MockInput::new(vec!["1", "2"]));
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "1");
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "2");
+    }
+
+    #[test]
+    fn recording_keeps_full_transcript() {
+        let lines: Vec<String> = (0..HISTORY_LEN + 5).map(|i| i.to_string()).collect();
+        let mut reader = RecordingReader::new(MockInput::new(lines.iter().map(String::as_str).collect()));
+        for _ in &lines {
+            reader.read_line("COMMAND").unwrap();
+        }
+        assert_eq!(reader.history().count(), HISTORY_LEN);
+        assert_eq!(reader.transcript(), &lines[..]);
+    }
+
+    #[test]
+    fn type_ahead_answers_the_first_prompt_only() {
+        let mut inner = MockInput::new(vec!["200"]);
+        let mut input = TypeAhead::new(" 3 4 ", &mut inner);
+        assert_eq!(input.read_line("DESTINATION").unwrap(), "3 4");
+        assert_eq!(input.read_line("ENERGY").unwrap(), "200");
+        assert_eq!(inner.prompts(), ["ENERGY"]);
+
+        let mut inner = MockInput::new(vec!["1"]);
+        assert_eq!(TypeAhead::new("", &mut inner).read_line("COURSE").unwrap(), "1");
+    }
 
-    impl OutputWriter for MockOutput {
-        fn write(&mut self, message: &str) {
-            self.messages.push(message.to_string());
+    #[test]
+    #[cfg(feature = "terminal")]
+    fn scripted_reader_falls_back_after_script() {
+        let mut reader = ScriptedReader::new(vec!["1".to_string()], MockInput::new(vec!["2"]));
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "1");
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "2");
+        assert!(reader.read_line("COMMAND").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "terminal")]
+    fn file_input_echoes_each_line_and_ends_at_eof() {
+        let mut echo = Vec::new();
+        let mut reader = FileInput::new(io::Cursor::new("1\n  \n2\n"), &mut echo);
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "1");
+        assert_eq!(reader.read_line("COURSE (1-9)").unwrap(), "  ");
+        assert_eq!(reader.read_line("COMMAND").unwrap(), "2");
+        let error = GameError::from(reader.read_line("COMMAND").unwrap_err());
+        assert!(is_end_of_input(&error));
+        assert!(!is_pending(&error));
+        assert_eq!(String::from_utf8(echo).unwrap(), "COMMAND 1\nCOURSE (1-9)   \nCOMMAND 2\nCOMMAND\n");
+
+        let mut text = Vec::new();
+        let mut output = FileOutput::new(&mut text);
+        output.write("> ");
+        output.writeln("DONE");
+        assert_eq!(text, b"> DONE\n");
+    }
+
+    #[test]
+    fn recording_digest_depends_on_input_order_not_line_endings() {
+        let digest_of = |lines: Vec<&str>| {
+            let mut reader = RecordingReader::new(MockInput::new(lines.clone()));
+            for _ in &lines {
+                reader.read_line("COMMAND").unwrap();
+            }
+            reader.digest()
+        };
+        assert_eq!(digest_of(vec!["1", "2"]), digest_of(vec!["1\n", "2\r\n"]));
+        assert_ne!(digest_of(vec!["1", "2"]), digest_of(vec!["2", "1"]));
+        assert_ne!(digest_of(vec!["12"]), digest_of(vec!["1", "2"]));
+    }
+
+    #[test]
+    fn rewinding_forgets_lines_as_if_never_read() {
+        let lines: Vec<String> = (0..HISTORY_LEN + 5).map(|i| i.to_string()).collect();
+        let mut reader = RecordingReader::new(MockInput::new(lines.iter().map(String::as_str).collect()));
+        for _ in &lines {
+            reader.read_line("COMMAND").unwrap();
         }
+        reader.rewind(HISTORY_LEN + 2);
 
-        fn writeln(&mut self, message: &str) {
-            self.messages.push(format!("{}\n", message));
+        let mut fresh = RecordingReader::new(MockInput::new(lines.iter().map(String::as_str).collect()));
+        for _ in 0..HISTORY_LEN + 2 {
+            fresh.read_line("COMMAND").unwrap();
         }
+        assert_eq!(reader.transcript(), fresh.transcript());
+        assert_eq!(reader.digest(), fresh.digest());
+        assert!(reader.history().eq(fresh.history()));
+    }
+
+    #[test]
+    fn recording_history_keeps_most_recent_lines() {
+        let lines: Vec<String> = (0..HISTORY_LEN + 5).map(|i| format!("{}\n", i)).collect();
+        let mut reader = RecordingReader::new(MockInput::new(lines.iter().map(String::as_str).collect()));
+        for _ in &lines {
+            reader.read_line("COMMAND").unwrap();
+        }
+
+        let history: Vec<&str> = reader.history().collect();
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(history[0], "5");
+        assert_eq!(history[HISTORY_LEN - 1], (HISTORY_LEN + 4).to_string());
     }
 }
@@ -0,0 +1,142 @@
+//! Mock I/O for tests and bots
+//!
+//! Scripted input and captured output, for testing commands without a
+//! terminal. Public so downstream crates writing bots and tests can use
+//! them too.
+
+use std::collections::VecDeque;
+use std::io;
+
+use super::{InputReader, OutputWriter};
+
+/// Input reader that answers prompts from a script. Answers keyed by
+/// prompt text go to the first prompt containing that text; every other
+/// prompt takes the next queued answer. Running out of answers fails with
+/// `ErrorKind::UnexpectedEof`.
+#[derive(Debug, Default)]
+pub struct MockInput {
+    responses: VecDeque<String>,
+    keyed: Vec<(String, String)>,
+    prompts: Vec<String>,
+}
+
+impl MockInput {
+    pub fn new(responses: Vec<&str>) -> Self {
+        Self {
+            responses: responses.into_iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Answer the next prompt containing `prompt` with `answer`, ahead of
+    /// the queued answers.
+    pub fn answer(mut self, prompt: &str, answer: &str) -> Self {
+        self.keyed.push((prompt.to_string(), answer.to_string()));
+        self
+    }
+
+    /// Every prompt asked so far, oldest first.
+    pub fn prompts(&self) -> &[String] {
+        &self.prompts
+    }
+}
+
+impl InputReader for MockInput {
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        self.prompts.push(prompt.to_string());
+        if let Some(i) = self.keyed.iter().position(|(key, _)| prompt.contains(key.as_str())) {
+            return Ok(self.keyed.remove(i).1);
+        }
+        self.responses
+            .pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "No more mock responses"))
+    }
+}
+
+/// Output writer that keeps everything written, one entry per call.
+#[derive(Debug, Default)]
+pub struct MockOutput {
+    pub messages: Vec<String>,
+    /// Reported as the writer's color support.
+    pub color: bool,
+}
+
+impl MockOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A writer that reports ANSI color, to see what presenters color.
+    pub fn with_color() -> Self {
+        Self { color: true, ..Self::default() }
+    }
+
+    /// Everything written, as one string.
+    pub fn text(&self) -> String {
+        self.messages.concat()
+    }
+
+    /// Whether any output contains `needle`.
+    pub fn contains(&self, needle: &str) -> bool {
+        self.text().contains(needle)
+    }
+
+    /// The first output line containing `needle`.
+    ///
+    /// # Panics
+    ///
+    /// If no line contains it, showing everything written.
+    pub fn expect_line_containing(&self, needle: &str) -> String {
+        let text = self.text();
+        match text.lines().find(|line| line.contains(needle)) {
+            Some(line) => line.to_string(),
+            None => panic!("no output line contains {:?}; output was:\n{}", needle, text),
+        }
+    }
+}
+
+impl OutputWriter for MockOutput {
+    fn write(&mut self, message: &str) {
+        self.messages.push(message.to_string());
+    }
+
+    fn writeln(&mut self, message: &str) {
+        self.messages.push(format!("{}\n", message));
+    }
+
+    fn color(&self) -> bool {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_answers_go_to_their_prompt_first() {
+        let mut input = MockInput::new(vec!["3", "1"]).answer("WARP", "0.5");
+        assert_eq!(input.read_line("COURSE (1-9)").unwrap(), "3");
+        assert_eq!(input.read_line("WARP FACTOR (0-8)").unwrap(), "0.5");
+        assert_eq!(input.read_line("WARP FACTOR (0-8)").unwrap(), "1");
+        assert_eq!(input.read_line("COMMAND").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(input.prompts().len(), 4);
+        assert_eq!(input.prompts()[1], "WARP FACTOR (0-8)");
+    }
+
+    #[test]
+    fn output_lines_can_be_searched() {
+        let mut output = MockOutput::new();
+        output.write("ENERGY ");
+        output.writeln("3000");
+        output.writeln("SHIELDS 0");
+        assert!(output.contains("ENERGY 3000\n"));
+        assert_eq!(output.expect_line_containing("SHIELDS"), "SHIELDS 0");
+    }
+
+    #[test]
+    #[should_panic(expected = "no output line contains \"TORPEDO\"")]
+    fn missing_lines_fail_with_the_output() {
+        MockOutput::new().expect_line_containing("TORPEDO");
+    }
+}
@@ -15,6 +15,16 @@
 //! - [`services`] - Game services (combat, navigation, scanning, etc.)
 //! - [`io`] - Input/output abstractions for testing
 //! - [`ui`] - User interface and presentation logic
+//! - [`prelude`] - The types a frontend needs, in one import
+//! - `cli` - Arguments, replays, and crash reports (requires the `cli` feature)
+//! - `ui::tui` - Full-screen terminal frontend (requires the `tui` feature)
+//! - `net` - Leaderboard score submission (requires the `net` feature)
+//!
+//! The `terminal` and `cli` features are on by default. Without them the
+//! library has no terminal or file access beyond the I/O traits, for
+//! embedding in other frontends, including browsers through
+//! `wasm32-unknown-unknown`. The optional `serde` feature makes the
+//! galaxy and its models serializable, random generator state included.
 //!
 //! # Example
 //!
@@ -30,7 +40,19 @@ pub mod models;
 pub mod services;
 pub mod io;
 pub mod ui;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "net")]
+pub mod net;
+
+pub mod prelude;
 
 // Re-export commonly used types
-pub use game_engine::{GameEngine, GameState, DefeatReason};
+pub use game_engine::{GameEngine, GameOverReport, GameState, DefeatReason, Execution};
+pub use models::output_event::OutputEvent;
+pub use models::status::StatusReport;
+pub use models::turn::TurnSummary;
+pub use services::battle_log::GameEvent;
+pub use services::commands::{CommandId, CustomCommand};
+pub use services::preview::{Command, PreviewReport};
+pub use services::turn::PlayerAction;
@@ -1,21 +1,45 @@
-mod cli;
-mod game_engine;
-mod models;
-mod services;
-mod io;
-mod ui;
-
 use std::io::{self as stdio, Write};
+use std::panic::{self, AssertUnwindSafe};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = cli::args::parse();
+use startrek::{cli, io, models, services, ui};
+
+/// Where a game paused with Ctrl-C is saved, unless it is being recorded.
+const SAVE_FILE: &str = "startrek-save.replay";
 
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = cli::args::parse();
+    #[cfg(feature = "net")]
+    if let Some(addr) = &args.serve_http {
+        startrek::net::server::serve(addr.as_str())?;
+        return Ok(());
+    }
+    if let Some(constraints) = &args.find_seed {
+        cli::seeds::find_seed(&args, constraints);
+        return Ok(());
+    }
+    if let Some(options) = &args.simulate {
+        cli::simulate::run(&args, options);
+        return Ok(());
+    }
+    if let Some(games) = args.autoplay {
+        cli::autoplay::run(&args, games);
+        return Ok(());
+    }
+    if args.scores {
+        let table = cli::scores::load_or_empty(&cli::scores::default_path());
+        ui::presenters::HighScorePresenter::show_high_scores(&table, &mut io::TerminalIO);
+        return Ok(());
+    }
+    let replay = match &args.replay {
+        Some(path) => Some(cli::replay::Replay::load(path)?),
+        None => None,
+    };
     // Centered title
     print_centered("STAR TREK", 80);
     println!();
 
-    // Instructions prompt (only if no seed provided via CLI)
-    if args.seed.is_none() {
+    // Instructions prompt (only if no seed provided via CLI or replay)
+    if args.seed.is_none() && replay.is_none() {
         print!("ENTER 1 OR 2 FOR INSTRUCTIONS (ENTER 2 TO PAGE) ");
         stdio::stdout().flush()?;
         let mut input = String::new();
@@ -27,8 +51,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Seed prompt (only if not provided via CLI)
-    let seed: u64 = if let Some(s) = args.seed {
+    // Seed prompt (only if not provided via CLI or replay)
+    let seed: u64 = if let Some(replay) = &replay {
+        replay.header.seed
+    } else if let Some(s) = args.seed {
         s
     } else {
         print!("ENTER SEED NUMBER ");
@@ -38,12 +64,293 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         input.trim().parse().unwrap_or(0)
     };
 
+    // Difficulty prompt (only if not provided via CLI or replay)
+    if args.seed.is_none() && replay.is_none() && args.difficulty.is_none() && !args.tournament {
+        print!("ENTER DIFFICULTY (1 = NOVICE, 2 = CAPTAIN, 3 = ADMIRAL) ");
+        stdio::stdout().flush()?;
+        let mut input = String::new();
+        stdio::stdin().read_line(&mut input)?;
+        args.difficulty = models::difficulty::Difficulty::parse(&input);
+    }
+
+    // A replay's own flags decide the rules it is played under
+    let replay_args = replay.as_ref().map(|r| cli::args::parse_from(r.header.flags.clone()));
+    let rules = replay_args.as_ref().unwrap_or(&args);
+
+    // Replayed and scripted games never reach the high score table
+    let playing_back = replay.is_some() || args.script.is_some();
     println!("INITIALIZING...");
-    let mut game = services::game::Game::new(seed);
-    game.run()?;
+    cli::crash::install_panic_hook();
+    // A scripted game has nobody at the keyboard to pause it for
+    if args.script.is_none() {
+        cli::interrupt::install_handler();
+    }
+    models::rng::set_dev_logging(args.dev_rng_log);
+    let header = cli::replay::ReplayHeader::new(seed, rules.logic_flags(), &rules.galaxy_options());
+    #[cfg(feature = "scenario-files")]
+    let header = match &rules.scenario_file {
+        Some(file) => header.with_scenario_digest(file.digest),
+        None => header,
+    };
+    // Scripted input is echoed alongside the rest of the output
+    let output_file = match &args.output {
+        Some(path) => Some(std::fs::File::create(path)?),
+        None => None,
+    };
+    let input: Box<dyn io::InputReader + Send> = match replay {
+        Some(replay) => {
+            if let Err(reason) = replay.header.check_compatible(&header) {
+                if !args.force {
+                    eprintln!("Error: cannot play back this replay: {}", reason);
+                    eprintln!("Use --force to play it back anyway.");
+                    std::process::exit(1);
+                }
+                eprintln!("Warning: {}; playing back anyway (--force)", reason);
+            }
+            Box::new(io::ScriptedReader::new(replay.inputs, io::TerminalIO))
+        }
+        None => match (&args.script, &output_file) {
+            (Some(path), Some(file)) => Box::new(io::FileInput::open(path, file.try_clone()?)?),
+            (Some(path), None) => Box::new(io::FileInput::open(path, stdio::stdout())?),
+            (None, _) => Box::new(io::TerminalIO),
+        },
+    };
+    #[cfg(feature = "scenario-files")]
+    let mission = rules.scenario_file.as_ref().map(|file| {
+        println!("SCENARIO: {}", file.name.to_uppercase());
+        println!("{}", file.briefing);
+        startrek::GameEngine::from_galaxy(file.galaxy(seed, rules.galaxy_options()))
+    });
+    #[cfg(not(feature = "scenario-files"))]
+    let mission = None;
+    let mut engine = if let Some(engine) = mission {
+        engine
+    } else if rules.tournament {
+        startrek::GameEngine::new_tournament(seed)
+    } else if let Some(scenario) = rules.scenario {
+        println!("SCENARIO: {}", scenario.name.to_uppercase());
+        println!("{}", scenario.briefing);
+        startrek::GameEngine::from_galaxy(scenario.galaxy(seed, rules.galaxy_options()))
+    } else {
+        startrek::GameEngine::with_options(seed, rules.galaxy_options())
+    };
+    engine.galaxy_mut().set_symbols(args.symbols);
+    #[cfg(feature = "tui")]
+    if args.tui {
+        let played = ui::tui::run(&mut engine)?;
+        if engine.game_over_report().is_some() {
+            let mut output = io::AnsiTerminalIO::detect(args.no_color);
+            services::game::Game::show_final_summary(engine.galaxy(), &mut output);
+        }
+        let finished = Finished {
+            engine: &engine,
+            inputs: &played.transcript,
+            replay_hash: played.replay_hash,
+            battle_log: &played.battle_log,
+            save_requested: false,
+        };
+        keep_records(&args, header, &finished);
+        return record_result(&args, &finished, playing_back);
+    }
+    let mut game = services::game::Game::with_input(engine, input);
+    if args.classic {
+        game.set_compat_mode(models::compat::CompatMode::Classic);
+    }
+    match output_file {
+        Some(file) => game.set_output(Box::new(io::FileOutput::new(file))),
+        None => game.set_output(Box::new(io::AnsiTerminalIO::detect(args.no_color))),
+    }
+    if rules.undo {
+        game.enable_undo(1)?;
+    }
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| game.run()));
+    keep_records(&args, header, &Finished::from_game(&game));
+    match outcome {
+        Ok(result) => result?,
+        Err(payload) => {
+            report_crash(&game, payload.as_ref());
+            std::process::exit(101);
+        }
+    }
+    record_result(&args, &Finished::from_game(&game), playing_back)
+}
+
+/// What the post-game steps need from a game, whichever frontend played it.
+struct Finished<'a> {
+    engine: &'a startrek::GameEngine,
+    inputs: &'a [String],
+    replay_hash: u64,
+    battle_log: &'a services::battle_log::BattleLog,
+    save_requested: bool,
+}
+
+impl<'a> Finished<'a> {
+    fn from_game(game: &'a services::game::Game) -> Self {
+        Finished {
+            engine: game.engine(),
+            inputs: game.input_transcript(),
+            replay_hash: game.replay_hash(),
+            battle_log: game.battle_log(),
+            save_requested: game.save_requested(),
+        }
+    }
+}
+
+/// Write the RNG log, replay, save and event export the player asked for.
+/// These are kept even for a game that crashed, to help reproduce it.
+fn keep_records(args: &cli::args::Args, header: cli::replay::ReplayHeader, game: &Finished) {
+    if args.dev_rng_log {
+        write_rng_log(game);
+    }
+    if let Some(path) = &args.record {
+        write_replay(path, header.clone(), game);
+    }
+    // A game saved from the pause menu is a replay to pick up from
+    if game.save_requested {
+        let saved = match &args.record {
+            Some(path) => Some(path.clone()),
+            None => save_game(header, game),
+        };
+        if let Some(path) = saved {
+            println!("RESUME THE GAME WITH --replay {}", path.display());
+        }
+    }
+    if let Some(path) = &args.export_events {
+        export_events(path, game);
+    }
+}
+
+/// Enter a played game's result on the high score table and leaderboard.
+#[cfg_attr(not(feature = "net"), allow(unused_variables))]
+fn record_result(args: &cli::args::Args, game: &Finished, playing_back: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !playing_back {
+        record_high_score(game)?;
+    }
+    #[cfg(feature = "net")]
+    if let Some(url) = &args.submit_url {
+        submit_score(url, game);
+    }
     Ok(())
 }
 
+/// Ask a victorious player for their name and add them to the high score table.
+fn record_high_score(game: &Finished) -> Result<(), Box<dyn std::error::Error>> {
+    use startrek::services::scoring::HighScore;
+
+    let engine = game.engine;
+    let Some(report) = engine.game_over_report() else {
+        return Ok(());
+    };
+    let (Some(rating), Some(rank)) = (report.rating, report.rank) else {
+        return Ok(());
+    };
+    let path = cli::scores::default_path();
+    let mut table = cli::scores::load_or_empty(&path);
+    if !table.qualifies(rating) {
+        return Ok(());
+    }
+
+    println!();
+    print!("YOU HAVE EARNED A PLACE ON THE HIGH SCORE TABLE. ENTER YOUR NAME ");
+    stdio::stdout().flush()?;
+    let mut input = String::new();
+    stdio::stdin().read_line(&mut input)?;
+    let name = match input.trim() {
+        "" => "ANONYMOUS".to_string(),
+        name => name.to_string(),
+    };
+    table.insert(HighScore {
+        name,
+        seed: engine.galaxy().seed(),
+        rating,
+        rank,
+        date: cli::scores::today(),
+        turns: report.turns,
+    });
+    match cli::scores::save(&table, &path) {
+        Ok(()) => ui::presenters::HighScorePresenter::show_high_scores(&table, &mut io::TerminalIO),
+        Err(e) => eprintln!("Error: could not save high scores: {}", e),
+    }
+    Ok(())
+}
+
+/// Post the finished game's result, queueing it if the leaderboard is down.
+#[cfg(feature = "net")]
+fn submit_score(url: &str, game: &Finished) {
+    use startrek::net::scores::{ScoreSubmission, ScoreSubmitter, SubmitOutcome};
+
+    let Some(submission) = ScoreSubmission::from_engine(game.engine, game.replay_hash) else {
+        return;
+    };
+    match ScoreSubmitter::new(url).submit(&submission) {
+        Ok(SubmitOutcome::Sent { .. }) => println!("SCORE SUBMITTED."),
+        Ok(SubmitOutcome::Queued) => println!("LEADERBOARD UNREACHABLE; SCORE QUEUED FOR LATER."),
+        Ok(SubmitOutcome::Rejected { status }) => println!("LEADERBOARD REJECTED THE SCORE ({}).", status),
+        Err(e) => eprintln!("Error: could not queue score: {}", e),
+    }
+}
+
+/// Write the labeled RNG draws to `rng-<seed>.log` for diffing between runs.
+fn write_rng_log(game: &Finished) {
+    let galaxy = game.engine.galaxy();
+    let path = format!("rng-{}.log", galaxy.seed());
+    let result = std::fs::File::create(&path)
+        .map(stdio::BufWriter::new)
+        .and_then(|mut file| galaxy.rng().write_log(&mut file).and_then(|_| file.flush()));
+    match result {
+        Ok(()) => println!("RNG LOG WRITTEN TO {}", path),
+        Err(e) => eprintln!("Error: could not write RNG log: {}", e),
+    }
+}
+
+/// Save the game's input with the header it was played under.
+fn write_replay(path: &std::path::Path, header: cli::replay::ReplayHeader, game: &Finished) {
+    let replay = cli::replay::Replay {
+        header,
+        inputs: game.inputs.to_vec(),
+        seal: game.engine.tournament_seal(game.replay_hash),
+    };
+    match replay.save(path) {
+        Ok(()) => println!("REPLAY WRITTEN TO {}", path.display()),
+        Err(e) => eprintln!("Error: could not write replay: {}", e),
+    }
+}
+
+/// Save a paused game to [`SAVE_FILE`], asking first when that would
+/// replace an earlier save. Returns where the game was saved, if it was.
+fn save_game(header: cli::replay::ReplayHeader, game: &Finished) -> Option<std::path::PathBuf> {
+    let path = std::path::PathBuf::from(SAVE_FILE);
+    if path.exists() {
+        let prompt = format!("{} HOLDS AN EARLIER SAVE. OVERWRITE IT (Y/N)?", SAVE_FILE);
+        if !matches!(io::InputReader::confirm(&mut io::TerminalIO, &prompt), Ok(true)) {
+            println!("GAME NOT SAVED");
+            return None;
+        }
+    }
+    write_replay(&path, header, game);
+    Some(path)
+}
+
+/// Write the game's battle log as JSON Lines.
+fn export_events(path: &std::path::Path, game: &Finished) {
+    match cli::events::export(path, game.battle_log.events()) {
+        Ok(()) => println!("EVENTS WRITTEN TO {}", path.display()),
+        Err(e) => eprintln!("Error: could not write events: {}", e),
+    }
+}
+
+/// Write a crash bundle to the working directory and tell the player where it is.
+fn report_crash(game: &services::game::Game, payload: &(dyn std::any::Any + Send)) {
+    let report = cli::crash::CrashReport::from_game(game, payload);
+    match report.write_to(std::path::Path::new(".")) {
+        Ok(path) => {
+            eprintln!("THE GAME HAS CRASHED. A BUG REPORT WAS SAVED TO {}", path.display());
+            eprintln!("PLEASE ATTACH IT WHEN REPORTING THE PROBLEM.");
+        }
+        Err(e) => eprintln!("Error: could not write crash report: {}", e),
+    }
+}
+
 /// Print text centered within a given width.
 fn print_centered(text: &str, width: usize) {
     let padding = (width.saturating_sub(text.len())) / 2;
@@ -52,7 +359,7 @@ fn print_centered(text: &str, width: usize) {
 
 /// Display game instructions, optionally paged.
 fn show_instructions(paged: bool) {
-    let instructions = vec![
+    let intro = [
         "INSTRUCTIONS FOR STAR TREK",
         "",
         "YOU ARE CAPTAIN OF THE STARSHIP ENTERPRISE. YOUR MISSION IS TO",
@@ -63,22 +370,23 @@ fn show_instructions(paged: bool) {
         "EACH QUADRANT IS FURTHER DIVIDED INTO AN 8X8 GRID OF SECTORS.",
         "",
         "COMMANDS:",
-        "  0 = SET COURSE           Navigate to a new location",
-        "  1 = SHORT RANGE SCAN     View current quadrant",
-        "  2 = LONG RANGE SCAN      View surrounding quadrants",
-        "  3 = FIRE PHASERS         Attack with phasers",
-        "  4 = FIRE TORPEDOES       Attack with photon torpedoes",
-        "  5 = SHIELD CONTROL       Transfer energy to/from shields",
-        "  6 = DAMAGE REPORT        View status of ship systems",
-        "  7 = LIBRARY COMPUTER     Access computer functions",
+    ];
+    // Command list comes from the registry so it never drifts from the menu
+    let commands: Vec<String> = services::commands::COMMANDS
+        .iter()
+        .map(|spec| format!("  {} = {:<25} {}", spec.key, spec.name, spec.help))
+        .collect();
+    let outro = [
         "",
         "SHIP SYSTEMS:",
         "  Each system can be damaged during combat or navigation.",
         "  Damaged systems are repaired slowly during warp travel.",
+        "  Hits that get past the shields damage the hull; the ship",
+        "  is destroyed when hull integrity reaches 0%.",
         "",
         "DOCKING:",
         "  Move adjacent to a starbase to dock automatically.",
-        "  Docking restores energy, shields, and torpedoes.",
+        "  Docking restores energy, shields, torpedoes, and the hull.",
         "",
         "STRATEGY TIPS:",
         "  - Keep shields up when Klingons are present",
@@ -89,6 +397,11 @@ fn show_instructions(paged: bool) {
         "GOOD LUCK, CAPTAIN!",
         "",
     ];
+    let instructions: Vec<&str> = intro
+        .into_iter()
+        .chain(commands.iter().map(String::as_str))
+        .chain(outro)
+        .collect();
 
     if paged {
         // Display 20 lines at a time
@@ -0,0 +1,85 @@
+//! Achievements
+//!
+//! Milestones awarded at the end of a game, read from the galaxy state.
+
+use super::constants::GALAXY_SIZE;
+use super::galaxy::Galaxy;
+
+/// Quadrants that must be entered for [`Achievement::Explorer`].
+const EXPLORER_QUADRANTS: i32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    /// Entered at least half of the galaxy's quadrants.
+    Explorer,
+    /// Entered every quadrant in the galaxy.
+    GrandTour,
+    /// Won without losing a starbase.
+    Guardian,
+}
+
+impl Achievement {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Achievement::Explorer => "EXPLORER",
+            Achievement::GrandTour => "GRAND TOUR",
+            Achievement::Guardian => "GUARDIAN OF THE STARBASES",
+        }
+    }
+
+    /// Achievements earned in the game so far, in declaration order.
+    pub fn earned(galaxy: &Galaxy) -> Vec<Achievement> {
+        let visited = galaxy.quadrants_visited();
+        let mut earned = Vec::new();
+        if visited >= EXPLORER_QUADRANTS {
+            earned.push(Achievement::Explorer);
+        }
+        if visited == (GALAXY_SIZE * GALAXY_SIZE) as i32 {
+            earned.push(Achievement::GrandTour);
+        }
+        if galaxy.total_klingons() == 0 && galaxy.starbases_lost() == 0 {
+            earned.push(Achievement::Guardian);
+        }
+        earned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::position::QuadrantPosition;
+
+    fn visit(galaxy: &mut Galaxy, count: i32) {
+        let sector = galaxy.enterprise().sector();
+        for i in 0..count {
+            let pos = QuadrantPosition { x: i % 8 + 1, y: i / 8 + 1 };
+            galaxy.enterprise_mut().move_to(pos, sector);
+            galaxy.enter_quadrant();
+        }
+    }
+
+    #[test]
+    fn new_game_has_no_achievements() {
+        assert!(Achievement::earned(&Galaxy::new(42)).is_empty());
+    }
+
+    #[test]
+    fn exploration_achievements_follow_visited_quadrants() {
+        let mut galaxy = Galaxy::new(42);
+        visit(&mut galaxy, 32);
+        assert_eq!(Achievement::earned(&galaxy), vec![Achievement::Explorer]);
+
+        visit(&mut galaxy, 64);
+        assert_eq!(
+            Achievement::earned(&galaxy),
+            vec![Achievement::Explorer, Achievement::GrandTour]
+        );
+    }
+
+    #[test]
+    fn guardian_requires_victory_without_losses() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.set_total_klingons(0);
+        assert_eq!(Achievement::earned(&galaxy), vec![Achievement::Guardian]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Clocks
+//!
+//! Wall-clock time for head-to-head play. Game logic never reads the time
+//! itself; it is handed a [`Clock`], so servers use the system clock while
+//! tests and replays drive a [`ManualClock`].
+//!
+//! `std::time::Instant` panics on `wasm32-unknown-unknown`, so there is no
+//! [`SystemClock`] on that target; browser frontends supply their own
+//! [`Clock`] backed by the page's timer.
+
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
+
+/// Source of monotonic time, measured from an arbitrary origin.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+}
+
+/// The system's monotonic clock, measured from when it was created.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[derive(Debug)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Mutex<Duration>,
+}
+
+impl ManualClock {
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// One player's side of a chess clock: a budget of thinking time that runs
+/// down while the player is deciding on a command.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChessClock {
+    budget: Duration,
+    used: Duration,
+    /// When the clock was last started, if it is running.
+    started: Option<Duration>,
+}
+
+impl ChessClock {
+    pub fn new(budget: Duration) -> Self {
+        ChessClock {
+            budget,
+            used: Duration::ZERO,
+            started: None,
+        }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Start the clock. Does nothing if it is already running.
+    pub fn start(&mut self, now: Duration) {
+        self.started.get_or_insert(now);
+    }
+
+    /// Stop the clock, adding the time since it was started to the time used.
+    pub fn stop(&mut self, now: Duration) {
+        if let Some(started) = self.started.take() {
+            self.used += now.saturating_sub(started);
+        }
+    }
+
+    /// Thinking time used so far, including the running turn.
+    pub fn used(&self, now: Duration) -> Duration {
+        let running = self.started.map_or(Duration::ZERO, |started| now.saturating_sub(started));
+        self.used + running
+    }
+
+    /// Thinking time left; zero once the budget is spent.
+    pub fn remaining(&self, now: Duration) -> Duration {
+        self.budget.saturating_sub(self.used(now))
+    }
+
+    /// True once the player has used more than their budget.
+    pub fn is_flagged(&self, now: Duration) -> bool {
+        self.used(now) > self.budget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn chess_clock_only_runs_while_started() {
+        let mut clock = ChessClock::new(secs(60));
+        clock.start(secs(0));
+        assert_eq!(clock.remaining(secs(10)), secs(50));
+        clock.stop(secs(10));
+        // Time between turns is not charged
+        clock.start(secs(100));
+        clock.stop(secs(120));
+        assert_eq!(clock.used(secs(500)), secs(30));
+        assert_eq!(clock.remaining(secs(500)), secs(30));
+        assert!(!clock.is_flagged(secs(500)));
+    }
+
+    #[test]
+    fn chess_clock_flags_past_the_budget() {
+        let mut clock = ChessClock::new(secs(5));
+        clock.start(secs(0));
+        clock.start(secs(3));
+        assert!(!clock.is_flagged(secs(5)));
+        assert!(clock.is_flagged(secs(6)));
+        assert_eq!(clock.remaining(secs(6)), Duration::ZERO);
+    }
+
+    #[test]
+    fn manual_clock_advances_on_request() {
+        let clock = ManualClock::default();
+        assert_eq!(clock.now(), Duration::ZERO);
+        clock.advance(secs(2));
+        clock.advance(secs(3));
+        assert_eq!(clock.now(), secs(5));
+    }
+}
@@ -0,0 +1,186 @@
+//! Combat odds
+//!
+//! Expected-value estimate of a fight in the current quadrant, from the
+//! Klingon attack and phaser formulas with every random factor at its mean.
+
+use super::constants::{Device, HULL_DAMAGE_DIVISOR};
+use super::galaxy::Galaxy;
+
+/// Recommend fighting when the ship can take at least this many exchanges.
+const SAFE_EXCHANGES: f64 = 3.0;
+/// Recommend withdrawing when the ship cannot take this many exchanges.
+const UNSAFE_EXCHANGES: f64 = 2.0;
+
+/// What the computer advises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recommendation {
+    NoThreat,
+    Fight,
+    Marginal,
+    Withdraw,
+}
+
+impl Recommendation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Recommendation::NoThreat => "NO KLINGONS IN THIS QUADRANT",
+            Recommendation::Fight => "STAND AND FIGHT",
+            Recommendation::Marginal => "PROCEED WITH CAUTION",
+            Recommendation::Withdraw => "WITHDRAW",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatOdds {
+    pub klingons: usize,
+    pub klingon_shields: f64,
+    /// Docked: starbase shields absorb every Klingon shot.
+    pub protected: bool,
+    /// Mean damage of one round of Klingon fire.
+    pub expected_damage: f64,
+    /// Damage if every Klingon rolls its maximum.
+    pub worst_case_damage: f64,
+    /// Rounds of Klingon fire until the shields are gone; `None` if no
+    /// damage is expected.
+    pub exchanges_to_shield_failure: Option<f64>,
+    /// Rounds of Klingon fire until the hull gives out.
+    pub exchanges_to_destruction: Option<f64>,
+    /// Phaser energy for a single volley expected to destroy every Klingon.
+    pub phaser_energy_needed: f64,
+    pub available_energy: f64,
+}
+
+impl CombatOdds {
+    pub fn from_galaxy(galaxy: &Galaxy) -> Self {
+        let enterprise = galaxy.enterprise();
+        let here = enterprise.sector();
+        let targets: Vec<(f64, f64)> = galaxy
+            .sector_map()
+            .klingons
+            .iter()
+            .filter(|k| k.is_alive())
+            .map(|k| {
+                let dx = (k.sector.x - here.x) as f64;
+                let dy = (k.sector.y - here.y) as f64;
+                (k.shields, (dx * dx + dy * dy).sqrt())
+            })
+            .collect();
+        let protected = enterprise.is_adjacent_to_starbase(galaxy.sector_map().starbase);
+
+        // Klingon hit = shields / distance * 2 * rnd * aggression,
+        // mean shields / distance * aggression
+        let expected_damage = if protected {
+            0.0
+        } else {
+            let mean: f64 = targets.iter().map(|&(shields, distance)| shields / distance).sum();
+            mean * galaxy.klingon_aggression()
+        };
+        let shields = enterprise.shields().max(0.0);
+        let exchanges = |absorb: f64| (expected_damage > 0.0).then(|| absorb / expected_damage);
+
+        // Phaser hit = energy / count / distance * 2 * rnd; a damaged
+        // computer also scales the energy by rnd
+        let count = targets.len() as f64;
+        let mut phaser_energy_needed = targets
+            .iter()
+            .map(|&(shields, distance)| shields * count * distance)
+            .fold(0.0, f64::max);
+        if enterprise.is_damaged(Device::Computer) {
+            phaser_energy_needed *= 2.0;
+        }
+
+        CombatOdds {
+            klingons: targets.len(),
+            klingon_shields: targets.iter().map(|&(shields, _)| shields).sum(),
+            protected,
+            expected_damage,
+            worst_case_damage: expected_damage * 2.0,
+            exchanges_to_shield_failure: exchanges(shields),
+            exchanges_to_destruction: exchanges(shields + enterprise.hull() * HULL_DAMAGE_DIVISOR),
+            phaser_energy_needed,
+            available_energy: enterprise.energy(),
+        }
+    }
+
+    pub fn recommendation(&self) -> Recommendation {
+        if self.klingons == 0 {
+            return Recommendation::NoThreat;
+        }
+        match self.exchanges_to_destruction {
+            None => Recommendation::Fight,
+            Some(n) if n < UNSAFE_EXCHANGES => Recommendation::Withdraw,
+            Some(n) if n >= SAFE_EXCHANGES && self.available_energy >= self.phaser_energy_needed => {
+                Recommendation::Fight
+            }
+            Some(_) => Recommendation::Marginal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::klingon::Klingon;
+    use crate::models::position::SectorPosition;
+
+    /// Enterprise at sector 1,1 of an otherwise empty quadrant.
+    fn battle(klingons: &[(i32, i32)]) -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 1, y: 1 });
+        let map = galaxy.sector_map_mut();
+        map.klingons.clear();
+        map.starbase = None;
+        for &(x, y) in klingons {
+            map.klingons.push(Klingon::new(SectorPosition { x, y }));
+        }
+        galaxy
+    }
+
+    #[test]
+    fn empty_quadrant_has_no_threat() {
+        let odds = CombatOdds::from_galaxy(&battle(&[]));
+        assert_eq!(odds.expected_damage, 0.0);
+        assert_eq!(odds.exchanges_to_destruction, None);
+        assert_eq!(odds.recommendation(), Recommendation::NoThreat);
+    }
+
+    #[test]
+    fn expected_values_follow_the_combat_formulas() {
+        let mut galaxy = battle(&[(1, 5), (5, 1)]);
+        galaxy.enterprise_mut().set_shields(400.0);
+        let odds = CombatOdds::from_galaxy(&galaxy);
+
+        // Two Klingons with 200 shields at distance 4
+        assert_eq!(odds.klingons, 2);
+        assert_eq!(odds.klingon_shields, 400.0);
+        assert_eq!(odds.expected_damage, 100.0);
+        assert_eq!(odds.worst_case_damage, 200.0);
+        assert_eq!(odds.exchanges_to_shield_failure, Some(4.0));
+        assert_eq!(odds.exchanges_to_destruction, Some(14.0));
+        assert_eq!(odds.phaser_energy_needed, 1600.0);
+        assert_eq!(odds.recommendation(), Recommendation::Fight);
+
+        galaxy.enterprise_mut().set_shields(0.0);
+        galaxy.enterprise_mut().set_hull(15.0);
+        assert_eq!(CombatOdds::from_galaxy(&galaxy).recommendation(), Recommendation::Withdraw);
+    }
+
+    #[test]
+    fn damaged_computer_doubles_phaser_estimate() {
+        let mut galaxy = battle(&[(1, 3)]);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 1.0);
+        assert_eq!(CombatOdds::from_galaxy(&galaxy).phaser_energy_needed, 800.0);
+    }
+
+    #[test]
+    fn docked_ship_is_protected() {
+        let mut galaxy = battle(&[(1, 3)]);
+        galaxy.sector_map_mut().starbase = Some(SectorPosition { x: 2, y: 1 });
+        let odds = CombatOdds::from_galaxy(&galaxy);
+        assert!(odds.protected);
+        assert_eq!(odds.expected_damage, 0.0);
+        assert_eq!(odds.recommendation(), Recommendation::Fight);
+    }
+}
@@ -0,0 +1,14 @@
+//! Compatibility modes
+//!
+//! Selects between the modern presentation and the wording of the original
+//! BASIC game where the two differ.
+
+/// Which era's wording and conventions to present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompatMode {
+    /// Concise wording used by this implementation.
+    #[default]
+    Modern,
+    /// The original game's text, including its multi-line orders.
+    Classic,
+}
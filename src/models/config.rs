@@ -0,0 +1,148 @@
+//! Game configuration
+//!
+//! The numbers the original game hard-codes, gathered so balance
+//! experiments can change them at runtime. The default is the original
+//! game; variant rules and difficulty levels apply on top of it.
+
+use super::constants::{
+    INITIAL_ENERGY, INITIAL_TORPEDOES, KLINGON_COMMANDER_SHIELDS, KLINGON_INITIAL_SHIELDS, MISSION_DURATION,
+    ROMULAN_SHIELDS,
+};
+use super::rng::GameRng;
+
+/// Base chance of a device event per navigation move (spec section 5.3).
+pub const DEVICE_EVENT_CHANCE: f64 = 0.2;
+
+/// Chance that a device event is a repair rather than damage.
+pub const DEVICE_REPAIR_CHANCE: f64 = 0.5;
+
+/// How much chance goes into phaser and enemy hits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CombatModel {
+    /// The original game: every hit is scaled by a random factor.
+    #[default]
+    Random,
+    /// Every hit does its average damage without drawing, for teaching,
+    /// puzzles, and reproducible benchmarks. Whether an enemy fires at all
+    /// can still be left to chance.
+    Deterministic,
+}
+
+impl CombatModel {
+    /// Factor scaling a hit, uniform between `1 - spread` and `1 + spread`
+    /// under the random model and always 1 under the deterministic one,
+    /// which makes no draw.
+    pub fn hit_factor(self, rng: &mut GameRng, label: &'static str, spread: f64) -> f64 {
+        match self {
+            CombatModel::Random => (1.0 - spread) + 2.0 * spread * rng.unit(label),
+            CombatModel::Deterministic => 1.0,
+        }
+    }
+}
+
+/// Stardates each kind of command takes. Moves keep their own timing;
+/// in the original game everything else is free.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CommandCosts {
+    pub short_range_scan: f64,
+    pub long_range_scan: f64,
+    pub phasers: f64,
+    /// Single torpedoes and salvos alike.
+    pub torpedoes: f64,
+    /// Shield control and the quick SU/SD commands.
+    pub shields: f64,
+    pub damage_report: f64,
+    pub library_computer: f64,
+    pub probe: f64,
+}
+
+impl CommandCosts {
+    /// The original game: only moves take time.
+    pub const FREE: CommandCosts = CommandCosts {
+        short_range_scan: 0.0,
+        long_range_scan: 0.0,
+        phasers: 0.0,
+        torpedoes: 0.0,
+        shields: 0.0,
+        damage_report: 0.0,
+        library_computer: 0.0,
+        probe: 0.0,
+    };
+
+    /// A stricter clock where sensor sweeps and combat take time too.
+    /// Reports read from the ship's own records stay free.
+    pub const STRICT: CommandCosts = CommandCosts {
+        short_range_scan: 0.05,
+        long_range_scan: 0.1,
+        phasers: 0.2,
+        torpedoes: 0.2,
+        shields: 0.1,
+        damage_report: 0.0,
+        library_computer: 0.0,
+        probe: 0.1,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+    /// Energy the Enterprise starts with and a starbase restocks to.
+    pub initial_energy: f64,
+    /// Torpedoes the Enterprise starts with and a starbase restocks to.
+    pub initial_torpedoes: i32,
+    /// Stardates allowed under the fixed mission duration rule. Variant
+    /// formulas such as the scaled duration keep their own numbers.
+    pub mission_duration: f64,
+    /// Shields of each Klingon when its quadrant is entered.
+    pub klingon_shields: f64,
+    /// Shields of each Klingon commander when its quadrant is entered.
+    pub commander_shields: f64,
+    /// Shields of each Romulan warbird when its quadrant is entered.
+    pub romulan_shields: f64,
+    /// Chance of a device event per navigation move.
+    pub device_event_chance: f64,
+    /// Chance that a device event repairs rather than damages.
+    pub device_repair_chance: f64,
+    /// Whether phaser, Klingon, and Romulan hits are random.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub combat: CombatModel,
+    /// Stardates taken by commands other than moves.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub command_costs: CommandCosts,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            initial_energy: INITIAL_ENERGY,
+            initial_torpedoes: INITIAL_TORPEDOES,
+            mission_duration: MISSION_DURATION,
+            klingon_shields: KLINGON_INITIAL_SHIELDS,
+            commander_shields: KLINGON_COMMANDER_SHIELDS,
+            romulan_shields: ROMULAN_SHIELDS,
+            device_event_chance: DEVICE_EVENT_CHANCE,
+            device_repair_chance: DEVICE_REPAIR_CHANCE,
+            combat: CombatModel::default(),
+            command_costs: CommandCosts::FREE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_hit_factors_keep_the_original_stream() {
+        let mut rng = GameRng::seed_from_u64(42);
+        let mut twin = GameRng::seed_from_u64(42);
+        assert_eq!(CombatModel::Random.hit_factor(&mut rng, "a", 1.0), 2.0 * twin.unit("a"));
+        assert_eq!(CombatModel::Random.hit_factor(&mut rng, "b", 0.5), 0.5 + twin.unit("b"));
+
+        assert_eq!(CombatModel::Deterministic.hit_factor(&mut rng, "c", 1.0), 1.0);
+        assert_eq!(rng.draws(), 2);
+    }
+}
@@ -5,12 +5,166 @@ pub const MAX_KLINGONS_PER_QUADRANT: usize = 3;
 pub const INITIAL_ENERGY: f64 = 3000.0;
 pub const INITIAL_TORPEDOES: i32 = 10;
 pub const INITIAL_SHIELDS: f64 = 0.0;
+/// Shield level set by the SHIELDS UP shortcut unless configured otherwise.
+pub const DEFAULT_QUICK_SHIELD_UNITS: f64 = 500.0;
+/// Auto-shields engage when shields are below this on entering a hostile quadrant.
+pub const AUTO_SHIELD_FLOOR: f64 = 200.0;
+/// Energy the auto-shield policy moves into the shields when it engages.
+pub const AUTO_SHIELD_UNITS: f64 = 300.0;
 pub const KLINGON_INITIAL_SHIELDS: f64 = 200.0;
+/// Shields of a Klingon commander when its quadrant is entered.
+pub const KLINGON_COMMANDER_SHIELDS: f64 = 400.0;
+/// Chance that a quadrant holding Klingons is led by a commander, when the
+/// commanders rule is on.
+pub const KLINGON_COMMANDER_CHANCE: f64 = 0.1;
+/// Chance that a commander fires a photon torpedo instead of its disruptors.
+pub const COMMANDER_TORPEDO_CHANCE: f64 = 0.25;
+/// Base damage of a commander's torpedo, which ignores range.
+pub const COMMANDER_TORPEDO_DAMAGE: f64 = 150.0;
+/// Chance that a commander locks a tractor beam on the Enterprise after it
+/// moves within the quadrant.
+pub const TRACTOR_BEAM_CHANCE: f64 = 0.2;
+/// Damage a photon torpedo does to a commander's shields; ordinary Klingons
+/// are destroyed outright.
+pub const TORPEDO_DAMAGE_TO_COMMANDER: f64 = 300.0;
+/// Under the torpedo dodging rule, Klingons at least this many sectors
+/// from the Enterprise may dodge a torpedo.
+pub const TORPEDO_DODGE_MIN_DISTANCE: f64 = 3.0;
+/// Chance of a dodge per sector of distance, before the difficulty factor.
+pub const TORPEDO_DODGE_CHANCE_PER_SECTOR: f64 = 0.05;
+/// Most torpedoes fired in one salvo.
+pub const SALVO_MAX_TORPEDOES: i32 = 3;
+/// Course offset between the torpedoes of a salvo.
+pub const SALVO_SPREAD: f64 = 0.1;
+/// Shields of a Romulan warbird when its quadrant is entered.
+pub const ROMULAN_SHIELDS: f64 = 300.0;
+/// Chance that a quadrant hides a Romulan warbird, when the Romulans rule is on.
+pub const ROMULAN_CHANCE: f64 = 0.05;
+/// Chance that a Romulan attacks on each enemy volley.
+pub const ROMULAN_FIRE_CHANCE: f64 = 0.5;
+/// Chance that a black hole forms in a quadrant as it is entered, when the
+/// black holes rule is on.
+pub const BLACK_HOLE_CHANCE: f64 = 0.2;
+/// Hull lost to a black hole's tides.
+pub const BLACK_HOLE_HULL_DAMAGE: f64 = 15.0;
+/// Chance that a quadrant is sown with mines, when the minefields rule is on.
+pub const MINEFIELD_CHANCE: f64 = 0.1;
+/// Most mines sown in one minefield.
+pub const MAX_MINES_PER_FIELD: i32 = 4;
+/// Shield energy lost to a mine's blast.
+pub const MINE_SHIELD_DAMAGE: f64 = 150.0;
+/// Chance that a Tholian is found spinning its web in a quadrant as it is
+/// entered, when the Tholians rule is on.
+pub const THOLIAN_CHANCE: f64 = 0.1;
+/// Damage a torpedo's blast does to each Klingon next to its target when
+/// splash damage is on.
+pub const TORPEDO_SPLASH_DAMAGE: f64 = 100.0;
+/// Stardates between Klingon migration steps when migration is enabled.
+pub const KLINGON_MIGRATION_INTERVAL: f64 = 3.0;
+/// Stardates between waves of Klingon reinforcements when waves are enabled.
+pub const REINFORCEMENT_WAVE_INTERVAL: f64 = 5.0;
+/// Starbases the Enterprise may destroy with its own torpedoes before the
+/// captain is court-martialed.
+pub const COURT_MARTIAL_STARBASES: i32 = 2;
+/// Efficiency rating lost for each diplomatic incident, such as a stray
+/// torpedo striking an inhabited world.
+pub const DIPLOMATIC_INCIDENT_PENALTY: i32 = 100;
+/// Chance per navigation move that Klingons attack a starbase, when
+/// starbase attacks are enabled.
+pub const STARBASE_ATTACK_CHANCE: f64 = 0.05;
+/// Stardates a starbase under attack holds out.
+pub const STARBASE_ATTACK_DEADLINE: f64 = 3.0;
+/// Default bounds on the adaptive director's chance per navigation move of
+/// a Klingon reinforcement arriving.
+pub const DIRECTOR_MIN_REINFORCEMENT: f64 = 0.0;
+pub const DIRECTOR_MAX_REINFORCEMENT: f64 = 0.1;
+/// Default bounds on the adaptive director's multiplier on Klingon fire.
+pub const DIRECTOR_MIN_AGGRESSION: f64 = 0.75;
+pub const DIRECTOR_MAX_AGGRESSION: f64 = 1.25;
+/// Hull integrity of an undamaged Enterprise, in percent.
+pub const INITIAL_HULL: f64 = 100.0;
+/// Units of a hit that get past the shields per percent of hull lost.
+pub const HULL_DAMAGE_DIVISOR: f64 = 10.0;
+/// Hull lost running into an object at warp.
+pub const COLLISION_HULL_DAMAGE: f64 = 5.0;
+/// Under the ramming rule, the hit the Enterprise takes ramming a Klingon;
+/// the shields absorb what they can and the rest damages the hull.
+pub const RAMMING_IMPACT: f64 = 1000.0;
+/// Most devices damaged by ramming a Klingon.
+pub const RAMMING_MAX_DEVICES_DAMAGED: i32 = 3;
+/// Stardates after which the computer shows its record of a quadrant as
+/// out of date.
+pub const STALE_RECORD_AGE: f64 = 5.0;
+/// Under the Klingon regeneration rule, the fraction of its full shields
+/// a Klingon recovers per stardate.
+pub const KLINGON_SHIELD_REGENERATION: f64 = 0.1;
+/// Under the debris rule, the chance that debris stops a torpedo.
+pub const DEBRIS_TORPEDO_BLOCK_CHANCE: f64 = 0.5;
+/// Under the debris rule, stardates lost for each sector of debris the
+/// Enterprise flies through.
+pub const DEBRIS_STARDATES_PER_SECTOR: f64 = 0.1;
+/// Crew aboard the Enterprise. Each percent of hull lost costs a percent
+/// of them as casualties.
+pub const CREW_COMPLEMENT: i32 = 430;
+/// Under the crew morale rule, repairs and phaser fire suffer once fewer
+/// than this fraction of the crew are left.
+pub const MORALE_CREW_FRACTION: f64 = 0.75;
+/// Under the shield drain rule, energy raised shields draw per stardate.
+pub const SHIELD_DRAIN_PER_STARDATE: f64 = 10.0;
+/// Under the phaser heat rule, heat in the phaser banks (in units fired)
+/// past which firing risks an overload.
+pub const PHASER_HEAT_LIMIT: f64 = 1000.0;
+/// Phaser heat shed per stardate.
+pub const PHASER_COOLING_PER_STARDATE: f64 = 500.0;
+/// Least share of its energy a hot phaser bank still delivers.
+pub const PHASER_MIN_HEAT_EFFICIENCY: f64 = 0.5;
+/// Stardates of damage to phaser control from an overload.
+pub const PHASER_OVERLOAD_DAMAGE: f64 = 2.0;
 pub const MISSION_DURATION: f64 = 30.0;
 
-pub const NUM_DEVICES: usize = 8;
+pub const NUM_DEVICES: usize = 11;
+
+/// Energy used per sector of impulse travel.
+pub const IMPULSE_ENERGY_PER_SECTOR: f64 = 1.0;
+/// Stardates taken per sector of impulse travel.
+pub const IMPULSE_STARDATES_PER_SECTOR: f64 = 0.1;
+
+/// Energy the autopilot keeps in reserve; it will not make a jump that
+/// would leave less.
+pub const AUTOPILOT_ENERGY_RESERVE: f64 = 200.0;
+/// Most warp jumps the autopilot makes for one AUTO command, enough to
+/// cross the galaxy twice over.
+pub const AUTOPILOT_MAX_JUMPS: usize = 14;
+
+/// Energy the shuttlecraft escapes with when the crew abandons ship.
+pub const SHUTTLECRAFT_ENERGY: f64 = 300.0;
+
+/// Energy released by burning the dilithium crystal reserve.
+pub const DILITHIUM_ENERGY: f64 = 1000.0;
+/// Chance the crystals explode when burned under damage control's watch.
+pub const DILITHIUM_EXPLOSION_CHANCE: f64 = 0.1;
+/// Chance of an explosion with damage control out of action.
+pub const DILITHIUM_UNSUPERVISED_EXPLOSION_CHANCE: f64 = 0.3;
+/// Most devices an explosion damages.
+pub const DILITHIUM_MAX_DEVICES_DAMAGED: i32 = 3;
+
+/// Repair rate, per move, of the device the repair crews are assigned to.
+pub const PRIORITY_REPAIR_RATE: f64 = 2.0;
+/// Repair rate of every other device while the crews are assigned.
+pub const BACKGROUND_REPAIR_RATE: f64 = 0.5;
+
+/// Quadrants scanned in each direction by the long range sensors (3x3).
+pub const LONG_RANGE_SCAN_RADIUS: i32 = 1;
+/// Radius after the fleet HQ sensor upgrade (5x5).
+pub const EXTENDED_LONG_RANGE_SCAN_RADIUS: i32 = 2;
+
+/// Computer damage down to this level (in stardates of repair) leaves the
+/// library computer online but unreliable; beyond it the computer is disabled.
+pub const COMPUTER_PARTIAL_DAMAGE_LIMIT: f64 = 2.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Device {
     WarpEngines = 0,
     ShortRangeSensors = 1,
@@ -20,6 +174,9 @@ pub enum Device {
     DamageControl = 5,
     ShieldControl = 6,
     Computer = 7,
+    ImpulseEngines = 8,
+    SubspaceRadio = 9,
+    ShuttleBay = 10,
 }
 
 impl Device {
@@ -33,6 +190,9 @@ impl Device {
             Device::DamageControl => "DAMAGE CNTRL",
             Device::ShieldControl => "SHIELD CNTRL",
             Device::Computer => "COMPUTER",
+            Device::ImpulseEngines => "IMPULSE ENGS",
+            Device::SubspaceRadio => "SUBSPACE RADIO",
+            Device::ShuttleBay => "SHUTTLE BAY",
         }
     }
 
@@ -45,26 +205,130 @@ impl Device {
         Device::DamageControl,
         Device::ShieldControl,
         Device::Computer,
+        Device::ImpulseEngines,
+        Device::SubspaceRadio,
+        Device::ShuttleBay,
     ];
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SectorContent {
     Empty = 0,
     Enterprise = 1,
     Klingon = 2,
     Starbase = 3,
     Star = 4,
+    KlingonCommander = 5,
+    Romulan = 6,
+    BlackHole = 7,
+    Tholian = 8,
+    Web = 9,
+    Mine = 10,
+    Debris = 11,
 }
 
 impl SectorContent {
+    /// The classic three-character symbol.
     pub fn symbol(&self) -> &'static str {
+        self.symbol_in(SymbolSet::Classic)
+    }
+
+    /// The symbol in `set`, `set.cell_width()` characters wide.
+    pub fn symbol_in(&self, set: SymbolSet) -> &'static str {
+        match set {
+            SymbolSet::Classic => self.classic_symbol(),
+            SymbolSet::Unicode => self.unicode_symbol(),
+            SymbolSet::Compact => self.compact_symbol(),
+        }
+    }
+
+    fn classic_symbol(&self) -> &'static str {
         match self {
             SectorContent::Empty => "   ",
             SectorContent::Enterprise => "<*>",
             SectorContent::Klingon => "+++",
             SectorContent::Starbase => ">!<",
             SectorContent::Star => " * ",
+            SectorContent::KlingonCommander => "+C+",
+            SectorContent::Romulan => "+R+",
+            SectorContent::BlackHole => " @ ",
+            SectorContent::Tholian => " T ",
+            SectorContent::Web => " # ",
+            SectorContent::Mine => " x ",
+            SectorContent::Debris => " % ",
+        }
+    }
+
+    fn unicode_symbol(&self) -> &'static str {
+        match self {
+            SectorContent::Empty => " · ",
+            SectorContent::Enterprise => "◄▲►",
+            SectorContent::Klingon => " ▼ ",
+            SectorContent::Starbase => "[■]",
+            SectorContent::Star => " ★ ",
+            SectorContent::KlingonCommander => "▼▼▼",
+            SectorContent::Romulan => " ◆ ",
+            SectorContent::BlackHole => " ● ",
+            SectorContent::Tholian => " ◇ ",
+            SectorContent::Web => " ░ ",
+            SectorContent::Mine => " × ",
+            SectorContent::Debris => " ∴ ",
+        }
+    }
+
+    fn compact_symbol(&self) -> &'static str {
+        match self {
+            SectorContent::Empty => ".",
+            SectorContent::Enterprise => "E",
+            SectorContent::Klingon => "K",
+            SectorContent::Starbase => "B",
+            SectorContent::Star => "*",
+            SectorContent::KlingonCommander => "C",
+            SectorContent::Romulan => "R",
+            SectorContent::BlackHole => "@",
+            SectorContent::Tholian => "T",
+            SectorContent::Web => "#",
+            SectorContent::Mine => "x",
+            SectorContent::Debris => "%",
+        }
+    }
+
+    /// Whether the sector holds a Klingon of any kind.
+    pub fn is_klingon(&self) -> bool {
+        matches!(self, SectorContent::Klingon | SectorContent::KlingonCommander)
+    }
+}
+
+/// How the short range scan draws each sector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymbolSet {
+    /// The original three-character symbols, such as `<*>` and `+++`.
+    #[default]
+    Classic,
+    /// Three-column Unicode shapes for modern terminals.
+    Unicode,
+    /// One character per sector, for narrow screens.
+    Compact,
+}
+
+impl SymbolSet {
+    /// Parse a set name as typed on the command line.
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "classic" => Some(SymbolSet::Classic),
+            "unicode" => Some(SymbolSet::Unicode),
+            "compact" => Some(SymbolSet::Compact),
+            _ => None,
+        }
+    }
+
+    /// Terminal columns each sector takes.
+    pub fn cell_width(self) -> usize {
+        match self {
+            SymbolSet::Classic | SymbolSet::Unicode => 3,
+            SymbolSet::Compact => 1,
         }
     }
 }
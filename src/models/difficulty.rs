@@ -0,0 +1,120 @@
+//! Difficulty levels
+//!
+//! How hard the galaxy is, chosen at startup. Captain is the original game;
+//! Novice and Admiral scale Klingon density, Klingon shields, the time
+//! allowed, and how often devices fail in transit.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    Novice,
+    /// The original game.
+    #[default]
+    Captain,
+    Admiral,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Novice, Difficulty::Captain, Difficulty::Admiral];
+
+    /// Parse a level by name or by its number in the startup prompt (1-3),
+    /// in any case.
+    pub fn parse(text: &str) -> Option<Difficulty> {
+        match text.trim().to_lowercase().as_str() {
+            "1" | "novice" => Some(Difficulty::Novice),
+            "2" | "captain" => Some(Difficulty::Captain),
+            "3" | "admiral" => Some(Difficulty::Admiral),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Novice => "NOVICE",
+            Difficulty::Captain => "CAPTAIN",
+            Difficulty::Admiral => "ADMIRAL",
+        }
+    }
+
+    /// Values a quadrant's Klingon draw must exceed for 3, 2, and 1
+    /// Klingons (spec 3.4).
+    pub fn klingon_thresholds(&self) -> [f64; 3] {
+        match self {
+            Difficulty::Novice => [0.99, 0.97, 0.85],
+            Difficulty::Captain => [0.98, 0.95, 0.80],
+            Difficulty::Admiral => [0.96, 0.92, 0.75],
+        }
+    }
+
+    /// Multiplier on each Klingon's starting shields.
+    pub fn klingon_shield_factor(&self) -> f64 {
+        match self {
+            Difficulty::Novice => 0.75,
+            Difficulty::Captain => 1.0,
+            Difficulty::Admiral => 1.25,
+        }
+    }
+
+    /// Multiplier on the stardates the mission allows.
+    pub fn mission_duration_factor(&self) -> f64 {
+        match self {
+            Difficulty::Novice => 1.25,
+            Difficulty::Captain => 1.0,
+            Difficulty::Admiral => 0.8,
+        }
+    }
+
+    /// Multiplier on the chance of a device event per navigation move.
+    pub fn device_event_factor(&self) -> f64 {
+        match self {
+            Difficulty::Novice => 0.5,
+            Difficulty::Captain => 1.0,
+            Difficulty::Admiral => 1.5,
+        }
+    }
+
+    /// Multiplier on a Klingon's chance to dodge a torpedo.
+    pub fn torpedo_dodge_factor(&self) -> f64 {
+        match self {
+            Difficulty::Novice => 0.5,
+            Difficulty::Captain => 1.0,
+            Difficulty::Admiral => 1.5,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_prompt_numbers() {
+        assert_eq!(Difficulty::parse("Admiral"), Some(Difficulty::Admiral));
+        assert_eq!(Difficulty::parse(" 1\n"), Some(Difficulty::Novice));
+        assert_eq!(Difficulty::parse("ensign"), None);
+        for level in Difficulty::ALL {
+            assert_eq!(Difficulty::parse(level.label()), Some(level));
+        }
+    }
+
+    #[test]
+    fn harder_levels_scale_every_knob_the_same_way() {
+        let [novice, captain, admiral] = Difficulty::ALL;
+        for [easier, harder] in [[novice, captain], [captain, admiral]] {
+            let (e, h) = (easier.klingon_thresholds(), harder.klingon_thresholds());
+            assert!(e.iter().zip(h).all(|(e, h)| *e > h));
+            assert!(easier.klingon_shield_factor() < harder.klingon_shield_factor());
+            assert!(easier.mission_duration_factor() > harder.mission_duration_factor());
+            assert!(easier.device_event_factor() < harder.device_event_factor());
+            assert!(easier.torpedo_dodge_factor() < harder.torpedo_dodge_factor());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Adaptive difficulty
+//!
+//! An optional director that watches the player's pace and tunes Klingon
+//! reinforcements and aggression to keep the game tense but winnable. Its
+//! settings are a pure function of the game state, so a seed and the
+//! player's input still decide the game exactly.
+
+use super::constants::{
+    DIRECTOR_MAX_AGGRESSION, DIRECTOR_MAX_REINFORCEMENT, DIRECTOR_MIN_AGGRESSION,
+    DIRECTOR_MIN_REINFORCEMENT,
+};
+use super::galaxy::Galaxy;
+
+/// Pace ratio (actual kill rate over the rate still needed) at which the
+/// director eases off completely.
+const STRUGGLING_PACE: f64 = 0.5;
+
+/// Pace ratio at which the director pushes as hard as its bounds allow.
+const DOMINATING_PACE: f64 = 2.0;
+
+/// Stardates to watch before judging the pace; until then the director
+/// holds the middle of its bounds.
+const SETTLING_STARDATES: f64 = 1.0;
+
+/// Limits the director tunes within.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectorBounds {
+    /// Chance per navigation move that a Klingon reinforcement arrives.
+    pub reinforcement: (f64, f64),
+    /// Multiplier on Klingon fire.
+    pub aggression: (f64, f64),
+}
+
+impl Default for DirectorBounds {
+    fn default() -> Self {
+        DirectorBounds {
+            reinforcement: (DIRECTOR_MIN_REINFORCEMENT, DIRECTOR_MAX_REINFORCEMENT),
+            aggression: (DIRECTOR_MIN_AGGRESSION, DIRECTOR_MAX_AGGRESSION),
+        }
+    }
+}
+
+/// What the director has decided for the current state of the game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirectorSettings {
+    /// 0.0 when the player is struggling, 1.0 when they are dominating.
+    pub pressure: f64,
+    pub reinforcement_chance: f64,
+    pub aggression: f64,
+}
+
+impl DirectorSettings {
+    pub fn assess(galaxy: &Galaxy, bounds: &DirectorBounds) -> Self {
+        let pressure = pressure(galaxy);
+        let lerp = |(low, high): (f64, f64)| low + (high - low) * pressure;
+        DirectorSettings {
+            pressure,
+            reinforcement_chance: lerp(bounds.reinforcement),
+            aggression: lerp(bounds.aggression),
+        }
+    }
+}
+
+/// Compare the Klingons destroyed per stardate so far with the rate needed
+/// to finish the rest in the time left.
+fn pressure(galaxy: &Galaxy) -> f64 {
+    let elapsed = galaxy.stardate() - galaxy.starting_stardate();
+    if elapsed < SETTLING_STARDATES {
+        return 0.5;
+    }
+    let destroyed = (galaxy.klingons_faced() - galaxy.total_klingons()) as f64;
+    let pace = destroyed / elapsed;
+    let time_left = galaxy.starting_stardate() + galaxy.mission_duration() - galaxy.stardate();
+    let needed = if time_left > 0.0 {
+        galaxy.total_klingons() as f64 / time_left
+    } else {
+        f64::INFINITY
+    };
+    let ratio = if needed > 0.0 { pace / needed } else { DOMINATING_PACE };
+    ((ratio - STRUGGLING_PACE) / (DOMINATING_PACE - STRUGGLING_PACE)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn galaxy_after(stardates: f64, destroyed: i32) -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.set_initial_klingons(16);
+        galaxy.set_total_klingons(16 - destroyed);
+        galaxy.advance_time(stardates);
+        galaxy
+    }
+
+    #[test]
+    fn holds_the_middle_until_the_pace_is_known() {
+        let settings = DirectorSettings::assess(&galaxy_after(0.5, 0), &DirectorBounds::default());
+        assert_eq!(settings.pressure, 0.5);
+        assert_eq!(settings.aggression, 1.0);
+    }
+
+    #[test]
+    fn eases_off_a_struggling_player() {
+        let settings = DirectorSettings::assess(&galaxy_after(10.0, 0), &DirectorBounds::default());
+        assert_eq!(settings.pressure, 0.0);
+        assert_eq!(settings.reinforcement_chance, DIRECTOR_MIN_REINFORCEMENT);
+        assert_eq!(settings.aggression, DIRECTOR_MIN_AGGRESSION);
+    }
+
+    #[test]
+    fn pushes_a_dominating_player_within_bounds() {
+        let bounds = DirectorBounds { reinforcement: (0.0, 0.2), aggression: (1.0, 1.5) };
+        // 12 destroyed in 6 stardates; 4 left in 24 stardates needs 1/6 per stardate
+        let settings = DirectorSettings::assess(&galaxy_after(6.0, 12), &bounds);
+        assert_eq!(settings.pressure, 1.0);
+        assert_eq!(settings.reinforcement_chance, 0.2);
+        assert_eq!(settings.aggression, 1.5);
+    }
+
+    #[test]
+    fn on_pace_players_feel_moderate_pressure() {
+        // 8 destroyed in 15 stardates, 8 left in 15: exactly on pace
+        let settings = DirectorSettings::assess(&galaxy_after(15.0, 8), &DirectorBounds::default());
+        assert!((settings.pressure - 1.0 / 3.0).abs() < 1e-9);
+    }
+}
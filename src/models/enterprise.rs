@@ -1,28 +1,85 @@
-use super::constants::{Device, INITIAL_ENERGY, INITIAL_SHIELDS, INITIAL_TORPEDOES, NUM_DEVICES};
+use super::constants::{
+    Device, BACKGROUND_REPAIR_RATE, CREW_COMPLEMENT, EXTENDED_LONG_RANGE_SCAN_RADIUS, HULL_DAMAGE_DIVISOR,
+    INITIAL_ENERGY, INITIAL_HULL, INITIAL_SHIELDS, INITIAL_TORPEDOES, LONG_RANGE_SCAN_RADIUS, MORALE_CREW_FRACTION,
+    NUM_DEVICES, PHASER_HEAT_LIMIT, PHASER_MIN_HEAT_EFFICIENCY, PRIORITY_REPAIR_RATE, SHUTTLECRAFT_ENERGY,
+};
+use super::ledger::EnergyLedger;
 use super::position::{QuadrantPosition, SectorPosition};
 
 /// The player's starship.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Enterprise {
     quadrant: QuadrantPosition,
     sector: SectorPosition,
     energy: f64,
     torpedoes: i32,
     shields: f64,
+    /// Hull integrity in percent; the ship is destroyed at 0.
+    hull: f64,
+    /// Shield level before the last SHIELDS UP, for SHIELDS DOWN to restore.
+    shields_before_raise: Option<f64>,
+    /// Quadrants the long range sensors reach in each direction.
+    long_range_scan_radius: i32,
     /// Damage state for each of the 8 devices.
     /// 0 = operational, negative = damaged, positive = improved.
     devices: [f64; NUM_DEVICES],
+    /// Device the repair crews work on first, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    repair_priority: Option<Device>,
+    /// Energy and torpedoes the ship starts with and a starbase restocks to.
+    capacity: Supplies,
+    /// Torpedoes fired this game, for scoring.
+    torpedoes_fired: i32,
+    /// Hull lost this game, in percent, counting damage since repaired.
+    hull_damage_taken: f64,
+    /// Crew killed this game: a percent of the complement for each percent
+    /// of hull lost. Starbases repair the hull but cannot replace the crew.
+    #[cfg_attr(feature = "serde", serde(default))]
+    casualties: i32,
+    /// The crew abandoned ship and flies the shuttlecraft until a starbase
+    /// issues a new ship.
+    #[cfg_attr(feature = "serde", serde(default))]
+    shuttlecraft: bool,
+    /// Dilithium crystals held back for one emergency burn; a new ship
+    /// comes with a fresh reserve.
+    #[cfg_attr(feature = "serde", serde(default))]
+    dilithium_reserve: bool,
+    /// Heat in the phaser banks, in units fired; it builds with each shot
+    /// and bleeds away over time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    phaser_heat: f64,
 }
 
 impl Enterprise {
     pub fn new(quadrant: QuadrantPosition, sector: SectorPosition) -> Self {
+        Self::with_capacity(
+            quadrant,
+            sector,
+            Supplies { energy: INITIAL_ENERGY, torpedoes: INITIAL_TORPEDOES },
+        )
+    }
+
+    /// A fully stocked ship whose energy and torpedo stores hold `capacity`.
+    pub fn with_capacity(quadrant: QuadrantPosition, sector: SectorPosition, capacity: Supplies) -> Self {
         Enterprise {
             quadrant,
             sector,
-            energy: INITIAL_ENERGY,
-            torpedoes: INITIAL_TORPEDOES,
+            energy: capacity.energy,
+            torpedoes: capacity.torpedoes,
             shields: INITIAL_SHIELDS,
+            hull: INITIAL_HULL,
+            shields_before_raise: None,
+            long_range_scan_radius: LONG_RANGE_SCAN_RADIUS,
             devices: [0.0; NUM_DEVICES],
+            repair_priority: None,
+            capacity,
+            torpedoes_fired: 0,
+            hull_damage_taken: 0.0,
+            casualties: 0,
+            shuttlecraft: false,
+            dilithium_reserve: true,
+            phaser_heat: 0.0,
         }
     }
 
@@ -43,6 +100,29 @@ impl Enterprise {
         self.shields
     }
 
+    pub fn hull(&self) -> f64 {
+        self.hull
+    }
+
+    /// Energy and torpedoes a full resupply brings the ship to.
+    pub fn capacity(&self) -> Supplies {
+        self.capacity
+    }
+
+    pub fn long_range_scan_radius(&self) -> i32 {
+        self.long_range_scan_radius
+    }
+
+    /// Whether the long range sensors have the fleet HQ upgrade.
+    pub fn has_extended_long_range_sensors(&self) -> bool {
+        self.long_range_scan_radius >= EXTENDED_LONG_RANGE_SCAN_RADIUS
+    }
+
+    /// Install the fleet HQ upgrade, widening long range scans to 5x5.
+    pub fn upgrade_long_range_sensors(&mut self) {
+        self.long_range_scan_radius = EXTENDED_LONG_RANGE_SCAN_RADIUS;
+    }
+
     pub fn torpedoes(&self) -> i32 {
         self.torpedoes
     }
@@ -75,6 +155,7 @@ impl Enterprise {
     pub fn consume_torpedo(&mut self) -> Result<(), &'static str> {
         if self.torpedoes > 0 {
             self.torpedoes -= 1;
+            self.torpedoes_fired += 1;
             Ok(())
         } else {
             Err("No torpedoes remaining")
@@ -89,6 +170,40 @@ impl Enterprise {
         self.devices[device as usize] += amount;
     }
 
+    /// Restore every damaged device to working order. Improved devices
+    /// keep their bonus.
+    pub fn repair_all_devices(&mut self) {
+        for state in self.devices.iter_mut() {
+            *state = state.max(0.0);
+        }
+    }
+
+    /// The device the repair crews are assigned to. An assignment lapses
+    /// once that device is working again.
+    pub fn repair_priority(&self) -> Option<Device> {
+        self.repair_priority.filter(|&device| self.is_damaged(device))
+    }
+
+    /// Assign the repair crews to `device`, or release them with `None`.
+    pub fn set_repair_priority(&mut self, device: Option<Device>) {
+        self.repair_priority = device;
+    }
+
+    /// Repair per move for `device`: `PRIORITY_REPAIR_RATE` for the device
+    /// the crews are assigned to and `BACKGROUND_REPAIR_RATE` for the rest,
+    /// or 1 for everything with no assignment.
+    pub fn repair_rate(&self, device: Device) -> f64 {
+        match self.repair_priority() {
+            None => 1.0,
+            Some(priority) if priority == device => PRIORITY_REPAIR_RATE,
+            Some(_) => BACKGROUND_REPAIR_RATE,
+        }
+    }
+
+    pub fn set_hull(&mut self, value: f64) {
+        self.hull = value;
+    }
+
     #[allow(dead_code)]
     pub fn set_energy(&mut self, value: f64) {
         self.energy = value;
@@ -111,15 +226,135 @@ impl Enterprise {
         self.shields -= amount;
     }
 
+    /// Take a hit: the shields absorb what they can and whatever gets past
+    /// them damages the hull. Returns the hull damage taken.
+    pub fn absorb_hit(&mut self, hit: f64) -> f64 {
+        let overflow = (hit - self.shields.max(0.0)).max(0.0);
+        self.shields = (self.shields - hit).max(0.0);
+        let hull_damage = overflow / HULL_DAMAGE_DIVISOR;
+        self.damage_hull(hull_damage);
+        hull_damage
+    }
+
+    pub fn damage_hull(&mut self, amount: f64) {
+        let before = self.hull;
+        self.hull = (self.hull - amount).max(0.0);
+        self.hull_damage_taken += before - self.hull;
+        let lost = (self.hull_damage_taken / INITIAL_HULL * CREW_COMPLEMENT as f64).round() as i32;
+        self.casualties = lost.min(CREW_COMPLEMENT);
+    }
+
+    /// Crew members still aboard.
+    pub fn crew(&self) -> i32 {
+        CREW_COMPLEMENT - self.casualties
+    }
+
+    /// Crew members killed this game.
+    pub fn casualties(&self) -> i32 {
+        self.casualties
+    }
+
+    /// How well a depleted crew keeps up repairs and phaser fire: 1 until
+    /// the crew falls below `MORALE_CREW_FRACTION` of the complement, then
+    /// falling in proportion.
+    pub fn crew_efficiency(&self) -> f64 {
+        (self.crew() as f64 / (CREW_COMPLEMENT as f64 * MORALE_CREW_FRACTION)).min(1.0)
+    }
+
+    pub fn torpedoes_fired(&self) -> i32 {
+        self.torpedoes_fired
+    }
+
+    /// Hull lost over the whole game, in percent; docking repairs the hull
+    /// but not this.
+    pub fn hull_damage_taken(&self) -> f64 {
+        self.hull_damage_taken
+    }
+
+    /// The ship is lost once its hull is breached.
+    pub fn is_destroyed(&self) -> bool {
+        self.hull <= 0.0
+    }
+
     pub fn is_damaged(&self, device: Device) -> bool {
         self.devices[device as usize] < 0.0
     }
 
+    /// Whether both sensor arrays are working, which mines need to be seen.
+    pub fn sensors_repaired(&self) -> bool {
+        !self.is_damaged(Device::ShortRangeSensors) && !self.is_damaged(Device::LongRangeSensors)
+    }
+
+    /// Whether the crew is flying the shuttlecraft after abandoning ship.
+    pub fn in_shuttlecraft(&self) -> bool {
+        self.shuttlecraft
+    }
+
+    /// The crew can escape a destroyed ship while the shuttle bay works,
+    /// but not a destroyed shuttlecraft.
+    pub fn can_abandon_ship(&self) -> bool {
+        !self.shuttlecraft && !self.is_damaged(Device::ShuttleBay)
+    }
+
+    /// Whether the dilithium reserve is still aboard.
+    pub fn has_dilithium_reserve(&self) -> bool {
+        self.dilithium_reserve
+    }
+
+    /// Use up the dilithium reserve. Returns false if it was already gone.
+    pub fn take_dilithium_reserve(&mut self) -> bool {
+        std::mem::take(&mut self.dilithium_reserve)
+    }
+
+    /// Heat in the phaser banks, in units fired.
+    pub fn phaser_heat(&self) -> f64 {
+        self.phaser_heat
+    }
+
+    /// Phaser heat as a percentage of `PHASER_HEAT_LIMIT`.
+    pub fn phaser_heat_percent(&self) -> i32 {
+        (self.phaser_heat / PHASER_HEAT_LIMIT * 100.0).round() as i32
+    }
+
+    /// Share of the energy fired that hot phasers deliver: all of it when
+    /// cold, falling to `PHASER_MIN_HEAT_EFFICIENCY` at the heat limit.
+    pub fn phaser_heat_efficiency(&self) -> f64 {
+        let heat = (self.phaser_heat / PHASER_HEAT_LIMIT).min(1.0);
+        1.0 - heat * (1.0 - PHASER_MIN_HEAT_EFFICIENCY)
+    }
+
+    pub fn heat_phasers(&mut self, units: f64) {
+        self.phaser_heat += units;
+    }
+
+    pub fn cool_phasers(&mut self, amount: f64) {
+        self.phaser_heat = (self.phaser_heat - amount).max(0.0);
+    }
+
+    /// Escape in the shuttlecraft: an intact hull, but only
+    /// `SHUTTLECRAFT_ENERGY` units and no shields, torpedoes, or dilithium.
+    pub fn abandon_ship(&mut self) {
+        self.shuttlecraft = true;
+        self.dilithium_reserve = false;
+        self.energy = SHUTTLECRAFT_ENERGY;
+        self.shields = 0.0;
+        self.shields_before_raise = None;
+        self.torpedoes = 0;
+        self.hull = INITIAL_HULL;
+    }
+
     /// Reset ship resources when docking at a starbase (spec section 9.2).
+    /// Starbase crews also patch the hull, and replace the ship of a crew
+    /// that arrives in the shuttlecraft.
     pub fn dock(&mut self) {
-        self.energy = INITIAL_ENERGY;
-        self.torpedoes = INITIAL_TORPEDOES;
+        if std::mem::take(&mut self.shuttlecraft) {
+            self.dilithium_reserve = true;
+        }
+        self.energy = self.capacity.energy;
+        self.torpedoes = self.capacity.torpedoes;
         self.shields = INITIAL_SHIELDS;
+        self.hull = INITIAL_HULL;
+        self.phaser_heat = 0.0;
     }
 
     /// Check if the Enterprise is adjacent to (or at) a starbase (spec section 9.1).
@@ -137,7 +372,6 @@ impl Enterprise {
     pub fn check_docking(&mut self, starbase: Option<SectorPosition>) -> bool {
         if self.is_adjacent_to_starbase(starbase) {
             self.dock();
-            println!("SHIELDS DROPPED FOR DOCKING PURPOSES");
             return true;
         }
         false
@@ -169,6 +403,100 @@ impl Enterprise {
 
         Ok(())
     }
+
+    /// SHIELDS UP: set the shields to `units` through [`Self::shield_control`],
+    /// remembering the current level for [`Self::restore_shields`].
+    pub fn raise_shields(&mut self, units: f64) -> Result<(), ShieldControlError> {
+        let previous = self.shields;
+        self.shield_control(units)?;
+        self.shields_before_raise = Some(previous);
+        Ok(())
+    }
+
+    /// SHIELDS DOWN: return the shields to their level before the last
+    /// SHIELDS UP, or drop them entirely if there was none. Shield energy
+    /// goes back to main power.
+    pub fn restore_shields(&mut self) -> Result<(), ShieldControlError> {
+        let target = self.shields_before_raise.unwrap_or(0.0);
+        if target > 0.0 {
+            self.shield_control(target)?;
+        } else if self.is_damaged(Device::ShieldControl) {
+            return Err(ShieldControlError::SystemDamaged);
+        } else {
+            self.energy += self.shields;
+            self.shields = 0.0;
+        }
+        self.shields_before_raise = None;
+        Ok(())
+    }
+
+    /// True if `other` is in the same quadrant and in a neighbouring sector.
+    pub fn is_adjacent_to(&self, other: &Enterprise) -> bool {
+        let dx = (self.sector.x - other.sector.x).abs();
+        let dy = (self.sector.y - other.sector.y).abs();
+        self.quadrant == other.quadrant && dx.max(dy) == 1
+    }
+
+    /// Hand energy and torpedoes to a Federation ship alongside. Only main
+    /// energy can be given, and the receiver cannot be filled past a
+    /// starbase resupply, so everything sent arrives. The energy each ship
+    /// actually lost and gained is recorded in `ledger` at `stardate`.
+    pub fn transfer_supplies(
+        &mut self,
+        to: &mut Enterprise,
+        supplies: Supplies,
+        ledger: &mut EnergyLedger,
+        stardate: f64,
+    ) -> Result<(), TransferError> {
+        if !self.is_adjacent_to(to) {
+            return Err(TransferError::NotAdjacent);
+        }
+        if supplies.energy < 0.0 || supplies.torpedoes < 0 || supplies == Supplies::default() {
+            return Err(TransferError::InvalidInput);
+        }
+        if supplies.energy > self.energy {
+            return Err(TransferError::InsufficientEnergy);
+        }
+        if supplies.torpedoes > self.torpedoes {
+            return Err(TransferError::InsufficientTorpedoes);
+        }
+        if to.energy + supplies.energy > to.capacity.energy
+            || to.torpedoes + supplies.torpedoes > to.capacity.torpedoes
+        {
+            return Err(TransferError::ReceiverFull);
+        }
+
+        let (sender_before, receiver_before) = (self.energy, to.energy);
+        self.energy -= supplies.energy;
+        self.torpedoes -= supplies.torpedoes;
+        to.energy += supplies.energy;
+        to.torpedoes += supplies.torpedoes;
+        ledger.record_ship_transfer(stardate, self.energy - sender_before, to.energy - receiver_before);
+        Ok(())
+    }
+}
+
+/// Energy and torpedoes moved between ships.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Supplies {
+    pub energy: f64,
+    pub torpedoes: i32,
+}
+
+/// Errors that can occur when transferring supplies between ships.
+#[derive(Debug, PartialEq)]
+pub enum TransferError {
+    /// The ships are not in neighbouring sectors of one quadrant
+    NotAdjacent,
+    /// Nothing, or a negative amount, was requested
+    InvalidInput,
+    /// The sender does not have that much main energy
+    InsufficientEnergy,
+    /// The sender does not have that many torpedoes
+    InsufficientTorpedoes,
+    /// The receiver cannot hold that much
+    ReceiverFull,
 }
 
 /// Errors that can occur during shield control operations.
@@ -185,7 +513,7 @@ pub enum ShieldControlError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::constants::{INITIAL_ENERGY, INITIAL_SHIELDS, INITIAL_TORPEDOES};
+    use crate::models::constants::{INITIAL_ENERGY, INITIAL_HULL, INITIAL_SHIELDS, INITIAL_TORPEDOES};
     use crate::models::position::SectorPosition;
 
     /// Helper: create an Enterprise with reduced resources at a given sector.
@@ -211,6 +539,108 @@ mod tests {
         assert_eq!(e.shields(), INITIAL_SHIELDS);
     }
 
+    #[test]
+    fn hull_damage_costs_crew_that_docking_does_not_replace() {
+        let mut e = enterprise_at(SectorPosition { x: 4, y: 4 });
+        assert_eq!((e.crew(), e.crew_efficiency()), (CREW_COMPLEMENT, 1.0));
+
+        // 200 units past the shields cost 20% of the hull
+        e.absorb_hit(700.0);
+        assert_eq!(e.casualties(), 86);
+        assert_eq!(e.crew_efficiency(), 1.0);
+        e.damage_hull(30.0);
+        assert_eq!(e.crew(), CREW_COMPLEMENT - 215);
+        assert!((e.crew_efficiency() - 215.0 / 322.5).abs() < 1e-9);
+
+        assert!(e.check_docking(Some(SectorPosition { x: 5, y: 4 })));
+        assert_eq!(e.casualties(), 215);
+    }
+
+    #[test]
+    fn docking_repairs_hull() {
+        let mut e = enterprise_at(SectorPosition { x: 4, y: 4 });
+        e.set_hull(40.0);
+
+        assert!(e.check_docking(Some(SectorPosition { x: 5, y: 4 })));
+        assert_eq!(e.hull(), INITIAL_HULL);
+    }
+
+    #[test]
+    fn abandoning_ship_leaves_a_weak_shuttlecraft_until_docking() {
+        let mut e = enterprise_at(SectorPosition { x: 4, y: 4 });
+        e.set_hull(0.0);
+        assert!(e.can_abandon_ship());
+        e.abandon_ship();
+        assert!(e.in_shuttlecraft() && !e.is_destroyed());
+        assert_eq!((e.energy(), e.shields(), e.torpedoes()), (SHUTTLECRAFT_ENERGY, 0.0, 0));
+        assert!(!e.can_abandon_ship() && !e.has_dilithium_reserve());
+
+        assert!(e.check_docking(Some(SectorPosition { x: 5, y: 4 })));
+        assert!(!e.in_shuttlecraft() && e.has_dilithium_reserve());
+        assert_eq!((e.energy(), e.torpedoes()), (INITIAL_ENERGY, INITIAL_TORPEDOES));
+
+        e.damage_device(Device::ShuttleBay, 2.0);
+        assert!(!e.can_abandon_ship());
+    }
+
+    #[test]
+    fn docking_restocks_to_capacity() {
+        let capacity = Supplies { energy: 5000.0, torpedoes: 4 };
+        let mut e = Enterprise::with_capacity(QuadrantPosition { x: 1, y: 1 }, SectorPosition { x: 4, y: 4 }, capacity);
+        assert_eq!((e.energy(), e.torpedoes()), (5000.0, 4));
+
+        e.set_energy(1000.0);
+        e.set_torpedoes(0);
+        assert!(e.check_docking(Some(SectorPosition { x: 5, y: 4 })));
+        assert_eq!((e.energy(), e.torpedoes()), (5000.0, 4));
+        assert_eq!(e.capacity(), capacity);
+    }
+
+    #[test]
+    fn shields_absorb_hits_before_hull() {
+        let mut e = enterprise_at(SectorPosition { x: 1, y: 1 });
+        // shields = 500
+        assert_eq!(e.absorb_hit(300.0), 0.0);
+        assert_eq!(e.shields(), 200.0);
+        assert_eq!(e.hull(), INITIAL_HULL);
+
+        assert_eq!(e.absorb_hit(400.0), 20.0);
+        assert_eq!(e.shields(), 0.0);
+        assert_eq!(e.hull(), INITIAL_HULL - 20.0);
+        assert!(!e.is_destroyed());
+    }
+
+    #[test]
+    fn hull_breach_destroys_ship() {
+        let mut e = enterprise_at(SectorPosition { x: 1, y: 1 });
+        e.set_shields(0.0);
+        e.absorb_hit(INITIAL_HULL * 10.0 + 1.0);
+        assert_eq!(e.hull(), 0.0);
+        assert!(e.is_destroyed());
+    }
+
+    #[test]
+    fn shields_up_then_down_restores_previous_level() {
+        let mut e = enterprise_at(SectorPosition { x: 1, y: 1 });
+        // energy = 1000, shields = 500
+        e.raise_shields(800.0).unwrap();
+        assert_eq!((e.energy(), e.shields()), (700.0, 800.0));
+
+        e.restore_shields().unwrap();
+        assert_eq!((e.energy(), e.shields()), (1000.0, 500.0));
+
+        // Without a remembered level, shields drop to zero
+        e.restore_shields().unwrap();
+        assert_eq!((e.energy(), e.shields()), (1500.0, 0.0));
+    }
+
+    #[test]
+    fn shields_up_fails_without_enough_energy() {
+        let mut e = enterprise_at(SectorPosition { x: 1, y: 1 });
+        assert_eq!(e.raise_shields(2000.0), Err(ShieldControlError::InsufficientEnergy));
+        assert_eq!(e.shields(), 500.0);
+    }
+
     #[test]
     fn docking_when_adjacent_diagonally() {
         let mut e = enterprise_at(SectorPosition { x: 3, y: 3 });
@@ -408,4 +838,63 @@ mod tests {
         let _ = e.shield_control(2000.0);
         assert_eq!(e.energy() + e.shields(), initial_total);
     }
+
+    #[test]
+    fn supplies_transfer_between_adjacent_ships() {
+        let mut giver = enterprise_at(SectorPosition { x: 4, y: 4 });
+        let mut taker = enterprise_at(SectorPosition { x: 5, y: 5 });
+        let before = giver.energy() + taker.energy();
+
+        let mut ledger = EnergyLedger::new();
+
+        let supplies = Supplies { energy: 400.0, torpedoes: 2 };
+        assert_eq!(giver.transfer_supplies(&mut taker, supplies, &mut ledger, 2500.0), Ok(()));
+        assert_eq!(giver.energy(), 600.0);
+        assert_eq!(taker.energy(), 1400.0);
+        assert_eq!((giver.torpedoes(), taker.torpedoes()), (1, 5));
+        assert_eq!(giver.energy() + taker.energy(), before);
+        let amounts: Vec<f64> = ledger.entries().iter().map(|e| e.amount).collect();
+        assert_eq!(amounts, vec![-400.0, 400.0]);
+        assert!(ledger.transfers_balance());
+    }
+
+    #[test]
+    fn phaser_heat_weakens_fire_and_cools_or_clears_on_docking() {
+        let mut e = enterprise_at(SectorPosition { x: 4, y: 4 });
+        assert_eq!(e.phaser_heat_efficiency(), 1.0);
+        e.heat_phasers(PHASER_HEAT_LIMIT / 2.0);
+        assert_eq!(e.phaser_heat_percent(), 50);
+        assert_eq!(e.phaser_heat_efficiency(), 0.75);
+        e.heat_phasers(PHASER_HEAT_LIMIT);
+        assert_eq!(e.phaser_heat_efficiency(), PHASER_MIN_HEAT_EFFICIENCY);
+
+        e.cool_phasers(PHASER_HEAT_LIMIT);
+        assert_eq!(e.phaser_heat(), PHASER_HEAT_LIMIT / 2.0);
+        e.dock();
+        assert_eq!(e.phaser_heat(), 0.0);
+        e.cool_phasers(10.0);
+        assert_eq!(e.phaser_heat(), 0.0);
+    }
+
+    #[test]
+    fn supply_transfer_checks_position_and_stores() {
+        let mut giver = enterprise_at(SectorPosition { x: 4, y: 4 });
+        let mut far = enterprise_at(SectorPosition { x: 6, y: 4 });
+        let mut taker = enterprise_at(SectorPosition { x: 3, y: 4 });
+        let energy = |energy| Supplies { energy, torpedoes: 0 };
+        let mut ledger = EnergyLedger::new();
+        let mut send = |to: &mut Enterprise, supplies| giver.transfer_supplies(to, supplies, &mut ledger, 2500.0);
+
+        assert_eq!(send(&mut far, energy(10.0)), Err(TransferError::NotAdjacent));
+        assert_eq!(send(&mut taker, Supplies::default()), Err(TransferError::InvalidInput));
+        assert_eq!(send(&mut taker, energy(1000.5)), Err(TransferError::InsufficientEnergy));
+        assert_eq!(
+            send(&mut taker, Supplies { energy: 0.0, torpedoes: 4 }),
+            Err(TransferError::InsufficientTorpedoes)
+        );
+        taker.set_energy(2900.0);
+        assert_eq!(send(&mut taker, energy(200.0)), Err(TransferError::ReceiverFull));
+        assert_eq!(giver.energy(), 1000.0);
+        assert!(ledger.entries().is_empty());
+    }
 }
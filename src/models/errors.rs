@@ -3,6 +3,7 @@ use std::fmt;
 
 /// Game-specific error types
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum GameError {
     /// Failed to parse user input
     ParseError(String),
@@ -0,0 +1,276 @@
+//! Seed analysis
+//!
+//! Profiles of the galaxy each seed generates, and distributions over
+//! thousands of them, for tooling that picks fair seeds. Generating many
+//! galaxies is spread over all cores with the `parallel` feature.
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+#[cfg(feature = "parallel")]
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::Galaxy;
+#[cfg(feature = "parallel")]
+use super::GalaxyOptions;
+
+/// The starting conditions of one seed's galaxy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalaxyProfile {
+    pub seed: u64,
+    pub klingons: i32,
+    pub starbases: i32,
+    pub stars: i32,
+    /// Klingons in the Enterprise's starting quadrant.
+    pub klingons_at_start: i32,
+    /// Quadrants from the start to the nearest starbase, counting diagonal
+    /// steps as one; `None` in a galaxy without starbases.
+    pub starbase_distance: Option<i32>,
+    pub mission_duration: f64,
+}
+
+/// How often each value came up.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Distribution {
+    pub counts: BTreeMap<i32, usize>,
+}
+
+impl Distribution {
+    pub fn add(&mut self, value: i32) {
+        *self.counts.entry(value).or_default() += 1;
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.counts.keys().next().copied()
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.counts.keys().next_back().copied()
+    }
+
+    pub fn mean(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.counts.iter().map(|(&v, &n)| v as f64 * n as f64).sum::<f64>() / total as f64
+    }
+
+    /// Fraction of the values within `range`.
+    pub fn share(&self, range: RangeInclusive<i32>) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.counts.range(range).map(|(_, &n)| n).sum::<usize>() as f64 / total as f64
+    }
+}
+
+/// Profiles of a range of seeds, in seed order, and their distributions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GalaxyAnalysis {
+    pub profiles: Vec<GalaxyProfile>,
+    pub klingons: Distribution,
+    pub starbases: Distribution,
+    pub stars: Distribution,
+    pub klingons_at_start: Distribution,
+    /// Galaxies without starbases are left out.
+    pub starbase_distance: Distribution,
+    /// Rounded to whole stardates.
+    pub mission_duration: Distribution,
+}
+
+impl FromIterator<GalaxyProfile> for GalaxyAnalysis {
+    fn from_iter<I: IntoIterator<Item = GalaxyProfile>>(profiles: I) -> Self {
+        let mut analysis = GalaxyAnalysis::default();
+        for profile in profiles {
+            analysis.klingons.add(profile.klingons);
+            analysis.starbases.add(profile.starbases);
+            analysis.stars.add(profile.stars);
+            analysis.klingons_at_start.add(profile.klingons_at_start);
+            if let Some(distance) = profile.starbase_distance {
+                analysis.starbase_distance.add(distance);
+            }
+            analysis.mission_duration.add(profile.mission_duration.round() as i32);
+            analysis.profiles.push(profile);
+        }
+        analysis
+    }
+}
+
+/// Bounds a seed's galaxy must fall within; `None` leaves a figure free.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedConstraints {
+    pub klingons: Option<RangeInclusive<i32>>,
+    pub starbases: Option<RangeInclusive<i32>>,
+    pub stars: Option<RangeInclusive<i32>>,
+    pub klingons_at_start: Option<RangeInclusive<i32>>,
+    pub starbase_distance: Option<RangeInclusive<i32>>,
+}
+
+impl SeedConstraints {
+    /// Parse comma-separated `name=bounds` pairs, such as
+    /// `klingons=15-20,starbases=3+`. Bounds are a number, a range `A-B`,
+    /// or a minimum `A+`. The names are `klingons`, `starbases`, `stars`,
+    /// `start-klingons`, and `starbase-distance`.
+    pub fn parse(text: &str) -> Option<SeedConstraints> {
+        let mut constraints = SeedConstraints::default();
+        for pair in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, bounds) = pair.split_once('=')?;
+            let bounds = parse_bounds(bounds.trim())?;
+            let field = match name.trim().to_ascii_lowercase().as_str() {
+                "klingons" => &mut constraints.klingons,
+                "starbases" => &mut constraints.starbases,
+                "stars" => &mut constraints.stars,
+                "start-klingons" => &mut constraints.klingons_at_start,
+                "starbase-distance" => &mut constraints.starbase_distance,
+                _ => return None,
+            };
+            *field = Some(bounds);
+        }
+        Some(constraints)
+    }
+
+    pub fn matches(&self, profile: &GalaxyProfile) -> bool {
+        let within = |bounds: &Option<RangeInclusive<i32>>, value: Option<i32>| match (bounds, value) {
+            (None, _) => true,
+            (Some(bounds), Some(value)) => bounds.contains(&value),
+            (Some(_), None) => false,
+        };
+        within(&self.klingons, Some(profile.klingons))
+            && within(&self.starbases, Some(profile.starbases))
+            && within(&self.stars, Some(profile.stars))
+            && within(&self.klingons_at_start, Some(profile.klingons_at_start))
+            && within(&self.starbase_distance, profile.starbase_distance)
+    }
+}
+
+fn parse_bounds(text: &str) -> Option<RangeInclusive<i32>> {
+    if let Some(min) = text.strip_suffix('+') {
+        return Some(min.parse().ok()?..=i32::MAX);
+    }
+    match text.split_once('-') {
+        Some((min, max)) => {
+            let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+            (min <= max).then_some(min..=max)
+        }
+        None => text.parse().ok().map(|n| n..=n),
+    }
+}
+
+impl Galaxy {
+    /// The galaxy's starting conditions. Meant for a freshly generated
+    /// galaxy; later on it describes the galaxy as it stands.
+    pub fn profile(&self) -> GalaxyProfile {
+        let start = self.enterprise.quadrant();
+        let starbase_distance = self
+            .quadrants
+            .cells()
+            .filter(|(_, q)| q.starbases > 0)
+            .map(|(pos, _)| (pos.x - start.x).abs().max((pos.y - start.y).abs()))
+            .min();
+        GalaxyProfile {
+            seed: self.seed,
+            klingons: self.klingon_count.total,
+            starbases: self.total_starbases,
+            stars: self.quadrants.iter().map(|q| q.stars).sum(),
+            klingons_at_start: self.quadrants[start].klingons,
+            starbase_distance,
+            mission_duration: self.mission_duration,
+        }
+    }
+
+    /// Profile the galaxy of every seed in `seeds`, under the default
+    /// options, on all cores.
+    #[cfg(feature = "parallel")]
+    pub fn analyze(seeds: Range<u64>) -> GalaxyAnalysis {
+        Galaxy::analyze_with(seeds, &GalaxyOptions::default())
+    }
+
+    /// [`Galaxy::analyze`] under `options`.
+    #[cfg(feature = "parallel")]
+    pub fn analyze_with(seeds: Range<u64>, options: &GalaxyOptions) -> GalaxyAnalysis {
+        let profiles: Vec<GalaxyProfile> =
+            seeds.into_par_iter().map(|seed| Galaxy::with_options(seed, options.clone()).profile()).collect();
+        profiles.into_iter().collect()
+    }
+
+    /// The lowest seed in `seeds` whose galaxy under `options` meets
+    /// `constraints`, searched on all cores.
+    #[cfg(feature = "parallel")]
+    pub fn find_seed(
+        seeds: Range<u64>,
+        options: &GalaxyOptions,
+        constraints: &SeedConstraints,
+    ) -> Option<GalaxyProfile> {
+        seeds
+            .into_par_iter()
+            .map(|seed| Galaxy::with_options(seed, options.clone()).profile())
+            .find_first(|profile| constraints.matches(profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_describes_the_generated_galaxy() {
+        let galaxy = Galaxy::new(42);
+        let profile = galaxy.profile();
+        assert_eq!(profile.seed, 42);
+        assert_eq!(profile.klingons, galaxy.total_klingons());
+        assert_eq!(profile.starbases, galaxy.total_starbases());
+        assert_eq!(profile.klingons_at_start, galaxy.quadrants()[galaxy.enterprise().quadrant()].klingons);
+        assert!(profile.starbase_distance.is_some_and(|d| (0..8).contains(&d)));
+    }
+
+    #[test]
+    fn constraints_parse_numbers_ranges_and_minimums() {
+        let constraints = SeedConstraints::parse("klingons=15-20, starbases=3+,start-klingons=0").unwrap();
+        assert_eq!(constraints.klingons, Some(15..=20));
+        assert_eq!(constraints.starbases, Some(3..=i32::MAX));
+        assert_eq!(constraints.klingons_at_start, Some(0..=0));
+        assert_eq!(constraints.stars, None);
+        for bad in ["klingons", "warbirds=2", "klingons=20-15", "stars=many"] {
+            assert_eq!(SeedConstraints::parse(bad), None, "{}", bad);
+        }
+
+        let profile = Galaxy::new(42).profile();
+        let exact = |n: i32| SeedConstraints { klingons: Some(n..=n), ..Default::default() };
+        assert!(exact(profile.klingons).matches(&profile));
+        assert!(!exact(profile.klingons + 1).matches(&profile));
+        assert!(SeedConstraints::default().matches(&profile));
+    }
+
+    #[test]
+    fn distributions_count_each_value() {
+        let mut distribution = Distribution::default();
+        for value in [3, 5, 5, 7] {
+            distribution.add(value);
+        }
+        assert_eq!((distribution.min(), distribution.max()), (Some(3), Some(7)));
+        assert_eq!(distribution.mean(), 5.0);
+        assert_eq!(distribution.share(4..=7), 0.75);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn analysis_and_search_match_profiling_each_seed() {
+        let analysis = Galaxy::analyze(1..41);
+        let expected: Vec<GalaxyProfile> = (1..41).map(|seed| Galaxy::new(seed).profile()).collect();
+        assert_eq!(analysis.profiles, expected);
+        assert_eq!(analysis.klingons.total(), 40);
+
+        let wanted = expected[17].klingons;
+        let constraints = SeedConstraints { klingons: Some(wanted..=wanted), ..Default::default() };
+        let first = expected.iter().find(|p| constraints.matches(p)).cloned();
+        assert_eq!(Galaxy::find_seed(1..41, &GalaxyOptions::default(), &constraints), first);
+    }
+}
@@ -0,0 +1,253 @@
+//! Hand-made galaxies
+//!
+//! [`GalaxyBuilder`] lays a galaxy out quadrant by quadrant instead of
+//! generating it, for scenarios and tests. The seed still drives the rest
+//! of the game: where things sit within a quadrant, combat, and events.
+
+use std::fmt;
+
+use super::{Galaxy, GalaxyOptions, Layout};
+use crate::models::constants::{GALAXY_SIZE, SECTOR_SIZE};
+use crate::models::grid::QuadrantGrid;
+use crate::models::limits::{GalaxyLimits, LimitError};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::quadrant::QuadrantData;
+use crate::models::rng::GameRng;
+
+/// Stardate a built galaxy starts on unless another is given.
+pub const DEFAULT_STARTING_STARDATE: f64 = 2500.0;
+
+const EMPTY: QuadrantData = QuadrantData { klingons: 0, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
+
+/// Why a [`GalaxyBuilder`] layout was refused.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildError {
+    /// A quadrant outside the galaxy
+    QuadrantOutOfRange(QuadrantPosition),
+    /// A starting sector outside the quadrant
+    SectorOutOfRange(SectorPosition),
+    /// A quadrant holding more than the galaxy limits allow
+    Limit(LimitError),
+    /// A starting stardate that is negative or not a number
+    Stardate(f64),
+    /// A mission duration that is not a positive number of stardates
+    MissionDuration(f64),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::QuadrantOutOfRange(pos) => write!(f, "quadrant {},{} is outside the galaxy", pos.x, pos.y),
+            BuildError::SectorOutOfRange(pos) => write!(f, "sector {},{} is outside the quadrant", pos.x, pos.y),
+            BuildError::Limit(e) => write!(f, "{}", e),
+            BuildError::Stardate(stardate) => write!(f, "stardate {} is not a valid start", stardate),
+            BuildError::MissionDuration(stardates) => {
+                write!(f, "a mission of {} stardates is not a valid duration", stardates)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl From<LimitError> for BuildError {
+    fn from(e: LimitError) -> Self {
+        BuildError::Limit(e)
+    }
+}
+
+/// A galaxy laid out by hand. Quadrants start empty and the Enterprise
+/// in the middle of the galaxy; nothing is drawn at random until the game
+/// begins. Mods, mirroring, and the rules that scatter commanders,
+/// Romulans, and mines at generation do not apply: put them in the
+/// quadrants' data instead.
+///
+/// The galaxy's totals are counted from the quadrants, so they always
+/// agree. Everything else is checked by [`GalaxyBuilder::build`]: a
+/// position outside the galaxy is remembered and refused there rather
+/// than panicking where it was given.
+#[derive(Debug, Clone)]
+pub struct GalaxyBuilder {
+    seed: u64,
+    options: GalaxyOptions,
+    quadrants: QuadrantGrid<QuadrantData>,
+    quadrant: QuadrantPosition,
+    sector: SectorPosition,
+    stardate: f64,
+    mission_duration: Option<f64>,
+    /// The first quadrant given outside the galaxy.
+    out_of_range: Option<QuadrantPosition>,
+}
+
+impl GalaxyBuilder {
+    pub fn new(seed: u64) -> Self {
+        GalaxyBuilder {
+            seed,
+            options: GalaxyOptions::default(),
+            quadrants: QuadrantGrid::filled(EMPTY),
+            quadrant: QuadrantPosition { x: 4, y: 4 },
+            sector: SectorPosition { x: 4, y: 4 },
+            stardate: DEFAULT_STARTING_STARDATE,
+            mission_duration: None,
+            out_of_range: None,
+        }
+    }
+
+    /// The rules and balance numbers the game is played under.
+    pub fn options(mut self, options: GalaxyOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Klingons, starbases, and stars in one quadrant.
+    pub fn quadrant(self, pos: QuadrantPosition, klingons: i32, starbases: i32, stars: i32) -> Self {
+        self.quadrant_data(pos, QuadrantData { klingons, starbases, stars, ..EMPTY })
+    }
+
+    /// Everything in one quadrant, commanders, Romulans, and mines included.
+    pub fn quadrant_data(self, pos: QuadrantPosition, data: QuadrantData) -> Self {
+        self.edit(pos, |q| *q = data)
+    }
+
+    /// Klingons in one quadrant, leaving the rest of it as it was.
+    pub fn klingons(self, pos: QuadrantPosition, count: i32) -> Self {
+        self.edit(pos, |q| q.klingons = count)
+    }
+
+    /// A starbase in one quadrant.
+    pub fn starbase(self, pos: QuadrantPosition) -> Self {
+        self.edit(pos, |q| q.starbases = 1)
+    }
+
+    /// Stars in one quadrant.
+    pub fn stars(self, pos: QuadrantPosition, count: i32) -> Self {
+        self.edit(pos, |q| q.stars = count)
+    }
+
+    fn edit(mut self, pos: QuadrantPosition, change: impl FnOnce(&mut QuadrantData)) -> Self {
+        match self.quadrants.get_mut(pos) {
+            Some(data) => change(data),
+            None => self.out_of_range = self.out_of_range.or(Some(pos)),
+        }
+        self
+    }
+
+    /// Where the Enterprise starts.
+    pub fn start(mut self, quadrant: QuadrantPosition, sector: SectorPosition) -> Self {
+        self.quadrant = quadrant;
+        self.sector = sector;
+        self
+    }
+
+    pub fn stardate(mut self, stardate: f64) -> Self {
+        self.stardate = stardate;
+        self
+    }
+
+    /// Stardates the mission lasts, in place of the ruleset's formula.
+    pub fn mission_duration(mut self, stardates: f64) -> Self {
+        self.mission_duration = Some(stardates);
+        self
+    }
+
+    /// Set the game up on the layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Galaxy)` ready to play
+    /// * `Err` if a position is outside the galaxy, a quadrant holds more
+    ///   than the galaxy limits allow, or the clock makes no sense
+    pub fn build(self) -> Result<Galaxy, BuildError> {
+        if let Some(pos) = self.out_of_range {
+            return Err(BuildError::QuadrantOutOfRange(pos));
+        }
+        let in_galaxy = 1..=GALAXY_SIZE as i32;
+        if !in_galaxy.contains(&self.quadrant.x) || !in_galaxy.contains(&self.quadrant.y) {
+            return Err(BuildError::QuadrantOutOfRange(self.quadrant));
+        }
+        let in_quadrant = 1..=SECTOR_SIZE as i32;
+        if !in_quadrant.contains(&self.sector.x) || !in_quadrant.contains(&self.sector.y) {
+            return Err(BuildError::SectorOutOfRange(self.sector));
+        }
+        if !(self.stardate.is_finite() && self.stardate >= 0.0) {
+            return Err(BuildError::Stardate(self.stardate));
+        }
+        if let Some(stardates) = self.mission_duration.filter(|d| !(d.is_finite() && *d > 0.0)) {
+            return Err(BuildError::MissionDuration(stardates));
+        }
+        let limits = GalaxyLimits::default();
+        for (pos, data) in self.quadrants.cells() {
+            limits.check_quadrant(pos, data)?;
+        }
+        let total_klingons = self.quadrants.iter().map(|q| q.klingons).sum();
+        let total_starbases = self.quadrants.iter().map(|q| q.starbases).sum();
+        let layout = Layout {
+            starting_stardate: self.stardate,
+            quadrants: self.quadrants,
+            total_klingons,
+            total_starbases,
+            quadrant: self.quadrant,
+            sector: self.sector,
+        };
+        let mut galaxy =
+            Galaxy::from_layout(self.seed, GameRng::seed_from_u64(self.seed), layout, self.options);
+        if let Some(stardates) = self.mission_duration {
+            galaxy.mission_duration = stardates;
+        }
+        Ok(galaxy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::constants::SectorContent;
+
+    #[test]
+    fn built_galaxy_has_exactly_the_layout() {
+        let start = QuadrantPosition { x: 2, y: 7 };
+        let galaxy = GalaxyBuilder::new(9)
+            .quadrant(start, 2, 1, 3)
+            .quadrant(QuadrantPosition { x: 8, y: 8 }, 3, 0, 0)
+            .start(start, SectorPosition { x: 5, y: 6 })
+            .stardate(3100.0)
+            .mission_duration(12.0)
+            .build()
+            .unwrap();
+        assert_eq!((galaxy.total_klingons(), galaxy.total_starbases()), (5, 1));
+        assert_eq!(galaxy.quadrants().iter().map(|q| q.stars).sum::<i32>(), 3);
+        assert_eq!(galaxy.enterprise().quadrant(), start);
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 5, y: 6 });
+        assert_eq!((galaxy.stardate(), galaxy.mission_duration()), (3100.0, 12.0));
+        assert_eq!(galaxy.sector_map().klingons.len(), 2);
+        assert!(galaxy.sector_map().starbase.is_some());
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 5, y: 6 }), SectorContent::Enterprise);
+    }
+
+    #[test]
+    fn field_setters_leave_the_rest_of_the_quadrant() {
+        let pos = QuadrantPosition { x: 3, y: 3 };
+        let galaxy = GalaxyBuilder::new(1).stars(pos, 4).klingons(pos, 2).starbase(pos).build().unwrap();
+        let data = galaxy.quadrants()[pos];
+        assert_eq!((data.klingons, data.starbases, data.stars), (2, 1, 4));
+        assert!(GalaxyLimits::default().check_galaxy(&galaxy).is_ok());
+    }
+
+    #[test]
+    fn build_refuses_bad_layouts() {
+        let build = |b: GalaxyBuilder| b.build().map(|_| ()).unwrap_err();
+        let corner = QuadrantPosition { x: 1, y: 1 };
+        assert!(matches!(build(GalaxyBuilder::new(1).quadrant(corner, 4, 0, 0)), BuildError::Limit(_)));
+
+        let outside = QuadrantPosition { x: 9, y: 1 };
+        let error = build(GalaxyBuilder::new(1).klingons(outside, 1).klingons(corner, 1));
+        assert_eq!(error, BuildError::QuadrantOutOfRange(outside));
+        assert_eq!(error.to_string(), "quadrant 9,1 is outside the galaxy");
+
+        let sector = SectorPosition { x: 0, y: 4 };
+        assert_eq!(build(GalaxyBuilder::new(1).start(corner, sector)), BuildError::SectorOutOfRange(sector));
+        assert_eq!(build(GalaxyBuilder::new(1).start(outside, sector)), BuildError::QuadrantOutOfRange(outside));
+        assert_eq!(build(GalaxyBuilder::new(1).mission_duration(0.0)), BuildError::MissionDuration(0.0));
+        assert!(matches!(build(GalaxyBuilder::new(1).stardate(f64::NAN)), BuildError::Stardate(_)));
+    }
+}
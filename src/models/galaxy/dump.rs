@@ -0,0 +1,111 @@
+//! State dump
+//!
+//! Serializes the full game state to JSON for bug reports. The output is
+//! meant for humans and tooling to inspect, not to be loaded back.
+
+use std::fmt::Write;
+
+use super::Galaxy;
+use crate::models::constants::{Device, SymbolSet, SECTOR_SIZE};
+
+/// Render the galaxy as a pretty-printed JSON object.
+pub fn state_dump(galaxy: &Galaxy) -> String {
+    let e = &galaxy.enterprise;
+    let mut out = String::from("{\n");
+
+    let _ = writeln!(out, "  \"seed\": {},", galaxy.seed);
+    let _ = writeln!(out, "  \"stardate\": {},", galaxy.stardate);
+    let _ = writeln!(out, "  \"starting_stardate\": {},", galaxy.starting_stardate);
+    let _ = writeln!(out, "  \"mission_duration\": {},", galaxy.mission_duration);
+    let _ = writeln!(out, "  \"klingons_remaining\": {},", galaxy.klingon_count.total);
+    let _ = writeln!(out, "  \"klingons_initial\": {},", galaxy.klingon_count.initial);
+    let _ = writeln!(out, "  \"starbases\": {},", galaxy.total_starbases);
+    let _ = writeln!(out, "  \"starbases_lost\": {},", galaxy.starbases_lost);
+    let _ = writeln!(out, "  \"quadrants_cleared\": {},", galaxy.quadrants_cleared);
+    let _ = writeln!(out, "  \"state_digest\": \"{:016x}\",", galaxy.state_digest());
+
+    let devices: Vec<String> = Device::ALL
+        .iter()
+        .map(|&d| format!("\"{}\": {}", d.name(), e.devices()[d as usize]))
+        .collect();
+    let _ = writeln!(out, "  \"enterprise\": {{");
+    let _ = writeln!(out, "    \"quadrant\": [{}, {}],", e.quadrant().x, e.quadrant().y);
+    let _ = writeln!(out, "    \"sector\": [{}, {}],", e.sector().x, e.sector().y);
+    let _ = writeln!(out, "    \"energy\": {},", e.energy());
+    let _ = writeln!(out, "    \"shields\": {},", e.shields());
+    let _ = writeln!(out, "    \"hull\": {},", e.hull());
+    let _ = writeln!(out, "    \"torpedoes\": {},", e.torpedoes());
+    let _ = writeln!(out, "    \"long_range_scan_radius\": {},", e.long_range_scan_radius());
+    let _ = writeln!(out, "    \"devices\": {{{}}}", devices.join(", "));
+    let _ = writeln!(out, "  }},");
+
+    let klingons: Vec<String> = galaxy
+        .sector_map
+        .klingons
+        .iter()
+        .map(|k| format!("{{\"sector\": [{}, {}], \"shields\": {}}}", k.sector.x, k.sector.y, k.shields))
+        .collect();
+    let rows: Vec<String> = (1..=SECTOR_SIZE as i32)
+        .map(|y| format!("\"{}\"", galaxy.sector_map.render_row(y, true, SymbolSet::Classic)))
+        .collect();
+    let _ = writeln!(out, "  \"sector_map\": {{");
+    let _ = writeln!(out, "    \"klingons\": [{}],", klingons.join(", "));
+    let _ = writeln!(out, "    \"rows\": [{}]", rows.join(", "));
+    let _ = writeln!(out, "  }},");
+
+    // Rows of 3-digit encoded quadrants; unscanned memory entries are null.
+    let quadrants = grid_json(galaxy.quadrants.rows().map(|row| {
+        row.iter().map(|q| q.encoded().to_string()).collect()
+    }));
+    let memory = grid_json(galaxy.computer_memory.rows().map(|row| {
+        row.iter()
+            .map(|q| q.map_or("null".to_string(), |q| q.encoded().to_string()))
+            .collect()
+    }));
+    let visited = grid_json(galaxy.visited.rows().map(|row| {
+        row.iter().map(|v| v.to_string()).collect()
+    }));
+    let _ = writeln!(out, "  \"quadrants\": {},", quadrants);
+    let _ = writeln!(out, "  \"computer_memory\": {},", memory);
+    let _ = writeln!(out, "  \"visited\": {}", visited);
+    out.push('}');
+    out
+}
+
+fn grid_json(rows: impl Iterator<Item = Vec<String>>) -> String {
+    let rows: Vec<String> = rows.map(|cells| format!("[{}]", cells.join(", "))).collect();
+    format!("[\n    {}\n  ]", rows.join(",\n    "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::grid::QuadrantGrid;
+    use crate::models::position::QuadrantPosition;
+
+    #[test]
+    fn dump_includes_core_state() {
+        let galaxy = Galaxy::new(42);
+        let dump = state_dump(&galaxy);
+
+        assert!(dump.starts_with("{\n  \"seed\": 42,"));
+        assert!(dump.ends_with('}'));
+        assert!(dump.contains(&format!("\"klingons_remaining\": {},", galaxy.total_klingons())));
+        assert!(dump.contains(&format!("\"state_digest\": \"{:016x}\"", galaxy.state_digest())));
+        assert!(dump.contains("\"WARP ENGINES\": 0"));
+        assert!(dump.contains(&galaxy.sector_map().render_row(1, true, SymbolSet::Classic)));
+    }
+
+    #[test]
+    fn unscanned_memory_is_null() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.computer_memory = QuadrantGrid::filled(None);
+        let corner = QuadrantPosition { x: 1, y: 1 };
+        galaxy.computer_memory[corner] = Some(galaxy.quadrants[corner]);
+        let dump = state_dump(&galaxy);
+
+        let memory = &dump[dump.find("\"computer_memory\"").unwrap()..];
+        assert!(memory.contains(&format!("[{}, null,", galaxy.quadrants[corner].encoded())));
+        assert_eq!(memory.matches("null").count(), 63);
+    }
+}
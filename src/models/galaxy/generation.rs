@@ -1,55 +1,143 @@
-use rand::rngs::StdRng;
-use rand::Rng;
-
-use crate::models::constants::GALAXY_SIZE;
+use crate::models::constants::{
+    GALAXY_SIZE, KLINGON_COMMANDER_CHANCE, MAX_MINES_PER_FIELD, MINEFIELD_CHANCE, ROMULAN_CHANCE,
+};
+use crate::models::difficulty::Difficulty;
+use crate::models::grid::{Grid, QuadrantGrid};
+use crate::models::hooks::{encounter_seed, ModRegistry};
+use crate::models::position::QuadrantPosition;
 use crate::models::quadrant::QuadrantData;
+use crate::models::rng::GameRng;
+
+/// Reflection applied to a generated galaxy to produce its mirror twin.
+///
+/// Two racers given the same seed with different axes play maps with the
+/// same quadrant contents, totals, and starting stardate, but laid out
+/// differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorAxis {
+    /// No reflection; the galaxy as generated.
+    #[default]
+    None,
+    /// Reflect left-right (x becomes 9 - x).
+    Horizontal,
+    /// Reflect top-bottom (y becomes 9 - y).
+    Vertical,
+    /// Reflect both ways (a half turn).
+    Both,
+}
+
+impl MirrorAxis {
+    /// Reflect a 1-based (x, y) coordinate on an 8x8 grid.
+    pub fn reflect(self, x: i32, y: i32) -> (i32, i32) {
+        let flip = |v: i32| GALAXY_SIZE as i32 + 1 - v;
+        match self {
+            MirrorAxis::None => (x, y),
+            MirrorAxis::Horizontal => (flip(x), y),
+            MirrorAxis::Vertical => (x, flip(y)),
+            MirrorAxis::Both => (flip(x), flip(y)),
+        }
+    }
+}
 
 /// Generate the 8x8 galaxy. Loops until the regeneration guard passes
-/// (total_klingons > 0 AND total_starbases > 0).
-pub fn generate_galaxy(
-    rng: &mut StdRng,
-) -> ([[QuadrantData; GALAXY_SIZE]; GALAXY_SIZE], i32, i32) {
+/// (total_klingons > 0 AND total_starbases > 0). The difficulty sets how
+/// likely each quadrant is to hold Klingons.
+pub fn generate_galaxy(rng: &mut GameRng, difficulty: Difficulty) -> (QuadrantGrid<QuadrantData>, i32, i32) {
+    let [three, two, one] = difficulty.klingon_thresholds();
     loop {
-        let mut quadrants = [[QuadrantData {
-            klingons: 0,
-            starbases: 0,
-            stars: 0,
-        }; GALAXY_SIZE]; GALAXY_SIZE];
         let mut total_klingons = 0;
         let mut total_starbases = 0;
 
-        // Using indexed loops here because we need both x and y indices for 2D array access
-        #[allow(clippy::needless_range_loop)]
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                let f: f64 = rng.gen();
-                let klingons = if f > 0.98 {
-                    3
-                } else if f > 0.95 {
-                    2
-                } else if f > 0.80 {
-                    1
-                } else {
-                    0
-                };
-
-                let f: f64 = rng.gen();
-                let starbases = if f > 0.96 { 1 } else { 0 };
-
-                let stars = (rng.gen::<f64>() * 8.0 + 1.0).floor() as i32;
-
-                quadrants[y][x] = QuadrantData {
-                    klingons,
-                    starbases,
-                    stars,
-                };
-                total_klingons += klingons;
-                total_starbases += starbases;
+        // Cells are generated in row-major order, one draw of each kind per quadrant
+        let quadrants = Grid::from_fn(|_: QuadrantPosition| {
+            let f = rng.unit("galaxy.klingons");
+            let klingons = if f > three {
+                3
+            } else if f > two {
+                2
+            } else if f > one {
+                1
+            } else {
+                0
+            };
+
+            let f = rng.unit("galaxy.starbases");
+            let starbases = if f > 0.96 { 1 } else { 0 };
+
+            let stars = (rng.unit("galaxy.stars") * 8.0 + 1.0).floor() as i32;
+
+            total_klingons += klingons;
+            total_starbases += starbases;
+            QuadrantData {
+                klingons,
+                starbases,
+                stars,
+                commanders: 0,
+                romulans: 0,
+                mines: 0,
             }
-        }
+        });
 
         if total_klingons > 0 && total_starbases > 0 {
             return (quadrants, total_klingons, total_starbases);
         }
     }
 }
+
+/// Klingon commanders rule: each quadrant holding Klingons has a small
+/// chance that one of them is a commander.
+pub fn promote_commanders(quadrants: &mut QuadrantGrid<QuadrantData>, rng: &mut GameRng) {
+    for (_, data) in quadrants.cells_mut() {
+        if data.klingons > 0 && rng.unit("galaxy.commanders") < KLINGON_COMMANDER_CHANCE {
+            data.commanders = 1;
+        }
+    }
+}
+
+/// Romulans rule: each quadrant has a small chance of hiding a cloaked
+/// Romulan warbird.
+pub fn deploy_romulans(quadrants: &mut QuadrantGrid<QuadrantData>, rng: &mut GameRng) {
+    for (_, data) in quadrants.cells_mut() {
+        if rng.unit("galaxy.romulans") < ROMULAN_CHANCE {
+            data.romulans = 1;
+        }
+    }
+}
+
+/// Minefields rule: each quadrant has a small chance of being sown with a
+/// few hidden mines.
+pub fn deploy_minefields(quadrants: &mut QuadrantGrid<QuadrantData>, rng: &mut GameRng) {
+    for (_, data) in quadrants.cells_mut() {
+        if rng.unit("galaxy.minefield") < MINEFIELD_CHANCE {
+            data.mines = rng.range("galaxy.mines", 2..=MAX_MINES_PER_FIELD);
+        }
+    }
+}
+
+/// Let registered mods adjust every quadrant, then recount the totals.
+/// Returns the new (total_klingons, total_starbases).
+pub fn apply_mods(
+    quadrants: &mut QuadrantGrid<QuadrantData>,
+    galaxy_seed: u64,
+    mods: &ModRegistry,
+) -> (i32, i32) {
+    let mut total_klingons = 0;
+    let mut total_starbases = 0;
+    for (pos, data) in quadrants.cells_mut() {
+        mods.adjust_quadrant(pos, encounter_seed(galaxy_seed, pos), data);
+        total_klingons += data.klingons;
+        total_starbases += data.starbases;
+    }
+    (total_klingons, total_starbases)
+}
+
+/// Reflect quadrant contents across the given axis.
+pub fn mirror_quadrants(
+    quadrants: &QuadrantGrid<QuadrantData>,
+    axis: MirrorAxis,
+) -> QuadrantGrid<QuadrantData> {
+    Grid::from_fn(|pos: QuadrantPosition| {
+        let (x, y) = axis.reflect(pos.x, pos.y);
+        quadrants[QuadrantPosition { x, y }]
+    })
+}
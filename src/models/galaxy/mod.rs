@@ -3,85 +3,288 @@
 //! Represents the game universe with 8x8 quadrants, each containing
 //! Klingons, starbases, stars, and the Enterprise.
 
+mod analysis;
+mod builder;
+mod dump;
 mod generation;
 mod quadrant_ops;
 
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
 use std::fmt;
+use std::hash::Hasher;
 
+use super::config::GameConfig;
 use super::constants::{
-    Condition, GALAXY_SIZE, INITIAL_ENERGY, MISSION_DURATION, SectorContent,
+    Condition, Device, DIPLOMATIC_INCIDENT_PENALTY, GALAXY_SIZE, KLINGON_SHIELD_REGENERATION, PHASER_COOLING_PER_STARDATE, REINFORCEMENT_WAVE_INTERVAL, SectorContent,
+    SymbolSet, SHIELD_DRAIN_PER_STARDATE, STALE_RECORD_AGE,
 };
-use super::enterprise::Enterprise;
+use super::digest::Fnv1a;
+use super::director::DirectorSettings;
+use super::enterprise::{Enterprise, Supplies};
 use super::errors::GameResult;
+use super::grid::QuadrantGrid;
+use super::hooks::{self, ModRegistry};
+use super::journal::{QuadrantEvent, QuadrantJournal};
+use super::ledger::{EnergyLedger, EnergyUse};
 use super::position::{QuadrantPosition, SectorPosition};
+use super::pending::PendingEvent;
+use super::probe::Probe;
 use super::quadrant::QuadrantData;
+use super::rng::GameRng;
+use super::ruleset::{MissionDuration, Ruleset};
 use super::sector_map::SectorMap;
+use super::war::WarStatus;
 
-use generation::generate_galaxy;
+use generation::{apply_mods, deploy_minefields, deploy_romulans, generate_galaxy, mirror_quadrants, promote_commanders};
+pub use analysis::{Distribution, GalaxyAnalysis, GalaxyProfile, SeedConstraints};
+pub use builder::{BuildError, GalaxyBuilder};
+pub use generation::MirrorAxis;
 use quadrant_ops::{
     decrement_quadrant_klingons, decrement_quadrant_starbases, enter_quadrant,
-    record_quadrant_to_memory,
+    find_random_empty_sector, migrate_klingons, record_quadrant_to_memory, restore_quadrant, full_shields,
 };
 
 /// Consolidated Klingon count tracking
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct KlingonCount {
     total: i32,
     initial: i32,
+    /// Klingons that have arrived since the game began.
+    reinforcements: i32,
+}
+
+/// Everything besides the seed that shapes a new galaxy.
+#[derive(Debug, Clone, Default)]
+pub struct GalaxyOptions {
+    /// Mods applied to generation, random events, and scoring.
+    pub mods: ModRegistry,
+    /// Reflection applied after generation, for racing pairs.
+    pub mirror: MirrorAxis,
+    /// Variant rules such as the mission duration formula.
+    pub ruleset: Ruleset,
+    /// Starting supplies, Klingon shields, and event chances.
+    pub config: GameConfig,
+}
+
+/// Where everything starts, before the game is set up around it.
+struct Layout {
+    starting_stardate: f64,
+    quadrants: QuadrantGrid<QuadrantData>,
+    total_klingons: i32,
+    total_starbases: i32,
+    quadrant: QuadrantPosition,
+    sector: SectorPosition,
 }
 
 /// Top-level game state container.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Galaxy {
+    seed: u64,
     stardate: f64,
     starting_stardate: f64,
     mission_duration: f64,
-    /// 8x8 grid of quadrant data. Internal 0-based: quadrants[y-1][x-1].
-    quadrants: [[QuadrantData; GALAXY_SIZE]; GALAXY_SIZE],
+    /// Turns completed so far.
+    #[cfg_attr(feature = "serde", serde(default))]
+    turns: u32,
+    /// Stardates charged for commands other than moves.
+    #[cfg_attr(feature = "serde", serde(default))]
+    command_time: f64,
+    /// 8x8 grid of quadrant data.
+    quadrants: QuadrantGrid<QuadrantData>,
     /// Computer's knowledge of the galaxy. None = unscanned, Some = scanned quadrant data.
-    computer_memory: [[Option<QuadrantData>; GALAXY_SIZE]; GALAXY_SIZE],
+    computer_memory: QuadrantGrid<Option<QuadrantData>>,
+    /// Stardate each quadrant in computer memory was last recorded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    recorded_at: QuadrantGrid<Option<f64>>,
     klingon_count: KlingonCount,
     total_starbases: i32,
+    /// Starbases destroyed so far this game.
+    starbases_lost: i32,
+    /// Starbases the Enterprise destroyed with its own torpedoes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    starbases_destroyed_by_enterprise: i32,
+    /// Diplomatic incidents the Enterprise has caused.
+    #[cfg_attr(feature = "serde", serde(default))]
+    diplomatic_incidents: i32,
+    /// Quadrants emptied of Klingons by the Enterprise.
+    quadrants_cleared: i32,
+    /// Quadrant of the starbase that serves as fleet headquarters.
+    fleet_hq: Option<QuadrantPosition>,
+    /// Stardate Klingon groups last migrated toward the starbases.
+    last_klingon_migration: f64,
+    /// Stardate the last wave of Klingon reinforcements was due.
+    #[cfg_attr(feature = "serde", serde(default))]
+    last_reinforcement_wave: f64,
+    /// Quadrants the Enterprise has physically entered.
+    visited: QuadrantGrid<bool>,
+    /// The Enterprise outlasted the attack on it while dead in space
+    /// (spec section 10.4); the game is lost all the same.
+    dead_in_space: bool,
+    /// The long-range probe in flight, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    probe: Option<Probe>,
+    /// Events waiting on a later stardate, oldest first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pending: Vec<PendingEvent>,
+    energy_ledger: EnergyLedger,
+    /// Energy drained by the shields since it was last announced.
+    #[cfg_attr(feature = "serde", serde(default))]
+    unreported_shield_drain: f64,
+    journal: QuadrantJournal,
+    /// Layouts of the quadrants the Enterprise has left, kept under the
+    /// persistent quadrants rule.
+    #[cfg_attr(feature = "serde", serde(default))]
+    saved_sector_maps: QuadrantGrid<Option<SectorMap>>,
     enterprise: Enterprise,
     sector_map: SectorMap,
-    rng: StdRng,
+    rng: GameRng,
+    /// Mods are code, not data: a deserialized galaxy has none registered.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    mods: ModRegistry,
+    ruleset: Ruleset,
+    config: GameConfig,
+    /// The player's choice of sector symbols; a display preference, not
+    /// part of the game.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    symbols: SymbolSet,
 }
 
 impl Galaxy {
     /// Create and initialize a new game from the player's seed number.
     pub fn new(seed: u64) -> Self {
-        let mut rng = StdRng::seed_from_u64(seed);
+        Self::with_options(seed, GalaxyOptions::default())
+    }
+
+    /// Create a new game whose generation, events, and score are adjusted by
+    /// the registered mods. With an empty registry this is identical to `new`.
+    pub fn new_with_mods(seed: u64, mods: ModRegistry) -> Self {
+        Self::with_options(seed, GalaxyOptions { mods, ..Default::default() })
+    }
+
+    /// Create the mirror twin of the galaxy for `seed`: quadrant contents and
+    /// the starting position are reflected across `axis` after generation.
+    /// Every random draw is the same as in the unmirrored galaxy.
+    pub fn new_mirrored(seed: u64, axis: MirrorAxis) -> Self {
+        Self::with_options(seed, GalaxyOptions { mirror: axis, ..Default::default() })
+    }
+
+    /// Create a new game with the balance numbers in `config` in place of
+    /// the original game's. With the default config this is identical to `new`.
+    pub fn new_with_config(seed: u64, config: GameConfig) -> Self {
+        Self::with_options(seed, GalaxyOptions { config, ..Default::default() })
+    }
+
+    /// Create a new game with every generation option spelled out.
+    pub fn with_options(seed: u64, options: GalaxyOptions) -> Self {
+        let GalaxyOptions { mods, mirror, ruleset, config } = options;
+        let mut rng = GameRng::seed_from_u64(seed);
 
         // Starting stardate (spec 3.2): floor(random * 20 + 20) * 100
-        let starting_stardate = (rng.gen::<f64>() * 20.0 + 20.0).floor() * 100.0;
+        let starting_stardate = (rng.unit("galaxy.stardate") * 20.0 + 20.0).floor() * 100.0;
 
         // Generate galaxy with regeneration guard (spec 3.4, 3.5)
-        let (quadrants, total_klingons, total_starbases) = generate_galaxy(&mut rng);
+        let (mut quadrants, mut total_klingons, mut total_starbases) = generate_galaxy(&mut rng, ruleset.difficulty);
+        if !mods.is_empty() {
+            (total_klingons, total_starbases) = apply_mods(&mut quadrants, seed, &mods);
+        }
 
         // Random starting position (spec 3.3)
         let quadrant = QuadrantPosition {
-            x: rng.gen_range(1..=8),
-            y: rng.gen_range(1..=8),
+            x: rng.range("galaxy.start_quadrant", 1..=8),
+            y: rng.range("galaxy.start_quadrant", 1..=8),
         };
         let sector = SectorPosition {
-            x: rng.gen_range(1..=8),
-            y: rng.gen_range(1..=8),
+            x: rng.range("galaxy.start_sector", 1..=8),
+            y: rng.range("galaxy.start_sector", 1..=8),
+        };
+
+        // Drawn after everything else so the rest of the galaxy matches the
+        // same seed without commanders, Romulans or mines
+        if ruleset.klingon_commanders {
+            promote_commanders(&mut quadrants, &mut rng);
+        }
+        if ruleset.romulans {
+            deploy_romulans(&mut quadrants, &mut rng);
+        }
+        if ruleset.minefields {
+            deploy_minefields(&mut quadrants, &mut rng);
+        }
+
+        // Mirror transform for racing pairs
+        let (quadrants, quadrant, sector) = if mirror == MirrorAxis::None {
+            (quadrants, quadrant, sector)
+        } else {
+            let (qx, qy) = mirror.reflect(quadrant.x, quadrant.y);
+            let (sx, sy) = mirror.reflect(sector.x, sector.y);
+            (
+                mirror_quadrants(&quadrants, mirror),
+                QuadrantPosition { x: qx, y: qy },
+                SectorPosition { x: sx, y: sy },
+            )
         };
 
+        let layout = Layout { starting_stardate, quadrants, total_klingons, total_starbases, quadrant, sector };
+        Self::from_layout(seed, rng, layout, GalaxyOptions { mods, mirror, ruleset, config })
+    }
+
+    /// Finish a new game from its generated or hand-made layout: the
+    /// Enterprise, the clock, and the starting quadrant.
+    fn from_layout(seed: u64, rng: GameRng, layout: Layout, options: GalaxyOptions) -> Self {
+        let Layout { starting_stardate, quadrants, total_klingons, total_starbases, quadrant, sector } = layout;
+        let GalaxyOptions { mods, ruleset, config, .. } = options;
+
+        // Fleet HQ is the first starbase in row-major order
+        let fleet_hq = quadrants
+            .cells()
+            .find(|(_, data)| data.starbases > 0)
+            .map(|(pos, _)| pos);
+
+        let capacity = Supplies { energy: config.initial_energy, torpedoes: config.initial_torpedoes };
+        let mut enterprise = Enterprise::with_capacity(quadrant, sector, capacity);
+        if ruleset.extended_long_range_sensors {
+            enterprise.upgrade_long_range_sensors();
+        }
+
         let mut galaxy = Galaxy {
+            seed,
             stardate: starting_stardate,
             starting_stardate,
-            mission_duration: MISSION_DURATION,
+            mission_duration: mission_duration(&ruleset, &config, total_klingons)
+                * ruleset.difficulty.mission_duration_factor(),
+            turns: 0,
+            command_time: 0.0,
             quadrants,
-            computer_memory: [[None; GALAXY_SIZE]; GALAXY_SIZE],
+            computer_memory: QuadrantGrid::filled(None),
+            recorded_at: QuadrantGrid::filled(None),
             klingon_count: KlingonCount {
                 total: total_klingons,
                 initial: total_klingons,
+                reinforcements: 0,
             },
             total_starbases,
-            enterprise: Enterprise::new(quadrant, sector),
+            starbases_lost: 0,
+            starbases_destroyed_by_enterprise: 0,
+            diplomatic_incidents: 0,
+            quadrants_cleared: 0,
+            fleet_hq,
+            last_klingon_migration: starting_stardate,
+            last_reinforcement_wave: starting_stardate,
+            visited: QuadrantGrid::filled(false),
+            dead_in_space: false,
+            probe: None,
+            pending: Vec::new(),
+            energy_ledger: EnergyLedger::new(),
+            unreported_shield_drain: 0.0,
+            journal: QuadrantJournal::new(),
+            saved_sector_maps: QuadrantGrid::default(),
+            enterprise,
             sector_map: SectorMap::new(),
             rng,
+            mods,
+            ruleset,
+            config,
+            symbols: SymbolSet::default(),
         };
 
         // Enter the starting quadrant (populates sector map)
@@ -98,6 +301,41 @@ impl Galaxy {
 
     // ========== Accessor Methods ==========
 
+    /// Get the seed this galaxy was generated from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the encounter seed for a quadrant, derived from the galaxy seed.
+    /// Stable across runs, and independent of how much RNG has been consumed.
+    pub fn encounter_seed(&self, pos: QuadrantPosition) -> u64 {
+        hooks::encounter_seed(self.seed, pos)
+    }
+
+    /// Get the registered mods
+    pub fn mods(&self) -> &ModRegistry {
+        &self.mods
+    }
+
+    /// Get the variant rules this game is played under
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    /// Get the balance numbers this game was created with
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Symbols the short range scan draws sectors with
+    pub fn symbols(&self) -> SymbolSet {
+        self.symbols
+    }
+
+    pub fn set_symbols(&mut self, symbols: SymbolSet) {
+        self.symbols = symbols;
+    }
+
     /// Get current stardate
     pub fn stardate(&self) -> f64 {
         self.stardate
@@ -124,11 +362,86 @@ impl Galaxy {
         self.klingon_count.initial
     }
 
+    /// Klingons that have arrived as reinforcements
+    pub fn klingon_reinforcements(&self) -> i32 {
+        self.klingon_count.reinforcements
+    }
+
+    /// Every Klingon the mission has had to face: the initial force plus
+    /// reinforcements
+    pub fn klingons_faced(&self) -> i32 {
+        self.klingon_count.initial + self.klingon_count.reinforcements
+    }
+
+    /// Multiplier on Klingon fire: 1.0 unless the adaptive director is on
+    pub fn klingon_aggression(&self) -> f64 {
+        match &self.ruleset.director {
+            Some(bounds) => DirectorSettings::assess(self, bounds).aggression,
+            None => 1.0,
+        }
+    }
+
+    /// Multiplier on repairs in transit and phaser fire: 1.0 unless the
+    /// crew morale rule is on
+    pub fn crew_efficiency(&self) -> f64 {
+        if self.ruleset.crew_morale {
+            self.enterprise.crew_efficiency()
+        } else {
+            1.0
+        }
+    }
+
     /// Get total starbases
     pub fn total_starbases(&self) -> i32 {
         self.total_starbases
     }
 
+    /// Get number of starbases destroyed so far
+    pub fn starbases_lost(&self) -> i32 {
+        self.starbases_lost
+    }
+
+    /// Starbases the Enterprise destroyed with its own torpedoes
+    pub fn starbases_destroyed_by_enterprise(&self) -> i32 {
+        self.starbases_destroyed_by_enterprise
+    }
+
+    /// Diplomatic incidents the Enterprise has caused
+    pub fn diplomatic_incidents(&self) -> i32 {
+        self.diplomatic_incidents
+    }
+
+    /// Count a diplomatic incident against the captain's rating
+    pub fn record_diplomatic_incident(&mut self) {
+        self.diplomatic_incidents += 1;
+    }
+
+    /// Get number of quadrants emptied of Klingons so far
+    pub fn quadrants_cleared(&self) -> i32 {
+        self.quadrants_cleared
+    }
+
+    /// Quadrant of the fleet HQ starbase, if it still stands.
+    pub fn fleet_hq(&self) -> Option<QuadrantPosition> {
+        self.fleet_hq
+            .filter(|&q| self.quadrants[q].starbases > 0)
+    }
+
+    /// Record of where the ship's energy went
+    pub fn energy_ledger(&self) -> &EnergyLedger {
+        &self.energy_ledger
+    }
+
+    /// Add an entry to the energy ledger at the current stardate
+    pub fn record_energy(&mut self, kind: EnergyUse, amount: f64) {
+        self.energy_ledger.record(self.stardate, kind, amount);
+    }
+
+    /// Progress of the war, from the galaxy's running totals.
+    pub fn war_status(&self) -> WarStatus {
+        WarStatus::from_galaxy(self)
+    }
+
     /// Get reference to Enterprise
     pub fn enterprise(&self) -> &Enterprise {
         &self.enterprise
@@ -149,19 +462,93 @@ impl Galaxy {
         &mut self.sector_map
     }
 
-    /// Get reference to quadrants array
-    pub fn quadrants(&self) -> &[[QuadrantData; GALAXY_SIZE]; GALAXY_SIZE] {
+    /// Get reference to the quadrant grid
+    pub fn quadrants(&self) -> &QuadrantGrid<QuadrantData> {
         &self.quadrants
     }
 
+    /// Every quadrant with its position, in row-major order.
+    pub fn quadrants_iter(&self) -> impl Iterator<Item = (QuadrantPosition, &QuadrantData)> {
+        self.quadrants.cells()
+    }
+
+    /// Quadrants in computer memory with the data last recorded there, in
+    /// row-major order.
+    pub fn scanned_quadrants(&self) -> impl Iterator<Item = (QuadrantPosition, &QuadrantData)> {
+        self.computer_memory
+            .cells()
+            .filter_map(|(pos, memory)| memory.as_ref().map(|data| (pos, data)))
+    }
+
+    /// Get reference to RNG (e.g. to read its draw log)
+    pub fn rng(&self) -> &GameRng {
+        &self.rng
+    }
+
     /// Get mutable reference to RNG
-    pub fn rng_mut(&mut self) -> &mut StdRng {
+    pub fn rng_mut(&mut self) -> &mut GameRng {
         &mut self.rng
     }
 
-    /// Advance stardate by delta
+    /// Get number of turns completed so far
+    pub fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    /// Count a completed turn, returning the new total
+    pub fn complete_turn(&mut self) -> u32 {
+        self.turns += 1;
+        self.turns
+    }
+
+    /// Stardates charged for commands other than moves so far
+    pub fn command_time(&self) -> f64 {
+        self.command_time
+    }
+
+    /// Advance time by what a command costs under the config's command
+    /// costs; free commands leave the clock alone
+    pub fn charge_command_time(&mut self, cost: f64) {
+        if cost > 0.0 {
+            self.command_time += cost;
+            self.advance_time(cost);
+        }
+    }
+
+    /// Advance stardate by delta; the phaser banks cool meanwhile, and
+    /// under the shield drain rule raised shields draw on main power
     pub fn advance_time(&mut self, delta: f64) {
         self.stardate += delta;
+        self.enterprise.cool_phasers(delta * PHASER_COOLING_PER_STARDATE);
+        if self.ruleset.shield_drain && self.enterprise.shields() > 0.0 {
+            let drain = (delta * SHIELD_DRAIN_PER_STARDATE).min(self.enterprise.energy().max(0.0));
+            if drain > 0.0 {
+                self.enterprise.subtract_energy(drain);
+                self.record_energy(EnergyUse::ShieldDrain, -drain);
+                self.unreported_shield_drain += drain;
+            }
+        }
+        if self.ruleset.klingon_regeneration {
+            self.regenerate_klingons(delta);
+        }
+    }
+
+    /// Surviving Klingons, here and in remembered quadrants, recover
+    /// `KLINGON_SHIELD_REGENERATION` of their full shields per stardate.
+    fn regenerate_klingons(&mut self, delta: f64) {
+        let saved = self.saved_sector_maps.iter_mut().flatten();
+        for klingon in std::iter::once(&mut self.sector_map).chain(saved).flat_map(|map| map.klingons.iter_mut()) {
+            let full = full_shields(&self.config, &self.ruleset, klingon.kind);
+            if klingon.shields < full {
+                klingon.shields = (klingon.shields + delta * KLINGON_SHIELD_REGENERATION * full).min(full);
+            }
+        }
+    }
+
+    /// Energy the shields have drained since the last call, for
+    /// announcing to the player.
+    pub fn take_shield_drain(&mut self) -> f64 {
+        std::mem::take(&mut self.unreported_shield_drain)
     }
 
     /// Decrement total Klingon count
@@ -177,13 +564,38 @@ impl Galaxy {
     }
 
     /// Get reference to computer memory
-    pub fn computer_memory(&self) -> &[[Option<QuadrantData>; GALAXY_SIZE]; GALAXY_SIZE] {
+    pub fn computer_memory(&self) -> &QuadrantGrid<Option<QuadrantData>> {
         &self.computer_memory
     }
 
+    /// Get which quadrants the Enterprise has entered
+    pub fn visited(&self) -> &QuadrantGrid<bool> {
+        &self.visited
+    }
+
+    /// Check whether the Enterprise has entered a quadrant
+    pub fn has_visited(&self, pos: QuadrantPosition) -> bool {
+        self.visited[pos]
+    }
+
+    /// Count the quadrants the Enterprise has entered
+    pub fn quadrants_visited(&self) -> i32 {
+        self.visited.iter().filter(|&&v| v).count() as i32
+    }
+
+    /// Percentage of the galaxy the Enterprise has entered, 0.0-100.0
+    pub fn exploration_percent(&self) -> f64 {
+        self.quadrants_visited() as f64 * 100.0 / (GALAXY_SIZE * GALAXY_SIZE) as f64
+    }
+
+    /// Get the computer's journal of events per quadrant
+    pub fn journal(&self) -> &QuadrantJournal {
+        &self.journal
+    }
+
     /// Get mutable reference to computer memory
     #[allow(dead_code)]
-    pub fn computer_memory_mut(&mut self) -> &mut [[Option<QuadrantData>; GALAXY_SIZE]; GALAXY_SIZE] {
+    pub fn computer_memory_mut(&mut self) -> &mut QuadrantGrid<Option<QuadrantData>> {
         &mut self.computer_memory
     }
 
@@ -226,20 +638,60 @@ impl Galaxy {
     /// Atomically destroy a Klingon, updating all tracking locations
     pub fn destroy_klingon(&mut self, pos: SectorPosition) -> GameResult<()> {
         // Remove from sector map
-        self.sector_map.set(pos, SectorContent::Empty);
+        let commander = self.sector_map.get(pos) == SectorContent::KlingonCommander;
+        let wreck = if self.ruleset.debris { SectorContent::Debris } else { SectorContent::Empty };
+        self.sector_map.set(pos, wreck);
 
         // Decrement global count
         self.klingon_count.total -= 1;
 
         // Decrement quadrant count
         let q = self.enterprise.quadrant();
-        let qy = (q.y - 1) as usize;
-        let qx = (q.x - 1) as usize;
-        self.quadrants[qy][qx].klingons -= 1;
+        self.quadrants[q].klingons -= 1;
+        if commander {
+            self.quadrants[q].commanders -= 1;
+        }
+        if self.quadrants[q].klingons == 0 {
+            self.quadrants_cleared += 1;
+            self.journal.record(q, self.stardate, QuadrantEvent::QuadrantCleared);
+        }
 
         Ok(())
     }
 
+    /// Atomically destroy a Romulan warbird, updating all tracking locations.
+    /// Romulans do not count toward victory.
+    pub fn destroy_romulan(&mut self, pos: SectorPosition) {
+        self.sector_map.set(pos, SectorContent::Empty);
+        self.sector_map.romulans.retain(|r| r.sector != pos);
+        let q = self.enterprise.quadrant();
+        self.quadrants[q].romulans -= 1;
+    }
+
+    /// Set off a mine, which is gone for good.
+    pub fn detonate_mine(&mut self, pos: SectorPosition) {
+        self.sector_map.set(pos, SectorContent::Empty);
+        let q = self.enterprise.quadrant();
+        self.quadrants[q].mines -= 1;
+    }
+
+    /// Destroy the Tholian, and with it the web it spun. Returns whether
+    /// any web was left to dissolve.
+    pub fn destroy_tholian(&mut self, pos: SectorPosition) -> bool {
+        self.sector_map.set(pos, SectorContent::Empty);
+        self.sector_map.tholian = None;
+        let web: Vec<SectorPosition> = self
+            .sector_map
+            .iter()
+            .filter(|&(_, content)| content == SectorContent::Web)
+            .map(|(pos, _)| pos)
+            .collect();
+        for &pos in &web {
+            self.sector_map.set(pos, SectorContent::Empty);
+        }
+        !web.is_empty()
+    }
+
     /// Atomically destroy a starbase, updating all tracking locations
     pub fn destroy_starbase(&mut self, pos: SectorPosition) {
         // Remove from sector map
@@ -248,33 +700,150 @@ impl Galaxy {
 
         // Decrement global count
         self.total_starbases -= 1;
+        self.starbases_lost += 1;
 
         // Decrement quadrant count
         let q = self.enterprise.quadrant();
-        let qy = (q.y - 1) as usize;
-        let qx = (q.x - 1) as usize;
-        self.quadrants[qy][qx].starbases = 0;
+        self.quadrants[q].starbases = 0;
+        self.journal.record(q, self.stardate, QuadrantEvent::StarbaseDestroyed);
+    }
+
+    /// Atomically destroy a starbase hit by the Enterprise's own torpedo,
+    /// counting it against the captain's record.
+    pub fn destroy_starbase_by_torpedo(&mut self, pos: SectorPosition) {
+        self.destroy_starbase(pos);
+        self.starbases_destroyed_by_enterprise += 1;
+    }
+
+    /// Atomically destroy the starbase in any quadrant, as Klingons do when
+    /// they overrun it. The computer's record is left as it was. Returns
+    /// whether there was a starbase left there to destroy.
+    pub fn overrun_starbase(&mut self, pos: QuadrantPosition) -> bool {
+        if self.quadrants[pos].starbases == 0 {
+            return false;
+        }
+        if pos == self.enterprise.quadrant() {
+            return match self.sector_map.starbase {
+                Some(sector) => {
+                    self.destroy_starbase(sector);
+                    true
+                }
+                None => false,
+            };
+        }
+        self.quadrants[pos].starbases = 0;
+        self.total_starbases -= 1;
+        self.starbases_lost += 1;
+        self.journal.record(pos, self.stardate, QuadrantEvent::StarbaseDestroyed);
+        true
+    }
+
+    /// Atomically add a starbase to another quadrant, updating the global
+    /// count and the computer's record of that quadrant.
+    pub fn construct_starbase(&mut self, pos: QuadrantPosition) {
+        self.quadrants[pos].starbases = 1;
+        self.total_starbases += 1;
+        self.computer_memory[pos] = Some(self.quadrants[pos]);
+        self.recorded_at[pos] = Some(self.stardate);
+        self.journal.record(pos, self.stardate, QuadrantEvent::StarbaseConstructed);
+    }
+
+    /// Atomically move Klingon groups one quadrant toward the nearest
+    /// starbase, recording the current stardate as the last migration.
+    /// Computer memory is left as it was, so the change shows on the next
+    /// scan. Returns the starbase quadrants groups arrived at.
+    pub fn migrate_klingons(&mut self) -> Vec<QuadrantPosition> {
+        self.last_klingon_migration = self.stardate;
+        migrate_klingons(&mut self.quadrants, self.enterprise.quadrant())
+    }
+
+    /// Atomically add one Klingon to a quadrant as a reinforcement. Computer
+    /// memory is left as it was, so the new arrival shows on the next scan.
+    pub fn reinforce_klingons(&mut self, pos: QuadrantPosition) {
+        self.quadrants[pos].klingons += 1;
+        self.klingon_count.total += 1;
+        self.klingon_count.reinforcements += 1;
+    }
+
+    /// Get the stardate the last reinforcement wave was due
+    pub fn last_reinforcement_wave(&self) -> f64 {
+        self.last_reinforcement_wave
+    }
+
+    /// Mark the next reinforcement wave as arrived, one interval after the
+    /// last, so a long move that spans several intervals brings several.
+    pub fn record_reinforcement_wave(&mut self) {
+        self.last_reinforcement_wave += REINFORCEMENT_WAVE_INTERVAL;
+    }
+
+    /// Get the stardate Klingon groups last migrated
+    pub fn last_klingon_migration(&self) -> f64 {
+        self.last_klingon_migration
     }
 
     // ========== End Atomic Update Methods ==========
 
     /// Enter the current quadrant: clear sector map and place all entities.
     /// Called on game start and every quadrant transition (spec section 4).
+    /// A layout saved by `leave_quadrant` is put back instead, if it still
+    /// fits the quadrant.
     pub fn enter_quadrant(&mut self) {
+        let q = self.enterprise.quadrant();
+        self.visited[q] = true;
+        if let Some(saved) = self.saved_sector_maps[q].take() {
+            if restore_quadrant(&mut self.sector_map, saved, &self.enterprise, &self.quadrants[q]) {
+                return;
+            }
+        }
         enter_quadrant(
             &mut self.sector_map,
             &self.enterprise,
             &self.quadrants,
+            &self.config,
+            &self.ruleset,
             &mut self.rng,
         );
     }
 
+    /// Remember the current quadrant's layout for the next visit, when the
+    /// persistent quadrants rule is on. Call before the Enterprise leaves.
+    pub fn leave_quadrant(&mut self) {
+        if self.ruleset.persistent_quadrants {
+            let mut saved = self.sector_map.clone();
+            saved.set(self.enterprise.sector(), SectorContent::Empty);
+            self.saved_sector_maps[self.enterprise.quadrant()] = Some(saved);
+        }
+    }
+
+    /// A random empty sector of the current quadrant.
+    pub fn random_empty_sector(&mut self) -> SectorPosition {
+        find_random_empty_sector(&self.sector_map, &mut self.rng)
+    }
+
+    /// Klingons present while the shields are dangerously low, checked on
+    /// entering a quadrant (spec section 4.2).
+    pub fn red_alert(&self) -> bool {
+        !self.sector_map.klingons.is_empty() && self.enterprise.shields() <= 200.0
+    }
+
     /// Check if the Enterprise is adjacent to a starbase and dock if so.
     /// Returns true if docked (spec section 9.1-9.2).
     pub fn check_docking(&mut self) -> bool {
         self.enterprise.check_docking(self.sector_map.starbase)
     }
 
+    /// Install the long range sensor upgrade if the Enterprise is docked at
+    /// fleet HQ and does not have it yet. Returns true if it was installed.
+    pub fn install_fleet_hq_upgrade(&mut self) -> bool {
+        let at_hq = self.fleet_hq() == Some(self.enterprise.quadrant())
+            && self.enterprise.is_adjacent_to_starbase(self.sector_map.starbase);
+        if !at_hq || self.enterprise.has_extended_long_range_sensors() {
+            return false;
+        }
+        self.enterprise.upgrade_long_range_sensors();
+        true
+    }
+
     /// Record a quadrant's data into computer memory.
     /// Does nothing if the Computer device is damaged or coordinates are out of range.
     pub fn record_quadrant_to_memory(&mut self, x: i32, y: i32) {
@@ -285,6 +854,33 @@ impl Galaxy {
             x,
             y,
         );
+        // Journal only what the computer actually recorded
+        if self.enterprise.is_damaged(Device::Computer) {
+            return;
+        }
+        if let Some(data) = self.computer_memory_at(x, y) {
+            self.recorded_at[QuadrantPosition { x, y }] = Some(self.stardate);
+            self.journal
+                .sight_klingons(QuadrantPosition { x, y }, self.stardate, data.klingons);
+        }
+    }
+
+    /// Stardates since the computer last recorded `pos`, `None` if it has
+    /// no dated record of it.
+    pub fn record_age(&self, pos: QuadrantPosition) -> Option<f64> {
+        self.recorded_at.get(pos).copied().flatten().map(|at| self.stardate - at)
+    }
+
+    /// Whether the computer's record of `pos` is more than
+    /// `STALE_RECORD_AGE` stardates old, so reinforcements or migrations
+    /// may have changed the quadrant since.
+    pub fn is_record_stale(&self, pos: QuadrantPosition) -> bool {
+        self.record_age(pos).is_some_and(|age| age > STALE_RECORD_AGE)
+    }
+
+    /// Computer memory for a quadrant, `None` if unscanned or off the map.
+    fn computer_memory_at(&self, x: i32, y: i32) -> Option<QuadrantData> {
+        self.computer_memory.get(QuadrantPosition { x, y }).copied().flatten()
     }
 
     /// Evaluate the ship's condition code (spec section 9.4).
@@ -293,9 +889,10 @@ impl Galaxy {
             return Condition::Docked;
         }
 
-        if !self.sector_map.klingons.is_empty() {
+        // A cloaked Romulan does not show on the sensors
+        if !self.sector_map.klingons.is_empty() || self.sector_map.visible_romulans().next().is_some() {
             Condition::Red
-        } else if self.enterprise.energy() < INITIAL_ENERGY * 0.1 {
+        } else if self.enterprise.energy() < self.enterprise.capacity().energy * 0.1 {
             Condition::Yellow
         } else {
             Condition::Green
@@ -307,15 +904,93 @@ impl Galaxy {
         self.klingon_count.total == 0
     }
 
+    /// Check if the Enterprise was left dead in space (spec section 10.4).
+    pub fn is_dead_in_space(&self) -> bool {
+        self.dead_in_space
+    }
+
+    /// Record that the Enterprise survived being dead in space. The game
+    /// engine ends the game on its next check.
+    pub fn mark_dead_in_space(&mut self) {
+        self.dead_in_space = true;
+    }
+
+    /// The long-range probe in flight, if any.
+    pub fn probe(&self) -> Option<Probe> {
+        self.probe
+    }
+
+    pub fn set_probe(&mut self, probe: Option<Probe>) {
+        self.probe = probe;
+    }
+
+    /// Events waiting on a later stardate, oldest first.
+    pub fn pending_events(&self) -> &[PendingEvent] {
+        &self.pending
+    }
+
+    pub fn schedule(&mut self, event: PendingEvent) {
+        self.pending.push(event);
+    }
+
+    /// Remove a pending event once it has been resolved.
+    pub fn unschedule(&mut self, event: &PendingEvent) {
+        self.pending.retain(|pending| pending != event);
+    }
+
     /// Check if time has expired (spec section 10.3).
     pub fn is_time_expired(&self) -> bool {
         self.stardate > self.starting_stardate + self.mission_duration
     }
 
-    /// Calculate the efficiency rating (spec section 7.7).
+    /// Calculate the efficiency rating (spec section 7.7), less
+    /// `DIPLOMATIC_INCIDENT_PENALTY` for each diplomatic incident.
     pub fn efficiency_rating(&self) -> i32 {
         let elapsed = self.stardate - self.starting_stardate;
-        ((self.klingon_count.initial as f64 / elapsed) * 1000.0) as i32
+        ((self.klingons_faced() as f64 / elapsed) * 1000.0) as i32
+            - self.diplomatic_incidents * DIPLOMATIC_INCIDENT_PENALTY
+    }
+
+    /// Fingerprint of the game state: seed, clock, counts, every quadrant, and
+    /// the Enterprise. Two games that reach the same state produce the same
+    /// digest on any platform, so it can be used to validate submitted scores.
+    pub fn state_digest(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write_u64(self.seed);
+        hasher.write_u64(self.stardate.to_bits());
+        hasher.write_u64(self.starting_stardate.to_bits());
+        hasher.write_u64(self.mission_duration.to_bits());
+        hasher.write_i32(self.klingon_count.total);
+        hasher.write_i32(self.klingon_count.initial);
+        hasher.write_i32(self.klingon_count.reinforcements);
+        hasher.write_i32(self.total_starbases);
+        hasher.write_i32(self.starbases_lost);
+        hasher.write_i32(self.quadrants_cleared);
+        hasher.write_u64(self.last_klingon_migration.to_bits());
+        for &visited in self.visited.iter() {
+            hasher.write_i32(visited as i32);
+        }
+        for quadrant in self.quadrants.iter() {
+            hasher.write_i32(quadrant.encoded());
+        }
+        let e = &self.enterprise;
+        for coord in [e.quadrant().x, e.quadrant().y, e.sector().x, e.sector().y] {
+            hasher.write_i32(coord);
+        }
+        hasher.write_u64(e.energy().to_bits());
+        hasher.write_u64(e.shields().to_bits());
+        hasher.write_u64(e.hull().to_bits());
+        hasher.write_i32(e.torpedoes());
+        hasher.write_i32(e.long_range_scan_radius());
+        for damage in e.devices() {
+            hasher.write_u64(damage.to_bits());
+        }
+        hasher.finish()
+    }
+
+    /// Pretty-printed JSON dump of the full game state, for bug reports.
+    pub fn state_dump(&self) -> String {
+        dump::state_dump(self)
     }
 
     /// Update the quadrant's klingon count after removing one.
@@ -331,6 +1006,16 @@ impl Galaxy {
     }
 }
 
+/// Stardates allowed before any difficulty scaling. The default fixed
+/// duration gives way to the configured one; variant formulas stand as set.
+fn mission_duration(ruleset: &Ruleset, config: &GameConfig, initial_klingons: i32) -> f64 {
+    if ruleset.mission_duration == MissionDuration::default() {
+        MissionDuration::Fixed(config.mission_duration).stardates(initial_klingons)
+    } else {
+        ruleset.mission_duration.stardates(initial_klingons)
+    }
+}
+
 // Custom Debug that doesn't expose RNG internals
 impl fmt::Debug for Galaxy {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -345,9 +1030,10 @@ impl fmt::Debug for Galaxy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::ruleset::MissionDuration;
     use crate::models::constants::{
         Condition, GALAXY_SIZE, INITIAL_ENERGY, INITIAL_SHIELDS, INITIAL_TORPEDOES,
-        MISSION_DURATION, SECTOR_SIZE, SectorContent,
+        MAX_MINES_PER_FIELD, MISSION_DURATION, SECTOR_SIZE, SectorContent,
     };
 
     // ========== Galaxy initialization tests ==========
@@ -401,6 +1087,78 @@ mod tests {
         assert_eq!(galaxy.mission_duration(), MISSION_DURATION);
     }
 
+    #[test]
+    fn scaled_mission_duration_uses_initial_klingons() {
+        struct Crowded;
+        impl hooks::ModHooks for Crowded {
+            fn adjust_quadrant(&self, _pos: QuadrantPosition, _seed: u64, data: &mut QuadrantData) {
+                data.klingons = 3;
+            }
+        }
+        let mut mods = ModRegistry::new();
+        mods.register(Crowded);
+        let ruleset = Ruleset { mission_duration: MissionDuration::SCALED, ..Default::default() };
+
+        let crowded = Galaxy::with_options(0, GalaxyOptions { mods, ruleset, ..Default::default() });
+        assert_eq!(crowded.initial_klingons(), 192);
+        assert_eq!(crowded.mission_duration(), 193.0);
+
+        let sparse = Galaxy::with_options(0, GalaxyOptions { ruleset, ..Default::default() });
+        assert_eq!(sparse.mission_duration(), MISSION_DURATION);
+        assert_eq!(sparse.ruleset(), &ruleset);
+    }
+
+    #[test]
+    fn difficulty_scales_klingons_shields_and_time() {
+        use crate::models::difficulty::Difficulty;
+
+        let at = |difficulty| {
+            let ruleset = Ruleset { difficulty, ..Default::default() };
+            let galaxy = Galaxy::with_options(7, GalaxyOptions { ruleset, ..Default::default() });
+            let shields = galaxy.sector_map().klingons.first().map(|k| k.shields);
+            (galaxy.total_klingons(), shields, galaxy.mission_duration())
+        };
+        assert_eq!(at(Difficulty::Captain), at(Difficulty::default()));
+        let default = Galaxy::new(7);
+        assert_eq!(at(Difficulty::Captain).0, default.total_klingons());
+        let (novice, captain, admiral) = (at(Difficulty::Novice), at(Difficulty::Captain), at(Difficulty::Admiral));
+        assert!(novice.0 < captain.0 && captain.0 < admiral.0);
+        assert_eq!(novice.2, MISSION_DURATION * 1.25);
+        assert_eq!(admiral.2, MISSION_DURATION * 0.8);
+        for (level, (_, shields, _)) in [(Difficulty::Novice, novice), (Difficulty::Admiral, admiral)] {
+            if let Some(shields) = shields {
+                assert!(shields <= 200.0 * level.klingon_shield_factor());
+            }
+        }
+    }
+
+    #[test]
+    fn game_config_sets_balance_numbers() {
+        let default = Galaxy::new_with_config(7, GameConfig::default());
+        assert_eq!(default.state_digest(), Galaxy::new(7).state_digest());
+
+        let config = GameConfig {
+            initial_energy: 5000.0,
+            initial_torpedoes: 4,
+            mission_duration: 45.0,
+            klingon_shields: 350.0,
+            ..Default::default()
+        };
+        let seed = (0..100)
+            .find(|&seed| !Galaxy::new(seed).sector_map().klingons.is_empty())
+            .unwrap();
+        let mut galaxy = Galaxy::new_with_config(seed, config);
+        assert_eq!(galaxy.config(), &config);
+        assert_eq!(galaxy.enterprise.energy(), 5000.0);
+        assert_eq!(galaxy.enterprise.torpedoes(), 4);
+        assert_eq!(galaxy.mission_duration(), 45.0);
+        assert!(galaxy.sector_map().klingons.iter().all(|k| k.shields == 350.0));
+
+        galaxy.enterprise.set_energy(400.0); // below 10% of 5000
+        galaxy.sector_map = SectorMap::new();
+        assert_eq!(galaxy.evaluate_condition(), Condition::Yellow);
+    }
+
     #[test]
     fn enterprise_position_in_valid_range() {
         for seed in 0..20 {
@@ -426,12 +1184,10 @@ mod tests {
     fn quadrant_klingon_counts_sum_to_total() {
         let galaxy = Galaxy::new(42);
         let mut sum = 0;
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                let k = galaxy.quadrants[y][x].klingons;
-                assert!((0..=3).contains(&k), "klingon count out of [0,3]");
-                sum += k;
-            }
+        for quadrant in galaxy.quadrants.iter() {
+            let k = quadrant.klingons;
+            assert!((0..=3).contains(&k), "klingon count out of [0,3]");
+            sum += k;
         }
         assert_eq!(sum, galaxy.total_klingons());
     }
@@ -440,12 +1196,10 @@ mod tests {
     fn quadrant_starbase_counts_sum_to_total() {
         let galaxy = Galaxy::new(42);
         let mut sum = 0;
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                let b = galaxy.quadrants[y][x].starbases;
-                assert!(b == 0 || b == 1, "starbase count not 0 or 1");
-                sum += b;
-            }
+        for quadrant in galaxy.quadrants.iter() {
+            let b = quadrant.starbases;
+            assert!(b == 0 || b == 1, "starbase count not 0 or 1");
+            sum += b;
         }
         assert_eq!(sum, galaxy.total_starbases());
     }
@@ -453,33 +1207,59 @@ mod tests {
     #[test]
     fn stars_in_valid_range() {
         let galaxy = Galaxy::new(42);
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                let s = galaxy.quadrants[y][x].stars;
-                assert!((1..=8).contains(&s), "stars {} out of range [1,8]", s);
-            }
+        for quadrant in galaxy.quadrants.iter() {
+            let s = quadrant.stars;
+            assert!((1..=8).contains(&s), "stars {} out of range [1,8]", s);
         }
     }
 
     #[test]
     fn computer_memory_starts_unscanned_except_starting_quadrant() {
         let galaxy = Galaxy::new(0);
-        let qx = galaxy.enterprise.quadrant().x;
-        let qy = galaxy.enterprise.quadrant().y;
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                if x == (qx - 1) as usize && y == (qy - 1) as usize {
-                    // Starting quadrant should be recorded
-                    let expected = galaxy.quadrants[y][x];
-                    assert_eq!(galaxy.computer_memory[y][x], Some(expected));
-                } else {
-                    // All other quadrants should be unscanned
-                    assert_eq!(galaxy.computer_memory[y][x], None);
-                }
+        let start = galaxy.enterprise.quadrant();
+        for (pos, &memory) in galaxy.computer_memory.cells() {
+            if pos == start {
+                // Starting quadrant should be recorded
+                assert_eq!(memory, Some(galaxy.quadrants[pos]));
+            } else {
+                // All other quadrants should be unscanned
+                assert_eq!(memory, None);
             }
         }
     }
 
+    #[test]
+    fn scanned_quadrants_follow_computer_memory() {
+        let mut galaxy = Galaxy::new(42);
+        let start = galaxy.enterprise.quadrant();
+        let scanned: Vec<_> = galaxy.scanned_quadrants().map(|(pos, &data)| (pos, data)).collect();
+        assert_eq!(scanned, vec![(start, galaxy.quadrants[start])]);
+
+        galaxy.record_quadrant_to_memory(1, 1);
+        galaxy.record_quadrant_to_memory(8, 8);
+        let positions: Vec<_> = galaxy.scanned_quadrants().map(|(pos, _)| pos).collect();
+        assert_eq!(positions.first(), Some(&QuadrantPosition { x: 1, y: 1 }));
+        assert_eq!(positions.last(), Some(&QuadrantPosition { x: 8, y: 8 }));
+        assert_eq!(galaxy.quadrants_iter().count(), GALAXY_SIZE * GALAXY_SIZE);
+    }
+
+    #[test]
+    fn records_go_stale_until_the_quadrant_is_scanned_again() {
+        let mut galaxy = Galaxy::new(42);
+        let start = galaxy.enterprise.quadrant();
+        let corner = QuadrantPosition { x: 1, y: 1 };
+        assert_eq!(galaxy.record_age(start), Some(0.0));
+        assert_eq!(galaxy.record_age(corner), None);
+
+        galaxy.advance_time(STALE_RECORD_AGE);
+        galaxy.record_quadrant_to_memory(1, 1);
+        assert!(!galaxy.is_record_stale(start));
+        galaxy.advance_time(1.0);
+        assert!(galaxy.is_record_stale(start));
+        assert_eq!(galaxy.record_age(corner), Some(1.0));
+        assert!(!galaxy.is_record_stale(corner));
+    }
+
     #[test]
     fn sector_map_has_enterprise_after_init() {
         let galaxy = Galaxy::new(42);
@@ -491,7 +1271,7 @@ mod tests {
     fn sector_map_entity_counts_match_quadrant_data() {
         let galaxy = Galaxy::new(42);
         let q = galaxy.enterprise.quadrant();
-        let qdata = galaxy.quadrants[(q.y - 1) as usize][(q.x - 1) as usize];
+        let qdata = galaxy.quadrants[q];
 
         assert_eq!(
             galaxy.sector_map.klingons.len() as i32,
@@ -506,14 +1286,7 @@ mod tests {
         }
 
         // Count stars in the sector map
-        let mut star_count = 0;
-        for y in 1..=8 {
-            for x in 1..=8 {
-                if galaxy.sector_map.get(SectorPosition { x, y }) == SectorContent::Star {
-                    star_count += 1;
-                }
-            }
-        }
+        let star_count = galaxy.sector_map.iter().filter(|&(_, c)| c == SectorContent::Star).count() as i32;
         assert_eq!(star_count, qdata.stars, "star count mismatch");
     }
 
@@ -541,6 +1314,97 @@ mod tests {
 
 
 
+    #[test]
+    fn mods_adjust_generated_quadrants_and_totals() {
+        use crate::models::hooks::ModHooks;
+
+        struct ExtraKlingon;
+        impl ModHooks for ExtraKlingon {
+            fn adjust_quadrant(&self, _pos: QuadrantPosition, _seed: u64, data: &mut QuadrantData) {
+                data.klingons += 1;
+            }
+        }
+
+        let plain = Galaxy::new(42);
+        let mut mods = ModRegistry::new();
+        mods.register(ExtraKlingon);
+        let modded = Galaxy::new_with_mods(42, mods);
+
+        let expected: i32 = plain
+            .quadrants
+            .iter()
+            .map(|q| (q.klingons + 1).min(3))
+            .sum();
+        assert_eq!(modded.total_klingons(), expected);
+        assert_eq!(modded.initial_klingons(), expected);
+        assert_eq!(modded.stardate(), plain.stardate());
+    }
+
+    #[test]
+    fn encounter_seed_ignores_rng_consumption() {
+        let mut galaxy = Galaxy::new(42);
+        let pos = QuadrantPosition { x: 2, y: 7 };
+        let before = galaxy.encounter_seed(pos);
+        galaxy.rng_mut().unit("test");
+        assert_eq!(galaxy.encounter_seed(pos), before);
+        assert_eq!(galaxy.seed(), 42);
+    }
+
+    #[test]
+    fn mirrored_galaxy_reflects_quadrants_and_start() {
+        let original = Galaxy::new(42);
+        let mirrored = Galaxy::new_mirrored(42, MirrorAxis::Horizontal);
+
+        for (pos, data) in original.quadrants.cells() {
+            let reflected = QuadrantPosition { x: GALAXY_SIZE as i32 + 1 - pos.x, y: pos.y };
+            assert_eq!(&mirrored.quadrants[reflected], data);
+        }
+        let (q, s) = (original.enterprise.quadrant(), original.enterprise.sector());
+        assert_eq!(mirrored.enterprise.quadrant(), QuadrantPosition { x: 9 - q.x, y: q.y });
+        assert_eq!(mirrored.enterprise.sector(), SectorPosition { x: 9 - s.x, y: s.y });
+        assert_eq!(mirrored.total_klingons(), original.total_klingons());
+        assert_eq!(mirrored.total_starbases(), original.total_starbases());
+        assert_eq!(mirrored.stardate(), original.stardate());
+        assert_eq!(mirrored.rng.draws(), original.rng.draws());
+    }
+
+    #[test]
+    fn mirror_axes_differ_but_none_is_identity() {
+        let original = Galaxy::new(7);
+        assert_eq!(Galaxy::new_mirrored(7, MirrorAxis::None).quadrants, original.quadrants);
+        assert_ne!(Galaxy::new_mirrored(7, MirrorAxis::Both).quadrants, original.quadrants);
+        assert_eq!(MirrorAxis::Both.reflect(1, 3), (8, 6));
+        assert_eq!(MirrorAxis::Vertical.reflect(1, 3), (1, 6));
+    }
+
+    #[test]
+    fn state_digest_tracks_game_state() {
+        let mut galaxy = Galaxy::new(42);
+        assert_eq!(galaxy.state_digest(), Galaxy::new(42).state_digest());
+        assert_ne!(galaxy.state_digest(), Galaxy::new(43).state_digest());
+
+        let before = galaxy.state_digest();
+        galaxy.advance_time(1.0);
+        assert_ne!(galaxy.state_digest(), before);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip_restores_the_game() {
+        let mut galaxy = Galaxy::new_with_config(42, GameConfig { initial_torpedoes: 5, ..Default::default() });
+        galaxy.advance_time(1.5);
+        galaxy.rng_mut().unit("test.burn");
+
+        let json = serde_json::to_string(&galaxy).unwrap();
+        let mut restored: Galaxy = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.state_digest(), galaxy.state_digest());
+        assert_eq!(restored.config(), galaxy.config());
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        for _ in 0..5 {
+            assert_eq!(restored.rng_mut().unit("test.next"), galaxy.rng_mut().unit("test.next"));
+        }
+    }
+
     // ========== Condition evaluation tests ==========
 
     #[test]
@@ -591,6 +1455,20 @@ mod tests {
         assert_eq!(galaxy.evaluate_condition(), Condition::Red);
     }
 
+    #[test]
+    fn condition_red_once_a_romulan_decloaks() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.sector_map = SectorMap::new();
+        galaxy.enterprise.move_to(galaxy.enterprise.quadrant(), SectorPosition { x: 4, y: 4 });
+        let rpos = SectorPosition { x: 1, y: 1 };
+        galaxy.sector_map.set(rpos, SectorContent::Romulan);
+        galaxy.sector_map.romulans.push(crate::models::romulan::Romulan::new(rpos, 300.0));
+        assert_ne!(galaxy.evaluate_condition(), Condition::Red);
+
+        galaxy.sector_map.romulans[0].cloaked = false;
+        assert_eq!(galaxy.evaluate_condition(), Condition::Red);
+    }
+
     /// Helper: set up a galaxy with a starbase at a known position.
     fn setup_galaxy_with_starbase(
         enterprise_sector: SectorPosition,
@@ -622,7 +1500,7 @@ mod tests {
     fn render_row_shows_enterprise_symbol() {
         let galaxy = Galaxy::new(42);
         let ey = galaxy.enterprise.sector().y;
-        let row = galaxy.sector_map.render_row(ey);
+        let row = galaxy.sector_map.render_row(ey, true, SymbolSet::Classic);
         assert!(
             row.contains("<*>"),
             "row {} should contain Enterprise symbol <*>, got: {}",
@@ -635,7 +1513,7 @@ mod tests {
     fn render_row_length_is_24_chars() {
         let galaxy = Galaxy::new(42);
         for y in 1..=SECTOR_SIZE as i32 {
-            let row = galaxy.sector_map.render_row(y);
+            let row = galaxy.sector_map.render_row(y, true, SymbolSet::Classic);
             assert_eq!(
                 row.len(),
                 SECTOR_SIZE * 3,
@@ -683,15 +1561,368 @@ mod tests {
         assert_eq!(galaxy.efficiency_rating(), 2428);
     }
 
+    #[test]
+    fn extended_sensor_rule_starts_with_upgrade() {
+        assert!(!Galaxy::new(42).enterprise.has_extended_long_range_sensors());
+
+        let ruleset = Ruleset { extended_long_range_sensors: true, ..Default::default() };
+        let galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        assert!(galaxy.enterprise.has_extended_long_range_sensors());
+    }
+
+    #[test]
+    fn fleet_hq_is_first_starbase_quadrant() {
+        let galaxy = Galaxy::new(42);
+        let hq = galaxy.fleet_hq().unwrap();
+        let first = galaxy
+            .quadrants
+            .iter()
+            .position(|q| q.starbases > 0)
+            .unwrap() as i32;
+        assert_eq!((hq.x, hq.y), (first % 8 + 1, first / 8 + 1));
+    }
+
+    #[test]
+    fn destroying_last_klingon_in_quadrant_counts_as_cleared() {
+        let mut galaxy = Galaxy::new(42);
+        let q = galaxy.enterprise.quadrant();
+        galaxy.quadrants[q].klingons = 2;
+        let pos = SectorPosition { x: 1, y: 1 };
+
+        galaxy.destroy_klingon(pos).unwrap();
+        assert_eq!(galaxy.quadrants_cleared(), 0);
+        galaxy.destroy_klingon(pos).unwrap();
+        assert_eq!(galaxy.quadrants_cleared(), 1);
+    }
+
     #[test]
     fn decrement_quadrant_klingons_updates_count() {
         let mut galaxy = Galaxy::new(42);
         let q = galaxy.enterprise.quadrant();
-        let initial_count = galaxy.quadrants[(q.y - 1) as usize][(q.x - 1) as usize].klingons;
+        let initial_count = galaxy.quadrants[q].klingons;
 
         galaxy.decrement_quadrant_klingons();
 
-        let new_count = galaxy.quadrants[(q.y - 1) as usize][(q.x - 1) as usize].klingons;
+        let new_count = galaxy.quadrants[q].klingons;
         assert_eq!(new_count, initial_count - 1);
     }
+
+    #[test]
+    fn visiting_is_tracked_separately_from_scanning() {
+        let mut galaxy = Galaxy::new(42);
+        let start = galaxy.enterprise().quadrant();
+        assert!(galaxy.has_visited(start));
+        assert_eq!(galaxy.quadrants_visited(), 1);
+        assert_eq!(galaxy.exploration_percent(), 100.0 / 64.0);
+
+        // A long range scan records memory but is not a visit
+        let neighbor = QuadrantPosition { x: start.x % 8 + 1, y: start.y };
+        galaxy.record_quadrant_to_memory(neighbor.x, neighbor.y);
+        assert!(!galaxy.has_visited(neighbor));
+
+        let sector = galaxy.enterprise().sector();
+        galaxy.enterprise_mut().move_to(neighbor, sector);
+        galaxy.enter_quadrant();
+        assert!(galaxy.has_visited(neighbor));
+        assert_eq!(galaxy.quadrants_visited(), 2);
+    }
+
+    #[test]
+    fn klingons_migrate_toward_nearest_starbase() {
+        let mut galaxy = Galaxy::new(42);
+        let empty = QuadrantData { klingons: 0, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
+        galaxy.quadrants = QuadrantGrid::filled(empty);
+        let at = |x, y| QuadrantPosition { x, y };
+        galaxy.enterprise_mut().move_to(QuadrantPosition { x: 8, y: 8 }, SectorPosition { x: 1, y: 1 });
+        galaxy.quadrants[at(1, 1)].starbases = 1;
+        galaxy.quadrants[at(3, 3)].klingons = 2;
+        galaxy.quadrants[at(5, 1)].klingons = 3;
+        galaxy.quadrants[at(8, 8)].klingons = 1; // the Enterprise's quadrant
+        galaxy.advance_time(4.0);
+
+        assert!(galaxy.migrate_klingons().is_empty());
+        assert_eq!(galaxy.last_klingon_migration(), galaxy.stardate());
+        assert_eq!(galaxy.quadrants[at(2, 2)].klingons, 2);
+        assert_eq!(galaxy.quadrants[at(4, 1)].klingons, 3);
+        assert_eq!(galaxy.quadrants[at(8, 8)].klingons, 1);
+
+        assert_eq!(galaxy.migrate_klingons(), vec![QuadrantPosition { x: 1, y: 1 }]);
+        assert_eq!(galaxy.quadrants[at(1, 1)].klingons, 2);
+        assert_eq!(galaxy.quadrants[at(3, 1)].klingons, 3);
+        galaxy.migrate_klingons();
+        assert_eq!(galaxy.quadrants[at(2, 1)].klingons, 3);
+        // Joining the besiegers would exceed three Klingons: wait
+        galaxy.migrate_klingons();
+        assert_eq!(galaxy.quadrants[at(2, 1)].klingons, 3);
+        assert_eq!(galaxy.quadrants[at(1, 1)].klingons, 2);
+        assert_eq!(galaxy.quadrants.iter().map(|q| q.klingons).sum::<i32>(), 6);
+    }
+
+    #[test]
+    fn journal_records_sightings_and_starbase_events() {
+        let mut galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().quadrant();
+        let klingons = galaxy.quadrants()[here].klingons;
+        let start = galaxy.stardate();
+        assert_eq!(galaxy.journal().last_sighting(here), Some((start, klingons)));
+
+        galaxy.advance_time(1.0);
+        galaxy.destroy_starbase(galaxy.enterprise().sector());
+        let events: Vec<QuadrantEvent> = galaxy.journal().entries(here).iter().map(|e| e.event).collect();
+        assert_eq!(events.last(), Some(&QuadrantEvent::StarbaseDestroyed));
+        assert_eq!(galaxy.journal().entries(here).last().unwrap().stardate, start + 1.0);
+
+        // A damaged computer records nothing
+        let far = QuadrantPosition { x: here.x % 8 + 1, y: here.y % 8 + 1 };
+        galaxy.enterprise_mut().damage_device(Device::Computer, 1.0);
+        galaxy.record_quadrant_to_memory(far.x, far.y);
+        assert!(galaxy.journal().entries(far).is_empty());
+    }
+
+    #[test]
+    fn commanders_lead_some_klingon_groups() {
+        let options = |klingon_commanders| GalaxyOptions {
+            ruleset: Ruleset { klingon_commanders, ..Default::default() },
+            ..Default::default()
+        };
+        let classic = Galaxy::with_options(42, options(false));
+        let galaxy = Galaxy::with_options(42, options(true));
+        assert_eq!(galaxy.enterprise().quadrant(), classic.enterprise().quadrant());
+        let mut commanders = 0;
+        for ((_, data), (_, before)) in galaxy.quadrants_iter().zip(classic.quadrants_iter()) {
+            assert_eq!(data.klingons, before.klingons);
+            assert!(data.commanders <= data.klingons.min(1));
+            commanders += data.commanders;
+        }
+        assert!(commanders > 0);
+        assert!(classic.quadrants_iter().all(|(_, data)| data.commanders == 0));
+    }
+
+    #[test]
+    fn destroying_a_commander_updates_the_quadrant() {
+        let mut galaxy = Galaxy::new(42);
+        let q = galaxy.enterprise().quadrant();
+        galaxy.quadrants[q] = QuadrantData { klingons: 2, starbases: 0, stars: 0, commanders: 1, romulans: 0, mines: 0 };
+        galaxy.enter_quadrant();
+        let klingons = galaxy.sector_map().klingons.clone();
+        assert_eq!(klingons.len(), 2);
+        assert!(klingons[0].is_commander() && !klingons[1].is_commander());
+        assert_eq!(klingons[0].shields, crate::models::constants::KLINGON_COMMANDER_SHIELDS);
+        assert_eq!(galaxy.sector_map().get(klingons[0].sector), SectorContent::KlingonCommander);
+
+        galaxy.destroy_klingon(klingons[1].sector).unwrap();
+        assert_eq!(galaxy.quadrants[q].commanders, 1);
+        galaxy.destroy_klingon(klingons[0].sector).unwrap();
+        assert_eq!(galaxy.quadrants[q], QuadrantData { klingons: 0, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 });
+    }
+
+    #[test]
+    fn destroyed_klingons_leave_debris_until_the_quadrant_is_entered() {
+        let ruleset = Ruleset { debris: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let q = galaxy.enterprise().quadrant();
+        galaxy.quadrants[q] = QuadrantData { klingons: 1, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
+        galaxy.enter_quadrant();
+        let wreck = galaxy.sector_map().klingons[0].sector;
+        galaxy.destroy_klingon(wreck).unwrap();
+        assert_eq!(galaxy.sector_map().get(wreck), SectorContent::Debris);
+        assert!(!galaxy.sector_map().is_empty(wreck));
+
+        galaxy.enter_quadrant();
+        assert!(galaxy.sector_map().iter().all(|(_, content)| content != SectorContent::Debris));
+    }
+
+    #[test]
+    fn persistent_quadrants_keep_their_layout_between_visits() {
+        let ruleset = Ruleset { persistent_quadrants: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let home = galaxy.enterprise().quadrant();
+        let sector = galaxy.enterprise().sector();
+        let away = QuadrantPosition { x: home.x % 8 + 1, y: home.y };
+        galaxy.quadrants[home] = QuadrantData { klingons: 2, starbases: 1, stars: 3, commanders: 0, romulans: 0, mines: 0 };
+        galaxy.enter_quadrant();
+        galaxy.sector_map_mut().klingons[0].shields = 50.0;
+        let before = galaxy.sector_map().clone();
+
+        let visit = |galaxy: &mut Galaxy, quadrant| {
+            galaxy.leave_quadrant();
+            galaxy.enterprise_mut().move_to(quadrant, sector);
+            galaxy.enter_quadrant();
+        };
+        visit(&mut galaxy, away);
+        visit(&mut galaxy, home);
+        assert_eq!(galaxy.sector_map().diff(&before), Vec::new());
+        assert_eq!(galaxy.sector_map().klingons[0].shields, 50.0);
+
+        // A Klingon arriving while the Enterprise is away spoils the layout
+        visit(&mut galaxy, away);
+        galaxy.reinforce_klingons(home);
+        visit(&mut galaxy, home);
+        assert_eq!(galaxy.sector_map().klingons.len(), 3);
+        assert_ne!(galaxy.sector_map().diff(&before), Vec::new());
+    }
+
+    #[test]
+    fn klingon_shields_regenerate_up_to_full_under_the_rule() {
+        use crate::models::constants::KLINGON_INITIAL_SHIELDS;
+
+        for regeneration in [false, true] {
+            let ruleset = Ruleset { klingon_regeneration: regeneration, persistent_quadrants: true, ..Default::default() };
+            let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+            let home = galaxy.enterprise().quadrant();
+            galaxy.quadrants[home] = QuadrantData { klingons: 1, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
+            galaxy.enter_quadrant();
+            galaxy.sector_map_mut().klingons[0].shields = 50.0;
+
+            galaxy.advance_time(2.0);
+            let expected = if regeneration { 50.0 + 0.2 * KLINGON_INITIAL_SHIELDS } else { 50.0 };
+            assert!((galaxy.sector_map().klingons[0].shields - expected).abs() < 1e-9);
+
+            // Klingons left behind recover too
+            galaxy.leave_quadrant();
+            let sector = galaxy.enterprise().sector();
+            galaxy.enterprise_mut().move_to(QuadrantPosition { x: home.x % 8 + 1, y: home.y }, sector);
+            galaxy.enter_quadrant();
+            galaxy.advance_time(20.0);
+            galaxy.leave_quadrant();
+            galaxy.enterprise_mut().move_to(home, sector);
+            galaxy.enter_quadrant();
+            let expected = if regeneration { KLINGON_INITIAL_SHIELDS } else { 50.0 };
+            assert_eq!(galaxy.sector_map().klingons[0].shields, expected);
+        }
+    }
+
+    #[test]
+    fn romulans_hide_in_a_few_quadrants() {
+        let ruleset = Ruleset { romulans: true, ..Default::default() };
+        let galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let classic = Galaxy::new(42);
+        let romulans: i32 = galaxy.quadrants_iter().map(|(_, data)| data.romulans).sum();
+        assert!(romulans > 0 && romulans < 16);
+        for ((_, data), (_, before)) in galaxy.quadrants_iter().zip(classic.quadrants_iter()) {
+            assert_eq!(data.encoded(), before.encoded());
+        }
+        assert_eq!(galaxy.total_klingons(), classic.total_klingons());
+    }
+
+    #[test]
+    fn romulans_are_placed_cloaked_and_destroyed_atomically() {
+        let mut galaxy = Galaxy::new(42);
+        let q = galaxy.enterprise().quadrant();
+        galaxy.quadrants[q].romulans = 1;
+        galaxy.enter_quadrant();
+        let romulan = galaxy.sector_map().romulans[0];
+        assert!(romulan.cloaked);
+        assert_eq!(romulan.shields, crate::models::constants::ROMULAN_SHIELDS);
+        assert_eq!(galaxy.sector_map().get(romulan.sector), SectorContent::Romulan);
+        assert_eq!(galaxy.sector_map().visible(romulan.sector), SectorContent::Empty);
+
+        let klingons = galaxy.total_klingons();
+        galaxy.destroy_romulan(romulan.sector);
+        assert!(galaxy.sector_map().romulans.is_empty());
+        assert_eq!(galaxy.sector_map().get(romulan.sector), SectorContent::Empty);
+        assert_eq!(galaxy.quadrants[q].romulans, 0);
+        assert_eq!(galaxy.total_klingons(), klingons);
+    }
+
+    #[test]
+    fn crew_losses_only_matter_under_the_morale_rule() {
+        let mut classic = Galaxy::new(42);
+        let ruleset = Ruleset { crew_morale: true, ..Default::default() };
+        let mut morale = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        for galaxy in [&mut classic, &mut morale] {
+            galaxy.enterprise_mut().damage_hull(50.0);
+        }
+        assert!(classic.enterprise().casualties() > 0);
+        assert_eq!(classic.crew_efficiency(), 1.0);
+        assert_eq!(morale.crew_efficiency(), morale.enterprise().crew_efficiency());
+        assert!(morale.crew_efficiency() < 1.0);
+    }
+
+    #[test]
+    fn raised_shields_drain_energy_only_under_the_drain_rule() {
+        let mut classic = Galaxy::new(42);
+        let ruleset = Ruleset { shield_drain: true, ..Default::default() };
+        let mut drained = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        for galaxy in [&mut classic, &mut drained] {
+            galaxy.enterprise_mut().set_shields(200.0);
+            galaxy.advance_time(1.5);
+        }
+        assert_eq!(classic.enterprise().energy(), drained.enterprise().energy() + 15.0);
+        assert_eq!(classic.take_shield_drain(), 0.0);
+        assert_eq!(drained.take_shield_drain(), 15.0);
+        assert_eq!(drained.take_shield_drain(), 0.0);
+        assert_eq!(drained.energy_ledger().total(EnergyUse::ShieldDrain), -15.0);
+
+        // Lowered shields draw nothing
+        drained.enterprise_mut().set_shields(0.0);
+        drained.advance_time(1.0);
+        assert_eq!(drained.take_shield_drain(), 0.0);
+    }
+
+    #[test]
+    fn minefields_are_sown_in_a_few_quadrants() {
+        let ruleset = Ruleset { minefields: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let classic = Galaxy::new(42);
+        let fields = galaxy.quadrants_iter().filter(|(_, data)| data.mines > 0).count();
+        assert!(fields > 0 && fields < 16);
+        for ((_, data), (_, before)) in galaxy.quadrants_iter().zip(classic.quadrants_iter()) {
+            assert!((0..=MAX_MINES_PER_FIELD).contains(&data.mines));
+            assert_eq!(data.encoded(), before.encoded());
+        }
+
+        let q = galaxy.enterprise().quadrant();
+        galaxy.quadrants[q].mines = 3;
+        galaxy.enter_quadrant();
+        let mines = galaxy.sector_map().iter().filter(|&(_, content)| content == SectorContent::Mine).count();
+        assert_eq!(mines, 3);
+
+        let (pos, _) = galaxy.sector_map().iter().find(|&(_, content)| content == SectorContent::Mine).unwrap();
+        galaxy.detonate_mine(pos);
+        assert_eq!(galaxy.sector_map().get(pos), SectorContent::Empty);
+        assert_eq!(galaxy.quadrants[q].mines, 2);
+    }
+
+    #[test]
+    fn black_holes_form_on_some_visits() {
+        let ruleset = Ruleset { black_holes: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let mut formed = 0;
+        for _ in 0..50 {
+            galaxy.enter_quadrant();
+            let holes = galaxy
+                .sector_map()
+                .iter()
+                .filter(|&(_, content)| content == SectorContent::BlackHole)
+                .count();
+            assert!(holes <= 1);
+            formed += holes;
+        }
+        assert!(formed > 0 && formed < 50);
+
+        let mut classic = Galaxy::new(42);
+        classic.enter_quadrant();
+        assert!(classic.sector_map().iter().all(|(_, content)| content != SectorContent::BlackHole));
+    }
+
+    #[test]
+    fn tholians_start_in_a_corner_on_some_visits() {
+        let ruleset = Ruleset { tholians: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let mut found = 0;
+        for _ in 0..100 {
+            galaxy.enter_quadrant();
+            if let Some(tholian) = galaxy.sector_map().tholian {
+                found += 1;
+                assert!([1, 8].contains(&tholian.sector.x) && [1, 8].contains(&tholian.sector.y));
+                assert_eq!(galaxy.sector_map().get(tholian.sector), SectorContent::Tholian);
+            }
+        }
+        assert!(found > 0 && found < 100);
+
+        let mut classic = Galaxy::new(42);
+        classic.enter_quadrant();
+        assert_eq!(classic.sector_map().tholian, None);
+    }
 }
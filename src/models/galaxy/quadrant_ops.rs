@@ -1,37 +1,52 @@
-use rand::rngs::StdRng;
-use rand::Rng;
-
-use crate::models::constants::{Device, SectorContent};
+use crate::models::config::GameConfig;
+use crate::models::constants::{
+    Device, SectorContent, BLACK_HOLE_CHANCE, MAX_KLINGONS_PER_QUADRANT, SECTOR_SIZE, THOLIAN_CHANCE,
+};
 use crate::models::enterprise::Enterprise;
-use crate::models::klingon::Klingon;
-use crate::models::position::SectorPosition;
+use crate::models::grid::QuadrantGrid;
+use crate::models::klingon::{Klingon, KlingonKind};
+use crate::models::position::{QuadrantPosition, SectorPosition};
 use crate::models::quadrant::QuadrantData;
+use crate::models::rng::GameRng;
+use crate::models::romulan::Romulan;
+use crate::models::ruleset::Ruleset;
 use crate::models::sector_map::SectorMap;
+use crate::models::tholian::Tholian;
 
 /// Enter the current quadrant: clear sector map and place all entities.
 /// Called on game start and every quadrant transition (spec section 4).
 pub fn enter_quadrant(
     sector_map: &mut SectorMap,
     enterprise: &Enterprise,
-    quadrants: &[[QuadrantData; 8]; 8],
-    rng: &mut StdRng,
+    quadrants: &QuadrantGrid<QuadrantData>,
+    config: &GameConfig,
+    ruleset: &Ruleset,
+    rng: &mut GameRng,
 ) {
     *sector_map = SectorMap::new();
+    let klingon_shields = full_shields(config, ruleset, KlingonKind::Warship);
+    let commander_shields = full_shields(config, ruleset, KlingonKind::Commander);
 
     // Place the Enterprise
     sector_map.set(enterprise.sector(), SectorContent::Enterprise);
 
-    // Place Klingons (each with shields = 200)
+    // Place Klingons (each with shields = 200 unless configured otherwise),
+    // commanders first
     let q = enterprise.quadrant();
-    let qdata = quadrants[(q.y - 1) as usize][(q.x - 1) as usize];
+    let qdata = quadrants[q];
     let num_klingons = qdata.klingons;
     let num_starbases = qdata.starbases;
     let num_stars = qdata.stars;
 
-    for _ in 0..num_klingons {
+    for i in 0..num_klingons {
         let pos = find_random_empty_sector(sector_map, rng);
-        sector_map.set(pos, SectorContent::Klingon);
-        sector_map.klingons.push(Klingon::new(pos));
+        let klingon = if i < qdata.commanders {
+            Klingon::commander(pos, commander_shields)
+        } else {
+            Klingon::with_shields(pos, klingon_shields)
+        };
+        sector_map.set(pos, klingon.content());
+        sector_map.klingons.push(klingon);
     }
 
     // Place starbases
@@ -47,19 +62,81 @@ pub fn enter_quadrant(
         sector_map.set(pos, SectorContent::Star);
     }
 
-    // Red alert check (spec section 4.2)
-    if !sector_map.klingons.is_empty() && enterprise.shields() <= 200.0 {
-        println!("COMBAT AREA      CONDITION RED");
-        println!("   SHIELDS DANGEROUSLY LOW");
+    // Place Romulans last, cloaked
+    for _ in 0..qdata.romulans {
+        let pos = find_random_empty_sector(sector_map, rng);
+        sector_map.set(pos, SectorContent::Romulan);
+        sector_map.romulans.push(Romulan::new(pos, config.romulan_shields));
+    }
+
+    // Mines, hidden from damaged sensors
+    for _ in 0..qdata.mines {
+        let pos = find_random_empty_sector(sector_map, rng);
+        sector_map.set(pos, SectorContent::Mine);
+    }
+
+    // Black holes are not remembered; one may form on any visit
+    if ruleset.black_holes && rng.unit("quadrant.black_hole") < BLACK_HOLE_CHANCE {
+        let pos = find_random_empty_sector(sector_map, rng);
+        sector_map.set(pos, SectorContent::BlackHole);
+    }
+
+    // A Tholian starts in a corner, if the one picked is free
+    if ruleset.tholians && rng.unit("quadrant.tholian") < THOLIAN_CHANCE {
+        let last = SECTOR_SIZE as i32;
+        let pos = SectorPosition {
+            x: if rng.unit("quadrant.tholian_corner") < 0.5 { 1 } else { last },
+            y: if rng.unit("quadrant.tholian_corner") < 0.5 { 1 } else { last },
+        };
+        if sector_map.is_empty(pos) {
+            sector_map.set(pos, SectorContent::Tholian);
+            sector_map.tholian = Some(Tholian::new(pos));
+        }
+    }
+}
+
+/// The shields a Klingon of `kind` is placed with.
+pub fn full_shields(config: &GameConfig, ruleset: &Ruleset, kind: KlingonKind) -> f64 {
+    let shields = match kind {
+        KlingonKind::Warship => config.klingon_shields,
+        KlingonKind::Commander => config.commander_shields,
+    };
+    shields * ruleset.difficulty.klingon_shield_factor()
+}
+
+/// Put back a quadrant's saved layout, with the Enterprise in its arrival
+/// sector. Returns false, leaving `sector_map` alone, when the layout no
+/// longer matches the quadrant's counts (Klingons arrived or a starbase was
+/// lost while the Enterprise was away) or the arrival sector is taken; the
+/// quadrant is then placed anew.
+pub fn restore_quadrant(
+    sector_map: &mut SectorMap,
+    saved: SectorMap,
+    enterprise: &Enterprise,
+    qdata: &QuadrantData,
+) -> bool {
+    let count = |content| saved.iter().filter(|&(_, c)| c == content).count() as i32;
+    let commanders = saved.klingons.iter().filter(|k| k.is_commander()).count() as i32;
+    let matches = saved.klingons.len() as i32 == qdata.klingons
+        && commanders == qdata.commanders
+        && count(SectorContent::Starbase) == qdata.starbases
+        && count(SectorContent::Star) == qdata.stars
+        && saved.romulans.len() as i32 == qdata.romulans
+        && count(SectorContent::Mine) == qdata.mines;
+    if !matches || !saved.is_empty(enterprise.sector()) {
+        return false;
     }
+    *sector_map = saved;
+    sector_map.set(enterprise.sector(), SectorContent::Enterprise);
+    true
 }
 
 /// Find a random empty sector by picking random coordinates until one is empty.
-fn find_random_empty_sector(sector_map: &SectorMap, rng: &mut StdRng) -> SectorPosition {
+pub fn find_random_empty_sector(sector_map: &SectorMap, rng: &mut GameRng) -> SectorPosition {
     loop {
         let pos = SectorPosition {
-            x: rng.gen_range(1..=8),
-            y: rng.gen_range(1..=8),
+            x: rng.range("quadrant.sector", 1..=8),
+            y: rng.range("quadrant.sector", 1..=8),
         };
         if sector_map.is_empty(pos) {
             return pos;
@@ -70,8 +147,8 @@ fn find_random_empty_sector(sector_map: &SectorMap, rng: &mut StdRng) -> SectorP
 /// Record a quadrant's data into computer memory.
 /// Does nothing if the Computer device is damaged or coordinates are out of range.
 pub fn record_quadrant_to_memory(
-    computer_memory: &mut [[Option<QuadrantData>; 8]; 8],
-    quadrants: &[[QuadrantData; 8]; 8],
+    computer_memory: &mut QuadrantGrid<Option<QuadrantData>>,
+    quadrants: &QuadrantGrid<QuadrantData>,
     enterprise: &Enterprise,
     x: i32,
     y: i32,
@@ -79,28 +156,72 @@ pub fn record_quadrant_to_memory(
     if enterprise.is_damaged(Device::Computer) {
         return;
     }
-    if (1..=8).contains(&x) && (1..=8).contains(&y) {
-        computer_memory[(y - 1) as usize][(x - 1) as usize] =
-            Some(quadrants[(y - 1) as usize][(x - 1) as usize]);
+    let pos = QuadrantPosition { x, y };
+    if let (Some(memory), Some(data)) = (computer_memory.get_mut(pos), quadrants.get(pos)) {
+        *memory = Some(*data);
     }
 }
 
 /// Update the quadrant's klingon count after removing one.
 #[allow(dead_code)]
 pub fn decrement_quadrant_klingons(
-    quadrants: &mut [[QuadrantData; 8]; 8],
+    quadrants: &mut QuadrantGrid<QuadrantData>,
     enterprise: &Enterprise,
 ) {
-    let q = enterprise.quadrant();
-    quadrants[(q.y - 1) as usize][(q.x - 1) as usize].klingons -= 1;
+    quadrants[enterprise.quadrant()].klingons -= 1;
 }
 
 /// Update the quadrant's starbase count after removing one.
 #[allow(dead_code)]
 pub fn decrement_quadrant_starbases(
-    quadrants: &mut [[QuadrantData; 8]; 8],
+    quadrants: &mut QuadrantGrid<QuadrantData>,
     enterprise: &Enterprise,
 ) {
-    let q = enterprise.quadrant();
-    quadrants[(q.y - 1) as usize][(q.x - 1) as usize].starbases -= 1;
+    quadrants[enterprise.quadrant()].starbases -= 1;
+}
+
+/// Move every Klingon group one quadrant toward its nearest starbase
+/// (fewest moves, ties broken in row-major order). Groups already at a
+/// starbase stay put, and neither the Enterprise's quadrant nor a quadrant
+/// that would exceed the per-quadrant limit is entered or left. Each group
+/// moves at most once. Returns the starbase quadrants groups arrived at.
+pub fn migrate_klingons(
+    quadrants: &mut QuadrantGrid<QuadrantData>,
+    here: QuadrantPosition,
+) -> Vec<QuadrantPosition> {
+    let starbases: Vec<QuadrantPosition> = quadrants
+        .cells()
+        .filter(|(_, data)| data.starbases > 0)
+        .map(|(q, _)| q)
+        .collect();
+
+    let before = *quadrants;
+    let mut sieged = Vec::new();
+    for (from, data) in before.cells() {
+        let group = data.klingons;
+        if group == 0 || from == here || data.starbases > 0 {
+            continue;
+        }
+        let Some(base) = starbases
+            .iter()
+            .min_by_key(|b| (b.x - from.x).abs().max((b.y - from.y).abs()))
+        else {
+            break;
+        };
+        let to = QuadrantPosition {
+            x: from.x + (base.x - from.x).signum(),
+            y: from.y + (base.y - from.y).signum(),
+        };
+        if to == here || quadrants[to].klingons + group > MAX_KLINGONS_PER_QUADRANT as i32 {
+            continue;
+        }
+        quadrants[from].klingons -= group;
+        quadrants[to].klingons += group;
+        quadrants[from].commanders -= data.commanders;
+        quadrants[to].commanders += data.commanders;
+        if quadrants[to].starbases > 0 && !sieged.contains(&to) {
+            sieged.push(to);
+        }
+    }
+    sieged
 }
@@ -0,0 +1,255 @@
+//! Square grids
+//!
+//! [`Grid`] holds one value per cell of a square grid addressed by 1-based
+//! positions, as the galaxy's quadrants and a quadrant's sectors are. It
+//! owns the conversion to storage indices so callers never compute
+//! `(y - 1) as usize` themselves.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+use super::constants::{GALAXY_SIZE, SECTOR_SIZE};
+use super::position::{QuadrantPosition, SectorPosition};
+
+/// A 1-based grid coordinate.
+pub trait GridPosition: Copy {
+    fn from_xy(x: i32, y: i32) -> Self;
+    fn xy(&self) -> (i32, i32);
+}
+
+impl GridPosition for QuadrantPosition {
+    fn from_xy(x: i32, y: i32) -> Self {
+        QuadrantPosition { x, y }
+    }
+
+    fn xy(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+}
+
+impl GridPosition for SectorPosition {
+    fn from_xy(x: i32, y: i32) -> Self {
+        SectorPosition { x, y }
+    }
+
+    fn xy(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+}
+
+/// An N x N grid of `T` addressed by positions of type `P`.
+pub struct Grid<P, T, const N: usize> {
+    /// Row-major: cells[y - 1][x - 1].
+    cells: [[T; N]; N],
+    position: PhantomData<P>,
+}
+
+/// One value per quadrant of the galaxy.
+pub type QuadrantGrid<T> = Grid<QuadrantPosition, T, GALAXY_SIZE>;
+
+/// One value per sector of a quadrant.
+pub type SectorGrid<T> = Grid<SectorPosition, T, SECTOR_SIZE>;
+
+impl<P: GridPosition, T, const N: usize> Grid<P, T, N> {
+    /// A grid with every cell set to `value`.
+    pub fn filled(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(|_| value.clone())
+    }
+
+    /// A grid with each cell computed from its position.
+    pub fn from_fn(mut f: impl FnMut(P) -> T) -> Self {
+        Grid {
+            cells: std::array::from_fn(|y| std::array::from_fn(|x| f(P::from_xy(x as i32 + 1, y as i32 + 1)))),
+            position: PhantomData,
+        }
+    }
+
+    /// Cells per side.
+    pub const fn size(&self) -> usize {
+        N
+    }
+
+    /// Whether a position lies on the grid.
+    pub fn contains(&self, pos: P) -> bool {
+        let (x, y) = pos.xy();
+        (1..=N as i32).contains(&x) && (1..=N as i32).contains(&y)
+    }
+
+    pub fn get(&self, pos: P) -> Option<&T> {
+        self.index_of(pos).map(|(x, y)| &self.cells[y][x])
+    }
+
+    pub fn get_mut(&mut self, pos: P) -> Option<&mut T> {
+        self.index_of(pos).map(|(x, y)| &mut self.cells[y][x])
+    }
+
+    /// Every position on the grid, in row-major order.
+    pub fn positions() -> impl Iterator<Item = P> {
+        (1..=N as i32).flat_map(|y| (1..=N as i32).map(move |x| P::from_xy(x, y)))
+    }
+
+    /// Cell values in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter().flatten()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut().flatten()
+    }
+
+    /// Cells with their positions, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (P, &T)> {
+        Self::positions().zip(self.iter())
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (P, &mut T)> {
+        Self::positions().zip(self.iter_mut())
+    }
+
+    /// Rows from top (y = 1) to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.iter().map(|row| row.as_slice())
+    }
+
+    /// A grid of `f` applied to every cell.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<P, U, N> {
+        Grid {
+            cells: std::array::from_fn(|y| std::array::from_fn(|x| f(&self.cells[y][x]))),
+            position: PhantomData,
+        }
+    }
+
+    /// Storage indices (x, y) of a position, if it is on the grid.
+    fn index_of(&self, pos: P) -> Option<(usize, usize)> {
+        self.contains(pos).then(|| {
+            let (x, y) = pos.xy();
+            ((x - 1) as usize, (y - 1) as usize)
+        })
+    }
+}
+
+/// Serialized as a list of rows from top to bottom, as [`Grid::rows`] yields them.
+#[cfg(feature = "serde")]
+impl<P, T: serde::Serialize, const N: usize> serde::Serialize for Grid<P, T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.cells.iter().map(|row| row.as_slice()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Grid<P, T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let size_error = || D::Error::custom(format!("expected a {N}x{N} grid"));
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|row| <[T; N]>::try_from(row).map_err(|_| size_error()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Grid {
+            cells: rows.try_into().map_err(|_| size_error())?,
+            position: PhantomData,
+        })
+    }
+}
+
+impl<P: GridPosition, T, const N: usize> Index<P> for Grid<P, T, N> {
+    type Output = T;
+
+    /// Panics if the position is off the grid.
+    fn index(&self, pos: P) -> &T {
+        let (x, y) = pos.xy();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {},{} is outside the {}x{} grid", x, y, N, N))
+    }
+}
+
+impl<P: GridPosition, T, const N: usize> IndexMut<P> for Grid<P, T, N> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let (x, y) = pos.xy();
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {},{} is outside the {}x{} grid", x, y, N, N))
+    }
+}
+
+impl<P: GridPosition, T: Default, const N: usize> Default for Grid<P, T, N> {
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<P, T: Clone, const N: usize> Clone for Grid<P, T, N> {
+    fn clone(&self) -> Self {
+        Grid {
+            cells: self.cells.clone(),
+            position: PhantomData,
+        }
+    }
+}
+
+impl<P, T: Copy, const N: usize> Copy for Grid<P, T, N> {}
+
+impl<P, T: PartialEq, const N: usize> PartialEq for Grid<P, T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<P, T: fmt::Debug, const N: usize> fmt::Debug for Grid<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.cells.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_are_one_based_and_row_major() {
+        let grid = QuadrantGrid::from_fn(|pos| pos.x * 10 + pos.y);
+        assert_eq!(grid[QuadrantPosition { x: 1, y: 1 }], 11);
+        assert_eq!(grid[QuadrantPosition { x: 8, y: 2 }], 82);
+        let first: Vec<(QuadrantPosition, i32)> = grid.cells().take(2).map(|(p, &v)| (p, v)).collect();
+        assert_eq!(
+            first,
+            vec![(QuadrantPosition { x: 1, y: 1 }, 11), (QuadrantPosition { x: 2, y: 1 }, 21)]
+        );
+        assert_eq!(grid.rows().nth(2).unwrap()[0], 13);
+    }
+
+    #[test]
+    fn access_is_bounds_checked() {
+        let mut grid: SectorGrid<bool> = SectorGrid::default();
+        for pos in [SectorPosition { x: 0, y: 1 }, SectorPosition { x: 1, y: 9 }] {
+            assert!(!grid.contains(pos));
+            assert_eq!(grid.get(pos), None);
+            assert!(grid.get_mut(pos).is_none());
+        }
+        grid[SectorPosition { x: 8, y: 8 }] = true;
+        assert_eq!(grid.iter().filter(|&&v| v).count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 8x8 grid")]
+    fn indexing_off_the_grid_panics() {
+        let grid = QuadrantGrid::filled(0);
+        let _ = grid[QuadrantPosition { x: 9, y: 1 }];
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_rows() {
+        let grid = SectorGrid::from_fn(|pos| pos.x + pos.y * 10);
+        let json = serde_json::to_string(&grid).unwrap();
+        assert!(json.starts_with("[[11,12,13,14,15,16,17,18],[21,"));
+        let restored: SectorGrid<i32> = serde_json::from_str(&json).unwrap();
+        assert!(restored.cells().all(|(pos, &v)| v == grid[pos]));
+
+        assert!(serde_json::from_str::<SectorGrid<i32>>("[[1,2],[3,4]]").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Modding hooks
+//!
+//! Embedding applications register [`ModHooks`] implementations in a
+//! [`ModRegistry`] to build game variants without forking the crate.
+//! Hooks can adjust quadrant population at generation time, the weight of
+//! random events, and the final score.
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::limits::GalaxyLimits;
+use super::position::QuadrantPosition;
+use super::quadrant::QuadrantData;
+
+/// Random events whose likelihood mods can adjust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// Device damage/repair event during warp travel (spec section 5.3).
+    DeviceMalfunction,
+    /// Starfleet completes a new starbase in a scanned, empty quadrant.
+    StarbaseConstruction,
+    /// A torpedo that leaves the quadrant strikes an inhabited world beyond
+    /// it. The stock galaxy has no planets, so this never happens unless a
+    /// mod raises its weight.
+    StrayTorpedo,
+}
+
+/// Extension points for game variants.
+///
+/// Every method has a no-op default, so a mod only overrides what it changes.
+pub trait ModHooks: Send + Sync {
+    /// Adjust the contents of a freshly generated quadrant.
+    ///
+    /// `encounter_seed` is stable for a given galaxy seed and position, so mods
+    /// can make deterministic per-quadrant decisions without consuming the
+    /// galaxy RNG.
+    fn adjust_quadrant(&self, _pos: QuadrantPosition, _encounter_seed: u64, _data: &mut QuadrantData) {}
+
+    /// Adjust the weight (chance per opportunity, 0.0-1.0) of a random event.
+    fn event_weight(&self, _event: EventKind, weight: f64) -> f64 {
+        weight
+    }
+
+    /// Adjust the final efficiency rating.
+    fn adjust_score(&self, rating: i32) -> i32 {
+        rating
+    }
+}
+
+/// Ordered collection of registered mods. Hooks run in registration order.
+#[derive(Clone, Default)]
+pub struct ModRegistry {
+    hooks: Vec<Arc<dyn ModHooks>>,
+}
+
+impl ModRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a mod. Later registrations see the adjustments of earlier ones.
+    pub fn register(&mut self, hooks: impl ModHooks + 'static) {
+        self.hooks.push(Arc::new(hooks));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Run every mod over a quadrant, then clamp the result to the
+    /// per-quadrant [`GalaxyLimits`].
+    pub fn adjust_quadrant(&self, pos: QuadrantPosition, encounter_seed: u64, data: &mut QuadrantData) {
+        for hooks in &self.hooks {
+            hooks.adjust_quadrant(pos, encounter_seed, data);
+        }
+        GalaxyLimits::default().clamp_quadrant(data);
+    }
+
+    /// Fold a base event weight through every mod, clamped to 0.0-1.0.
+    pub fn event_weight(&self, event: EventKind, base: f64) -> f64 {
+        self.hooks
+            .iter()
+            .fold(base, |weight, hooks| hooks.event_weight(event, weight))
+            .clamp(0.0, 1.0)
+    }
+
+    /// Fold the efficiency rating through every mod.
+    pub fn adjust_score(&self, rating: i32) -> i32 {
+        self.hooks
+            .iter()
+            .fold(rating, |score, hooks| hooks.adjust_score(score))
+    }
+}
+
+impl fmt::Debug for ModRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModRegistry")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+/// Derive the encounter seed for a quadrant from the galaxy seed.
+/// Uses a SplitMix64 finalizer so neighbouring quadrants get unrelated seeds.
+pub fn encounter_seed(galaxy_seed: u64, pos: QuadrantPosition) -> u64 {
+    let mut z = galaxy_seed
+        ^ (((pos.x as u64) << 32) | pos.y as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DoubleStars;
+
+    impl ModHooks for DoubleStars {
+        fn adjust_quadrant(&self, _pos: QuadrantPosition, _seed: u64, data: &mut QuadrantData) {
+            data.stars *= 2;
+        }
+    }
+
+    struct Calm;
+
+    impl ModHooks for Calm {
+        fn event_weight(&self, _event: EventKind, weight: f64) -> f64 {
+            weight / 2.0
+        }
+
+        fn adjust_score(&self, rating: i32) -> i32 {
+            rating + 100
+        }
+    }
+
+    #[test]
+    fn empty_registry_is_identity() {
+        let mods = ModRegistry::new();
+        let mut data = QuadrantData { klingons: 2, starbases: 1, stars: 5, commanders: 0, romulans: 0, mines: 0 };
+        mods.adjust_quadrant(QuadrantPosition { x: 1, y: 1 }, 7, &mut data);
+        assert_eq!(data, QuadrantData { klingons: 2, starbases: 1, stars: 5, commanders: 0, romulans: 0, mines: 0 });
+        assert_eq!(mods.event_weight(EventKind::DeviceMalfunction, 0.2), 0.2);
+        assert_eq!(mods.adjust_score(1500), 1500);
+    }
+
+    #[test]
+    fn hooks_apply_in_registration_order() {
+        let mut mods = ModRegistry::new();
+        mods.register(Calm);
+        mods.register(Calm);
+        assert_eq!(mods.len(), 2);
+        assert_eq!(mods.event_weight(EventKind::DeviceMalfunction, 0.2), 0.05);
+        assert_eq!(mods.adjust_score(1000), 1200);
+    }
+
+    #[test]
+    fn adjusted_quadrant_is_clamped() {
+        let mut mods = ModRegistry::new();
+        mods.register(DoubleStars);
+        let mut data = QuadrantData { klingons: 0, starbases: 0, stars: 8, commanders: 0, romulans: 0, mines: 0 };
+        mods.adjust_quadrant(QuadrantPosition { x: 1, y: 1 }, 0, &mut data);
+        assert_eq!(data.stars, 9, "stars must fit the single encoded digit");
+    }
+
+    #[test]
+    fn encounter_seed_is_stable_and_position_dependent() {
+        let a = QuadrantPosition { x: 3, y: 4 };
+        let b = QuadrantPosition { x: 4, y: 3 };
+        assert_eq!(encounter_seed(42, a), encounter_seed(42, a));
+        assert_ne!(encounter_seed(42, a), encounter_seed(42, b));
+        assert_ne!(encounter_seed(42, a), encounter_seed(43, a));
+    }
+}
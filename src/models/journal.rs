@@ -0,0 +1,92 @@
+//! Quadrant journal
+//!
+//! Notable events per quadrant, stamped with the stardate they were
+//! recorded, so the computer's record has history beyond the latest
+//! encoded value of each quadrant.
+
+use super::grid::QuadrantGrid;
+use super::position::QuadrantPosition;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuadrantEvent {
+    /// Klingon count seen by a scan or visit. Only changes are recorded.
+    KlingonsSighted(i32),
+    /// The Enterprise destroyed the last Klingon here.
+    QuadrantCleared,
+    StarbaseDestroyed,
+    StarbaseConstructed,
+}
+
+impl QuadrantEvent {
+    pub fn describe(&self) -> String {
+        match self {
+            QuadrantEvent::KlingonsSighted(count) => format!("KLINGONS SIGHTED: {}", count),
+            QuadrantEvent::QuadrantCleared => "CLEARED OF KLINGONS".to_string(),
+            QuadrantEvent::StarbaseDestroyed => "STARBASE DESTROYED".to_string(),
+            QuadrantEvent::StarbaseConstructed => "STARBASE COMPLETED".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JournalEntry {
+    pub stardate: f64,
+    pub event: QuadrantEvent,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadrantJournal {
+    entries: QuadrantGrid<Vec<JournalEntry>>,
+}
+
+impl QuadrantJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, pos: QuadrantPosition, stardate: f64, event: QuadrantEvent) {
+        self.entries[pos].push(JournalEntry { stardate, event });
+    }
+
+    /// Record a Klingon count unless it matches the last one sighted here.
+    pub fn sight_klingons(&mut self, pos: QuadrantPosition, stardate: f64, count: i32) {
+        if self.last_sighting(pos).map(|(_, seen)| seen) != Some(count) {
+            self.record(pos, stardate, QuadrantEvent::KlingonsSighted(count));
+        }
+    }
+
+    /// Stardate and count of the latest Klingon sighting in a quadrant.
+    pub fn last_sighting(&self, pos: QuadrantPosition) -> Option<(f64, i32)> {
+        self.entries(pos).iter().rev().find_map(|entry| match entry.event {
+            QuadrantEvent::KlingonsSighted(count) => Some((entry.stardate, count)),
+            _ => None,
+        })
+    }
+
+    /// Entries for one quadrant, oldest first.
+    pub fn entries(&self, pos: QuadrantPosition) -> &[JournalEntry] {
+        &self.entries[pos]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sightings_are_recorded_only_when_the_count_changes() {
+        let mut journal = QuadrantJournal::new();
+        let pos = QuadrantPosition { x: 3, y: 4 };
+        journal.sight_klingons(pos, 2500.0, 2);
+        journal.sight_klingons(pos, 2501.0, 2);
+        journal.record(pos, 2502.0, QuadrantEvent::StarbaseDestroyed);
+        journal.sight_klingons(pos, 2503.0, 1);
+
+        assert_eq!(journal.entries(pos).len(), 3);
+        assert_eq!(journal.last_sighting(pos), Some((2503.0, 1)));
+        assert!(journal.entries(QuadrantPosition { x: 4, y: 3 }).is_empty());
+    }
+}
@@ -1,23 +1,54 @@
-use super::constants::KLINGON_INITIAL_SHIELDS;
+use super::constants::{SectorContent, KLINGON_INITIAL_SHIELDS};
 use super::position::SectorPosition;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KlingonKind {
+    /// An ordinary battle cruiser.
+    #[default]
+    Warship,
+    /// An elite ship with heavier shields that closes in on the Enterprise
+    /// and sometimes fires torpedoes.
+    Commander,
+}
+
 /// A Klingon warship within a quadrant's sector grid.
 /// Up to 3 per quadrant.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Klingon {
     pub sector: SectorPosition,
     pub shields: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: KlingonKind,
 }
 
 impl Klingon {
     pub fn new(sector: SectorPosition) -> Self {
-        Klingon {
-            sector,
-            shields: KLINGON_INITIAL_SHIELDS,
-        }
+        Self::with_shields(sector, KLINGON_INITIAL_SHIELDS)
+    }
+
+    pub fn with_shields(sector: SectorPosition, shields: f64) -> Self {
+        Klingon { sector, shields, kind: KlingonKind::Warship }
+    }
+
+    pub fn commander(sector: SectorPosition, shields: f64) -> Self {
+        Klingon { sector, shields, kind: KlingonKind::Commander }
     }
 
     pub fn is_alive(&self) -> bool {
         self.shields > 0.0
     }
+
+    pub fn is_commander(&self) -> bool {
+        self.kind == KlingonKind::Commander
+    }
+
+    /// How the Klingon shows on the sector map.
+    pub fn content(&self) -> SectorContent {
+        match self.kind {
+            KlingonKind::Warship => SectorContent::Klingon,
+            KlingonKind::Commander => SectorContent::KlingonCommander,
+        }
+    }
 }
@@ -0,0 +1,103 @@
+//! Energy ledger
+//!
+//! A running record of where the ship's energy went, stamped with the
+//! stardate, so players and tools can audit transfers after the fact.
+
+/// What an energy movement was for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EnergyUse {
+    /// Energy moved between main power and the shields. Positive amounts
+    /// went into the shields.
+    ShieldTransfer,
+    /// Energy sent to another ship (negative) or received from one
+    /// (positive). In a ledger shared by both ships these cancel out.
+    ShipTransfer,
+    /// Energy the raised shields drew from main power under the shield
+    /// drain rule; always negative.
+    ShieldDrain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LedgerEntry {
+    pub stardate: f64,
+    pub kind: EnergyUse,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnergyLedger {
+    entries: Vec<LedgerEntry>,
+}
+
+impl EnergyLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry. Zero amounts are not recorded.
+    pub fn record(&mut self, stardate: f64, kind: EnergyUse, amount: f64) {
+        if amount != 0.0 {
+            self.entries.push(LedgerEntry { stardate, kind, amount });
+        }
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    /// Record energy moving from one ship to another: the change in the
+    /// sender's energy (negative) and in the receiver's, each as measured
+    /// on its own ship.
+    pub fn record_ship_transfer(&mut self, stardate: f64, debit: f64, credit: f64) {
+        self.record(stardate, EnergyUse::ShipTransfer, debit);
+        self.record(stardate, EnergyUse::ShipTransfer, credit);
+    }
+
+    /// Conservation check: ship-to-ship transfers neither create nor
+    /// destroy energy, so in a ledger shared by every ship they net to zero.
+    pub fn transfers_balance(&self) -> bool {
+        self.total(EnergyUse::ShipTransfer).abs() < 1e-9
+    }
+
+    /// Net amount recorded for one kind of use.
+    pub fn total(&self, kind: EnergyUse) -> f64 {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.amount)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_entries_in_order_and_skips_zero() {
+        let mut ledger = EnergyLedger::new();
+        ledger.record(2500.0, EnergyUse::ShieldTransfer, 300.0);
+        ledger.record(2500.0, EnergyUse::ShieldTransfer, 0.0);
+        ledger.record(2501.0, EnergyUse::ShieldTransfer, -100.0);
+
+        assert_eq!(ledger.entries().len(), 2);
+        assert_eq!(ledger.entries()[1].stardate, 2501.0);
+        assert_eq!(ledger.total(EnergyUse::ShieldTransfer), 200.0);
+    }
+
+    #[test]
+    fn ship_transfers_balance() {
+        let mut ledger = EnergyLedger::new();
+        ledger.record_ship_transfer(2500.0, -400.0, 400.0);
+        assert_eq!(ledger.entries().len(), 2);
+        assert!(ledger.transfers_balance());
+
+        // Energy lost on the way shows up as an imbalance
+        ledger.record_ship_transfer(2501.0, -50.0, 45.0);
+        assert!(!ledger.transfers_balance());
+    }
+}
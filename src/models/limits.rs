@@ -0,0 +1,200 @@
+//! Galaxy limits
+//!
+//! Caps on galaxy dimensions and contents, checked before anything from
+//! outside the engine (mods, embedding applications, future scenario files)
+//! is allowed to size the galaxy, so bad input is refused with a typed error
+//! instead of driving an absurd allocation or an unplaceable quadrant.
+
+use std::fmt;
+
+use super::constants::{GALAXY_SIZE, MAX_KLINGONS_PER_QUADRANT, MAX_MINES_PER_FIELD, SECTOR_SIZE};
+use super::galaxy::Galaxy;
+use super::position::QuadrantPosition;
+use super::quadrant::QuadrantData;
+
+/// Largest galaxy edge, in quadrants, the engine will allocate.
+pub const MAX_GALAXY_DIMENSION: usize = 64;
+
+/// Most stars a quadrant can hold; the 3-digit encoding has one digit.
+const MAX_STARS_PER_QUADRANT: i32 = 9;
+
+/// Most Romulan warbirds a quadrant can hide.
+const MAX_ROMULANS_PER_QUADRANT: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GalaxyLimits {
+    /// Largest width or height, in quadrants.
+    pub max_dimension: usize,
+    pub max_klingons_per_quadrant: i32,
+    pub max_starbases_per_quadrant: i32,
+    pub max_stars_per_quadrant: i32,
+    pub max_romulans_per_quadrant: i32,
+    pub max_mines_per_quadrant: i32,
+    /// Most Klingons, starbases, stars, and Romulans in the whole galaxy.
+    pub max_entities: usize,
+}
+
+impl Default for GalaxyLimits {
+    fn default() -> Self {
+        GalaxyLimits {
+            max_dimension: MAX_GALAXY_DIMENSION,
+            max_klingons_per_quadrant: MAX_KLINGONS_PER_QUADRANT as i32,
+            max_starbases_per_quadrant: 1,
+            max_stars_per_quadrant: MAX_STARS_PER_QUADRANT,
+            max_romulans_per_quadrant: MAX_ROMULANS_PER_QUADRANT,
+            max_mines_per_quadrant: MAX_MINES_PER_FIELD,
+            max_entities: MAX_GALAXY_DIMENSION * MAX_GALAXY_DIMENSION * (SECTOR_SIZE * SECTOR_SIZE - 1),
+        }
+    }
+}
+
+/// Why a galaxy or quadrant was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitError {
+    /// A width or height of zero, or above the maximum
+    Dimensions { width: usize, height: usize },
+    /// A quadrant count that is negative or above its cap, or contents
+    /// that do not fit in the quadrant's sectors beside the Enterprise
+    QuadrantContents { pos: QuadrantPosition, data: QuadrantData },
+    /// More entities than the galaxy allows in total
+    TooManyEntities { count: usize, max: usize },
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitError::Dimensions { width, height } => {
+                write!(f, "galaxy of {}x{} quadrants is out of range", width, height)
+            }
+            LimitError::QuadrantContents { pos, data } => write!(
+                f,
+                "quadrant {},{} cannot hold {} klingons, {} starbases, {} stars, {} romulans, and {} mines",
+                pos.x, pos.y, data.klingons, data.starbases, data.stars, data.romulans, data.mines
+            ),
+            LimitError::TooManyEntities { count, max } => {
+                write!(f, "{} entities exceeds the limit of {}", count, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+impl GalaxyLimits {
+    pub fn check_dimensions(&self, width: usize, height: usize) -> Result<(), LimitError> {
+        let in_range = |edge: usize| (1..=self.max_dimension).contains(&edge);
+        if in_range(width) && in_range(height) {
+            Ok(())
+        } else {
+            Err(LimitError::Dimensions { width, height })
+        }
+    }
+
+    pub fn check_quadrant(&self, pos: QuadrantPosition, data: &QuadrantData) -> Result<(), LimitError> {
+        if (0..=self.max_klingons_per_quadrant).contains(&data.klingons)
+            && (0..=data.klingons).contains(&data.commanders)
+            && (0..=self.max_starbases_per_quadrant).contains(&data.starbases)
+            && (0..=self.max_stars_per_quadrant).contains(&data.stars)
+            && (0..=self.max_romulans_per_quadrant).contains(&data.romulans)
+            && (0..=self.max_mines_per_quadrant).contains(&data.mines)
+            && Self::sectors_used(data) < (SECTOR_SIZE * SECTOR_SIZE) as i32
+        {
+            Ok(())
+        } else {
+            Err(LimitError::QuadrantContents { pos, data: *data })
+        }
+    }
+
+    /// Bring a quadrant within the per-quadrant caps.
+    pub fn clamp_quadrant(&self, data: &mut QuadrantData) {
+        data.klingons = data.klingons.clamp(0, self.max_klingons_per_quadrant);
+        data.commanders = data.commanders.clamp(0, data.klingons);
+        data.starbases = data.starbases.clamp(0, self.max_starbases_per_quadrant);
+        data.stars = data.stars.clamp(0, self.max_stars_per_quadrant);
+        data.romulans = data.romulans.clamp(0, self.max_romulans_per_quadrant);
+        data.mines = data.mines.clamp(0, self.max_mines_per_quadrant);
+    }
+
+    /// Sectors a quadrant's contents take up, leaving the Enterprise out.
+    fn sectors_used(data: &QuadrantData) -> i32 {
+        data.klingons + data.starbases + data.stars + data.romulans + data.mines
+    }
+
+    /// Check a galaxy's size, every quadrant and every remembered quadrant,
+    /// and its total contents.
+    pub fn check_galaxy(&self, galaxy: &Galaxy) -> Result<(), LimitError> {
+        self.check_dimensions(GALAXY_SIZE, GALAXY_SIZE)?;
+        let mut entities = 0;
+        for (pos, data) in galaxy.quadrants_iter() {
+            self.check_quadrant(pos, data)?;
+            entities += (data.klingons + data.starbases + data.stars + data.romulans) as usize;
+        }
+        for (pos, memory) in galaxy.scanned_quadrants() {
+            self.check_quadrant(pos, memory)?;
+        }
+        if entities > self.max_entities {
+            return Err(LimitError::TooManyEntities { count: entities, max: self.max_entities });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimensions_must_be_nonzero_and_capped() {
+        let limits = GalaxyLimits::default();
+        assert_eq!(limits.check_dimensions(8, 8), Ok(()));
+        assert_eq!(limits.check_dimensions(MAX_GALAXY_DIMENSION, 1), Ok(()));
+        assert_eq!(
+            limits.check_dimensions(0, 8),
+            Err(LimitError::Dimensions { width: 0, height: 8 })
+        );
+        assert!(limits.check_dimensions(8, 1_000_000).is_err());
+    }
+
+    #[test]
+    fn quadrant_contents_are_checked_and_clamped() {
+        let limits = GalaxyLimits::default();
+        let pos = QuadrantPosition { x: 2, y: 3 };
+        let mut data = QuadrantData { klingons: 40, starbases: -1, stars: 9, commanders: 0, romulans: 0, mines: 70 };
+        assert_eq!(limits.check_quadrant(pos, &data), Err(LimitError::QuadrantContents { pos, data }));
+        limits.clamp_quadrant(&mut data);
+        assert_eq!(data, QuadrantData { klingons: 3, starbases: 0, stars: 9, commanders: 0, romulans: 0, mines: 4 });
+        assert_eq!(limits.check_quadrant(pos, &data), Ok(()));
+
+        // Every count within its cap, but too many to place
+        let roomy = GalaxyLimits { max_stars_per_quadrant: 99, ..limits };
+        let crowded = QuadrantData { stars: 60, ..data };
+        assert!(roomy.check_quadrant(pos, &crowded).is_err());
+        assert!(roomy.check_quadrant(pos, &QuadrantData { stars: 56, ..data }).is_ok());
+    }
+
+    #[test]
+    fn generated_galaxies_are_within_limits() {
+        let limits = GalaxyLimits::default();
+        for seed in 0..20 {
+            assert_eq!(limits.check_galaxy(&Galaxy::new(seed)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn tampered_memory_and_totals_are_refused() {
+        let mut galaxy = Galaxy::new(42);
+        let corner = QuadrantPosition { x: 1, y: 1 };
+        galaxy.computer_memory_mut()[corner] = Some(QuadrantData { klingons: 500, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 });
+        let limits = GalaxyLimits::default();
+        assert!(matches!(
+            limits.check_galaxy(&galaxy),
+            Err(LimitError::QuadrantContents { pos, .. }) if pos == corner
+        ));
+
+        let tight = GalaxyLimits { max_entities: 10, ..Default::default() };
+        assert!(matches!(
+            tight.check_galaxy(&Galaxy::new(42)),
+            Err(LimitError::TooManyEntities { max: 10, .. })
+        ));
+    }
+}
@@ -5,10 +5,36 @@
 
 pub mod constants;
 pub mod position;
+pub mod grid;
 pub mod enterprise;
 pub mod klingon;
+pub mod romulan;
+pub mod tholian;
+pub mod probe;
+pub mod pending;
 pub mod quadrant;
 pub mod sector_map;
 pub mod galaxy;
 pub mod errors;
 pub mod navigation_types;
+pub mod hooks;
+pub mod digest;
+pub mod rng;
+pub mod status;
+pub mod ruleset;
+pub mod repair;
+pub mod war;
+pub mod turn;
+pub mod ledger;
+pub mod achievements;
+pub mod combat_odds;
+pub mod director;
+pub mod difficulty;
+pub mod journal;
+pub mod clock;
+pub mod limits;
+pub mod output_event;
+pub mod region;
+pub mod config;
+pub mod compat;
+pub mod scenarios;
@@ -0,0 +1,111 @@
+//! Output events
+//!
+//! Structured records of what a command did, emitted by the services in
+//! place of preformatted text. A terminal renders each one as soon as it is
+//! emitted; other frontends can queue them and draw their own display.
+
+use super::constants::{Condition, Device, SectorContent, SymbolSet};
+use super::position::{QuadrantPosition, SectorPosition};
+
+/// A short range scan: the quadrant as the sensors show it, and the ship's
+/// status read out alongside.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorReadout {
+    /// Rows north to south, each west to east.
+    pub rows: Vec<Vec<SectorContent>>,
+    pub symbols: SymbolSet,
+    pub stardate: f64,
+    pub condition: Condition,
+    pub quadrant: QuadrantPosition,
+    pub sector: SectorPosition,
+    pub energy: f64,
+    pub shields: f64,
+    pub torpedoes: i32,
+    pub hull: f64,
+    pub crew: i32,
+    /// Phaser heat in percent, under the phaser heat rule.
+    pub phaser_heat: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputEvent {
+    /// A Klingon's shot landed on the Enterprise.
+    KlingonHit {
+        from: SectorPosition,
+        damage: f64,
+        shields_left: f64,
+        /// Hull integrity left, when the shot got through the shields.
+        hull: Option<f64>,
+    },
+    /// A Klingon commander launched a torpedo; its hit follows.
+    CommanderTorpedo { from: SectorPosition },
+    /// A Klingon commander closed in on the Enterprise.
+    CommanderMoved { from: SectorPosition, to: SectorPosition },
+    /// A Klingon commander's tractor beam dragged the Enterprise toward it.
+    TractorBeam {
+        from: SectorPosition,
+        to: SectorPosition,
+        /// Hull integrity left, when something in the way stopped the ship.
+        hull: Option<f64>,
+    },
+    /// A Romulan dropped its cloak to fire; its hit follows.
+    RomulanDecloaked { at: SectorPosition },
+    /// A Romulan's shot landed on the Enterprise.
+    RomulanHit {
+        from: SectorPosition,
+        damage: f64,
+        shields_left: f64,
+        /// Hull integrity left, when the shot got through the shields.
+        hull: Option<f64>,
+    },
+    /// A Tholian left a web segment behind it.
+    WebSpun { at: SectorPosition },
+    /// The subspace radio picked up a Klingon warping into a quadrant.
+    ReinforcementsArrived { quadrant: QuadrantPosition },
+    /// Klingons began an attack on the starbase in a quadrant.
+    StarbaseUnderAttack { quadrant: QuadrantPosition, stardates_left: f64 },
+    /// The attack on a starbase was broken before its deadline.
+    StarbaseRelieved { quadrant: QuadrantPosition },
+    /// Klingons destroyed the starbase they were attacking.
+    StarbaseOverrun { quadrant: QuadrantPosition },
+    /// A long-range probe entered a quadrant and sent back its contents.
+    ProbeReport { quadrant: QuadrantPosition, encoded: i32 },
+    /// A long-range probe passed the edge of the galaxy.
+    ProbeLeftGalaxy,
+    /// The Enterprise was destroyed and the crew escaped in the
+    /// shuttlecraft with `energy` units.
+    ShipAbandoned { energy: f64 },
+    /// Docked: the starbase's shields absorbed the Klingons' fire.
+    StarbaseShieldsProtect,
+    /// A torpedo left the tubes.
+    TorpedoFired,
+    /// A torpedo passed through a sector.
+    TorpedoTrack { x: i32, y: i32 },
+    /// A torpedo left the quadrant without hitting anything.
+    TorpedoMissed,
+    /// A torpedo's blast hit a Klingon next to its target.
+    SplashHit { at: SectorPosition, damage: f64, shields_left: f64 },
+    /// The Enterprise's phasers or a torpedo hit a Klingon.
+    HitOnKlingon { at: SectorPosition, damage: f64, shields_left: f64 },
+    /// The Enterprise's phasers hit a Romulan.
+    HitOnRomulan { at: SectorPosition, damage: f64, shields_left: f64 },
+    /// A Klingon was destroyed.
+    KlingonDestroyed,
+    /// A Romulan warbird was destroyed.
+    RomulanDestroyed,
+    /// The short range sensors scanned the quadrant.
+    ShortRangeScan(Box<SensorReadout>),
+    /// The long range sensors scanned the quadrants around `quadrant`: rows
+    /// of encoded contents, north to south, `None` outside the galaxy.
+    LongRangeScan { quadrant: QuadrantPosition, grid: Vec<Vec<Option<i32>>> },
+    /// The Enterprise crossed into another quadrant.
+    QuadrantEntered { quadrant: QuadrantPosition, stardate: f64 },
+    /// Damage control reports a device damaged in transit.
+    DeviceDamaged { device: Device, severity: f64 },
+    /// Damage control reports a device's repairs advanced in transit.
+    DeviceRepaired { device: Device, severity: f64 },
+    /// A line of text not yet given its own event.
+    Message(String),
+    /// Text without a line break.
+    Text(String),
+}
@@ -0,0 +1,11 @@
+use super::position::QuadrantPosition;
+
+/// Something set in motion that comes due at a later stardate. The galaxy
+/// keeps a list of them, resolved once per turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PendingEvent {
+    /// Klingons are attacking the starbase in `quadrant`. It falls at
+    /// `deadline` unless the quadrant is cleared of Klingons first.
+    StarbaseAttack { quadrant: QuadrantPosition, deadline: f64 },
+}
@@ -4,6 +4,7 @@ use std::fmt::{self, Display, Formatter};
 /// Values range 1-8. (1,1) is upper-left, (8,8) is lower-right.
 /// X increases left-to-right, Y increases top-to-bottom.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuadrantPosition {
     pub x: i32,
     pub y: i32,
@@ -19,6 +20,7 @@ impl Display for QuadrantPosition {
 /// Values range 1-8. (1,1) is upper-left, (8,8) is lower-right.
 /// X increases left-to-right, Y increases top-to-bottom.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SectorPosition {
     pub x: i32,
     pub y: i32,
@@ -0,0 +1,59 @@
+use super::position::QuadrantPosition;
+
+/// A long-range probe in flight. It crosses one quadrant per stardate
+/// along its course, reporting each quadrant it enters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Probe {
+    /// Position in quadrant units, unrounded so off-axis courses hold.
+    x: f64,
+    y: f64,
+    /// Quadrants travelled per stardate along each axis.
+    dx: f64,
+    dy: f64,
+    /// Stardate the probe reaches its next quadrant.
+    next_arrival: f64,
+}
+
+impl Probe {
+    /// A probe leaving `from` at `stardate`, heading along `(dx, dy)`.
+    pub fn launch(from: QuadrantPosition, (dx, dy): (f64, f64), stardate: f64) -> Self {
+        Probe { x: from.x as f64, y: from.y as f64, dx, dy, next_arrival: stardate + 1.0 }
+    }
+
+    /// The quadrant the probe is in.
+    pub fn quadrant(&self) -> QuadrantPosition {
+        QuadrantPosition { x: (self.x + 0.5).floor() as i32, y: (self.y + 0.5).floor() as i32 }
+    }
+
+    /// Whether the probe has reached its next quadrant by `stardate`.
+    pub fn is_due(&self, stardate: f64) -> bool {
+        stardate >= self.next_arrival
+    }
+
+    /// Move into the next quadrant along the course and return it. It may
+    /// be outside the galaxy.
+    pub fn advance(&mut self) -> QuadrantPosition {
+        self.x += self.dx;
+        self.y += self.dy;
+        self.next_arrival += 1.0;
+        self.quadrant()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probes_cross_one_quadrant_per_stardate() {
+        let mut probe = Probe::launch(QuadrantPosition { x: 2, y: 5 }, (1.0, -0.5), 2000.0);
+        assert_eq!(probe.quadrant(), QuadrantPosition { x: 2, y: 5 });
+        assert!(!probe.is_due(2000.5));
+        assert!(probe.is_due(2001.0));
+        assert_eq!(probe.advance(), QuadrantPosition { x: 3, y: 5 });
+        assert_eq!(probe.advance(), QuadrantPosition { x: 4, y: 4 });
+        assert!(!probe.is_due(2002.5));
+        assert!(probe.is_due(2003.0));
+    }
+}
@@ -1,16 +1,30 @@
 /// Persistent data about a single quadrant in the galaxy.
 /// Stores only counts — sector positions are not preserved between visits.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuadrantData {
     pub klingons: i32,
     pub starbases: i32,
     pub stars: i32,
+    /// How many of the Klingons are commanders; always zero unless the
+    /// commanders rule is on.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub commanders: i32,
+    /// Cloaked Romulan warbirds; left out of the encoding, since the
+    /// sensors cannot see them. Always zero unless the Romulans rule is on.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub romulans: i32,
+    /// Hidden mines; left out of the encoding like the Romulans. Always
+    /// zero unless the minefields rule is on.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mines: i32,
 }
 
 impl QuadrantData {
-    /// The 3-digit encoded value: klingons*100 + starbases*10 + stars.
+    /// The encoded value: klingons*100 + starbases*10 + stars, with a
+    /// thousands digit for commanders when there are any.
     pub fn encoded(&self) -> i32 {
-        self.klingons * 100 + self.starbases * 10 + self.stars
+        self.commanders * 1000 + self.klingons * 100 + self.starbases * 10 + self.stars
     }
 }
 
@@ -20,37 +34,43 @@ mod tests {
 
     #[test]
     fn encoded_all_zeros() {
-        let q = QuadrantData { klingons: 0, starbases: 0, stars: 0 };
+        let q = QuadrantData { klingons: 0, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 0);
     }
 
     #[test]
     fn encoded_only_klingons() {
-        let q = QuadrantData { klingons: 3, starbases: 0, stars: 0 };
+        let q = QuadrantData { klingons: 3, starbases: 0, stars: 0, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 300);
     }
 
     #[test]
     fn encoded_only_starbases() {
-        let q = QuadrantData { klingons: 0, starbases: 1, stars: 0 };
+        let q = QuadrantData { klingons: 0, starbases: 1, stars: 0, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 10);
     }
 
     #[test]
     fn encoded_only_stars() {
-        let q = QuadrantData { klingons: 0, starbases: 0, stars: 5 };
+        let q = QuadrantData { klingons: 0, starbases: 0, stars: 5, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 5);
     }
 
     #[test]
     fn encoded_mixed() {
-        let q = QuadrantData { klingons: 2, starbases: 1, stars: 7 };
+        let q = QuadrantData { klingons: 2, starbases: 1, stars: 7, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 217);
     }
 
     #[test]
     fn encoded_max_values() {
-        let q = QuadrantData { klingons: 3, starbases: 1, stars: 8 };
+        let q = QuadrantData { klingons: 3, starbases: 1, stars: 8, commanders: 0, romulans: 0, mines: 0 };
         assert_eq!(q.encoded(), 318);
     }
+
+    #[test]
+    fn encoded_commanders_add_a_thousands_digit() {
+        let q = QuadrantData { klingons: 2, starbases: 1, stars: 7, commanders: 1, romulans: 0, mines: 0 };
+        assert_eq!(q.encoded(), 1217);
+    }
 }
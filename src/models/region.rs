@@ -0,0 +1,55 @@
+//! Galactic region names
+//!
+//! The 1978 Super Star Trek naming table. Each row of the galaxy is split
+//! into two regions named after stars, four quadrants wide; a quadrant is
+//! named by its region and a Roman numeral for its place within it, e.g.
+//! "ANTARES IV".
+
+use super::constants::GALAXY_SIZE;
+use super::position::QuadrantPosition;
+
+/// Regions covering columns 1-4, by row.
+const WESTERN_REGIONS: [&str; GALAXY_SIZE] =
+    ["ANTARES", "RIGEL", "PROCYON", "VEGA", "CANOPUS", "ALTAIR", "SAGITTARIUS", "POLLUX"];
+
+/// Regions covering columns 5-8, by row.
+const EASTERN_REGIONS: [&str; GALAXY_SIZE] =
+    ["SIRIUS", "DENEB", "CAPELLA", "BETELGEUSE", "ALDEBARAN", "REGULUS", "ARCTURUS", "SPICA"];
+
+const NUMERALS: [&str; 4] = ["I", "II", "III", "IV"];
+
+/// Width of a region in quadrants.
+const REGION_WIDTH: i32 = 4;
+
+/// The region a quadrant lies in, e.g. "ANTARES"; `None` off the galaxy.
+pub fn region_name(pos: QuadrantPosition) -> Option<&'static str> {
+    let range = 1..=GALAXY_SIZE as i32;
+    if !range.contains(&pos.x) || !range.contains(&pos.y) {
+        return None;
+    }
+    let regions = if pos.x <= REGION_WIDTH { &WESTERN_REGIONS } else { &EASTERN_REGIONS };
+    Some(regions[(pos.y - 1) as usize])
+}
+
+/// A quadrant's full name, e.g. "ANTARES IV"; `None` off the galaxy.
+pub fn quadrant_name(pos: QuadrantPosition) -> Option<String> {
+    let region = region_name(pos)?;
+    Some(format!("{} {}", region, NUMERALS[((pos.x - 1) % REGION_WIDTH) as usize]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadrants_are_named_by_region_and_numeral() {
+        let name = |x, y| quadrant_name(QuadrantPosition { x, y });
+        assert_eq!(name(1, 1).as_deref(), Some("ANTARES I"));
+        assert_eq!(name(4, 1).as_deref(), Some("ANTARES IV"));
+        assert_eq!(name(5, 1).as_deref(), Some("SIRIUS I"));
+        assert_eq!(name(8, 8).as_deref(), Some("SPICA IV"));
+        assert_eq!(name(2, 7).as_deref(), Some("SAGITTARIUS II"));
+        assert_eq!(name(0, 1), None);
+        assert_eq!(name(1, 9), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Repair-time model
+//!
+//! A device's damage value is also its repair time: damage control mends
+//! one unit per warp move (spec section 5.2), so a device at -3 needs three
+//! moves to come back online. Starbase crews can instead finish a chosen
+//! device on the spot, working faster than the ship's crew but still
+//! costing stardates.
+
+use super::constants::{Condition, Device};
+use super::enterprise::Enterprise;
+use super::galaxy::Galaxy;
+
+/// Fraction of a device's repair time a starbase crew needs to finish it.
+pub const STARBASE_REPAIR_FACTOR: f64 = 0.5;
+
+/// Shortest priority repair, in stardates.
+pub const MIN_PRIORITY_REPAIR_TIME: f64 = 0.1;
+
+/// Repair time, in stardates of warp travel, for a device damage value.
+/// Working devices need none.
+pub fn repair_time(damage: f64) -> f64 {
+    (-damage).max(0.0)
+}
+
+/// Stardates a starbase crew spends finishing a device immediately,
+/// rounded up to the nearest tenth.
+pub fn priority_repair_time(damage: f64) -> f64 {
+    let time = (repair_time(damage) * STARBASE_REPAIR_FACTOR * 10.0).ceil() / 10.0;
+    time.max(MIN_PRIORITY_REPAIR_TIME)
+}
+
+/// Repair per warp move for `device`: one unit, adjusted by the repair
+/// crew assignment and, under the crew morale rule, by a depleted crew.
+pub fn transit_repair_rate(galaxy: &Galaxy, device: Device) -> f64 {
+    galaxy.enterprise().repair_rate(device) * galaxy.crew_efficiency()
+}
+
+/// Stardates until `device` works again at the current rates, rounded up
+/// to the nearest tenth. Docked, a starbase crew could finish it in its
+/// priority repair time; in flight, the ship's crew mends it at its
+/// transit repair rate. Working devices need none.
+pub fn estimated_repair_time(galaxy: &Galaxy, device: Device) -> f64 {
+    let damage = galaxy.enterprise().devices()[device as usize];
+    if damage >= 0.0 {
+        return 0.0;
+    }
+    if galaxy.evaluate_condition() == Condition::Docked {
+        return priority_repair_time(damage);
+    }
+    (repair_time(damage) / transit_repair_rate(galaxy, device) * 10.0).ceil() / 10.0
+}
+
+/// A damaged device and what it would cost to repair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairEstimate {
+    pub device: Device,
+    /// Stardates until automatic repair brings the device back online.
+    pub repair_time: f64,
+    /// Stardates a starbase priority repair would take.
+    pub priority_time: f64,
+}
+
+/// Stardates a starbase crew spends repairing every listed device, as the
+/// sum of their priority times.
+pub fn full_repair_time(estimates: &[RepairEstimate]) -> f64 {
+    let total: f64 = estimates.iter().map(|e| e.priority_time).sum();
+    (total * 10.0).round() / 10.0
+}
+
+/// Estimates for every damaged device, in device order.
+pub fn repair_estimates(enterprise: &Enterprise) -> Vec<RepairEstimate> {
+    Device::ALL
+        .iter()
+        .filter(|&&device| enterprise.is_damaged(device))
+        .map(|&device| {
+            let damage = enterprise.devices()[device as usize];
+            RepairEstimate {
+                device,
+                repair_time: repair_time(damage),
+                priority_time: priority_repair_time(damage),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::position::{QuadrantPosition, SectorPosition};
+
+    #[test]
+    fn repair_time_is_damage_magnitude() {
+        assert_eq!(repair_time(-3.0), 3.0);
+        assert_eq!(repair_time(0.0), 0.0);
+        assert_eq!(repair_time(2.0), 0.0);
+    }
+
+    #[test]
+    fn priority_repair_is_faster_but_never_free() {
+        assert_eq!(priority_repair_time(-4.0), 2.0);
+        assert_eq!(priority_repair_time(-3.0), 1.5);
+        assert_eq!(priority_repair_time(-0.25), 0.2);
+        assert_eq!(priority_repair_time(-0.01), MIN_PRIORITY_REPAIR_TIME);
+    }
+
+    #[test]
+    fn estimates_list_only_damaged_devices() {
+        let mut enterprise = Enterprise::new(
+            QuadrantPosition { x: 1, y: 1 },
+            SectorPosition { x: 1, y: 1 },
+        );
+        assert!(repair_estimates(&enterprise).is_empty());
+
+        enterprise.damage_device(Device::Computer, 3.0);
+        enterprise.damage_device(Device::WarpEngines, 1.0);
+        let estimates = repair_estimates(&enterprise);
+        assert_eq!(estimates.len(), 2);
+        assert_eq!(estimates[0].device, Device::WarpEngines);
+        assert_eq!(estimates[1].device, Device::Computer);
+        assert_eq!(estimates[1].repair_time, 3.0);
+        assert_eq!(estimates[1].priority_time, 1.5);
+        assert_eq!(full_repair_time(&estimates), 2.0);
+
+        enterprise.repair_device(Device::PhotonTubes, 1.0);
+        enterprise.repair_all_devices();
+        assert!(repair_estimates(&enterprise).is_empty());
+        assert_eq!(enterprise.devices()[Device::PhotonTubes as usize], 1.0);
+    }
+
+    #[test]
+    fn estimates_follow_crew_assignments_and_docking() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 3.0);
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 1.0);
+        assert_eq!(estimated_repair_time(&galaxy, Device::Computer), 3.0);
+        assert_eq!(estimated_repair_time(&galaxy, Device::PhotonTubes), 0.0);
+
+        galaxy.enterprise_mut().set_repair_priority(Some(Device::Computer));
+        assert_eq!(transit_repair_rate(&galaxy, Device::WarpEngines), 0.5);
+        assert_eq!(estimated_repair_time(&galaxy, Device::Computer), 1.5);
+        assert_eq!(estimated_repair_time(&galaxy, Device::WarpEngines), 2.0);
+    }
+}
@@ -0,0 +1,294 @@
+//! Game random number generator
+//!
+//! Every random draw in the game goes through [`GameRng`], which names the
+//! subsystem consuming it. In dev mode (`--dev-rng-log`) each draw is
+//! recorded, so two runs of the same seed can be diffed to find where their
+//! random streams diverge.
+//!
+//! With the `serde` feature the generator's position in its stream is saved
+//! along with the game, so a restored game draws exactly the values the
+//! original would have. The draw log is not saved.
+//!
+//! Tests can swap the seeded stream for any [`RandomSource`], such as a
+//! [`SequenceRng`] of chosen values, to pin down an outcome without hunting
+//! for a seed that produces it.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+/// Identifies the random stream behind [`GameRng`]: the generator and how
+/// draws are mapped to game values. Change it whenever either changes, so
+/// replays recorded on the old stream are refused.
+pub const RNG_STREAM_ID: &str = "rand0.8-stdrng/unit-range-v1";
+
+static DEV_LOGGING: AtomicBool = AtomicBool::new(false);
+
+/// Turn draw logging on or off for generators created afterwards.
+pub fn set_dev_logging(enabled: bool) {
+    DEV_LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+/// Where [`GameRng`] takes its values from when not from its seed.
+pub trait RandomSource: Send {
+    /// Uniform value in `[0, 1)`.
+    fn unit(&mut self) -> f64;
+
+    /// Uniform integer in an inclusive range.
+    fn range(&mut self, range: RangeInclusive<i32>) -> i32;
+
+    /// A copy at the same position, so galaxies using it can be cloned.
+    fn box_clone(&self) -> Box<dyn RandomSource>;
+}
+
+impl Clone for Box<dyn RandomSource> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Source that plays back chosen unit values in order, starting over when
+/// they run out. Range draws scale the next value onto the range, so 0.0
+/// gives its start and values near 1.0 its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceRng {
+    values: Vec<f64>,
+    next: usize,
+}
+
+impl SequenceRng {
+    /// # Panics
+    ///
+    /// If `values` is empty or any value lies outside `[0, 1)`.
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Self {
+        let values: Vec<f64> = values.into_iter().collect();
+        assert!(!values.is_empty(), "a sequence needs at least one value");
+        assert!(
+            values.iter().all(|v| (0.0..1.0).contains(v)),
+            "sequence values must lie in [0, 1)"
+        );
+        SequenceRng { values, next: 0 }
+    }
+}
+
+impl RandomSource for SequenceRng {
+    fn unit(&mut self) -> f64 {
+        let value = self.values[self.next];
+        self.next = (self.next + 1) % self.values.len();
+        value
+    }
+
+    fn range(&mut self, range: RangeInclusive<i32>) -> i32 {
+        let (start, end) = range.into_inner();
+        let span = (end - start + 1) as f64;
+        start + (self.unit() * span).floor() as i32
+    }
+
+    fn box_clone(&self) -> Box<dyn RandomSource> {
+        Box::new(self.clone())
+    }
+}
+
+/// One recorded random draw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RngDraw {
+    /// Position of the draw in the stream, starting at 0.
+    pub index: u64,
+    /// Subsystem that consumed the draw, e.g. "damage.device".
+    pub label: &'static str,
+    pub value: f64,
+}
+
+impl fmt::Display for RngDraw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\t{}\t{}", self.index, self.label, self.value)
+    }
+}
+
+/// Seeded generator with labeled draw methods.
+///
+/// Draws produce exactly the values rand 0.8's `StdRng` would, so
+/// wrapping it does not change any seed's game. The generator is held as
+/// the `ChaCha12Rng` that `StdRng` wraps, whose state can be saved. A
+/// source set with [`GameRng::set_source`] is not saved; a restored
+/// generator goes back to its seeded stream.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRng {
+    inner: ChaCha12Rng,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: Option<Box<dyn RandomSource>>,
+    draws: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    log: Option<Vec<RngDraw>>,
+}
+
+impl GameRng {
+    /// Create a generator, logging draws if dev logging is enabled.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        GameRng {
+            inner: ChaCha12Rng::seed_from_u64(seed),
+            source: None,
+            draws: 0,
+            log: DEV_LOGGING.load(Ordering::Relaxed).then(Vec::new),
+        }
+    }
+
+    /// Create a generator that always logs its draws.
+    pub fn with_log(seed: u64) -> Self {
+        GameRng {
+            log: Some(Vec::new()),
+            ..Self::seed_from_u64(seed)
+        }
+    }
+
+    /// Take every draw from here on from `source` instead of the seed.
+    /// Draws are still counted, labeled, and logged.
+    pub fn set_source(&mut self, source: impl RandomSource + 'static) {
+        self.source = Some(Box::new(source));
+    }
+
+    /// Uniform value in `[0, 1)`, the BASIC `RND(1)`.
+    pub fn unit(&mut self, label: &'static str) -> f64 {
+        let value = match &mut self.source {
+            Some(source) => source.unit(),
+            None => self.inner.gen::<f64>(),
+        };
+        self.record(label, value);
+        value
+    }
+
+    /// Uniform integer in an inclusive range.
+    pub fn range(&mut self, label: &'static str, range: RangeInclusive<i32>) -> i32 {
+        let value = match &mut self.source {
+            Some(source) => source.range(range),
+            None => self.inner.gen_range(range),
+        };
+        self.record(label, value as f64);
+        value
+    }
+
+    /// Stop recording draws and discard any recorded so far.
+    pub fn stop_logging(&mut self) {
+        self.log = None;
+    }
+
+    /// Total number of draws so far.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Recorded draws, if logging is enabled.
+    pub fn log(&self) -> Option<&[RngDraw]> {
+        self.log.as_deref()
+    }
+
+    /// Write the recorded draws one per line as `index<TAB>label<TAB>value`.
+    pub fn write_log(&self, out: &mut impl Write) -> io::Result<()> {
+        for draw in self.log().unwrap_or_default() {
+            writeln!(out, "{}", draw)?;
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, label: &'static str, value: f64) {
+        if let Some(log) = &mut self.log {
+            log.push(RngDraw {
+                index: self.draws,
+                label,
+                value,
+            });
+        }
+        self.draws += 1;
+    }
+}
+
+impl fmt::Debug for GameRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GameRng")
+            .field("draws", &self.draws)
+            .field("logging", &self.log.is_some())
+            .field("custom_source", &self.source.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn draws_match_the_underlying_stream() {
+        let mut plain = StdRng::seed_from_u64(42);
+        let mut wrapped = GameRng::seed_from_u64(42);
+
+        assert_eq!(wrapped.unit("a"), plain.gen::<f64>());
+        assert_eq!(wrapped.range("b", 1..=8), plain.gen_range(1..=8));
+        assert_eq!(wrapped.unit("c"), plain.gen::<f64>());
+        assert_eq!(wrapped.draws(), 3);
+    }
+
+    #[test]
+    fn injected_sources_replace_the_stream_but_keep_the_log() {
+        let mut rng = GameRng::with_log(42);
+        rng.unit("a");
+        rng.set_source(SequenceRng::new([0.25, 0.999]));
+        assert_eq!(rng.unit("b"), 0.25);
+        assert_eq!(rng.range("c", 1..=8), 8);
+        assert_eq!(rng.range("d", 1..=8), 3);
+
+        let copy = rng.clone();
+        assert_eq!(rng.unit("e"), 0.999);
+        assert_eq!(copy.clone().unit("e"), 0.999);
+        assert_eq!(rng.draws(), 5);
+        assert_eq!(rng.log().unwrap()[3].label, "d");
+    }
+
+    #[test]
+    #[should_panic(expected = "[0, 1)")]
+    fn sequences_refuse_values_outside_the_unit_interval() {
+        SequenceRng::new([0.5, 1.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn saved_generator_resumes_the_stream() {
+        let mut rng = GameRng::with_log(42);
+        rng.unit("a");
+        rng.range("b", 1..=8);
+
+        let json = serde_json::to_string(&rng).unwrap();
+        let mut restored: GameRng = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.draws(), 2);
+        assert!(restored.log().is_none());
+        assert_eq!(restored.unit("c"), rng.unit("c"));
+        assert_eq!(restored.range("d", 1..=8), rng.range("d", 1..=8));
+    }
+
+    #[test]
+    fn logging_records_labeled_draws() {
+        let mut rng = GameRng::with_log(7);
+        let a = rng.unit("galaxy.stardate");
+        let b = rng.range("quadrant.sector", 1..=8);
+
+        assert_eq!(
+            rng.log().unwrap(),
+            &[
+                RngDraw { index: 0, label: "galaxy.stardate", value: a },
+                RngDraw { index: 1, label: "quadrant.sector", value: b as f64 },
+            ]
+        );
+
+        let mut out = Vec::new();
+        rng.write_log(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("0\tgalaxy.stardate\t{}\n1\tquadrant.sector\t{}\n", a, b)
+        );
+    }
+}
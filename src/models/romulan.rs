@@ -0,0 +1,22 @@
+use super::position::SectorPosition;
+
+/// A Romulan warbird within a quadrant's sector grid. It hides under its
+/// cloaking device, invisible to the short range sensors, until it fires.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Romulan {
+    pub sector: SectorPosition,
+    pub shields: f64,
+    pub cloaked: bool,
+}
+
+impl Romulan {
+    /// A cloaked warbird, as found on entering its quadrant.
+    pub fn new(sector: SectorPosition, shields: f64) -> Self {
+        Romulan { sector, shields, cloaked: true }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.shields > 0.0
+    }
+}
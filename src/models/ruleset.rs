@@ -0,0 +1,194 @@
+//! Rulesets
+//!
+//! Rules that differ between Star Trek variants. The default ruleset is the
+//! 1971 game described in the specification.
+
+use super::constants::{Device, AUTO_SHIELD_FLOOR, AUTO_SHIELD_UNITS, MISSION_DURATION};
+use super::difficulty::Difficulty;
+use super::director::DirectorBounds;
+
+/// How long the mission lasts, in stardates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissionDuration {
+    /// A fixed number of stardates regardless of the galaxy (spec 3.2).
+    Fixed(f64),
+    /// `max(klingons + extra, minimum)`, from variants that give dense
+    /// galaxies more time so they stay winnable.
+    ScaledByKlingons { extra: f64, minimum: f64 },
+}
+
+impl MissionDuration {
+    /// The common variant rule: `max(klingons + 1, 30)`.
+    pub const SCALED: MissionDuration = MissionDuration::ScaledByKlingons {
+        extra: 1.0,
+        minimum: MISSION_DURATION,
+    };
+
+    /// Stardates allowed for a galaxy with this many Klingons.
+    pub fn stardates(&self, initial_klingons: i32) -> f64 {
+        match *self {
+            MissionDuration::Fixed(stardates) => stardates,
+            MissionDuration::ScaledByKlingons { extra, minimum } => {
+                (initial_klingons as f64 + extra).max(minimum)
+            }
+        }
+    }
+
+    /// The formula as shown in the mission briefing.
+    pub fn formula(&self) -> String {
+        match *self {
+            MissionDuration::Fixed(stardates) => format!("{} STARDATES", stardates),
+            MissionDuration::ScaledByKlingons { extra, minimum } => {
+                format!("MAX(KLINGONS + {}, {}) STARDATES", extra, minimum)
+            }
+        }
+    }
+}
+
+impl Default for MissionDuration {
+    fn default() -> Self {
+        MissionDuration::Fixed(MISSION_DURATION)
+    }
+}
+
+/// Automatic shield transfer on entering a quadrant with Klingons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoShieldPolicy {
+    /// Engage only when the shields are below this level.
+    pub floor: f64,
+    /// Energy moved into the shields when the policy engages.
+    pub units: f64,
+}
+
+impl Default for AutoShieldPolicy {
+    fn default() -> Self {
+        AutoShieldPolicy {
+            floor: AUTO_SHIELD_FLOOR,
+            units: AUTO_SHIELD_UNITS,
+        }
+    }
+}
+
+/// Variant rules applied to a game.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ruleset {
+    pub mission_duration: MissionDuration,
+    /// Start with the fleet HQ long range sensor upgrade already installed.
+    pub extended_long_range_sensors: bool,
+    /// Raise shields automatically on entering hostile quadrants. Off
+    /// (`None`) in the original game.
+    pub auto_shields: Option<AutoShieldPolicy>,
+    /// Klingon groups drift toward the nearest starbase every few
+    /// stardates, a harder variant that punishes slow play.
+    pub klingon_migration: bool,
+    /// A new Klingon warps into the galaxy every few stardates, so long
+    /// games grow harder.
+    pub reinforcement_waves: bool,
+    /// Klingons now and then attack a starbase, which falls unless the
+    /// Enterprise clears them from its quadrant in time.
+    pub starbase_attacks: bool,
+    /// Adaptive difficulty: reinforcements and Klingon aggression follow
+    /// the player's pace within these bounds. Off in the original game.
+    pub director: Option<DirectorBounds>,
+    /// Scales Klingon numbers and shields, mission time, and device
+    /// failures. Captain is the original game.
+    pub difficulty: Difficulty,
+    /// Damage a torpedo that destroys a Klingon also does to Klingons in
+    /// the eight surrounding sectors. Off (`None`) in the original game.
+    pub torpedo_splash: Option<f64>,
+    /// On docking, the starbase offers to repair every damaged device at
+    /// once for a cost in stardates, as in the 1978 game.
+    pub docking_repairs: bool,
+    /// A few Klingon groups are led by commanders: heavily shielded ships
+    /// that close in each turn and sometimes fire torpedoes.
+    pub klingon_commanders: bool,
+    /// Cloaked Romulan warbirds lurk in a few quadrants, unseen until they
+    /// fire. They are not counted toward victory.
+    pub romulans: bool,
+    /// Black holes form in some quadrants as they are entered. They swallow
+    /// torpedoes and throw the Enterprise elsewhere in the quadrant.
+    pub black_holes: bool,
+    /// Tholians spin a web around the edge of some quadrants, fencing the
+    /// Enterprise in until it leaves or destroys them.
+    pub tholians: bool,
+    /// When the Enterprise is destroyed, the crew escapes in the
+    /// shuttlecraft if the shuttle bay is working, and carries on with
+    /// little energy and no torpedoes until a starbase issues a new ship.
+    pub shuttlecraft: bool,
+    /// Some quadrants are sown with mines that cost shield energy when the
+    /// Enterprise runs into them. Only fully repaired sensors show them.
+    pub minefields: bool,
+    /// Heavy crew casualties slow repairs in transit and weaken phaser
+    /// fire.
+    pub crew_morale: bool,
+    /// Distant Klingons may dodge a torpedo into a neighboring empty
+    /// sector, more often the farther away they are.
+    pub torpedo_dodging: bool,
+    /// Phaser fire heats the banks: hot phasers deliver less, and firing
+    /// past the heat limit risks an overload that damages phaser control.
+    pub phaser_heat: bool,
+    /// Raised shields draw a little main power for every stardate that
+    /// passes.
+    pub shield_drain: bool,
+    /// Steering into a Klingon rams it instead of shutting down the
+    /// engines: the Klingon is destroyed and the Enterprise badly damaged.
+    pub ramming: bool,
+    /// A destroyed Klingon leaves debris in its sector until the quadrant
+    /// is next entered. Debris stops half the torpedoes fired through it
+    /// and slows the Enterprise down.
+    pub debris: bool,
+    /// A quadrant keeps its exact layout when the Enterprise leaves, and
+    /// is restored on the next visit instead of being placed anew.
+    pub persistent_quadrants: bool,
+    /// Surviving Klingons recover their shields a little every stardate,
+    /// up to what they started with.
+    pub klingon_regeneration: bool,
+}
+
+impl Ruleset {
+    /// The devices the ship carries. The shuttle bay is only fitted under
+    /// the shuttlecraft rule, so other games neither report nor damage it.
+    pub fn devices(&self) -> &'static [Device] {
+        if self.shuttlecraft {
+            &Device::ALL
+        } else {
+            &Device::ALL[..Device::ShuttleBay as usize]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_fixed_thirty_stardates() {
+        let rules = Ruleset::default();
+        assert_eq!(rules.mission_duration.stardates(5), 30.0);
+        assert_eq!(rules.mission_duration.stardates(50), 30.0);
+    }
+
+    #[test]
+    fn scaled_duration_grows_past_minimum() {
+        assert_eq!(MissionDuration::SCALED.stardates(17), 30.0);
+        assert_eq!(MissionDuration::SCALED.stardates(29), 30.0);
+        assert_eq!(MissionDuration::SCALED.stardates(40), 41.0);
+    }
+
+    #[test]
+    fn formula_text() {
+        assert_eq!(MissionDuration::default().formula(), "30 STARDATES");
+        assert_eq!(MissionDuration::SCALED.formula(), "MAX(KLINGONS + 1, 30) STARDATES");
+    }
+
+    #[test]
+    fn the_shuttle_bay_is_only_fitted_under_its_rule() {
+        assert_eq!(Ruleset::default().devices().len(), 10);
+        assert!(!Ruleset::default().devices().contains(&Device::ShuttleBay));
+        let rules = Ruleset { shuttlecraft: true, ..Default::default() };
+        assert_eq!(rules.devices().last(), Some(&Device::ShuttleBay));
+    }
+}
@@ -0,0 +1,147 @@
+//! Scenarios
+//!
+//! Curated starting setups, picked with `--scenario`. Some are seeds known
+//! to generate a particular galaxy under the default rules; the rest are
+//! laid out by hand with a [`GalaxyBuilder`].
+
+use super::galaxy::{Galaxy, GalaxyBuilder, GalaxyOptions};
+use super::grid::QuadrantGrid;
+use super::position::{QuadrantPosition, SectorPosition};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Scenario {
+    pub name: &'static str,
+    /// Shown to the player before the mission starts.
+    pub briefing: &'static str,
+    setup: Setup,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Setup {
+    /// The galaxy this seed generates.
+    Seed(u64),
+    /// A hand-made galaxy; the game's seed still places things within
+    /// each quadrant.
+    Layout(fn(GalaxyBuilder) -> GalaxyBuilder),
+}
+
+pub const SCENARIOS: [Scenario; 3] = [
+    Scenario {
+        name: "Training Cruise",
+        briefing: "THREE KLINGONS, THREE STARBASES, AND ONE OF THEM NEXT DOOR. TAKE YOUR TIME.",
+        setup: Setup::Seed(528),
+    },
+    Scenario {
+        name: "Outnumbered",
+        briefing: "TWENTY-SEVEN KLINGONS, ONE STARBASE ACROSS THE GALAXY, AND THREE OF THEM ALREADY HERE.",
+        setup: Setup::Layout(outnumbered),
+    },
+    Scenario {
+        name: "Last Starbase",
+        briefing: "ONE STARBASE IS LEFT, AND SIXTEEN KLINGONS ARE CLOSING IN ON IT.",
+        setup: Setup::Layout(last_starbase),
+    },
+];
+
+impl Scenario {
+    /// Look a scenario up by name in any case, with hyphens for spaces,
+    /// e.g. `last-starbase`.
+    pub fn find(name: &str) -> Option<&'static Scenario> {
+        SCENARIOS.iter().find(|s| s.key() == name.trim().to_lowercase().replace(' ', "-"))
+    }
+
+    /// The name as `--scenario` takes it, e.g. `training-cruise`.
+    pub fn key(&self) -> String {
+        self.name.to_lowercase().replace(' ', "-")
+    }
+
+    /// The seed the scenario has to be played on; `None` if any will do.
+    pub fn seed(&self) -> Option<u64> {
+        match self.setup {
+            Setup::Seed(seed) => Some(seed),
+            Setup::Layout(_) => None,
+        }
+    }
+
+    /// Set the scenario's galaxy up under `options`. A scenario built from
+    /// a seed plays on its own seed, whatever `seed` is, and only matches
+    /// its briefing under the default ruleset, which the command line
+    /// insists on.
+    pub fn galaxy(&self, seed: u64, options: GalaxyOptions) -> Galaxy {
+        match self.setup {
+            Setup::Seed(seed) => Galaxy::with_options(seed, options),
+            Setup::Layout(layout) => {
+                // The layouts stay within the galaxy limits
+                layout(GalaxyBuilder::new(seed).options(options)).build().unwrap()
+            }
+        }
+    }
+}
+
+/// A few stars in every quadrant, the same in every layout.
+fn stars(pos: QuadrantPosition) -> i32 {
+    (pos.x * 5 + pos.y * 3) % 7 + 1
+}
+
+/// Lay out every quadrant: `klingons` and `starbases` give the counts in
+/// each, the stars come from [`stars`].
+fn fill(
+    builder: GalaxyBuilder,
+    klingons: impl Fn(QuadrantPosition) -> i32,
+    starbases: impl Fn(QuadrantPosition) -> i32,
+) -> GalaxyBuilder {
+    QuadrantGrid::<()>::positions().fold(builder, |b, pos| b.quadrant(pos, klingons(pos), starbases(pos), stars(pos)))
+}
+
+fn outnumbered(builder: GalaxyBuilder) -> GalaxyBuilder {
+    const FULL: [(i32, i32); 9] = [(1, 8), (2, 4), (3, 7), (4, 2), (5, 5), (6, 1), (6, 8), (7, 3), (8, 6)];
+    let start = QuadrantPosition { x: 1, y: 8 };
+    fill(
+        builder,
+        |pos| if FULL.contains(&(pos.x, pos.y)) { 3 } else { 0 },
+        |pos| (pos == QuadrantPosition { x: 8, y: 1 }) as i32,
+    )
+    .start(start, SectorPosition { x: 4, y: 4 })
+    .mission_duration(35.0)
+}
+
+fn last_starbase(builder: GalaxyBuilder) -> GalaxyBuilder {
+    let starbase = QuadrantPosition { x: 5, y: 4 };
+    let ring = |pos: QuadrantPosition| pos != starbase && (pos.x - starbase.x).abs() <= 1 && (pos.y - starbase.y).abs() <= 1;
+    fill(builder, |pos| if ring(pos) { 2 } else { 0 }, |pos| (pos == starbase) as i32)
+        .start(QuadrantPosition { x: 1, y: 8 }, SectorPosition { x: 4, y: 4 })
+        .mission_duration(25.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenarios_are_found_by_name_or_key() {
+        for scenario in &SCENARIOS {
+            assert_eq!(Scenario::find(scenario.name).unwrap().name, scenario.name);
+            assert_eq!(Scenario::find(&scenario.key()).unwrap().name, scenario.name);
+        }
+        assert_eq!(Scenario::find("LAST STARBASE").unwrap().key(), "last-starbase");
+        assert!(Scenario::find("kobayashi-maru").is_none());
+    }
+
+    #[test]
+    fn scenarios_set_up_what_their_briefings_promise() {
+        let galaxy = |name: &str| Scenario::find(name).unwrap().galaxy(1, GalaxyOptions::default());
+
+        let training = galaxy("training-cruise");
+        assert_eq!((training.total_klingons(), training.total_starbases()), (3, 3));
+        assert!(training.sector_map().starbase.is_some());
+        assert!(training.sector_map().klingons.is_empty());
+
+        let outnumbered = galaxy("outnumbered");
+        assert_eq!((outnumbered.total_klingons(), outnumbered.total_starbases()), (27, 1));
+        assert_eq!(outnumbered.sector_map().klingons.len(), 3);
+
+        let last = galaxy("last-starbase");
+        assert_eq!((last.total_klingons(), last.total_starbases()), (16, 1));
+        assert_eq!(last.mission_duration(), 25.0);
+    }
+}
@@ -1,16 +1,36 @@
-use super::constants::{SectorContent, SECTOR_SIZE, MAX_KLINGONS_PER_QUADRANT};
+use super::constants::{SectorContent, SymbolSet, SECTOR_SIZE, MAX_KLINGONS_PER_QUADRANT};
+use super::grid::SectorGrid;
 use super::klingon::Klingon;
 use super::position::SectorPosition;
+use super::romulan::Romulan;
+use super::tholian::Tholian;
+
+/// A single cell that differs between two sector maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectorChange {
+    pub pos: SectorPosition,
+    pub from: SectorContent,
+    pub to: SectorContent,
+}
 
 /// The 8x8 sector grid for the current quadrant.
-/// Regenerated every time the Enterprise enters a quadrant.
+/// Regenerated every time the Enterprise enters a quadrant, unless the
+/// persistent quadrants rule kept the last one.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SectorMap {
-    /// 8x8 grid of sector contents. Internal 0-based indexing: grid[y-1][x-1].
-    grid: [[SectorContent; SECTOR_SIZE]; SECTOR_SIZE],
+    /// 8x8 grid of sector contents.
+    grid: SectorGrid<SectorContent>,
     /// Active Klingons in this quadrant (up to 3).
     pub klingons: Vec<Klingon>,
     /// Position of the starbase in this quadrant, if any.
     pub starbase: Option<SectorPosition>,
+    /// Romulan warbirds in this quadrant, cloaked or not.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub romulans: Vec<Romulan>,
+    /// The Tholian spinning its web here, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tholian: Option<Tholian>,
 }
 
 impl Default for SectorMap {
@@ -22,20 +42,51 @@ impl Default for SectorMap {
 impl SectorMap {
     pub fn new() -> Self {
         SectorMap {
-            grid: [[SectorContent::Empty; SECTOR_SIZE]; SECTOR_SIZE],
+            grid: SectorGrid::filled(SectorContent::Empty),
             klingons: Vec::with_capacity(MAX_KLINGONS_PER_QUADRANT),
             starbase: None,
+            romulans: Vec::new(),
+            tholian: None,
         }
     }
 
+    /// Whether any living enemy ship here can fire on the Enterprise:
+    /// a Klingon, or a Romulan whether cloaked or not.
+    pub fn hostiles_present(&self) -> bool {
+        self.klingons.iter().any(|k| k.is_alive()) || self.romulans.iter().any(|r| r.is_alive())
+    }
+
+    /// Living Romulans that have dropped their cloak, and so show on the
+    /// sensors and can be targeted.
+    pub fn visible_romulans(&self) -> impl Iterator<Item = &Romulan> {
+        self.romulans.iter().filter(|r| r.is_alive() && !r.cloaked)
+    }
+
     /// Get the content at a 1-based sector position.
     pub fn get(&self, pos: SectorPosition) -> SectorContent {
-        self.grid[(pos.y - 1) as usize][(pos.x - 1) as usize]
+        self.grid[pos]
     }
 
     /// Set the content at a 1-based sector position.
     pub fn set(&mut self, pos: SectorPosition, content: SectorContent) {
-        self.grid[(pos.y - 1) as usize][(pos.x - 1) as usize] = content;
+        self.grid[pos] = content;
+    }
+
+    /// Every sector with its content, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (SectorPosition, SectorContent)> + '_ {
+        self.grid.cells().map(|(pos, &content)| (pos, content))
+    }
+
+    /// The content the sensors show at a 1-based sector position: a cloaked
+    /// Romulan or a mine looks like empty space.
+    pub fn visible(&self, pos: SectorPosition) -> SectorContent {
+        match self.get(pos) {
+            SectorContent::Romulan if self.romulans.iter().any(|r| r.sector == pos && r.cloaked) => {
+                SectorContent::Empty
+            }
+            SectorContent::Mine => SectorContent::Empty,
+            content => content,
+        }
     }
 
     /// Check if a 1-based sector position is empty.
@@ -43,11 +94,137 @@ impl SectorMap {
         self.get(pos) == SectorContent::Empty
     }
 
-    /// Render a row of the sector grid as a 24-character string.
-    /// y is 1-based (1-8).
-    pub fn render_row(&self, y: i32) -> String {
+    /// Render a row of the sector grid in `symbols`, 24 characters wide
+    /// in the three-column sets. y is 1-based (1-8). Mines show only when
+    /// `show_mines` is set.
+    pub fn render_row(&self, y: i32, show_mines: bool, symbols: SymbolSet) -> String {
+        self.render_row_styled(y, show_mines, symbols, |_, symbol| symbol.to_string())
+    }
+
+    /// [`render_row`](Self::render_row), with each cell's symbol passed
+    /// through `style` along with what the cell shows, e.g. to color it.
+    pub fn render_row_styled(
+        &self,
+        y: i32,
+        show_mines: bool,
+        symbols: SymbolSet,
+        style: impl Fn(SectorContent, &str) -> String,
+    ) -> String {
+        self.visible_row(y, show_mines)
+            .into_iter()
+            .map(|content| style(content, content.symbol_in(symbols)))
+            .collect()
+    }
+
+    /// What the sensors show along a row, west to east. y is 1-based (1-8).
+    /// Mines show only when `show_mines` is set.
+    pub fn visible_row(&self, y: i32, show_mines: bool) -> Vec<SectorContent> {
         (1..=SECTOR_SIZE as i32)
-            .map(|x| self.get(SectorPosition { x, y }).symbol())
+            .map(|x| {
+                let pos = SectorPosition { x, y };
+                match self.get(pos) {
+                    SectorContent::Mine if show_mines => SectorContent::Mine,
+                    _ => self.visible(pos),
+                }
+            })
+            .collect()
+    }
+
+    /// List the cells that differ from `self` to `other`, in row-major order.
+    /// Frontends use this to animate a command's effect instead of redrawing
+    /// the whole grid.
+    pub fn diff(&self, other: &SectorMap) -> Vec<SectorChange> {
+        self.iter()
+            .zip(other.grid.iter())
+            .filter(|&((_, from), &to)| from != to)
+            .map(|((pos, from), &to)| SectorChange { pos, from, to })
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_visits_every_sector_in_row_major_order() {
+        let mut map = SectorMap::new();
+        map.set(SectorPosition { x: 2, y: 1 }, SectorContent::Star);
+        let cells: Vec<_> = map.iter().collect();
+        assert_eq!(cells.len(), SECTOR_SIZE * SECTOR_SIZE);
+        assert_eq!(cells[1], (SectorPosition { x: 2, y: 1 }, SectorContent::Star));
+        assert_eq!(cells[SECTOR_SIZE].0, SectorPosition { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn identical_maps_have_no_diff() {
+        let mut map = SectorMap::new();
+        map.set(SectorPosition { x: 3, y: 5 }, SectorContent::Star);
+        assert!(map.diff(&map.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_movement_in_row_major_order() {
+        let mut before = SectorMap::new();
+        before.set(SectorPosition { x: 6, y: 2 }, SectorContent::Enterprise);
+        let mut after = before.clone();
+        after.set(SectorPosition { x: 6, y: 2 }, SectorContent::Empty);
+        after.set(SectorPosition { x: 1, y: 2 }, SectorContent::Enterprise);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                SectorChange {
+                    pos: SectorPosition { x: 1, y: 2 },
+                    from: SectorContent::Empty,
+                    to: SectorContent::Enterprise,
+                },
+                SectorChange {
+                    pos: SectorPosition { x: 6, y: 2 },
+                    from: SectorContent::Enterprise,
+                    to: SectorContent::Empty,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cloaked_romulans_render_as_empty_space() {
+        let mut map = SectorMap::new();
+        let pos = SectorPosition { x: 3, y: 1 };
+        map.set(pos, SectorContent::Romulan);
+        map.romulans.push(Romulan::new(pos, 300.0));
+        assert_eq!(map.get(pos), SectorContent::Romulan);
+        assert_eq!(map.visible(pos), SectorContent::Empty);
+        assert_eq!(map.render_row(1, true, SymbolSet::Classic), " ".repeat(24));
+
+        map.romulans[0].cloaked = false;
+        assert_eq!(map.visible(pos), SectorContent::Romulan);
+        assert_eq!(&map.render_row(1, true, SymbolSet::Classic)[6..9], "+R+");
+    }
+
+    #[test]
+    fn mines_show_only_when_asked_for() {
+        let mut map = SectorMap::new();
+        let pos = SectorPosition { x: 2, y: 1 };
+        map.set(pos, SectorContent::Mine);
+        assert_eq!(map.visible(pos), SectorContent::Empty);
+        assert_eq!(map.render_row(1, false, SymbolSet::Classic), " ".repeat(24));
+        assert_eq!(&map.render_row(1, true, SymbolSet::Classic)[3..6], " x ");
+    }
+
+    #[test]
+    fn every_symbol_set_keeps_rows_aligned() {
+        let mut map = SectorMap::new();
+        map.set(SectorPosition { x: 1, y: 1 }, SectorContent::Enterprise);
+        map.set(SectorPosition { x: 4, y: 1 }, SectorContent::Star);
+        map.set(SectorPosition { x: 8, y: 1 }, SectorContent::KlingonCommander);
+        for symbols in [SymbolSet::Classic, SymbolSet::Unicode, SymbolSet::Compact] {
+            let row = map.render_row(1, true, symbols);
+            assert_eq!(row.chars().count(), SECTOR_SIZE * symbols.cell_width());
+        }
+        assert_eq!(map.render_row(1, true, SymbolSet::Compact), "E..*...C");
+        assert_eq!(SymbolSet::parse(" Unicode "), Some(SymbolSet::Unicode));
+        assert_eq!(SymbolSet::parse("emoji"), None);
+    }
+}
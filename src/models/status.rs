@@ -0,0 +1,146 @@
+//! Structured status report
+//!
+//! A snapshot of mission status plus derived tactical metrics. The library
+//! computer's status report, hints, and bots all read the same numbers from
+//! here rather than recomputing them.
+
+use super::galaxy::Galaxy;
+use super::pending::PendingEvent;
+use super::position::QuadrantPosition;
+use super::quadrant::QuadrantData;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusReport {
+    pub klingons_left: i32,
+    pub stardates_left: f64,
+    pub starbases_left: i32,
+    /// Turns completed so far.
+    pub turns: u32,
+    /// Stardates charged for commands other than moves.
+    pub command_time: f64,
+    /// Distance in quadrants to the nearest quadrant the computer knows has
+    /// Klingons; `None` if no such quadrant has been scanned.
+    pub nearest_klingon_distance: Option<f64>,
+    /// Distance in quadrants to the nearest known starbase quadrant.
+    pub nearest_starbase_distance: Option<f64>,
+    /// Ship energy (including shields) available per remaining Klingon.
+    pub energy_per_klingon: Option<f64>,
+    /// Stardates the mission can spend per remaining Klingon.
+    pub stardates_per_klingon: Option<f64>,
+    /// Percentage of quadrants the Enterprise has entered.
+    pub exploration_percent: f64,
+    /// Starbases under attack, with the stardates each has left.
+    pub starbases_under_attack: Vec<(QuadrantPosition, f64)>,
+}
+
+impl StatusReport {
+    pub fn from_galaxy(galaxy: &Galaxy) -> Self {
+        let klingons_left = galaxy.total_klingons();
+        let stardates_left =
+            (galaxy.starting_stardate() + galaxy.mission_duration()) - galaxy.stardate();
+        let enterprise = galaxy.enterprise();
+        let per_klingon = |amount: f64| (klingons_left > 0).then(|| amount / klingons_left as f64);
+
+        StatusReport {
+            klingons_left,
+            stardates_left,
+            starbases_left: galaxy.total_starbases(),
+            turns: galaxy.turns(),
+            command_time: galaxy.command_time(),
+            nearest_klingon_distance: nearest_known(galaxy, |q| q.klingons > 0),
+            nearest_starbase_distance: nearest_known(galaxy, |q| q.starbases > 0),
+            energy_per_klingon: per_klingon(enterprise.energy() + enterprise.shields()),
+            stardates_per_klingon: per_klingon(stardates_left),
+            exploration_percent: galaxy.exploration_percent(),
+            starbases_under_attack: galaxy
+                .pending_events()
+                .iter()
+                .map(|event| match *event {
+                    PendingEvent::StarbaseAttack { quadrant, deadline } => (quadrant, deadline - galaxy.stardate()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Distance from the Enterprise's quadrant to the nearest quadrant in
+/// computer memory matching `wanted`.
+fn nearest_known(galaxy: &Galaxy, wanted: impl Fn(&QuadrantData) -> bool) -> Option<f64> {
+    let here = galaxy.enterprise().quadrant();
+    galaxy
+        .scanned_quadrants()
+        .filter(|(_, data)| wanted(data))
+        .map(|(pos, _)| quadrant_distance(here, pos))
+        .min_by(f64::total_cmp)
+}
+
+fn quadrant_distance(from: QuadrantPosition, to: QuadrantPosition) -> f64 {
+    let dx = (to.x - from.x) as f64;
+    let dy = (to.y - from.y) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::grid::QuadrantGrid;
+
+    fn galaxy_with_memory(entries: &[(i32, i32, QuadrantData)]) -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(None);
+        for &(x, y, data) in entries {
+            galaxy.computer_memory_mut()[QuadrantPosition { x, y }] = Some(data);
+        }
+        galaxy
+    }
+
+    #[test]
+    fn counts_match_galaxy() {
+        let galaxy = Galaxy::new(42);
+        let report = StatusReport::from_galaxy(&galaxy);
+        assert_eq!(report.klingons_left, galaxy.total_klingons());
+        assert_eq!(report.starbases_left, galaxy.total_starbases());
+        assert_eq!(report.exploration_percent, galaxy.exploration_percent());
+        assert_eq!((report.turns, report.command_time), (0, 0.0));
+        assert_eq!(report.stardates_left, galaxy.mission_duration());
+        assert_eq!(
+            report.stardates_per_klingon,
+            Some(galaxy.mission_duration() / galaxy.total_klingons() as f64)
+        );
+    }
+
+    #[test]
+    fn nearest_distances_use_only_known_quadrants() {
+        let empty = galaxy_with_memory(&[]);
+        let report = StatusReport::from_galaxy(&empty);
+        assert_eq!(report.nearest_klingon_distance, None);
+        assert_eq!(report.nearest_starbase_distance, None);
+
+        let here = empty.enterprise().quadrant();
+        let far_x = if here.x > 4 { 1 } else { 8 };
+        let klingons = QuadrantData { klingons: 2, starbases: 0, stars: 1, commanders: 0, romulans: 0, mines: 0 };
+        let starbase = QuadrantData { klingons: 0, starbases: 1, stars: 1, commanders: 0, romulans: 0, mines: 0 };
+        let galaxy = galaxy_with_memory(&[
+            (far_x, here.y, klingons),
+            (here.x, here.y, klingons),
+            (far_x, here.y, starbase),
+        ]);
+        let report = StatusReport::from_galaxy(&galaxy);
+        assert_eq!(report.nearest_klingon_distance, Some(0.0));
+        assert_eq!(report.nearest_starbase_distance, Some((far_x - here.x).abs() as f64));
+    }
+
+    #[test]
+    fn per_klingon_budgets_are_none_when_no_klingons_remain() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().set_energy(2000.0);
+        galaxy.enterprise_mut().set_shields(1000.0);
+        galaxy.set_total_klingons(3);
+        assert_eq!(StatusReport::from_galaxy(&galaxy).energy_per_klingon, Some(1000.0));
+
+        galaxy.set_total_klingons(0);
+        let report = StatusReport::from_galaxy(&galaxy);
+        assert_eq!(report.energy_per_klingon, None);
+        assert_eq!(report.stardates_per_klingon, None);
+    }
+}
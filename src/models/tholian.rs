@@ -0,0 +1,56 @@
+use super::constants::SECTOR_SIZE;
+use super::position::SectorPosition;
+
+/// A Tholian within a quadrant's sector grid. It crawls clockwise around
+/// the edge of the quadrant, spinning web behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tholian {
+    pub sector: SectorPosition,
+}
+
+impl Tholian {
+    pub fn new(sector: SectorPosition) -> Self {
+        Tholian { sector }
+    }
+    /// The next edge sector clockwise: right along the top row, down the
+    /// right column, left along the bottom row, and up the left column.
+    pub fn next_sector(&self) -> SectorPosition {
+        let last = SECTOR_SIZE as i32;
+        let SectorPosition { x, y } = self.sector;
+        if y == 1 && x < last {
+            SectorPosition { x: x + 1, y }
+        } else if x == last && y < last {
+            SectorPosition { x, y: y + 1 }
+        } else if y == last && x > 1 {
+            SectorPosition { x: x - 1, y }
+        } else {
+            SectorPosition { x, y: y - 1 }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tholians_circle_the_edge_clockwise() {
+        let start = SectorPosition { x: 1, y: 1 };
+        let mut tholian = Tholian::new(start);
+        let mut path = Vec::new();
+        loop {
+            tholian.sector = tholian.next_sector();
+            path.push(tholian.sector);
+            if tholian.sector == start {
+                break;
+            }
+        }
+        assert_eq!(path.len(), 28);
+        assert_eq!(path[0], SectorPosition { x: 2, y: 1 });
+        assert_eq!(path[7], SectorPosition { x: 8, y: 2 });
+        assert_eq!(path[14], SectorPosition { x: 7, y: 8 });
+        assert_eq!(path[21], SectorPosition { x: 1, y: 7 });
+        assert!(path.iter().all(|p| p.x == 1 || p.x == 8 || p.y == 1 || p.y == 8));
+    }
+}
@@ -0,0 +1,107 @@
+//! Turn summaries
+//!
+//! One consolidated record per command the player issues, so dashboards and
+//! agents can consume a single step record instead of parsing output.
+
+use super::galaxy::Galaxy;
+
+/// The parts of the game state a [`TurnSummary`] reports changes in,
+/// captured before a command runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnSnapshot {
+    stardate: f64,
+    energy: f64,
+    device_damage: f64,
+    hull: f64,
+    klingons: i32,
+}
+
+impl TurnSnapshot {
+    pub fn capture(galaxy: &Galaxy) -> Self {
+        let e = galaxy.enterprise();
+        TurnSnapshot {
+            stardate: galaxy.stardate(),
+            energy: e.energy() + e.shields(),
+            device_damage: e.devices().iter().map(|d| (-d).max(0.0)).sum(),
+            hull: e.hull(),
+            klingons: galaxy.total_klingons(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnSummary {
+    /// 1 for the first command of the game.
+    pub turn: u32,
+    /// Name of the command issued, e.g. "FIRE PHASERS".
+    pub command: String,
+    /// Stardate at the end of the turn.
+    pub stardate: f64,
+    /// Change in energy plus shields.
+    pub energy_delta: f64,
+    /// Change in total device damage; positive means more damaged.
+    pub damage_delta: f64,
+    /// Change in hull integrity.
+    pub hull_delta: f64,
+    pub klingons_destroyed: i32,
+    pub time_delta: f64,
+}
+
+impl TurnSummary {
+    /// Summarize what changed between `before` and the galaxy as it is now.
+    pub fn between(turn: u32, command: &str, before: &TurnSnapshot, galaxy: &Galaxy) -> Self {
+        let after = TurnSnapshot::capture(galaxy);
+        TurnSummary {
+            turn,
+            command: command.to_string(),
+            stardate: after.stardate,
+            energy_delta: after.energy - before.energy,
+            damage_delta: after.device_damage - before.device_damage,
+            hull_delta: after.hull - before.hull,
+            klingons_destroyed: before.klingons - after.klingons,
+            time_delta: after.stardate - before.stardate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::constants::Device;
+    use crate::models::position::SectorPosition;
+
+    #[test]
+    fn quiet_turn_has_no_deltas() {
+        let galaxy = Galaxy::new(42);
+        let before = TurnSnapshot::capture(&galaxy);
+        let summary = TurnSummary::between(1, "SHORT RANGE SENSOR SCAN", &before, &galaxy);
+        assert_eq!(summary.turn, 1);
+        assert_eq!(summary.command, "SHORT RANGE SENSOR SCAN");
+        assert_eq!(summary.stardate, galaxy.stardate());
+        assert_eq!(summary.energy_delta, 0.0);
+        assert_eq!(summary.damage_delta, 0.0);
+        assert_eq!(summary.hull_delta, 0.0);
+        assert_eq!(summary.klingons_destroyed, 0);
+        assert_eq!(summary.time_delta, 0.0);
+    }
+
+    #[test]
+    fn deltas_reflect_changes_during_turn() {
+        let mut galaxy = Galaxy::new(42);
+        let before = TurnSnapshot::capture(&galaxy);
+
+        galaxy.enterprise_mut().subtract_energy(150.0);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 2.0);
+        galaxy.enterprise_mut().repair_device(Device::WarpEngines, 1.0);
+        galaxy.enterprise_mut().damage_hull(10.0);
+        galaxy.destroy_klingon(SectorPosition { x: 1, y: 1 }).unwrap();
+        galaxy.advance_time(1.0);
+
+        let summary = TurnSummary::between(3, "FIRE PHASERS", &before, &galaxy);
+        assert_eq!(summary.energy_delta, -150.0);
+        assert_eq!(summary.damage_delta, 2.0, "improved devices do not offset damage");
+        assert_eq!(summary.hull_delta, -10.0);
+        assert_eq!(summary.klingons_destroyed, 1);
+        assert_eq!(summary.time_delta, 1.0);
+    }
+}
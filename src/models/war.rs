@@ -0,0 +1,124 @@
+//! War progress
+//!
+//! How the Federation-Klingon war is going, computed from galaxy
+//! aggregates. Shown by the library computer and in the final summary.
+
+use super::galaxy::Galaxy;
+
+/// Destroyed and elapsed fractions within this margin count as even.
+const OUTLOOK_MARGIN: f64 = 0.1;
+
+/// Which side the war is going for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarOutlook {
+    /// Klingons are falling faster than time is running out.
+    FederationAdvancing,
+    Stalemate,
+    /// Time is running out faster than Klingons are falling.
+    KlingonsAdvancing,
+}
+
+impl WarOutlook {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WarOutlook::FederationAdvancing => "FEDERATION ADVANCING",
+            WarOutlook::Stalemate => "STALEMATE",
+            WarOutlook::KlingonsAdvancing => "KLINGONS ADVANCING",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarStatus {
+    pub klingons_destroyed: i32,
+    /// Klingons faced, reinforcements included.
+    pub klingons_initial: i32,
+    /// Quadrants the Enterprise has emptied of Klingons.
+    pub quadrants_cleared: i32,
+    /// Quadrants that still hold Klingons.
+    pub quadrants_occupied: i32,
+    pub starbases_remaining: i32,
+    pub starbases_lost: i32,
+    /// Fraction of the mission's stardates still left, 0.0-1.0.
+    pub time_remaining_ratio: f64,
+}
+
+impl WarStatus {
+    pub fn from_galaxy(galaxy: &Galaxy) -> Self {
+        let deadline = galaxy.starting_stardate() + galaxy.mission_duration();
+        let time_remaining_ratio =
+            ((deadline - galaxy.stardate()) / galaxy.mission_duration()).clamp(0.0, 1.0);
+
+        WarStatus {
+            klingons_destroyed: galaxy.klingons_faced() - galaxy.total_klingons(),
+            klingons_initial: galaxy.klingons_faced(),
+            quadrants_cleared: galaxy.quadrants_cleared(),
+            quadrants_occupied: galaxy
+                .quadrants()
+                .iter()
+                .filter(|q| q.klingons > 0)
+                .count() as i32,
+            starbases_remaining: galaxy.total_starbases(),
+            starbases_lost: galaxy.starbases_lost(),
+            time_remaining_ratio,
+        }
+    }
+
+    /// Fraction of the invasion force destroyed, 0.0-1.0.
+    pub fn klingons_destroyed_ratio(&self) -> f64 {
+        if self.klingons_initial > 0 {
+            self.klingons_destroyed as f64 / self.klingons_initial as f64
+        } else {
+            1.0
+        }
+    }
+
+    /// Compare the share of Klingons destroyed with the share of time used.
+    pub fn outlook(&self) -> WarOutlook {
+        let lead = self.klingons_destroyed_ratio() - (1.0 - self.time_remaining_ratio);
+        if lead > OUTLOOK_MARGIN {
+            WarOutlook::FederationAdvancing
+        } else if lead < -OUTLOOK_MARGIN {
+            WarOutlook::KlingonsAdvancing
+        } else {
+            WarOutlook::Stalemate
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_game_is_a_stalemate_with_all_time_left() {
+        let galaxy = Galaxy::new(42);
+        let status = galaxy.war_status();
+        assert_eq!(status.klingons_destroyed, 0);
+        assert_eq!(status.klingons_initial, galaxy.initial_klingons());
+        assert_eq!(status.quadrants_cleared, 0);
+        assert!(status.quadrants_occupied > 0);
+        assert_eq!(status.starbases_lost, 0);
+        assert_eq!(status.time_remaining_ratio, 1.0);
+        assert_eq!(status.outlook(), WarOutlook::Stalemate);
+    }
+
+    #[test]
+    fn outlook_compares_kills_with_time_used() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.set_initial_klingons(10);
+        galaxy.set_total_klingons(5);
+        galaxy.advance_time(galaxy.mission_duration() * 0.2);
+        assert_eq!(galaxy.war_status().outlook(), WarOutlook::FederationAdvancing);
+
+        galaxy.advance_time(galaxy.mission_duration() * 0.6);
+        assert_eq!(galaxy.war_status().outlook(), WarOutlook::KlingonsAdvancing);
+    }
+
+    #[test]
+    fn time_ratio_is_clamped_after_deadline() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.advance_time(galaxy.mission_duration() * 2.0);
+        assert_eq!(galaxy.war_status().time_remaining_ratio, 0.0);
+    }
+}
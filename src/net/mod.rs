@@ -0,0 +1,7 @@
+//! Networking (requires the `net` feature)
+//!
+//! Optional online features. Everything here uses plain `std::net`, so the
+//! feature adds no dependencies.
+
+pub mod scores;
+pub mod server;
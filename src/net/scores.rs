@@ -0,0 +1,432 @@
+//! Score submission
+//!
+//! POSTs end-of-game results to a community leaderboard as JSON. When the
+//! leaderboard cannot be reached or answers with a server error, results are
+//! appended to an offline queue file and sent with the next successful
+//! submission. A result the leaderboard refuses outright (a 4xx response)
+//! would be refused again, so it is dropped rather than queued.
+//!
+//! Only plain `http://` URLs are supported; put a TLS-terminating proxy in
+//! front of the leaderboard if it must be reached over HTTPS.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::game_engine::GameEngine;
+
+/// Queue file used when no other path is configured.
+pub const DEFAULT_QUEUE_PATH: &str = "score-queue.jsonl";
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// End-of-game result as sent to the leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreSubmission {
+    pub seed: u64,
+    /// "VICTORY", or the defeat reason.
+    pub outcome: &'static str,
+    /// Efficiency rating; 0 for a defeat.
+    pub rating: i32,
+    pub klingons_destroyed: i32,
+    pub klingons_initial: i32,
+    pub starbases_remaining: i32,
+    pub stardates_used: f64,
+    /// `Galaxy::state_digest` of the final state.
+    pub state_digest: u64,
+    /// Digest of every command entered during the game.
+    pub replay_hash: u64,
+    /// `GameEngine::tournament_seal`, for tournament games only.
+    pub tournament_seal: Option<u64>,
+}
+
+impl ScoreSubmission {
+    /// Build a submission from a finished game. Returns `None` while the game
+    /// is still being played.
+    pub fn from_engine(engine: &GameEngine, replay_hash: u64) -> Option<Self> {
+        let report = engine.game_over_report()?;
+        let galaxy = engine.galaxy();
+        Some(ScoreSubmission {
+            seed: galaxy.seed(),
+            outcome: report.outcome_label(),
+            rating: report.rating.unwrap_or(0),
+            klingons_destroyed: report.klingons_destroyed(),
+            klingons_initial: report.klingons_initial,
+            starbases_remaining: report.starbases_remaining,
+            stardates_used: report.stardates_used,
+            state_digest: galaxy.state_digest(),
+            replay_hash,
+            tournament_seal: engine.tournament_seal(replay_hash),
+        })
+    }
+
+    /// Single-line JSON body. Digests are hex strings because JSON numbers
+    /// cannot hold every u64 exactly.
+    /// The tournament seal is only present for tournament games.
+    pub fn to_json(&self) -> String {
+        let seal = match self.tournament_seal {
+            Some(seal) => format!(",\"tournament_seal\":\"{:016x}\"", seal),
+            None => String::new(),
+        };
+        format!(
+            concat!(
+                "{{\"seed\":{},\"outcome\":\"{}\",\"score\":{{\"rating\":{},",
+                "\"klingons_destroyed\":{},\"klingons_initial\":{},",
+                "\"starbases_remaining\":{},\"stardates_used\":{:.1}}},",
+                "\"state_digest\":\"{:016x}\",\"replay_hash\":\"{:016x}\"{}}}"
+            ),
+            self.seed,
+            self.outcome,
+            self.rating,
+            self.klingons_destroyed,
+            self.klingons_initial,
+            self.starbases_remaining,
+            self.stardates_used,
+            self.state_digest,
+            self.replay_hash,
+            seal,
+        )
+    }
+}
+
+/// What happened to a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// Sent, along with this many previously queued results.
+    Sent { flushed: usize },
+    /// The leaderboard was unreachable; the result was queued.
+    Queued,
+    /// The leaderboard refused the result with this status line; it was
+    /// not queued.
+    Rejected { status: String },
+}
+
+/// How the leaderboard answered a result it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Response {
+    /// A 2xx response.
+    Accepted,
+    /// Any other response short of a server error, with its status line.
+    Rejected(String),
+}
+
+/// Posts results to a leaderboard URL, queueing them while offline.
+#[derive(Debug, Clone)]
+pub struct ScoreSubmitter {
+    url: String,
+    queue_path: PathBuf,
+}
+
+impl ScoreSubmitter {
+    pub fn new(url: &str) -> Self {
+        Self::with_queue(url, DEFAULT_QUEUE_PATH)
+    }
+
+    pub fn with_queue(url: &str, queue_path: impl AsRef<Path>) -> Self {
+        ScoreSubmitter {
+            url: url.to_string(),
+            queue_path: queue_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Send queued results, then this one. If the network is down or the
+    /// leaderboard has a server error the result is queued instead; if the
+    /// leaderboard refuses it, it is dropped. Only failures to write the
+    /// queue are errors.
+    pub fn submit(&self, submission: &ScoreSubmission) -> io::Result<SubmitOutcome> {
+        let body = submission.to_json();
+        let flushed = match self.flush() {
+            Ok(flushed) => flushed,
+            Err(_) => {
+                self.enqueue(&body)?;
+                return Ok(SubmitOutcome::Queued);
+            }
+        };
+        match post_json(&self.url, &body) {
+            Ok(Response::Accepted) => Ok(SubmitOutcome::Sent { flushed }),
+            Ok(Response::Rejected(status)) => Ok(SubmitOutcome::Rejected { status }),
+            Err(_) => {
+                self.enqueue(&body)?;
+                Ok(SubmitOutcome::Queued)
+            }
+        }
+    }
+
+    /// Number of results waiting in the offline queue.
+    pub fn queued(&self) -> io::Result<usize> {
+        Ok(self.read_queue()?.len())
+    }
+
+    /// Send every queued result in order, returning how many left the
+    /// queue. Results the leaderboard refuses are logged and dropped; the
+    /// first transport or server error stops the flush and keeps the unsent
+    /// results queued.
+    pub fn flush(&self) -> io::Result<usize> {
+        let pending = self.read_queue()?;
+        for (sent, body) in pending.iter().enumerate() {
+            match post_json(&self.url, body) {
+                Ok(Response::Accepted) => {}
+                Ok(Response::Rejected(status)) => {
+                    eprintln!("Warning: leaderboard rejected a queued score ({}); dropping it", status);
+                }
+                Err(e) => {
+                    self.write_queue(&pending[sent..])?;
+                    return Err(e);
+                }
+            }
+        }
+        if !pending.is_empty() {
+            fs::remove_file(&self.queue_path)?;
+        }
+        Ok(pending.len())
+    }
+
+    fn enqueue(&self, body: &str) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.queue_path)?;
+        writeln!(file, "{}", body)
+    }
+
+    fn read_queue(&self) -> io::Result<Vec<String>> {
+        match fs::read_to_string(&self.queue_path) {
+            Ok(contents) => Ok(contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn write_queue(&self, bodies: &[String]) -> io::Result<()> {
+        let mut contents = bodies.join("\n");
+        contents.push('\n');
+        fs::write(&self.queue_path, contents)
+    }
+}
+
+/// Split an `http://host[:port][/path]` URL into its address and path.
+fn parse_http_url(url: &str) -> io::Result<(String, String)> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported URL {} (only http:// is supported)", url),
+        )
+    })?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    if authority.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("URL {} has no host", url),
+        ));
+    }
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+    Ok((address, path.to_string()))
+}
+
+/// POST a JSON body. Transport failures and 5xx responses are errors, worth
+/// trying again later; anything else is the leaderboard's answer.
+fn post_json(url: &str, body: &str) -> io::Result<Response> {
+    let (address, path) = parse_http_url(url)?;
+    let host = address.trim_end_matches(":80");
+    let socket = address.to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("cannot resolve {}", address))
+    })?;
+
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    let status = status_line.split_whitespace().nth(1).unwrap_or("");
+    if status.starts_with('2') {
+        Ok(Response::Accepted)
+    } else if status.starts_with('5') || status.len() != 3 {
+        Err(io::Error::other(format!(
+            "leaderboard responded {}",
+            status_line.trim()
+        )))
+    } else {
+        Ok(Response::Rejected(status_line.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::io::Read;
+    use std::thread;
+
+    fn sample() -> ScoreSubmission {
+        ScoreSubmission {
+            seed: 42,
+            outcome: "VICTORY",
+            rating: 1250,
+            klingons_destroyed: 17,
+            klingons_initial: 17,
+            starbases_remaining: 2,
+            stardates_used: 13.6,
+            state_digest: 0xdead_beef,
+            replay_hash: 1,
+            tournament_seal: None,
+        }
+    }
+
+    fn temp_queue(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("startrek-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// Accept `count` requests, answer each with `status`, and return the bodies.
+    fn leaderboard(status: &'static str, count: usize) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/scores", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for _ in 0..count {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(String::from_utf8(body).unwrap());
+                write!(reader.get_mut(), "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn json_contains_score_breakdown_and_digests() {
+        assert_eq!(
+            sample().to_json(),
+            "{\"seed\":42,\"outcome\":\"VICTORY\",\"score\":{\"rating\":1250,\
+             \"klingons_destroyed\":17,\"klingons_initial\":17,\
+             \"starbases_remaining\":2,\"stardates_used\":13.6},\
+             \"state_digest\":\"00000000deadbeef\",\"replay_hash\":\"0000000000000001\"}"
+        );
+    }
+
+    #[test]
+    fn tournament_scores_carry_their_seal() {
+        let mut engine = GameEngine::new_tournament(42);
+        engine.galaxy_mut().set_total_klingons(0);
+        engine.check_game_over();
+        let submission = ScoreSubmission::from_engine(&engine, 7).unwrap();
+        let seal = engine.tournament_seal(7).unwrap();
+        assert_eq!(submission.tournament_seal, Some(seal));
+        assert!(submission.to_json().ends_with(&format!(",\"tournament_seal\":\"{:016x}\"}}", seal)));
+    }
+
+    #[test]
+    fn no_submission_while_playing() {
+        assert!(ScoreSubmission::from_engine(&GameEngine::new(42), 0).is_none());
+    }
+
+    #[test]
+    fn parses_http_urls() {
+        assert_eq!(
+            parse_http_url("http://scores.example:8080/api/submit").unwrap(),
+            ("scores.example:8080".to_string(), "/api/submit".to_string())
+        );
+        assert_eq!(
+            parse_http_url("http://scores.example").unwrap(),
+            ("scores.example:80".to_string(), "/".to_string())
+        );
+        assert!(parse_http_url("https://scores.example").is_err());
+        assert!(parse_http_url("http:///path").is_err());
+    }
+
+    #[test]
+    fn unreachable_leaderboard_queues_result() {
+        let queue = temp_queue("offline");
+        // Bind then drop a listener so the port is known to be closed.
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let submitter = ScoreSubmitter::with_queue(&format!("http://127.0.0.1:{}/", port), &queue);
+
+        assert_eq!(submitter.submit(&sample()).unwrap(), SubmitOutcome::Queued);
+        assert_eq!(submitter.submit(&sample()).unwrap(), SubmitOutcome::Queued);
+        assert_eq!(submitter.queued().unwrap(), 2);
+        fs::remove_file(&queue).unwrap();
+    }
+
+    #[test]
+    fn queued_results_are_sent_before_new_one() {
+        let queue = temp_queue("flush");
+        let mut older = sample();
+        older.seed = 7;
+        fs::write(&queue, format!("{}\n", older.to_json())).unwrap();
+
+        let (url, server) = leaderboard("200 OK", 2);
+        let submitter = ScoreSubmitter::with_queue(&url, &queue);
+        assert_eq!(submitter.submit(&sample()).unwrap(), SubmitOutcome::Sent { flushed: 1 });
+
+        assert_eq!(server.join().unwrap(), vec![older.to_json(), sample().to_json()]);
+        assert_eq!(submitter.queued().unwrap(), 0);
+    }
+
+    #[test]
+    fn rejected_submission_is_queued() {
+        let queue = temp_queue("rejected");
+        let (url, server) = leaderboard("503 Service Unavailable", 1);
+        let submitter = ScoreSubmitter::with_queue(&url, &queue);
+
+        assert_eq!(submitter.submit(&sample()).unwrap(), SubmitOutcome::Queued);
+        server.join().unwrap();
+        assert_eq!(submitter.queued().unwrap(), 1);
+        fs::remove_file(&queue).unwrap();
+    }
+
+    #[test]
+    fn refused_results_are_dropped_instead_of_blocking_the_queue() {
+        let queue = temp_queue("refused");
+        let mut older = sample();
+        older.seed = 7;
+        fs::write(&queue, format!("{}\n", older.to_json())).unwrap();
+
+        let (url, server) = leaderboard("400 Bad Request", 2);
+        let submitter = ScoreSubmitter::with_queue(&url, &queue);
+        assert_eq!(
+            submitter.submit(&sample()).unwrap(),
+            SubmitOutcome::Rejected { status: "HTTP/1.1 400 Bad Request".to_string() }
+        );
+
+        assert_eq!(server.join().unwrap(), vec![older.to_json(), sample().to_json()]);
+        assert_eq!(submitter.queued().unwrap(), 0);
+    }
+}
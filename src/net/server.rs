@@ -0,0 +1,466 @@
+//! REST API server
+//!
+//! `startrek serve --http` exposes a [`SessionManager`] over plain HTTP/1.1
+//! for integrations that cannot hold a WebSocket open. Every request gets a
+//! JSON response (a replay download is plain text) and the connection is
+//! closed afterwards.
+//!
+//! | Method   | Path                     | Action                                   |
+//! |----------|--------------------------|------------------------------------------|
+//! | `POST`   | `/games[?seed=N&clock=S]`| Create a game, optionally with S seconds on a chess clock; returns its opening |
+//! | `POST`   | `/games/{id}/commands`   | Run a command; body is the command line, then one line per prompt answer |
+//! | `GET`    | `/games/{id}`            | Status of a game                         |
+//! | `GET`    | `/games/{id}/events`     | Every turn played so far                 |
+//! | `GET`    | `/games/{id}/replay`     | Replay file for `--replay`               |
+//! | `DELETE` | `/games/{id}`            | End a game                               |
+//!
+//! The server holds at most [`MAX_SESSIONS`] games and handles at most
+//! [`MAX_CONNECTIONS`] requests at once; past either limit it answers 503.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::cli::replay::{Replay, ReplayHeader};
+use crate::game_engine::{GameEngine, GameState};
+use crate::models::galaxy::GalaxyOptions;
+use crate::models::turn::TurnSummary;
+use crate::services::session::{SessionId, SessionManager, StepResult};
+
+/// Address `serve --http` listens on when no other is given.
+pub const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8080";
+
+/// Most games the server holds at once.
+pub const MAX_SESSIONS: usize = 100;
+
+/// Most connections handled at once.
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// Largest request body accepted, in bytes.
+const MAX_BODY: usize = 64 * 1024;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A response ready to be written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Response {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }).to_string())
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            503 => "Service Unavailable",
+            _ => "Error",
+        }
+    }
+}
+
+/// Serve games until the listener fails. Each connection is handled on its
+/// own thread; the session manager keeps commands for one game in order.
+/// A connection arriving while [`MAX_CONNECTIONS`] are open is turned away.
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("LISTENING ON http://{}", listener.local_addr()?);
+    let manager = Arc::new(SessionManager::new());
+    let open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            open.fetch_sub(1, Ordering::SeqCst);
+            let _ = stream.set_write_timeout(Some(TIMEOUT));
+            let _ = write_response(&mut stream, &Response::error(503, "server is busy"));
+            continue;
+        }
+        let manager = Arc::clone(&manager);
+        let open = Arc::clone(&open);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &manager) {
+                eprintln!("Error: {}", e);
+            }
+            open.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, manager: &SessionManager) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" || line == "\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let response = if length > MAX_BODY {
+        Response::error(413, "request body is too large")
+    } else {
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        handle(manager, method, target, &String::from_utf8_lossy(&body))
+    };
+
+    write_response(&mut reader.into_inner(), &response)
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Route one request to the session manager.
+pub fn handle(manager: &SessionManager, method: &str, target: &str, body: &str) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (method, segments.as_slice()) {
+        ("POST", ["games"]) => {
+            if manager.len() >= MAX_SESSIONS {
+                return Response::error(503, "too many games in progress");
+            }
+            let seed = match query_param(query, "seed").map(str::parse::<u64>) {
+                Some(Ok(seed)) => seed,
+                Some(Err(_)) => return Response::error(400, "seed must be an integer"),
+                None => rand::random(),
+            };
+            let (id, opening) = match query_param(query, "clock").map(str::parse::<u64>) {
+                Some(Ok(secs)) => manager.create_timed(GameEngine::new(seed), Duration::from_secs(secs)),
+                Some(Err(_)) => return Response::error(400, "clock must be a number of seconds"),
+                None => manager.create(GameEngine::new(seed)),
+            };
+            Response::json(201, step_json(Some((id, seed)), &opening))
+        }
+        (_, ["games"]) => Response::error(405, "use POST to create a game"),
+        (method, ["games", id, rest @ ..]) => {
+            let Some(id) = id.parse::<SessionId>().ok().filter(|&id| manager.contains(id)) else {
+                return Response::error(404, "no such game");
+            };
+            match (method, rest) {
+                ("POST", ["commands"]) => {
+                    let input: Vec<&str> = body.lines().collect();
+                    match manager.step(id, &input) {
+                        Ok(result) => Response::json(200, step_json(None, &result)),
+                        Err(e) => Response::error(400, &e.to_string()),
+                    }
+                }
+                ("GET", []) => status(manager, id),
+                ("GET", ["events"]) => events(manager, id),
+                ("GET", ["replay"]) => replay(manager, id),
+                ("DELETE", []) => {
+                    manager.destroy(id);
+                    Response {
+                        status: 204,
+                        content_type: "application/json",
+                        body: String::new(),
+                    }
+                }
+                (_, [] | ["commands"] | ["events"] | ["replay"]) => {
+                    Response::error(405, "method not allowed")
+                }
+                _ => Response::error(404, "not found"),
+            }
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn status(manager: &SessionManager, id: SessionId) -> Response {
+    let Ok(snapshot) = manager.snapshot(id) else {
+        return Response::error(404, "no such game");
+    };
+    let status = &snapshot.status;
+    let body = StatusJson {
+        id: snapshot.id,
+        seed: snapshot.seed,
+        state: state_label(&snapshot.state),
+        turns: snapshot.turns,
+        stardate: tenths(snapshot.stardate),
+        klingons_left: status.klingons_left,
+        stardates_left: tenths(status.stardates_left),
+        starbases_left: status.starbases_left,
+        exploration_percent: tenths(status.exploration_percent),
+        clock_remaining: snapshot.clock_remaining.map(|remaining| tenths(remaining.as_secs_f64())),
+        state_digest: format!("{:016x}", snapshot.state_digest),
+    };
+    Response::json(200, serde_json::to_string(&body).expect("status bodies always serialize"))
+}
+
+fn events(manager: &SessionManager, id: SessionId) -> Response {
+    let Ok(turns) = manager.turns(id) else {
+        return Response::error(404, "no such game");
+    };
+    let body = EventsJson {
+        turns: turns.iter().map(turn_json).collect(),
+    };
+    Response::json(200, serde_json::to_string(&body).expect("event bodies always serialize"))
+}
+
+fn replay(manager: &SessionManager, id: SessionId) -> Response {
+    let (Ok(snapshot), Ok(inputs)) = (manager.snapshot(id), manager.transcript(id)) else {
+        return Response::error(404, "no such game");
+    };
+    // Server games are always played under the default rules
+    let header = ReplayHeader::new(snapshot.seed, Vec::new(), &GalaxyOptions::default());
+    let replay = Replay {
+        header,
+        inputs,
+        seal: None,
+    };
+    Response {
+        status: 200,
+        content_type: "text/plain",
+        body: replay.to_text(),
+    }
+}
+
+/// Body of a status response. Digests are hex strings because JSON numbers
+/// cannot hold every u64 exactly.
+#[derive(Serialize)]
+struct StatusJson {
+    id: SessionId,
+    seed: u64,
+    state: &'static str,
+    turns: u32,
+    stardate: f64,
+    klingons_left: i32,
+    stardates_left: f64,
+    starbases_left: i32,
+    exploration_percent: f64,
+    clock_remaining: Option<f64>,
+    state_digest: String,
+}
+
+/// Body of a create or command response.
+#[derive(Serialize)]
+struct StepJson<'a> {
+    /// Only included when a game is created, as is `seed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<SessionId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    state: &'static str,
+    turn: Option<TurnJson<'a>>,
+    output: &'a str,
+}
+
+/// Body of an events response.
+#[derive(Serialize)]
+struct EventsJson<'a> {
+    turns: Vec<TurnJson<'a>>,
+}
+
+/// A completed turn, rounded the way the game shows it.
+#[derive(Serialize)]
+struct TurnJson<'a> {
+    turn: u32,
+    command: &'a str,
+    stardate: f64,
+    energy_delta: f64,
+    damage_delta: f64,
+    hull_delta: f64,
+    klingons_destroyed: i32,
+    time_delta: f64,
+}
+
+fn step_json(created: Option<(SessionId, u64)>, result: &StepResult) -> String {
+    let body = StepJson {
+        id: created.map(|(id, _)| id),
+        seed: created.map(|(_, seed)| seed),
+        state: state_label(&result.state),
+        turn: result.turn.as_ref().map(turn_json),
+        output: &result.output,
+    };
+    serde_json::to_string(&body).expect("step bodies always serialize")
+}
+
+fn turn_json(turn: &TurnSummary) -> TurnJson<'_> {
+    TurnJson {
+        turn: turn.turn,
+        command: &turn.command,
+        stardate: tenths(turn.stardate),
+        energy_delta: tenths(turn.energy_delta),
+        damage_delta: (turn.damage_delta * 100.0).round() / 100.0,
+        hull_delta: tenths(turn.hull_delta),
+        klingons_destroyed: turn.klingons_destroyed,
+        time_delta: (turn.time_delta * 100.0).round() / 100.0,
+    }
+}
+
+/// `value` rounded to one decimal place, as the game displays it.
+fn tenths(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn state_label(state: &GameState) -> &'static str {
+    match state {
+        GameState::Playing => "PLAYING",
+        GameState::Victory { .. } => "VICTORY",
+        GameState::Defeat { reason } => reason.label(),
+    }
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(manager: &SessionManager) -> SessionId {
+        let response = handle(manager, "POST", "/games?seed=42", "");
+        assert_eq!(response.status, 201);
+        assert!(response.body.starts_with("{\"id\":1,\"seed\":42,\"state\":\"PLAYING\",\"turn\":null,"));
+        1
+    }
+
+    #[test]
+    fn game_lifecycle_over_rest() {
+        let manager = SessionManager::new();
+        let id = create(&manager);
+
+        let response = handle(&manager, "POST", &format!("/games/{}/commands", id), "SRS\n");
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("\"turn\":{\"turn\":1,\"command\":\"SHORT RANGE SENSOR SCAN\""));
+
+        let status = handle(&manager, "GET", "/games/1", "");
+        assert!(status.body.starts_with("{\"id\":1,\"seed\":42,\"state\":\"PLAYING\",\"turns\":1,"));
+
+        let events = handle(&manager, "GET", "/games/1/events", "");
+        assert!(events.body.starts_with("{\"turns\":[{\"turn\":1,"));
+
+        let replay = handle(&manager, "GET", "/games/1/replay", "");
+        assert_eq!(replay.content_type, "text/plain");
+        let replay = Replay::parse(&replay.body).unwrap();
+        assert_eq!(replay.header.seed, 42);
+        assert_eq!(replay.inputs, vec!["SRS"]);
+
+        assert_eq!(handle(&manager, "DELETE", "/games/1", "").status, 204);
+        assert_eq!(handle(&manager, "GET", "/games/1", "").status, 404);
+    }
+
+    #[test]
+    fn bad_requests_are_reported() {
+        let manager = SessionManager::new();
+        assert_eq!(handle(&manager, "POST", "/games?seed=x", "").status, 400);
+        assert_eq!(handle(&manager, "POST", "/games?seed=1&clock=soon", "").status, 400);
+        assert_eq!(handle(&manager, "GET", "/games", "").status, 405);
+        assert_eq!(handle(&manager, "GET", "/games/7", "").status, 404);
+        assert_eq!(handle(&manager, "GET", "/nowhere", "").status, 404);
+
+        let id = create(&manager);
+        let response = handle(&manager, "POST", &format!("/games/{}/commands", id), "X\n");
+        assert_eq!(response.status, 400);
+        assert!(response.body.starts_with("{\"error\":"));
+        assert_eq!(handle(&manager, "PUT", "/games/1/events", "").status, 405);
+    }
+
+    #[test]
+    fn timed_games_report_their_clock() {
+        let manager = SessionManager::new();
+        assert_eq!(handle(&manager, "POST", "/games?seed=42&clock=600", "").status, 201);
+        assert_eq!(handle(&manager, "POST", "/games?seed=42", "").status, 201);
+        assert!(handle(&manager, "GET", "/games/1", "").body.contains("\"clock_remaining\":"));
+        assert!(!handle(&manager, "GET", "/games/1", "").body.contains("\"clock_remaining\":null"));
+        assert!(handle(&manager, "GET", "/games/2", "").body.contains("\"clock_remaining\":null"));
+    }
+
+    #[test]
+    fn full_server_turns_new_games_away() {
+        let manager = SessionManager::new();
+        for _ in 0..MAX_SESSIONS {
+            assert_eq!(handle(&manager, "POST", "/games?seed=42", "").status, 201);
+        }
+        let response = handle(&manager, "POST", "/games?seed=42", "");
+        assert_eq!(response.status, 503);
+        assert_eq!(response.body, "{\"error\":\"too many games in progress\"}");
+
+        manager.destroy(1);
+        assert_eq!(handle(&manager, "POST", "/games?seed=42", "").status, 201);
+    }
+
+    #[test]
+    fn output_is_escaped_for_json() {
+        let manager = SessionManager::new();
+        let body = handle(&manager, "POST", "/games?seed=42", "").body;
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert!(json["output"].as_str().unwrap().contains('\n'));
+    }
+
+    #[test]
+    fn serves_requests_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let manager = Arc::new(SessionManager::new());
+        let server = {
+            let manager = Arc::clone(&manager);
+            thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                handle_connection(stream, &manager).unwrap();
+            })
+        };
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "POST /games?seed=5 HTTP/1.1\r\nHost: test\r\nContent-Length: 0\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+        assert!(response.contains("\r\n\r\n{\"id\":1,\"seed\":5,"));
+        assert_eq!(manager.len(), 1);
+    }
+}
@@ -0,0 +1,24 @@
+//! Prelude
+//!
+//! The types a frontend needs to run a game, in one import:
+//!
+//! ```rust
+//! use startrek::prelude::*;
+//!
+//! let engine = GameEngine::new(42);
+//! assert_eq!(engine.state(), &GameState::Playing);
+//! ```
+
+pub use crate::game_engine::{DefeatReason, Execution, GameEngine, GameOverReport, GameState};
+pub use crate::io::{BufferedOutput, EventQueue, InputReader, OutputWriter, PendingInput};
+pub use crate::models::constants::{Condition, Device, SectorContent};
+pub use crate::models::errors::{GameError, GameResult};
+pub use crate::models::galaxy::{Galaxy, GalaxyBuilder, GalaxyOptions, MirrorAxis};
+pub use crate::models::output_event::OutputEvent;
+pub use crate::models::position::{QuadrantPosition, SectorPosition};
+pub use crate::models::status::StatusReport;
+pub use crate::models::turn::TurnSummary;
+pub use crate::services::battle_log::GameEvent;
+pub use crate::services::commands::{CommandId, CustomCommand};
+pub use crate::services::preview::{Command, PreviewReport};
+pub use crate::ui::presenters::Emit;
@@ -0,0 +1,401 @@
+//! Autoplayer
+//!
+//! A simple heuristic captain that plays whole games through
+//! [`GameEngine::step`], for balance testing, catching regressions, and
+//! demos. Each turn it fights whatever is in the quadrant, docks when
+//! supplies run low, and otherwise scans and flies to the nearest Klingons
+//! it knows of, or to the nearest quadrant it has not seen.
+
+use crate::game_engine::{GameEngine, GameOverReport};
+use crate::models::constants::{Condition, Device, IMPULSE_ENERGY_PER_SECTOR};
+use crate::models::galaxy::Galaxy;
+use crate::models::grid::QuadrantGrid;
+use crate::models::navigation_types::{Course, WarpFactor};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::quadrant::QuadrantData;
+use crate::services::combat::calculate_distance;
+use crate::services::commands::CommandId;
+use crate::services::navigation::{calculate_direction, calculate_direction_and_distance, plan_route};
+use crate::services::preview::{self, Command};
+use crate::services::turn::PlayerAction;
+
+/// Turns an autoplayed game may take before it is given up as unfinished.
+pub const AUTOPLAY_MAX_TURNS: u32 = 1000;
+
+/// Fraction of its energy capacity below which the captain heads for a
+/// starbase.
+const LOW_ENERGY: f64 = 0.3;
+
+/// The captain's next action.
+pub fn choose_action(galaxy: &Galaxy) -> PlayerAction {
+    let enterprise = galaxy.enterprise();
+    if enterprise.energy() <= 0.0 && galaxy.evaluate_condition() == Condition::Docked {
+        return command(CommandId::ShortRangeScan);
+    }
+    if enterprise.energy() <= 0.0 && !enterprise.is_damaged(Device::ShieldControl) {
+        // Nothing to move on: the shields give back all but part of a unit,
+        // and with nothing left at all the next move is the last
+        let available = enterprise.energy() + enterprise.shields();
+        if enterprise.shields() >= 1.0 && available > 0.0 {
+            return PlayerAction::Command {
+                id: CommandId::ShieldControl,
+                answers: vec![(available / 2.0).min(0.5).to_string()],
+            };
+        }
+    }
+    if galaxy.sector_map().klingons.iter().any(|k| k.is_alive()) {
+        return fight(galaxy);
+    }
+    let low = needs_supplies(galaxy);
+    if low && galaxy.enterprise().shields() > 1.0 && !galaxy.enterprise().is_damaged(Device::ShieldControl) {
+        // Nothing to shield against: put the energy back
+        return set_shields(1.0);
+    }
+    if low && galaxy.evaluate_condition() == Condition::Docked {
+        // Docking happens on the short range scan
+        return command(CommandId::ShortRangeScan);
+    }
+    if low {
+        if let Some(action) = head_for_starbase(galaxy) {
+            return action;
+        }
+    }
+    // The scan is only remembered while the computer works
+    let sensors = [Device::LongRangeSensors, Device::Computer];
+    if !sensors.iter().any(|&d| galaxy.enterprise().is_damaged(d)) && !neighbours_scanned(galaxy) {
+        return command(CommandId::LongRangeScan);
+    }
+    hunt(galaxy)
+}
+
+/// Play the game out with the captain, for at most `max_turns` turns. The
+/// game's output is discarded.
+///
+/// # Returns
+///
+/// The game over report, or `None` if the game was still going or the
+/// captain resigned, having nothing left to try
+pub fn autoplay(engine: &mut GameEngine, max_turns: u32) -> Option<GameOverReport> {
+    autoplay_observed(engine, max_turns, |_| {})
+}
+
+/// [`autoplay`], handing the galaxy to `observe` after every action.
+pub fn autoplay_observed(
+    engine: &mut GameEngine,
+    max_turns: u32,
+    mut observe: impl FnMut(&Galaxy),
+) -> Option<GameOverReport> {
+    // Counted here rather than from the engine: an unavailable command
+    // takes no turn, and must not stall the captain
+    let mut last: Option<(PlayerAction, u64)> = None;
+    for _ in 0..max_turns {
+        if engine.game_over_report().is_some() {
+            break;
+        }
+        let action = choose_action(engine.galaxy());
+        // The same action again from the same position would fail the same
+        // way: the captain resigns rather than repeat it
+        let digest = engine.galaxy().state_digest();
+        if last.as_ref() == Some(&(action.clone(), digest)) {
+            break;
+        }
+        // An action short of answers is simply wasted
+        let _ = engine.step(action.clone());
+        engine.take_events();
+        observe(engine.galaxy());
+        if engine.galaxy().state_digest() == digest {
+            last = Some((action, digest));
+        } else {
+            last = None;
+        }
+    }
+    engine.game_over_report()
+}
+
+fn command(id: CommandId) -> PlayerAction {
+    PlayerAction::Command { id, answers: Vec::new() }
+}
+
+/// Shield control answers of zero or less cancel, so a unit is the least.
+fn set_shields(units: f64) -> PlayerAction {
+    let units = units.max(1.0);
+    PlayerAction::Command { id: CommandId::ShieldControl, answers: vec![units.to_string()] }
+}
+
+fn needs_supplies(galaxy: &Galaxy) -> bool {
+    let enterprise = galaxy.enterprise();
+    let capacity = enterprise.capacity();
+    enterprise.energy() < capacity.energy * LOW_ENERGY || (capacity.torpedoes > 0 && enterprise.torpedoes() == 0)
+}
+
+/// Shields up against the Klingons' worst volley, then torpedoes at the
+/// nearest Klingon in the clear, then phasers, and if all else fails a
+/// jump out of the quadrant.
+fn fight(galaxy: &Galaxy) -> PlayerAction {
+    let enterprise = galaxy.enterprise();
+    let here = enterprise.sector();
+    let mut klingons: Vec<(SectorPosition, f64, f64)> = galaxy
+        .sector_map()
+        .klingons
+        .iter()
+        .filter(|k| k.is_alive())
+        .map(|k| (k.sector, k.shields, calculate_distance(here, k.sector)))
+        .collect();
+    klingons.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    // A Klingon hits for up to twice its shields over the distance
+    let threat: f64 = klingons.iter().map(|&(_, shields, distance)| 2.0 * shields / distance).sum();
+    let available = enterprise.energy() + enterprise.shields();
+    let wanted = (2.0 * threat).min(available * 0.5).floor();
+    if !enterprise.is_damaged(Device::ShieldControl) {
+        if enterprise.shields() < threat && wanted > enterprise.shields() {
+            return set_shields(wanted);
+        }
+        // Short of energy to fight with, take back what the shields can spare
+        if enterprise.energy() < enterprise.capacity().energy * LOW_ENERGY && enterprise.shields() > wanted.max(1.0) + 1.0 {
+            return set_shields(wanted);
+        }
+    }
+
+    if !enterprise.is_damaged(Device::PhotonTubes) && enterprise.torpedoes() > 0 {
+        for &(sector, _, _) in &klingons {
+            let course = course_to(here, sector);
+            if preview::preview(galaxy, Command::Torpedo { course }).expected_damage_dealt > 0.0 {
+                return PlayerAction::Torpedo { course };
+            }
+        }
+    }
+
+    // Something kept back to get away on
+    let spare = enterprise.energy() - enterprise.capacity().energy * 0.05;
+    if !enterprise.is_damaged(Device::PhaserControl) && spare >= 1.0 {
+        // Enough on average to destroy the toughest Klingon, the energy
+        // being shared between them all
+        let needed = klingons
+            .iter()
+            .map(|&(_, shields, distance)| shields * distance * klingons.len() as f64)
+            .fold(0.0, f64::max);
+        return PlayerAction::Phasers { energy: needed.ceil().min(spare.floor()) };
+    }
+
+    let quadrant = galaxy.enterprise().quadrant();
+    let away = QuadrantPosition { x: if quadrant.x < 8 { quadrant.x + 1 } else { quadrant.x - 1 }, y: quadrant.y };
+    warp_to(galaxy, away)
+}
+
+/// Toward the starbase in this quadrant, or the nearest one the computer
+/// knows of.
+fn head_for_starbase(galaxy: &Galaxy) -> Option<PlayerAction> {
+    let here = galaxy.enterprise().sector();
+    if let Some(starbase) = galaxy.sector_map().starbase {
+        // The nearest empty sector alongside the starbase that a straight
+        // course reaches
+        let quadrant = galaxy.enterprise().quadrant();
+        let mut berths: Vec<SectorPosition> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| SectorPosition { x: starbase.x + dx, y: starbase.y + dy }))
+            .filter(|&s| (1..=8).contains(&s.x) && (1..=8).contains(&s.y) && galaxy.sector_map().is_empty(s))
+            .collect();
+        berths.sort_by(|&a, &b| calculate_distance(here, a).total_cmp(&calculate_distance(here, b)));
+        for berth in berths {
+            let course = course_to(here, berth);
+            let sectors = (berth.x - here.x).abs().max((berth.y - here.y).abs());
+            let Ok(warp) = WarpFactor::new(sectors as f64 / 8.0) else { continue };
+            if preview::preview(galaxy, Command::Warp { course, warp }).endpoint == Some((quadrant, berth)) {
+                return Some(sector_move(galaxy, course, sectors));
+            }
+        }
+        return sidestep(galaxy, course_to(here, starbase).value()).map(|course| sector_move(galaxy, course, 1));
+    }
+    nearest_known(galaxy, |data| data.starbases > 0).map(|target| warp_to(galaxy, target))
+}
+
+/// Toward the nearest Klingons the computer knows of, else the nearest
+/// quadrant never scanned, else the one scanned longest ago.
+fn hunt(galaxy: &Galaxy) -> PlayerAction {
+    let here = galaxy.enterprise().quadrant();
+    let target = nearest_known(galaxy, |data| data.klingons > 0)
+        .or_else(|| nearest(here, galaxy.computer_memory().cells().filter(|&(pos, m)| pos != here && m.is_none()).map(|(pos, _)| pos)))
+        .or_else(|| {
+            QuadrantGrid::<()>::positions()
+                .filter(|&pos| pos != here)
+                .max_by(|&a, &b| galaxy.record_age(a).unwrap_or(0.0).total_cmp(&galaxy.record_age(b).unwrap_or(0.0)))
+        });
+    match target {
+        Some(target) => warp_to(galaxy, target),
+        None => command(CommandId::LongRangeScan),
+    }
+}
+
+fn nearest_known(galaxy: &Galaxy, wanted: impl Fn(&QuadrantData) -> bool) -> Option<QuadrantPosition> {
+    let here = galaxy.enterprise().quadrant();
+    nearest(here, galaxy.scanned_quadrants().filter(|(pos, data)| *pos != here && wanted(data)).map(|(pos, _)| pos))
+}
+
+fn nearest(here: QuadrantPosition, positions: impl Iterator<Item = QuadrantPosition>) -> Option<QuadrantPosition> {
+    positions.min_by_key(|pos| (pos.x - here.x).abs().max((pos.y - here.y).abs()))
+}
+
+/// Whether every quadrant next to this one is in computer memory.
+fn neighbours_scanned(galaxy: &Galaxy) -> bool {
+    let here = galaxy.enterprise().quadrant();
+    (-1..=1).all(|dy| {
+        (-1..=1).all(|dx| {
+            let pos = QuadrantPosition { x: here.x + dx, y: here.y + dy };
+            !matches!(galaxy.computer_memory().get(pos), Some(None))
+        })
+    })
+}
+
+/// The warp jump to `target`, at warp 0.2 when the engines are damaged
+/// and warp 0.5 when the energy would not last the jump.
+/// If something is in the way, a sidestep to an empty sector next to the
+/// Enterprise comes first.
+fn warp_to(galaxy: &Galaxy, target: QuadrantPosition) -> PlayerAction {
+    let route = plan_route(galaxy, target);
+    let speed = if galaxy.enterprise().is_damaged(Device::WarpEngines) {
+        0.2
+    } else if galaxy.enterprise().energy() < warp_cost(route.warp.min(8.0)) {
+        // Half a quadrant at a time gains energy rather than spending it
+        0.5
+    } else {
+        route.warp.min(8.0)
+    };
+    let course = course_from(route.course);
+    let warp = WarpFactor::new(speed).unwrap_or_else(|_| WarpFactor::new(1.0).unwrap());
+    if preview::preview(galaxy, Command::Warp { course, warp }).blocked {
+        if let Some(course) = sidestep(galaxy, route.course) {
+            return sector_move(galaxy, course, 1);
+        }
+    }
+    PlayerAction::Warp { course, warp }
+}
+
+/// Energy a warp move costs: a unit per sector past the fifth.
+fn warp_cost(warp: f64) -> f64 {
+    (warp * 8.0).floor() - 5.0
+}
+
+/// A move of a few sectors within the quadrant, on impulse while those
+/// engines work and there is the energy for it. Short hops at warp cost
+/// nothing.
+fn sector_move(galaxy: &Galaxy, course: Course, sectors: i32) -> PlayerAction {
+    let enterprise = galaxy.enterprise();
+    if enterprise.is_damaged(Device::ImpulseEngines) || enterprise.energy() < sectors as f64 * IMPULSE_ENERGY_PER_SECTOR {
+        let warp = WarpFactor::new(sectors as f64 / 8.0).unwrap_or_else(|_| WarpFactor::new(0.125).unwrap());
+        return PlayerAction::Warp { course, warp };
+    }
+    PlayerAction::Impulse { course, sectors }
+}
+
+/// A course to an empty sector next to the Enterprise, trying those
+/// square to `heading` first.
+fn sidestep(galaxy: &Galaxy, heading: f64) -> Option<Course> {
+    let here = galaxy.enterprise().sector();
+    let heading = heading.round() as i32;
+    [2, 6, 1, 7, 3, 5].into_iter().find_map(|turn| {
+        let course = course_from(((heading - 1 + turn) % 8 + 1) as f64);
+        let (dx, dy) = calculate_direction(course.value());
+        let next = SectorPosition { x: here.x + dx.round() as i32, y: here.y + dy.round() as i32 };
+        let inside = (1..=8).contains(&next.x) && (1..=8).contains(&next.y);
+        (inside && galaxy.sector_map().is_empty(next)).then_some(course)
+    })
+}
+
+fn course_to(from: SectorPosition, to: SectorPosition) -> Course {
+    let (direction, _) = calculate_direction_and_distance(from, to);
+    course_from(direction)
+}
+
+/// A computed course to two decimal places, as a player would type it.
+fn course_from(direction: f64) -> Course {
+    let value = (direction * 100.0).round() / 100.0;
+    let value = if value >= 9.0 { 1.0 } else { value };
+    Course::new(value).unwrap_or_else(|_| Course::new(1.0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::constants::SectorContent;
+    use crate::models::klingon::Klingon;
+    use crate::models::sector_map::SectorMap;
+
+    fn empty_quadrant() -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        let here = SectorPosition { x: 4, y: 4 };
+        galaxy.enterprise_mut().move_to(quadrant, here);
+        galaxy.sector_map_mut().set(here, SectorContent::Enterprise);
+        galaxy
+    }
+
+    #[test]
+    fn the_captain_raises_shields_then_fires_torpedoes() {
+        let mut galaxy = empty_quadrant();
+        let klingon = Klingon::new(SectorPosition { x: 4, y: 7 });
+        galaxy.sector_map_mut().set(klingon.sector, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+
+        galaxy.enterprise_mut().set_shields(0.0);
+        assert!(matches!(
+            choose_action(&galaxy),
+            PlayerAction::Command { id: CommandId::ShieldControl, .. }
+        ));
+        galaxy.enterprise_mut().set_shields(500.0);
+        assert!(matches!(choose_action(&galaxy), PlayerAction::Torpedo { .. }));
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 1.0);
+        assert!(matches!(choose_action(&galaxy), PlayerAction::Phasers { .. }));
+    }
+
+    #[test]
+    fn the_captain_scans_then_hunts_known_klingons() {
+        let mut galaxy = empty_quadrant();
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(None);
+        assert_eq!(choose_action(&galaxy), command(CommandId::LongRangeScan));
+
+        let here = galaxy.enterprise().quadrant();
+        let far = QuadrantPosition { x: if here.x > 4 { 1 } else { 8 }, y: here.y };
+        let klingons = QuadrantData { klingons: 2, starbases: 0, stars: 1, commanders: 0, romulans: 0, mines: 0 };
+        let empty = QuadrantData { klingons: 0, ..klingons };
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(Some(empty));
+        galaxy.computer_memory_mut()[far] = Some(klingons);
+        assert_eq!(choose_action(&galaxy), warp_to(&galaxy, far));
+    }
+
+    #[test]
+    fn low_supplies_send_the_captain_to_dock() {
+        let mut galaxy = empty_quadrant();
+        galaxy.sector_map_mut().starbase = Some(SectorPosition { x: 4, y: 8 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 8 }, SectorContent::Starbase);
+        galaxy.enterprise_mut().set_energy(100.0);
+        match choose_action(&galaxy) {
+            PlayerAction::Impulse { sectors, .. } => assert_eq!(sectors, 3),
+            action => panic!("expected impulse, got {:?}", action),
+        }
+    }
+
+    #[test]
+    fn autoplay_stops_at_a_finish_or_the_limit() {
+        let mut engine = GameEngine::new(1);
+        let report = autoplay(&mut engine, 300);
+        assert!(engine.turns() > 0 && engine.turns() <= 300);
+        assert_eq!(report.is_some(), engine.game_over_report().is_some());
+    }
+
+    #[test]
+    fn an_empty_tank_draws_on_the_shields_instead_of_stalling() {
+        let mut galaxy = empty_quadrant();
+        galaxy.enterprise_mut().set_energy(-0.25);
+        galaxy.enterprise_mut().set_shields(1.0);
+        assert_eq!(
+            choose_action(&galaxy),
+            PlayerAction::Command { id: CommandId::ShieldControl, answers: vec!["0.375".to_string()] }
+        );
+
+        // Seed 5 once ran dry on a warp and tried impulse until the limit
+        let mut engine = GameEngine::new(5);
+        assert!(autoplay(&mut engine, AUTOPLAY_MAX_TURNS).is_some());
+        assert!(engine.turns() < AUTOPLAY_MAX_TURNS);
+    }
+}
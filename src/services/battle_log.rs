@@ -0,0 +1,171 @@
+//! Battle log
+//!
+//! The ordered stream of what happened in a game: the mission start, every
+//! turn, the quadrant events the computer journaled along the way, and the
+//! ending. Each event carries the turn it happened in and the stardate, so
+//! the stream can be exported for analysis after the game.
+
+use crate::game_engine::GameOverReport;
+use crate::models::achievements::Achievement;
+use crate::models::galaxy::Galaxy;
+use crate::models::grid::QuadrantGrid;
+use crate::models::journal::QuadrantEvent;
+use crate::models::position::QuadrantPosition;
+use crate::models::turn::TurnSummary;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// The mission begins; always turn 0.
+    MissionStart {
+        seed: u64,
+        stardate: f64,
+        mission_duration: f64,
+        klingons: i32,
+        starbases: i32,
+    },
+    /// A quadrant event recorded in the computer's journal.
+    Quadrant {
+        turn: u32,
+        stardate: f64,
+        quadrant: QuadrantPosition,
+        event: QuadrantEvent,
+    },
+    /// A command completed.
+    Turn(TurnSummary),
+    /// The game ended.
+    GameOver {
+        report: GameOverReport,
+        achievements: Vec<Achievement>,
+    },
+}
+
+impl GameEvent {
+    /// Turn the event happened in; 0 before the first command.
+    pub fn turn(&self) -> u32 {
+        match self {
+            GameEvent::MissionStart { .. } => 0,
+            GameEvent::Quadrant { turn, .. } => *turn,
+            GameEvent::Turn(summary) => summary.turn,
+            GameEvent::GameOver { report, .. } => report.turns,
+        }
+    }
+
+    pub fn stardate(&self) -> f64 {
+        match self {
+            GameEvent::MissionStart { stardate, .. } | GameEvent::Quadrant { stardate, .. } => *stardate,
+            GameEvent::Turn(summary) => summary.stardate,
+            GameEvent::GameOver { report, .. } => report.stardate,
+        }
+    }
+}
+
+/// Collects a game's events as it is played.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BattleLog {
+    events: Vec<GameEvent>,
+    /// Journal entries already logged, per quadrant.
+    journaled: QuadrantGrid<usize>,
+}
+
+impl BattleLog {
+    /// Start a log for a game about to take its first command.
+    pub fn begin(galaxy: &Galaxy) -> Self {
+        let mut log = BattleLog::default();
+        log.events.push(GameEvent::MissionStart {
+            seed: galaxy.seed(),
+            stardate: galaxy.starting_stardate(),
+            mission_duration: galaxy.mission_duration(),
+            klingons: galaxy.total_klingons(),
+            starbases: galaxy.total_starbases(),
+        });
+        log.catch_up_journal(0, galaxy);
+        log
+    }
+
+    /// Start a log partway through a game, taking the journal entries
+    /// recorded so far as already logged.
+    pub fn resume(galaxy: &Galaxy) -> Self {
+        let mut log = BattleLog::default();
+        for (quadrant, seen) in log.journaled.cells_mut() {
+            *seen = galaxy.journal().entries(quadrant).len();
+        }
+        log
+    }
+
+    /// Log a completed turn, after the journal entries it produced.
+    pub fn record_turn(&mut self, summary: &TurnSummary, galaxy: &Galaxy) {
+        self.catch_up_journal(summary.turn, galaxy);
+        self.events.push(GameEvent::Turn(summary.clone()));
+    }
+
+    /// Log the ending.
+    pub fn finish(&mut self, report: &GameOverReport, galaxy: &Galaxy) {
+        self.catch_up_journal(report.turns, galaxy);
+        self.events.push(GameEvent::GameOver {
+            report: report.clone(),
+            achievements: Achievement::earned(galaxy),
+        });
+    }
+
+    /// Every event so far, oldest first.
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Take the events logged so far.
+    pub fn into_events(self) -> Vec<GameEvent> {
+        self.events
+    }
+
+    /// Log journal entries recorded since the last call, in stardate order.
+    fn catch_up_journal(&mut self, turn: u32, galaxy: &Galaxy) {
+        let mut new = Vec::new();
+        for (quadrant, seen) in self.journaled.cells_mut() {
+            let entries = galaxy.journal().entries(quadrant);
+            new.extend(entries[*seen..].iter().map(|entry| GameEvent::Quadrant {
+                turn,
+                stardate: entry.stardate,
+                quadrant,
+                event: entry.event,
+            }));
+            *seen = entries.len();
+        }
+        new.sort_by(|a, b| a.stardate().total_cmp(&b.stardate()));
+        self.events.extend(new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::turn::TurnSnapshot;
+
+    #[test]
+    fn journal_entries_are_logged_once_under_their_turn() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enter_quadrant();
+        let mut log = BattleLog::begin(&galaxy);
+        assert!(matches!(log.events()[0], GameEvent::MissionStart { seed: 42, .. }));
+        let opening = log.events().len();
+
+        let before = TurnSnapshot::capture(&galaxy);
+        galaxy.advance_time(1.0);
+        galaxy.construct_starbase(QuadrantPosition { x: 1, y: 1 });
+        let summary = TurnSummary::between(1, "WARP", &before, &galaxy);
+        log.record_turn(&summary, &galaxy);
+        log.record_turn(&TurnSummary { turn: 2, ..summary.clone() }, &galaxy);
+
+        let turns: Vec<(u32, &str)> = log.events()[opening..]
+            .iter()
+            .map(|event| {
+                let kind = match event {
+                    GameEvent::Quadrant { .. } => "quadrant",
+                    GameEvent::Turn(_) => "turn",
+                    _ => "other",
+                };
+                (event.turn(), kind)
+            })
+            .collect();
+        assert_eq!(turns, vec![(1, "quadrant"), (1, "turn"), (2, "turn")]);
+    }
+}
@@ -0,0 +1,498 @@
+use crate::io::OutputWriter;
+use crate::models::constants::{
+    SectorContent, COMMANDER_TORPEDO_CHANCE, COMMANDER_TORPEDO_DAMAGE, ROMULAN_FIRE_CHANCE,
+};
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::models::position::SectorPosition;
+use crate::ui::presenters::Emit;
+
+use super::phasers::calculate_distance;
+
+/// Klingons, then any Romulans, attack the Enterprise (spec section 8).
+/// Returns true if the Enterprise is destroyed, false otherwise.
+pub fn enemies_fire(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> bool {
+    // Skip if docked (spec section 8.3)
+    if galaxy
+        .enterprise()
+        .is_adjacent_to_starbase(galaxy.sector_map().starbase)
+    {
+        output.emit(OutputEvent::StarbaseShieldsProtect);
+        return false;
+    }
+
+    let e_pos = galaxy.enterprise().sector();
+
+    // Collect klingon data to avoid borrow conflicts
+    let klingon_attacks: Vec<_> = galaxy
+        .sector_map()
+        .klingons
+        .iter()
+        .filter(|k| k.is_alive())
+        .map(|k| (k.sector, k.shields, k.is_commander(), calculate_distance(e_pos, k.sector)))
+        .collect();
+
+    let aggression = galaxy.klingon_aggression();
+    let combat = galaxy.config().combat;
+    for (k_sector, k_shields, commander, distance) in klingon_attacks {
+        // Only commanders draw for a torpedo, so games without them keep
+        // their random stream
+        let torpedo = commander && galaxy.rng_mut().unit("klingon_attack.torpedo") < COMMANDER_TORPEDO_CHANCE;
+        let hit = if torpedo {
+            output.emit(OutputEvent::CommanderTorpedo { from: k_sector });
+            COMMANDER_TORPEDO_DAMAGE * combat.hit_factor(galaxy.rng_mut(), "klingon_attack.torpedo_hit", 0.5) * aggression
+        } else {
+            (k_shields / distance) * combat.hit_factor(galaxy.rng_mut(), "klingon_attack.hit", 1.0) * aggression
+        };
+
+        let hull_damage = galaxy.enterprise_mut().absorb_hit(hit);
+
+        output.emit(OutputEvent::KlingonHit {
+            from: k_sector,
+            damage: hit,
+            shields_left: galaxy.enterprise().shields(),
+            hull: (hull_damage > 0.0).then(|| galaxy.enterprise().hull()),
+        });
+        if galaxy.enterprise().is_destroyed() {
+            break;
+        }
+    }
+
+    // Check if Enterprise is destroyed (spec section 8.4, with hull)
+    // Return true so caller can check game over condition
+    if galaxy.enterprise().is_destroyed() || romulans_fire(galaxy, output) {
+        return true;
+    }
+    advance_commanders(galaxy, output);
+    false
+}
+
+/// Each Romulan attacks half the time, dropping its cloak to fire. It stays
+/// visible until the Enterprise leaves the quadrant.
+fn romulans_fire(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> bool {
+    let e_pos = galaxy.enterprise().sector();
+    for i in 0..galaxy.sector_map().romulans.len() {
+        let romulan = galaxy.sector_map().romulans[i];
+        if !romulan.is_alive() || galaxy.rng_mut().unit("romulan_attack.fire") >= ROMULAN_FIRE_CHANCE {
+            continue;
+        }
+        if romulan.cloaked {
+            galaxy.sector_map_mut().romulans[i].cloaked = false;
+            output.emit(OutputEvent::RomulanDecloaked { at: romulan.sector });
+        }
+
+        let distance = calculate_distance(e_pos, romulan.sector);
+        let combat = galaxy.config().combat;
+        let hit = (romulan.shields / distance) * combat.hit_factor(galaxy.rng_mut(), "romulan_attack.hit", 1.0);
+        let hull_damage = galaxy.enterprise_mut().absorb_hit(hit);
+        output.emit(OutputEvent::RomulanHit {
+            from: romulan.sector,
+            damage: hit,
+            shields_left: galaxy.enterprise().shields(),
+            hull: (hull_damage > 0.0).then(|| galaxy.enterprise().hull()),
+        });
+        if galaxy.enterprise().is_destroyed() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Commanders close one sector on the Enterprise after firing, stopping
+/// once adjacent or when the way is blocked.
+fn advance_commanders(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let target = galaxy.enterprise().sector();
+    for i in 0..galaxy.sector_map().klingons.len() {
+        let klingon = galaxy.sector_map().klingons[i];
+        if !klingon.is_commander() || !klingon.is_alive() {
+            continue;
+        }
+        let from = klingon.sector;
+        let (dx, dy) = (target.x - from.x, target.y - from.y);
+        if dx.abs().max(dy.abs()) <= 1 {
+            continue;
+        }
+        let to = SectorPosition { x: from.x + dx.signum(), y: from.y + dy.signum() };
+        if !galaxy.sector_map().is_empty(to) {
+            continue;
+        }
+        let map = galaxy.sector_map_mut();
+        map.set(from, SectorContent::Empty);
+        map.set(to, SectorContent::KlingonCommander);
+        map.klingons[i].sector = to;
+        output.emit(OutputEvent::CommanderMoved { from, to });
+    }
+}
+
+/// Handle the dead-in-space scenario where Klingons fire repeatedly (spec 10.4).
+/// The Enterprise is stuck with no energy and minimal shields. All Klingons in the
+/// quadrant fire until either the Enterprise is destroyed or miraculously survives.
+pub fn dead_in_space_loop(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    loop {
+        // Check if there are any enemies left to fire; docked, the
+        // starbase's shields would stop every volley
+        let docked = galaxy.enterprise().is_adjacent_to_starbase(galaxy.sector_map().starbase);
+        if docked || !galaxy.sector_map().hostiles_present() {
+            // No Klingons to fire - Enterprise survives, demoted to private
+            output.writeln("");
+            output.writeln(&format!(
+                "THERE ARE STILL {} KLINGON BATTLE CRUISERS",
+                galaxy.total_klingons()
+            ));
+            return; // Exit loop; the caller marks the ship dead in space
+        }
+
+        // Klingons fire (uses existing enemies_fire function)
+        // This function returns true if Enterprise is destroyed (hull breached)
+        if enemies_fire(galaxy, output) {
+            return; // Enterprise destroyed, let game engine handle defeat
+        }
+
+        // If we reach here, the hull is still holding despite the attack
+        // The spec says "fire repeatedly until" so we continue the loop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::config::{CombatModel, GameConfig};
+    use crate::models::constants::SectorContent;
+    use crate::models::galaxy::Galaxy;
+    use crate::models::klingon::Klingon;
+    use crate::models::position::SectorPosition;
+    use crate::models::rng::SequenceRng;
+    use crate::models::romulan::Romulan;
+    use crate::models::sector_map::SectorMap;
+
+    /// Helper: Set up a combat scenario with specified parameters.
+    fn setup_combat_scenario(
+        seed: u64,
+        enterprise_energy: f64,
+        enterprise_shields: f64,
+        klingon_shields: f64,
+    ) -> Galaxy {
+        let mut galaxy = Galaxy::new(seed);
+
+        // Clear sector map
+        *galaxy.sector_map_mut() = SectorMap::new();
+
+        // Place Enterprise at (4, 4)
+        let sector = SectorPosition { x: 4, y: 4 };
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, sector);
+        galaxy.enterprise_mut().set_energy(enterprise_energy);
+        galaxy.enterprise_mut().set_shields(enterprise_shields);
+        let enterprise_sector = galaxy.enterprise().sector();
+        galaxy
+            .sector_map_mut()
+            .set(enterprise_sector, SectorContent::Enterprise);
+
+        // Place one Klingon at (2, 2)
+        let klingon_pos = SectorPosition { x: 2, y: 2 };
+        let mut klingon = Klingon::new(klingon_pos);
+        klingon.shields = klingon_shields;
+        galaxy.sector_map_mut().set(klingon_pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+
+        galaxy
+    }
+
+    // ========== Klingon firing tests ==========
+
+    #[test]
+    fn enemies_fire_reduces_shields() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        let initial_shields = galaxy.enterprise().shields();
+
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+
+        assert!(galaxy.enterprise().shields() < initial_shields);
+    }
+
+    #[test]
+    fn enemies_fire_skips_when_docked() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+
+        // Place starbase adjacent to Enterprise
+        let starbase_pos = SectorPosition { x: 5, y: 4 };
+        galaxy.sector_map_mut().set(starbase_pos, SectorContent::Starbase);
+        galaxy.sector_map_mut().starbase = Some(starbase_pos);
+
+        let initial_shields = galaxy.enterprise().shields();
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+
+        assert_eq!(galaxy.enterprise().shields(), initial_shields);
+    }
+
+    #[test]
+    fn dead_in_space_loop_ends_when_docked() {
+        let mut galaxy = setup_combat_scenario(42, 0.0, 0.0, 200.0);
+        let starbase_pos = SectorPosition { x: 5, y: 4 };
+        galaxy.sector_map_mut().set(starbase_pos, SectorContent::Starbase);
+        galaxy.sector_map_mut().starbase = Some(starbase_pos);
+
+        dead_in_space_loop(&mut galaxy, &mut MockOutput::new());
+        assert!(!galaxy.enterprise().is_destroyed());
+    }
+
+    #[test]
+    fn enemies_fire_does_not_hit_from_dead_klingons() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.sector_map_mut().klingons[0].shields = 0.0;
+
+        let initial_shields = galaxy.enterprise().shields();
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+
+        // Shields should not change if all Klingons are dead
+        assert_eq!(galaxy.enterprise().shields(), initial_shields);
+    }
+
+    #[test]
+    fn enemies_fire_damage_depends_on_distance() {
+        // Closer Klingon should do more damage
+        let mut galaxy1 = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        // Klingon at (2,2), Enterprise at (4,4) - distance = sqrt(8) ≈ 2.83
+
+        let mut galaxy2 = Galaxy::new(42);
+        *galaxy2.sector_map_mut() = SectorMap::new();
+        let sector = SectorPosition { x: 4, y: 4 };
+        let quadrant = galaxy2.enterprise().quadrant();
+        galaxy2.enterprise_mut().move_to(quadrant, sector);
+        galaxy2.enterprise_mut().set_energy(3000.0);
+        galaxy2.enterprise_mut().set_shields(500.0);
+        let enterprise_sector = galaxy2.enterprise().sector();
+        galaxy2
+            .sector_map_mut()
+            .set(enterprise_sector, SectorContent::Enterprise);
+
+        // Place Klingon farther away at (1, 1)
+        let far_klingon_pos = SectorPosition { x: 1, y: 1 };
+        let mut far_klingon = Klingon::new(far_klingon_pos);
+        far_klingon.shields = 200.0;
+        galaxy2.sector_map_mut().set(far_klingon_pos, SectorContent::Klingon);
+        galaxy2.sector_map_mut().klingons.push(far_klingon);
+
+        // Pin the random factor (2 * 0.5) so only the distance differs
+        galaxy1.rng_mut().set_source(SequenceRng::new([0.5]));
+        galaxy2.rng_mut().set_source(SequenceRng::new([0.5]));
+        enemies_fire(&mut galaxy1, &mut MockOutput::new());
+        enemies_fire(&mut galaxy2, &mut MockOutput::new());
+
+        let near_hit = 500.0 - galaxy1.enterprise().shields();
+        let far_hit = 500.0 - galaxy2.enterprise().shields();
+        assert!((near_hit - 200.0 / 8f64.sqrt()).abs() < 1e-9);
+        assert!((far_hit - 200.0 / 18f64.sqrt()).abs() < 1e-9);
+        assert!(near_hit > far_hit);
+    }
+
+    #[test]
+    fn deterministic_klingon_hits_are_their_average() {
+        let config = GameConfig { combat: CombatModel::Deterministic, ..Default::default() };
+        let mut galaxy = Galaxy::new_with_config(42, config);
+        *galaxy.sector_map_mut() = setup_combat_scenario(42, 3000.0, 500.0, 200.0).sector_map().clone();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        galaxy.enterprise_mut().set_shields(500.0);
+        let draws = galaxy.rng().draws();
+
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+        assert!((500.0 - galaxy.enterprise().shields() - 200.0 / 8f64.sqrt()).abs() < 1e-9);
+        assert_eq!(galaxy.rng().draws(), draws);
+    }
+
+    #[test]
+    fn multiple_klingons_all_take_damage() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+
+        // Add second Klingon
+        let k2_pos = SectorPosition { x: 6, y: 6 };
+        let k2 = Klingon::new(k2_pos);
+        galaxy.sector_map_mut().set(k2_pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(k2);
+
+        // Add third Klingon
+        let k3_pos = SectorPosition { x: 3, y: 7 };
+        let k3 = Klingon::new(k3_pos);
+        galaxy.sector_map_mut().set(k3_pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(k3);
+
+        assert_eq!(galaxy.sector_map().klingons.len(), 3);
+
+        // All Klingons fire
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+
+        // Enterprise shields should be reduced by attacks from all 3
+        assert!(galaxy.enterprise().shields() < 500.0);
+    }
+
+    #[test]
+    fn hits_past_depleted_shields_damage_hull() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 0.0, 200.0);
+
+        let destroyed = enemies_fire(&mut galaxy, &mut MockOutput::new());
+
+        assert_eq!(galaxy.enterprise().shields(), 0.0);
+        assert!(galaxy.enterprise().hull() < crate::models::constants::INITIAL_HULL);
+        assert!(!destroyed, "a single hit should not breach a full hull");
+    }
+
+    #[test]
+    fn enemies_fire_reports_destruction_when_hull_breached() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 0.0, 200.0);
+        galaxy.enterprise_mut().set_hull(0.01);
+
+        assert!(enemies_fire(&mut galaxy, &mut MockOutput::new()));
+    }
+
+    // ========== Victory/defeat tests ==========
+
+    #[test]
+    fn victory_when_last_klingon_destroyed() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 10.0);
+        galaxy.set_total_klingons(1); // Only one Klingon in entire galaxy
+
+        // Manually destroy the Klingon
+        let klingon_pos = galaxy.sector_map().klingons[0].sector;
+        galaxy.sector_map_mut().klingons[0].shields = 0.0;
+        galaxy.sector_map_mut().set(klingon_pos, SectorContent::Empty);
+        galaxy.decrement_klingons();
+        galaxy.decrement_quadrant_klingons();
+
+        assert_eq!(galaxy.total_klingons(), 0);
+        assert!(galaxy.all_klingons_destroyed());
+        // Victory check now handled by GameEngine
+    }
+
+    // ========== Retain cleanup tests ==========
+
+    #[test]
+    fn retain_removes_only_dead_klingons() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+
+        // Add second Klingon that's already dead
+        let dead_klingon_pos = SectorPosition { x: 6, y: 6 };
+        let mut dead_klingon = Klingon::new(dead_klingon_pos);
+        dead_klingon.shields = 0.0;
+        galaxy.sector_map_mut().set(dead_klingon_pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(dead_klingon);
+
+        // Add third Klingon that's alive
+        let alive_klingon_pos = SectorPosition { x: 7, y: 7 };
+        let alive_klingon = Klingon::new(alive_klingon_pos);
+        galaxy
+            .sector_map_mut()
+            .set(alive_klingon_pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(alive_klingon);
+
+        assert_eq!(galaxy.sector_map().klingons.len(), 3);
+
+        // Apply retain
+        galaxy.sector_map_mut().klingons.retain(|k| k.is_alive());
+
+        // Should have 2 living Klingons left
+        assert_eq!(galaxy.sector_map().klingons.len(), 2);
+        for k in &galaxy.sector_map().klingons {
+            assert!(k.is_alive());
+        }
+    }
+
+    #[test]
+    fn klingon_destruction_clears_grid() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        let klingon_pos = galaxy.sector_map().klingons[0].sector;
+
+        // Verify Klingon is in grid
+        assert_eq!(
+            galaxy.sector_map().get(klingon_pos),
+            SectorContent::Klingon
+        );
+
+        // Destroy Klingon
+        galaxy.sector_map_mut().klingons[0].shields = 0.0;
+        galaxy.sector_map_mut().set(klingon_pos, SectorContent::Empty);
+        galaxy.sector_map_mut().klingons.retain(|k| k.is_alive());
+
+        // Verify grid is cleared and vector is empty
+        assert_eq!(galaxy.sector_map().get(klingon_pos), SectorContent::Empty);
+        assert_eq!(galaxy.sector_map().klingons.len(), 0);
+    }
+
+    // ========== Commander tests ==========
+
+    fn place_commander(galaxy: &mut Galaxy, pos: SectorPosition) {
+        galaxy.sector_map_mut().set(pos, SectorContent::KlingonCommander);
+        galaxy.sector_map_mut().klingons.push(Klingon::commander(pos, 400.0));
+    }
+
+    #[test]
+    fn commanders_close_in_after_firing() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 3000.0, 200.0);
+        place_commander(&mut galaxy, SectorPosition { x: 8, y: 1 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 6, y: 3 }, SectorContent::Star);
+
+        let mut output = MockOutput::new();
+        enemies_fire(&mut galaxy, &mut output);
+        let commander = galaxy.sector_map().klingons[1];
+        assert_eq!(commander.sector, SectorPosition { x: 7, y: 2 });
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 8, y: 1 }), SectorContent::Empty);
+        assert_eq!(galaxy.sector_map().get(commander.sector), SectorContent::KlingonCommander);
+        assert!(output.messages.concat().contains("KLINGON COMMANDER MOVES TO SECTOR 7,2"));
+        // The ordinary Klingon holds its position
+        assert_eq!(galaxy.sector_map().klingons[0].sector, SectorPosition { x: 2, y: 2 });
+
+        // Blocked by the star, then held once adjacent
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+        assert_eq!(galaxy.sector_map().klingons[1].sector, SectorPosition { x: 7, y: 2 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 6, y: 3 }, SectorContent::Empty);
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+        assert_eq!(galaxy.sector_map().klingons[1].sector, SectorPosition { x: 5, y: 4 });
+    }
+
+    #[test]
+    fn commanders_sometimes_fire_torpedoes() {
+        let mut torpedoes = 0;
+        for seed in 0..40 {
+            let mut galaxy = setup_combat_scenario(seed, 3000.0, 3000.0, 0.0);
+            place_commander(&mut galaxy, SectorPosition { x: 5, y: 5 });
+            let mut output = MockOutput::new();
+            enemies_fire(&mut galaxy, &mut output);
+            if output.messages.concat().contains("PHOTON TORPEDO FIRED BY KLINGON COMMANDER AT SECTOR 5,5") {
+                torpedoes += 1;
+            }
+        }
+        assert!(torpedoes > 0 && torpedoes < 40);
+    }
+
+    // ========== Romulan tests ==========
+
+    #[test]
+    fn romulans_decloak_when_they_fire() {
+        let mut fired = 0;
+        for seed in 0..20 {
+            let mut galaxy = setup_combat_scenario(seed, 3000.0, 3000.0, 200.0);
+            galaxy.sector_map_mut().klingons.clear();
+            let pos = SectorPosition { x: 7, y: 7 };
+            galaxy.sector_map_mut().set(pos, SectorContent::Romulan);
+            galaxy.sector_map_mut().romulans.push(Romulan::new(pos, 300.0));
+
+            let mut output = MockOutput::new();
+            enemies_fire(&mut galaxy, &mut output);
+            let romulan = galaxy.sector_map().romulans[0];
+            let text = output.messages.concat();
+            if romulan.cloaked {
+                assert_eq!(galaxy.enterprise().shields(), 3000.0);
+                assert_eq!(galaxy.sector_map().visible(pos), SectorContent::Empty);
+            } else {
+                fired += 1;
+                assert!(text.starts_with("A ROMULAN WARBIRD DECLOAKS AT SECTOR 7,7\n"));
+                assert!(text.contains("UNIT HIT ON ENTERPRISE FROM SECTOR 7,7"));
+                assert!(galaxy.enterprise().shields() < 3000.0);
+                assert_eq!(galaxy.sector_map().visible(pos), SectorContent::Romulan);
+            }
+        }
+        assert!(fired > 0 && fired < 20);
+    }
+}
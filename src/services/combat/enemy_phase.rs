@@ -0,0 +1,114 @@
+use crate::io::OutputWriter;
+use crate::models::constants::{SectorContent, COLLISION_HULL_DAMAGE, TRACTOR_BEAM_CHANCE};
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::models::position::SectorPosition;
+use crate::ui::presenters::Emit;
+
+use super::tholian::spin_web;
+
+/// The enemy's turn after a command that left the Enterprise in the same
+/// quadrant, given the sector it started the command in. Any Tholian
+/// spins more of its web, and if the Enterprise moved a Klingon commander
+/// may lock a tractor beam on it.
+pub fn enemy_phase(galaxy: &mut Galaxy, from: SectorPosition, output: &mut dyn OutputWriter) {
+    spin_web(galaxy, output);
+    if galaxy.enterprise().sector() != from {
+        tractor_beam(galaxy, output);
+    }
+}
+
+/// The first commander whose beam locks on drags the Enterprise one sector
+/// toward it. An occupied sector stops the ship short and damages the hull.
+/// Starbase shields protect a docked ship.
+fn tractor_beam(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    if galaxy.enterprise().is_adjacent_to_starbase(galaxy.sector_map().starbase) {
+        return;
+    }
+    let commanders: Vec<SectorPosition> = galaxy
+        .sector_map()
+        .klingons
+        .iter()
+        .filter(|k| k.is_commander() && k.is_alive())
+        .map(|k| k.sector)
+        .collect();
+    for from in commanders {
+        let ship = galaxy.enterprise().sector();
+        let (dx, dy) = (from.x - ship.x, from.y - ship.y);
+        if dx.abs().max(dy.abs()) <= 1 || galaxy.rng_mut().unit("klingon_commander.tractor") >= TRACTOR_BEAM_CHANCE {
+            continue;
+        }
+        let to = SectorPosition { x: ship.x + dx.signum(), y: ship.y + dy.signum() };
+        let hull = if galaxy.sector_map().is_empty(to) {
+            let quadrant = galaxy.enterprise().quadrant();
+            let map = galaxy.sector_map_mut();
+            map.set(ship, SectorContent::Empty);
+            map.set(to, SectorContent::Enterprise);
+            galaxy.enterprise_mut().move_to(quadrant, to);
+            None
+        } else {
+            galaxy.enterprise_mut().damage_hull(COLLISION_HULL_DAMAGE);
+            Some(galaxy.enterprise().hull())
+        };
+        output.emit(OutputEvent::TractorBeam { from, to, hull });
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::constants::INITIAL_HULL;
+    use crate::models::klingon::Klingon;
+    use crate::models::position::QuadrantPosition;
+
+    /// A galaxy whose Enterprise at 4,4 faces a commander at 7,4, and whose
+    /// first tractor draw locks on.
+    fn galaxy_with_commander() -> Galaxy {
+        (0..)
+            .map(|seed| {
+                let mut galaxy = Galaxy::new(seed);
+                *galaxy.sector_map_mut() = Default::default();
+                let ship = SectorPosition { x: 4, y: 4 };
+                galaxy.enterprise_mut().move_to(QuadrantPosition { x: 4, y: 4 }, ship);
+                galaxy.sector_map_mut().set(ship, SectorContent::Enterprise);
+                let at = SectorPosition { x: 7, y: 4 };
+                galaxy.sector_map_mut().set(at, SectorContent::KlingonCommander);
+                galaxy.sector_map_mut().klingons.push(Klingon::commander(at, 400.0));
+                galaxy
+            })
+            .find(|galaxy| galaxy.clone().rng_mut().unit("klingon_commander.tractor") < TRACTOR_BEAM_CHANCE)
+            .unwrap()
+    }
+
+    #[test]
+    fn commanders_drag_the_enterprise_after_it_moves() {
+        let mut galaxy = galaxy_with_commander();
+        let mut output = MockOutput::new();
+        enemy_phase(&mut galaxy, SectorPosition { x: 4, y: 4 }, &mut output);
+        assert!(output.messages.is_empty());
+
+        enemy_phase(&mut galaxy, SectorPosition { x: 3, y: 4 }, &mut output);
+        let to = SectorPosition { x: 5, y: 4 };
+        assert_eq!(galaxy.enterprise().sector(), to);
+        assert_eq!(galaxy.sector_map().get(to), SectorContent::Enterprise);
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 4, y: 4 }), SectorContent::Empty);
+        assert_eq!(
+            output.messages.concat(),
+            "KLINGON COMMANDER AT SECTOR 7,4 LOCKS A TRACTOR BEAM ON THE ENTERPRISE\n\
+             ENTERPRISE DRAGGED TO SECTOR 5,4\n"
+        );
+    }
+
+    #[test]
+    fn dragging_into_an_obstacle_damages_the_hull() {
+        let mut galaxy = galaxy_with_commander();
+        galaxy.sector_map_mut().set(SectorPosition { x: 5, y: 4 }, SectorContent::Star);
+        let mut output = MockOutput::new();
+        enemy_phase(&mut galaxy, SectorPosition { x: 3, y: 4 }, &mut output);
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 4, y: 4 });
+        assert_eq!(galaxy.enterprise().hull(), INITIAL_HULL - COLLISION_HULL_DAMAGE);
+        assert!(output.messages.concat().contains("ENTERPRISE DRAGGED INTO AN OBSTACLE AT SECTOR 5,4"));
+    }
+}
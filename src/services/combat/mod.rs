@@ -1,18 +1,22 @@
 //! Combat system
 //!
 //! Handles all combat operations including phaser fire, torpedo launch,
-//! shield control, and Klingon attacks.
+//! shield control, Klingon and Romulan attacks, and the enemy's turn
+//! after each command.
 
 mod phasers;
 mod torpedoes;
 mod shields;
-mod klingon_attack;
+mod enemy_attack;
+mod tholian;
+mod enemy_phase;
 
 // Re-export public functions
-pub use phasers::fire_phasers;
-pub use torpedoes::fire_torpedoes;
-pub use shields::shield_control;
-pub use klingon_attack::{klingons_fire, dead_in_space_loop};
+pub use phasers::{can_fire_phasers, charge_phasers, fire_phasers, phaser_volley};
+pub use torpedoes::{can_fire_torpedoes, fire_salvo, fire_torpedoes, launch_salvo, launch_torpedo};
+pub use shields::{auto_shields, red_alert, report_shield_drain, shield_control, shields_down, shields_up};
+pub use enemy_attack::{enemies_fire, dead_in_space_loop};
+pub use enemy_phase::enemy_phase;
 
 // Re-export helper functions (used in property tests)
 // Exported for property-based tests, may appear unused in bin target
@@ -1,14 +1,15 @@
-use rand::Rng;
-
 use crate::io::{InputReader, OutputWriter};
-use crate::models::constants::Device;
+use crate::models::config::CombatModel;
+use crate::models::constants::{Device, PHASER_HEAT_LIMIT, PHASER_OVERLOAD_DAMAGE};
 use crate::models::errors::GameResult;
 use crate::models::galaxy::Galaxy;
 use crate::models::klingon::Klingon;
+use crate::models::output_event::OutputEvent;
 use crate::models::position::SectorPosition;
-use crate::ui::presenters::CombatPresenter;
+use crate::models::rng::GameRng;
+use crate::ui::presenters::Emit;
 
-use super::klingon_attack::klingons_fire;
+use crate::services::turn::{self, PlayerAction};
 
 /// Calculate the Euclidean distance between two sector positions (spec section 7.1).
 pub fn calculate_distance(from: SectorPosition, to: SectorPosition) -> f64 {
@@ -20,8 +21,8 @@ pub fn calculate_distance(from: SectorPosition, to: SectorPosition) -> f64 {
 /// Check preconditions for firing phasers.
 /// Returns (can_fire, computer_damaged).
 fn check_phaser_readiness(galaxy: &Galaxy, output: &mut dyn OutputWriter) -> (bool, bool) {
-    // Check for Klingons in quadrant
-    if galaxy.sector_map().klingons.is_empty() {
+    // Check for Klingons, or Romulans out of their cloak, in quadrant
+    if galaxy.sector_map().klingons.is_empty() && galaxy.sector_map().visible_romulans().next().is_none() {
         output.writeln("SHORT RANGE SENSORS REPORT NO KLINGONS IN THIS QUADRANT");
         return (false, false);
     }
@@ -69,79 +70,127 @@ fn read_and_validate_phaser_energy(
     Ok(Some(units))
 }
 
-/// Apply computer damage degradation to phaser energy.
-fn calculate_phaser_energy(units: f64, computer_damaged: bool, rng: &mut impl Rng) -> f64 {
+/// Apply computer damage degradation to phaser energy: a random fraction
+/// of it, or half under the deterministic combat model.
+fn calculate_phaser_energy(units: f64, computer_damaged: bool, combat: CombatModel, rng: &mut GameRng) -> f64 {
     if computer_damaged {
-        units * rng.gen::<f64>()
+        units * 0.5 * combat.hit_factor(rng, "phasers.computer_damage", 1.0)
     } else {
         units
     }
 }
 
-/// Apply phaser damage to all Klingons and return positions of destroyed ones.
-fn apply_phaser_damage_to_klingons(
-    galaxy: &mut Galaxy,
-    phaser_energy: f64,
-    output: &mut dyn OutputWriter,
-) -> Vec<SectorPosition> {
-    // Count living Klingons for damage distribution
+/// Phaser heat variant: the shot heats the banks, and past
+/// `PHASER_HEAT_LIMIT` they may overload, with a chance growing with the
+/// excess heat, damaging phaser control.
+fn heat_phasers(galaxy: &mut Galaxy, units: f64, output: &mut dyn OutputWriter) {
+    galaxy.enterprise_mut().heat_phasers(units);
+    let excess = galaxy.enterprise().phaser_heat() - PHASER_HEAT_LIMIT;
+    if excess <= 0.0 {
+        return;
+    }
+    let chance = (excess / PHASER_HEAT_LIMIT).min(1.0);
+    if galaxy.rng_mut().unit("phasers.overload") < chance {
+        output.writeln("*** PHASER BANKS OVERLOAD ***");
+        galaxy.enterprise_mut().damage_device(Device::PhaserControl, PHASER_OVERLOAD_DAMAGE);
+        output.emit(OutputEvent::DeviceDamaged { device: Device::PhaserControl, severity: PHASER_OVERLOAD_DAMAGE });
+    } else {
+        output.writeln(&format!("PHASER BANKS OVERHEATING ({}%)", galaxy.enterprise().phaser_heat_percent()));
+    }
+}
+
+/// Ships a phaser volley was aimed at that it destroyed.
+#[derive(Debug, Default)]
+struct Destroyed {
+    klingons: Vec<SectorPosition>,
+    romulans: Vec<SectorPosition>,
+}
+
+/// Apply phaser damage to every Klingon and every decloaked Romulan, each
+/// taking an equal share, and return the positions of those destroyed.
+fn apply_phaser_damage(galaxy: &mut Galaxy, phaser_energy: f64, output: &mut dyn OutputWriter) -> Destroyed {
+    // Count living targets for damage distribution
     let num_klingons = galaxy
         .sector_map()
         .klingons
         .iter()
         .filter(|k: &&Klingon| k.is_alive())
         .count();
+    let num_targets = num_klingons + galaxy.sector_map().visible_romulans().count();
 
-    if num_klingons == 0 {
-        return Vec::new(); // All Klingons already dead
+    if num_targets == 0 {
+        return Destroyed::default(); // All targets already dead
     }
 
     let e_pos = galaxy.enterprise().sector();
-    let mut destroyed_positions = Vec::new();
+    let efficiency = galaxy.crew_efficiency();
+    let mut destroyed = Destroyed::default();
 
-    // Generate random factors for each klingon first to avoid borrow conflicts
-    let random_factors: Vec<f64> = (0..num_klingons)
-        .map(|_| 2.0 * galaxy.rng_mut().gen::<f64>())
+    // Generate random factors for each target first to avoid borrow conflicts
+    let combat = galaxy.config().combat;
+    let random_factors: Vec<f64> = (0..num_targets)
+        .map(|_| combat.hit_factor(galaxy.rng_mut(), "phasers.hit", 1.0))
         .collect();
+    let share = phaser_energy / num_targets as f64;
+    let mut rand_idx = 0;
 
     // Apply damage to each Klingon
-    let mut rand_idx = 0;
     for klingon in galaxy.sector_map_mut().klingons.iter_mut() {
         if !klingon.is_alive() {
             continue; // Already dead
         }
 
         let distance = calculate_distance(e_pos, klingon.sector);
-        let hit = (phaser_energy / num_klingons as f64 / distance) * random_factors[rand_idx];
+        let hit = (share / distance) * random_factors[rand_idx] * efficiency;
         rand_idx += 1;
 
         klingon.shields -= hit;
 
-        CombatPresenter::show_klingon_hit(hit, klingon.sector, klingon.shields, output);
+        output.emit(OutputEvent::HitOnKlingon { at: klingon.sector, damage: hit, shields_left: klingon.shields });
 
         // If Klingon destroyed, collect position for cleanup
         if !klingon.is_alive() {
-            destroyed_positions.push(klingon.sector);
+            destroyed.klingons.push(klingon.sector);
         }
     }
 
-    destroyed_positions
+    // Then each Romulan the sensors can see
+    for romulan in galaxy.sector_map_mut().romulans.iter_mut() {
+        if !romulan.is_alive() || romulan.cloaked {
+            continue;
+        }
+
+        let distance = calculate_distance(e_pos, romulan.sector);
+        let hit = (share / distance) * random_factors[rand_idx] * efficiency;
+        rand_idx += 1;
+
+        romulan.shields -= hit;
+
+        output.emit(OutputEvent::HitOnRomulan { at: romulan.sector, damage: hit, shields_left: romulan.shields });
+
+        if !romulan.is_alive() {
+            destroyed.romulans.push(romulan.sector);
+        }
+    }
+
+    destroyed
 }
 
-/// Clean up destroyed Klingons from all tracking structures.
-fn cleanup_destroyed_klingons(
-    galaxy: &mut Galaxy,
-    destroyed_positions: &[SectorPosition],
-    output: &mut dyn OutputWriter,
-) -> GameResult<()> {
+/// Clean up destroyed Klingons and Romulans from all tracking structures.
+fn cleanup_destroyed_ships(galaxy: &mut Galaxy, destroyed: &Destroyed, output: &mut dyn OutputWriter) -> GameResult<()> {
     // Clean up destroyed Klingons
-    for pos in destroyed_positions {
-        CombatPresenter::show_klingon_destroyed(output);
+    for pos in &destroyed.klingons {
+        output.emit(OutputEvent::KlingonDestroyed);
         galaxy.destroy_klingon(*pos)?;
     }
 
     // Remove all dead Klingons from the vector in one pass
     galaxy.sector_map_mut().klingons.retain(|k| k.is_alive());
+
+    for pos in &destroyed.romulans {
+        output.emit(OutputEvent::RomulanDestroyed);
+        galaxy.destroy_romulan(*pos);
+    }
     Ok(())
 }
 
@@ -151,10 +200,11 @@ fn check_phaser_victory(_galaxy: &mut Galaxy, _output: &mut dyn OutputWriter) {
     // Victory check moved to game loop / GameEngine
 }
 
-/// Fires phasers at all Klingons in the current sector (Command 3)
+/// Fires phasers at all Klingons, and any Romulans out of their cloak, in
+/// the current quadrant (Command 3)
 ///
 /// Prompts the player for phaser energy units to fire. Energy is distributed
-/// among all Klingons based on distance, with closer targets receiving more damage.
+/// among all targets based on distance, with closer targets receiving more damage.
 /// Computer damage reduces phaser accuracy. Klingons fire back before phaser damage
 /// is applied per spec 8.1.
 ///
@@ -178,7 +228,7 @@ pub fn fire_phasers(
     output: &mut dyn OutputWriter,
 ) -> GameResult<()> {
     // Phase 1: Preconditions
-    let (can_fire, computer_damaged) = check_phaser_readiness(galaxy, output);
+    let (can_fire, _) = check_phaser_readiness(galaxy, output);
     if !can_fire {
         return Ok(());
     }
@@ -189,20 +239,40 @@ pub fn fire_phasers(
         None => return Ok(()),
     };
 
-    // Phase 3: Energy deduction
+    // Phase 3: The volley, with the Klingons firing first (spec 8.1)
+    turn::resolve(galaxy, &PlayerAction::Phasers { energy: units }, output)
+}
+
+/// Whether phasers can fire `units` of energy: the checks `fire_phasers`
+/// makes before and after its prompt, for an answer given in advance.
+pub fn can_fire_phasers(galaxy: &Galaxy, units: f64, output: &mut dyn OutputWriter) -> bool {
+    check_phaser_readiness(galaxy, output).0 && units > 0.0 && galaxy.enterprise().energy() - units >= 0.0
+}
+
+/// Draw the phaser energy from the ship's reserves. Done as the order is
+/// given, before the Klingons return fire.
+pub fn charge_phasers(galaxy: &mut Galaxy, units: f64) {
     galaxy.enterprise_mut().subtract_energy(units);
+}
 
-    // Phase 4: CRITICAL - Klingons fire BEFORE phaser damage (spec 8.1)
-    if klingons_fire(galaxy, output) {
-        return Ok(()); // Enterprise destroyed
-    }
+/// Apply a phaser volley of `units` to the enemies in the quadrant, once
+/// the energy is drawn and the Klingons have had their shot.
+pub fn phaser_volley(galaxy: &mut Galaxy, units: f64, output: &mut dyn OutputWriter) -> GameResult<()> {
+    let computer_damaged = galaxy.enterprise().is_damaged(Device::Computer);
 
     // Phase 5: Apply phaser damage
-    let phaser_energy = calculate_phaser_energy(units, computer_damaged, galaxy.rng_mut());
-    let destroyed = apply_phaser_damage_to_klingons(galaxy, phaser_energy, output);
+    let combat = galaxy.config().combat;
+    let mut phaser_energy = calculate_phaser_energy(units, computer_damaged, combat, galaxy.rng_mut());
+    if galaxy.ruleset().phaser_heat {
+        phaser_energy *= galaxy.enterprise().phaser_heat_efficiency();
+    }
+    let destroyed = apply_phaser_damage(galaxy, phaser_energy, output);
+    if galaxy.ruleset().phaser_heat {
+        heat_phasers(galaxy, units, output);
+    }
 
     // Phase 6: Cleanup
-    cleanup_destroyed_klingons(galaxy, &destroyed, output)?;
+    cleanup_destroyed_ships(galaxy, &destroyed, output)?;
 
     // Phase 7: Victory check
     check_phaser_victory(galaxy, output);
@@ -212,6 +282,8 @@ pub fn fire_phasers(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::config::GameConfig;
     use crate::models::constants::SectorContent;
     use crate::models::galaxy::Galaxy;
     use crate::models::klingon::Klingon;
@@ -252,6 +324,82 @@ mod tests {
         galaxy
     }
 
+    #[test]
+    fn deterministic_phasers_split_energy_by_distance_without_drawing() {
+        let config = GameConfig { combat: CombatModel::Deterministic, ..Default::default() };
+        let mut galaxy = Galaxy::new_with_config(42, config);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        for (x, y) in [(4, 1), (8, 4)] {
+            let klingon = Klingon::new(SectorPosition { x, y });
+            galaxy.sector_map_mut().set(klingon.sector, SectorContent::Klingon);
+            galaxy.sector_map_mut().klingons.push(klingon);
+        }
+        let draws = galaxy.rng().draws();
+
+        apply_phaser_damage(&mut galaxy, 1200.0, &mut MockOutput::new());
+        let shields: Vec<f64> = galaxy.sector_map().klingons.iter().map(|k| k.shields).collect();
+        assert_eq!(shields, vec![200.0 - 600.0 / 3.0, 200.0 - 600.0 / 4.0]);
+        assert_eq!(calculate_phaser_energy(500.0, true, CombatModel::Deterministic, galaxy.rng_mut()), 250.0);
+        assert_eq!(galaxy.rng().draws(), draws);
+    }
+
+    #[test]
+    fn decloaked_romulans_take_a_share_of_the_volley() {
+        use crate::models::romulan::Romulan;
+
+        let config = GameConfig { combat: CombatModel::Deterministic, ..Default::default() };
+        let mut galaxy = Galaxy::new_with_config(42, config);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        let klingon = Klingon::new(SectorPosition { x: 4, y: 1 });
+        galaxy.sector_map_mut().set(klingon.sector, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+        for (x, y, cloaked) in [(8, 4, false), (1, 8, true)] {
+            let romulan = Romulan { cloaked, ..Romulan::new(SectorPosition { x, y }, 300.0) };
+            galaxy.sector_map_mut().set(romulan.sector, SectorContent::Romulan);
+            galaxy.sector_map_mut().romulans.push(romulan);
+        }
+
+        let mut output = MockOutput::new();
+        let destroyed = apply_phaser_damage(&mut galaxy, 1200.0, &mut output);
+        assert_eq!(galaxy.sector_map().klingons[0].shields, 200.0 - 600.0 / 3.0);
+        assert_eq!(destroyed.klingons, vec![SectorPosition { x: 4, y: 1 }]);
+        let shields: Vec<f64> = galaxy.sector_map().romulans.iter().map(|r| r.shields).collect();
+        assert_eq!(shields, vec![300.0 - 600.0 / 4.0, 300.0]);
+        assert!(output.contains("150 UNIT HIT ON ROMULAN AT SECTOR 8,4"));
+
+        // With the Klingon gone, the warbird alone is a target
+        galaxy.sector_map_mut().klingons.clear();
+        assert!(can_fire_phasers(&galaxy, 100.0, &mut MockOutput::new()));
+        galaxy.sector_map_mut().romulans[0].cloaked = true;
+        assert!(!can_fire_phasers(&galaxy, 100.0, &mut MockOutput::new()));
+    }
+
+    #[test]
+    fn hot_phasers_overload_past_the_heat_limit() {
+        use crate::models::rng::SequenceRng;
+
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.enterprise_mut().heat_phasers(PHASER_HEAT_LIMIT - 200.0);
+        let mut output = MockOutput::new();
+        heat_phasers(&mut galaxy, 100.0, &mut output);
+        assert!(output.messages.is_empty());
+
+        // 400 units past the limit: a 40% chance, missed by the 0.5 draw
+        // and taken by the 0.3
+        galaxy.rng_mut().set_source(SequenceRng::new([0.5, 0.3]));
+        heat_phasers(&mut galaxy, 500.0, &mut output);
+        output.expect_line_containing("PHASER BANKS OVERHEATING (140%)");
+        assert!(!galaxy.enterprise().is_damaged(Device::PhaserControl));
+        galaxy.enterprise_mut().cool_phasers(100.0);
+        heat_phasers(&mut galaxy, 100.0, &mut output);
+        output.expect_line_containing("PHASER BANKS OVERLOAD");
+        assert!(galaxy.enterprise().is_damaged(Device::PhaserControl));
+    }
+
     // ========== Distance calculation tests ==========
 
     #[test]
@@ -3,6 +3,8 @@ use crate::models::constants::Device;
 use crate::models::enterprise::ShieldControlError;
 use crate::models::errors::{GameError, GameResult};
 use crate::models::galaxy::Galaxy;
+use crate::models::ledger::EnergyUse;
+use crate::services::commands::{self, CommandId};
 
 /// Transfers energy between shields and main power reserves (Command 5)
 ///
@@ -53,9 +55,12 @@ pub fn shield_control(
     }
 
     // Attempt to transfer energy
+    let previous = galaxy.enterprise().shields();
     match galaxy.enterprise_mut().shield_control(units) {
         Ok(()) => {
             // Success - energy transferred, return to command prompt
+            galaxy.record_energy(EnergyUse::ShieldTransfer, units - previous);
+            commands::charge_time(galaxy, CommandId::ShieldControl);
         }
         Err(ShieldControlError::InsufficientEnergy) => {
             // Return error instead of recursion - caller will handle retry
@@ -73,3 +78,209 @@ pub fn shield_control(
     }
     Ok(())
 }
+
+/// Raises the shields to a preset level without the numeric prompt
+/// (SHIELDS UP). The level before the raise is remembered for
+/// [`shields_down`], and the transfer is recorded in the energy ledger.
+///
+/// # Returns
+///
+/// * `Ok(())` on success or when shield control is damaged
+/// * `Err(GameError::InsufficientResources)` if `units` exceeds the energy available
+pub fn shields_up(galaxy: &mut Galaxy, units: f64, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if galaxy.enterprise().is_damaged(Device::ShieldControl) {
+        output.writeln("SHIELD CONTROL IS NON-OPERATIONAL");
+        return Ok(());
+    }
+
+    let previous = galaxy.enterprise().shields();
+    match galaxy.enterprise_mut().raise_shields(units) {
+        Ok(()) => {}
+        Err(ShieldControlError::InsufficientEnergy) => {
+            return Err(GameError::InsufficientResources {
+                required: units,
+                available: galaxy.enterprise().energy() + previous,
+            });
+        }
+        Err(_) => return Ok(()),
+    }
+    galaxy.record_energy(EnergyUse::ShieldTransfer, units - previous);
+    commands::charge_time(galaxy, CommandId::ShieldsUp);
+    output.writeln(&format!("SHIELDS UP: {} UNITS", units as i32));
+    Ok(())
+}
+
+/// Returns the shields to their level before the last SHIELDS UP, or
+/// lowers them completely (SHIELDS DOWN). The transfer is recorded in the
+/// energy ledger.
+pub fn shields_down(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if galaxy.enterprise().is_damaged(Device::ShieldControl) {
+        output.writeln("SHIELD CONTROL IS NON-OPERATIONAL");
+        return Ok(());
+    }
+
+    let previous = galaxy.enterprise().shields();
+    if galaxy.enterprise_mut().restore_shields().is_err() {
+        return Ok(());
+    }
+    let shields = galaxy.enterprise().shields();
+    galaxy.record_energy(EnergyUse::ShieldTransfer, shields - previous);
+    commands::charge_time(galaxy, CommandId::ShieldsDown);
+    if shields > 0.0 {
+        output.writeln(&format!("SHIELDS RESTORED TO {} UNITS", shields as i32));
+    } else {
+        output.writeln("SHIELDS DOWN");
+    }
+    Ok(())
+}
+
+/// Red alert on entering a quadrant with Klingons while the shields are
+/// dangerously low (spec section 4.2).
+pub fn red_alert(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+    if galaxy.red_alert() {
+        output.writeln("COMBAT AREA      CONDITION RED");
+        output.writeln("   SHIELDS DANGEROUSLY LOW");
+    }
+}
+
+/// Auto-shield policy, run by the game loop when the Enterprise enters a
+/// quadrant. If the ruleset enables it, Klingons are present and the shields
+/// are below the policy floor, moves the policy's units into the shields
+/// before the enemy can fire, and tells the player.
+pub fn auto_shields(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let Some(policy) = galaxy.ruleset().auto_shields else {
+        return;
+    };
+    let enterprise = galaxy.enterprise();
+    if galaxy.sector_map().klingons.is_empty()
+        || enterprise.shields() >= policy.floor
+        || enterprise.is_damaged(Device::ShieldControl)
+        || enterprise.is_destroyed()
+    {
+        return;
+    }
+    if enterprise.energy() < policy.units {
+        output.writeln("AUTO-SHIELDS: NOT ENOUGH ENERGY TO RAISE SHIELDS");
+        return;
+    }
+
+    let target = enterprise.shields() + policy.units;
+    if galaxy.enterprise_mut().shield_control(target).is_ok() {
+        galaxy.record_energy(EnergyUse::ShieldTransfer, policy.units);
+        output.writeln(&format!(
+            "AUTO-SHIELDS: {} UNITS TRANSFERRED, SHIELDS NOW AT {}",
+            policy.units as i32, target as i32
+        ));
+    }
+}
+
+/// Announces the energy the raised shields have drawn from main power
+/// since the last announcement, under the shield drain rule.
+pub fn report_shield_drain(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let drain = galaxy.take_shield_drain();
+    if drain > 0.0 {
+        output.writeln(&format!("SHIELD GENERATORS DREW {} UNITS FROM MAIN POWER", drain.ceil() as i32));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::klingon::Klingon;
+    use crate::models::position::SectorPosition;
+
+    #[test]
+    fn shield_drain_is_announced_once() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { shield_drain: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        galaxy.enterprise_mut().set_shields(100.0);
+        galaxy.advance_time(0.1);
+        galaxy.advance_time(1.0);
+        let mut output = MockOutput::new();
+        report_shield_drain(&mut galaxy, &mut output);
+        report_shield_drain(&mut galaxy, &mut output);
+        assert_eq!(output.messages, vec!["SHIELD GENERATORS DREW 11 UNITS FROM MAIN POWER\n".to_string()]);
+    }
+
+    #[test]
+    fn quick_shields_round_trip_through_the_ledger() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().set_shields(100.0);
+        let mut output = MockOutput::new();
+
+        shields_up(&mut galaxy, 600.0, &mut output).unwrap();
+        assert_eq!(galaxy.enterprise().shields(), 600.0);
+        shields_down(&mut galaxy, &mut output).unwrap();
+        assert_eq!(galaxy.enterprise().shields(), 100.0);
+
+        let amounts: Vec<f64> = galaxy.energy_ledger().entries().iter().map(|e| e.amount).collect();
+        assert_eq!(amounts, vec![500.0, -500.0]);
+        assert_eq!(
+            output.messages,
+            vec!["SHIELDS UP: 600 UNITS\n".to_string(), "SHIELDS RESTORED TO 100 UNITS\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn shields_up_reports_insufficient_energy() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().set_energy(200.0);
+        let result = shields_up(&mut galaxy, 500.0, &mut MockOutput::new());
+        assert!(matches!(result, Err(GameError::InsufficientResources { .. })));
+        assert!(galaxy.energy_ledger().entries().is_empty());
+    }
+
+    fn galaxy_with_auto_shields() -> Galaxy {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::{AutoShieldPolicy, Ruleset};
+
+        let ruleset = Ruleset { auto_shields: Some(AutoShieldPolicy::default()), ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        galaxy.sector_map_mut().klingons.push(Klingon::new(SectorPosition { x: 1, y: 1 }));
+        galaxy
+    }
+
+    #[test]
+    fn auto_shields_engage_below_floor_with_klingons() {
+        let mut galaxy = galaxy_with_auto_shields();
+        galaxy.enterprise_mut().set_shields(50.0);
+        let mut output = MockOutput::new();
+
+        auto_shields(&mut galaxy, &mut output);
+        assert_eq!(galaxy.enterprise().shields(), 350.0);
+        assert_eq!(galaxy.energy_ledger().total(EnergyUse::ShieldTransfer), 300.0);
+        assert_eq!(output.messages, vec!["AUTO-SHIELDS: 300 UNITS TRANSFERRED, SHIELDS NOW AT 350\n".to_string()]);
+
+        // Already above the floor: nothing more happens
+        auto_shields(&mut galaxy, &mut output);
+        assert_eq!(galaxy.enterprise().shields(), 350.0);
+    }
+
+    #[test]
+    fn auto_shields_need_energy_and_are_off_by_default() {
+        let mut galaxy = galaxy_with_auto_shields();
+        galaxy.enterprise_mut().set_energy(100.0);
+        let mut output = MockOutput::new();
+        auto_shields(&mut galaxy, &mut output);
+        assert_eq!(galaxy.enterprise().shields(), 0.0);
+        assert_eq!(output.messages, vec!["AUTO-SHIELDS: NOT ENOUGH ENERGY TO RAISE SHIELDS\n".to_string()]);
+
+        let mut galaxy = Galaxy::new(42);
+        galaxy.sector_map_mut().klingons.push(Klingon::new(SectorPosition { x: 1, y: 1 }));
+        let mut output = MockOutput::new();
+        auto_shields(&mut galaxy, &mut output);
+        assert_eq!(galaxy.enterprise().shields(), 0.0);
+        assert!(output.messages.is_empty());
+    }
+
+    #[test]
+    fn numeric_transfer_is_recorded() {
+        let mut galaxy = Galaxy::new(42);
+        shield_control(&mut galaxy, &mut MockInput::new(vec!["250"]), &mut MockOutput::new()).unwrap();
+        assert_eq!(galaxy.energy_ledger().total(EnergyUse::ShieldTransfer), 250.0);
+    }
+}
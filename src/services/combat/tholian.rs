@@ -0,0 +1,72 @@
+use crate::io::OutputWriter;
+use crate::models::constants::SectorContent;
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::models::tholian::Tholian;
+use crate::ui::presenters::Emit;
+
+/// A Tholian in the quadrant crawls one sector along the edge, leaving web
+/// where it was. It waits while the way is blocked, so once it comes back
+/// around to its own web the quadrant is fenced in. Called once per turn.
+pub fn spin_web(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let Some(tholian) = galaxy.sector_map().tholian else {
+        return;
+    };
+    let (from, to) = (tholian.sector, tholian.next_sector());
+    if !galaxy.sector_map().is_empty(to) {
+        return;
+    }
+    let map = galaxy.sector_map_mut();
+    map.set(from, SectorContent::Web);
+    map.set(to, SectorContent::Tholian);
+    map.tholian = Some(Tholian::new(to));
+    output.emit(OutputEvent::WebSpun { at: from });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::position::{QuadrantPosition, SectorPosition};
+
+    fn galaxy_with_tholian(at: SectorPosition) -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = Default::default();
+        galaxy.enterprise_mut().move_to(QuadrantPosition { x: 4, y: 4 }, SectorPosition { x: 4, y: 4 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 4 }, SectorContent::Enterprise);
+        galaxy.sector_map_mut().set(at, SectorContent::Tholian);
+        galaxy.sector_map_mut().tholian = Some(Tholian::new(at));
+        galaxy
+    }
+
+    #[test]
+    fn tholians_spin_web_behind_them() {
+        let start = SectorPosition { x: 1, y: 1 };
+        let mut galaxy = galaxy_with_tholian(start);
+        let mut output = MockOutput::new();
+        spin_web(&mut galaxy, &mut output);
+        let at = SectorPosition { x: 2, y: 1 };
+        assert_eq!(galaxy.sector_map().get(start), SectorContent::Web);
+        assert_eq!(galaxy.sector_map().get(at), SectorContent::Tholian);
+        assert_eq!(galaxy.sector_map().tholian, Some(Tholian::new(at)));
+        assert_eq!(output.messages.concat(), "THOLIAN WEB SPUN AT SECTOR 1,1\n");
+    }
+
+    #[test]
+    fn tholians_wait_when_blocked_and_close_the_web() {
+        let mut galaxy = galaxy_with_tholian(SectorPosition { x: 3, y: 1 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 1 }, SectorContent::Star);
+        spin_web(&mut galaxy, &mut MockOutput::new());
+        assert_eq!(galaxy.sector_map().tholian.unwrap().sector, SectorPosition { x: 3, y: 1 });
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 3, y: 1 }), SectorContent::Tholian);
+
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 1 }, SectorContent::Empty);
+        for _ in 0..40 {
+            spin_web(&mut galaxy, &mut MockOutput::new());
+        }
+        // Back around at its first web, with every other edge sector spun
+        assert_eq!(galaxy.sector_map().tholian.unwrap().sector, SectorPosition { x: 2, y: 1 });
+        let webs = galaxy.sector_map().iter().filter(|&(_, c)| c == SectorContent::Web).count();
+        assert_eq!(webs, 27);
+    }
+}
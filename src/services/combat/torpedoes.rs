@@ -1,13 +1,19 @@
 use crate::io::{InputReader, OutputWriter};
-use crate::models::constants::{Device, SectorContent};
+use crate::models::constants::{
+    Device, SectorContent, COURT_MARTIAL_STARBASES, DEBRIS_TORPEDO_BLOCK_CHANCE, SALVO_MAX_TORPEDOES, SALVO_SPREAD, SECTOR_SIZE,
+    TORPEDO_DAMAGE_TO_COMMANDER, TORPEDO_DODGE_CHANCE_PER_SECTOR, TORPEDO_DODGE_MIN_DISTANCE,
+};
 use crate::models::errors::GameResult;
 use crate::models::galaxy::Galaxy;
+use crate::models::hooks::EventKind;
 use crate::models::navigation_types::Course;
+use crate::models::output_event::OutputEvent;
 use crate::models::position::SectorPosition;
-use crate::services::navigation;
-use crate::ui::presenters::CombatPresenter;
+use crate::services::{computer, navigation};
+use crate::ui::presenters::Emit;
 
-use super::klingon_attack::klingons_fire;
+use crate::services::turn::{self, PlayerAction};
+use super::phasers::calculate_distance;
 
 /// Check preconditions for firing torpedoes (spec section 6.4).
 /// Returns true if ready to fire, false otherwise.
@@ -29,9 +35,22 @@ fn check_torpedo_readiness(galaxy: &Galaxy, output: &mut dyn OutputWriter) -> bo
 
 /// Read and validate torpedo course input (spec section 6.4).
 /// Returns Some(course) if valid, None if cancelled.
-fn read_torpedo_course(io: &mut dyn InputReader) -> GameResult<Option<Course>> {
+///
+/// A target sector given as "X,Y" instead of a course is converted to one
+/// by the computer, as long as it is online.
+fn read_torpedo_course(
+    galaxy: &Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<Option<Course>> {
     loop {
         let input = io.read_line("TORPEDO COURSE (1-9)")?;
+        if input.contains(',') {
+            if let Some(course) = target_course(galaxy, &input, output) {
+                return Ok(Some(course));
+            }
+            continue;
+        }
         let value: f64 = match input.trim().parse() {
             Ok(v) => v,
             Err(_) => continue, // Invalid input, re-prompt
@@ -48,9 +67,47 @@ fn read_torpedo_course(io: &mut dyn InputReader) -> GameResult<Option<Course>> {
     }
 }
 
+/// The course to the sector in `input` ("X,Y"), plotted by the computer
+/// with the same math as its photon torpedo data. `None`, after saying why
+/// where it helps, if the computer is down or the sector is not a target.
+fn target_course(galaxy: &Galaxy, input: &str, output: &mut dyn OutputWriter) -> Option<Course> {
+    if !computer::computer_online(galaxy) {
+        output.writeln("COMPUTER DISABLED, ENTER A COURSE");
+        return None;
+    }
+    let coords: Vec<i32> = input.trim().split(',').filter_map(|c| c.trim().parse().ok()).collect();
+    let range = 1..=SECTOR_SIZE as i32;
+    let [x, y] = coords[..] else {
+        return None;
+    };
+    let target = SectorPosition { x, y };
+    let here = galaxy.enterprise().sector();
+    if !range.contains(&x) || !range.contains(&y) || target == here {
+        return None;
+    }
+    let (direction, _) = navigation::calculate_direction_and_distance(here, target);
+    output.writeln(&format!("COMPUTER COURSE = {:.2}", direction));
+    Course::new(direction).ok()
+}
+
 /// Handle Klingon hit by torpedo (spec section 6.4).
 fn handle_klingon_hit(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) -> GameResult<()> {
-    CombatPresenter::show_klingon_destroyed(output);
+    // A commander's shields can outlast a torpedo
+    let commander = galaxy
+        .sector_map_mut()
+        .klingons
+        .iter_mut()
+        .find(|k| k.sector == pos && k.is_commander());
+    if let Some(commander) = commander {
+        commander.shields -= TORPEDO_DAMAGE_TO_COMMANDER;
+        if commander.is_alive() {
+            let remaining = commander.shields;
+            output.emit(OutputEvent::HitOnKlingon { at: pos, damage: TORPEDO_DAMAGE_TO_COMMANDER, shields_left: remaining });
+            return Ok(());
+        }
+    }
+
+    output.emit(OutputEvent::KlingonDestroyed);
 
     // Atomically destroy Klingon
     galaxy.destroy_klingon(pos)?;
@@ -58,16 +115,120 @@ fn handle_klingon_hit(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn
     // Remove from klingons vector
     galaxy.sector_map_mut().klingons.retain(|k| k.sector != pos);
 
+    if let Some(damage) = galaxy.ruleset().torpedo_splash {
+        apply_splash_damage(galaxy, pos, damage, output)?;
+    }
+
     // Victory check moved to game loop / GameEngine
     Ok(())
 }
 
+/// Torpedo dodging variant: a Klingon at least
+/// `TORPEDO_DODGE_MIN_DISTANCE` sectors away may slip into a neighboring
+/// empty sector, with a chance growing with the distance and the
+/// difficulty. Returns whether it did. No draws are made under the
+/// original rules.
+fn dodge(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) -> bool {
+    if !galaxy.ruleset().torpedo_dodging {
+        return false;
+    }
+    let distance = calculate_distance(galaxy.enterprise().sector(), pos);
+    if distance < TORPEDO_DODGE_MIN_DISTANCE {
+        return false;
+    }
+    let chance = distance * TORPEDO_DODGE_CHANCE_PER_SECTOR * galaxy.ruleset().difficulty.torpedo_dodge_factor();
+    if galaxy.rng_mut().unit("torpedo.dodge") >= chance {
+        return false;
+    }
+    let range = 1..=SECTOR_SIZE as i32;
+    let open: Vec<SectorPosition> = (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| SectorPosition { x: pos.x + dx, y: pos.y + dy }))
+        .filter(|s| range.contains(&s.x) && range.contains(&s.y))
+        .filter(|&s| galaxy.sector_map().get(s) == SectorContent::Empty)
+        .collect();
+    if open.is_empty() {
+        return false;
+    }
+    let to = open[(galaxy.rng_mut().unit("torpedo.dodge_sector") * open.len() as f64).floor() as usize];
+
+    let content = galaxy.sector_map().get(pos);
+    galaxy.sector_map_mut().set(pos, SectorContent::Empty);
+    galaxy.sector_map_mut().set(to, content);
+    if let Some(klingon) = galaxy.sector_map_mut().klingons.iter_mut().find(|k| k.sector == pos) {
+        klingon.sector = to;
+    }
+    output.writeln(&format!("KLINGON AT SECTOR {},{} DODGES TO SECTOR {},{}", pos.x, pos.y, to.x, to.y));
+    true
+}
+
+/// Splash damage variant: the blast hits every Klingon in the eight
+/// sectors around the one destroyed, one event per Klingon.
+fn apply_splash_damage(
+    galaxy: &mut Galaxy,
+    center: SectorPosition,
+    damage: f64,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let mut destroyed = Vec::new();
+    for klingon in galaxy.sector_map_mut().klingons.iter_mut() {
+        let (dx, dy) = (klingon.sector.x - center.x, klingon.sector.y - center.y);
+        if dx.abs().max(dy.abs()) != 1 || !klingon.is_alive() {
+            continue;
+        }
+        klingon.shields -= damage;
+        output.emit(OutputEvent::SplashHit { at: klingon.sector, damage, shields_left: klingon.shields });
+        if !klingon.is_alive() {
+            destroyed.push(klingon.sector);
+        }
+    }
+
+    for pos in destroyed {
+        output.emit(OutputEvent::KlingonDestroyed);
+        galaxy.destroy_klingon(pos)?;
+    }
+    galaxy.sector_map_mut().klingons.retain(|k| k.is_alive());
+    Ok(())
+}
+
+/// Handle Romulan hit by torpedo, cloaked or not.
+fn handle_romulan_hit(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) {
+    output.emit(OutputEvent::RomulanDestroyed);
+    galaxy.destroy_romulan(pos);
+}
+
+/// Handle Tholian hit by torpedo; its web goes with it.
+fn handle_tholian_hit(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) {
+    output.writeln("*** THOLIAN DESTROYED ***");
+    if galaxy.destroy_tholian(pos) {
+        output.writeln("THE THOLIAN WEB DISSOLVES");
+    }
+}
+
 /// Handle starbase hit by torpedo (spec section 6.4).
 fn handle_starbase_hit(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) {
     output.writeln("*** STAR BASE DESTROYED ***  .......CONGRATULATIONS");
 
     // Atomically destroy starbase
-    galaxy.destroy_starbase(pos);
+    galaxy.destroy_starbase_by_torpedo(pos);
+
+    // A second one, or the last one, ends the game (see GameEngine::check_game_over)
+    if galaxy.starbases_destroyed_by_enterprise() < COURT_MARTIAL_STARBASES && galaxy.total_starbases() > 0 {
+        output.writeln("STARFLEET COMMAND REVIEWING YOUR RECORD TO CONSIDER COURT MARTIAL!");
+    }
+}
+
+/// A torpedo that left the quadrant may fly on into an inhabited world,
+/// with a chance set by mods (see `EventKind::StrayTorpedo`). The stock
+/// galaxy has no planets, so nothing is drawn from the RNG unless a mod
+/// makes it possible.
+fn stray_torpedo(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let chance = galaxy.mods().event_weight(EventKind::StrayTorpedo, 0.0);
+    if chance <= 0.0 || galaxy.rng_mut().unit("torpedo.stray") >= chance {
+        return;
+    }
+    galaxy.record_diplomatic_incident();
+    output.writeln("THE TORPEDO HAS STRUCK AN INHABITED WORLD BEYOND THE QUADRANT");
+    output.writeln("THE FEDERATION COUNCIL LODGES A FORMAL PROTEST WITH STARFLEET COMMAND");
 }
 
 /// Fire torpedo along trajectory and check for hits (spec section 6.4).
@@ -79,7 +240,7 @@ fn fire_torpedo_trajectory(galaxy: &mut Galaxy, course: Course, output: &mut dyn
     let mut x = galaxy.enterprise().sector().x as f64;
     let mut y = galaxy.enterprise().sector().y as f64;
 
-    output.writeln("TORPEDO TRACK:");
+    output.emit(OutputEvent::TorpedoFired);
 
     // Travel sector-by-sector
     loop {
@@ -88,12 +249,13 @@ fn fire_torpedo_trajectory(galaxy: &mut Galaxy, course: Course, output: &mut dyn
 
         // Boundary check: outside quadrant?
         if !(0.5..8.5).contains(&x) || !(0.5..8.5).contains(&y) {
-            output.writeln("TORPEDO MISSED");
+            output.emit(OutputEvent::TorpedoMissed);
+            stray_torpedo(galaxy, output);
             return Ok(());
         }
 
         // Print current position as truncated integers
-        output.writeln(&format!("{},{}", x as i32, y as i32));
+        output.emit(OutputEvent::TorpedoTrack { x: x as i32, y: y as i32 });
 
         // Check sector at rounded position
         let check_x = (x + 0.5).floor() as i32;
@@ -105,15 +267,41 @@ fn fire_torpedo_trajectory(galaxy: &mut Galaxy, course: Course, output: &mut dyn
 
         // Check what's in this sector
         match galaxy.sector_map().get(check_pos) {
-            SectorContent::Empty => continue, // Keep traveling
-            SectorContent::Klingon => {
+            SectorContent::Empty | SectorContent::Mine => continue, // Keep traveling
+            SectorContent::Klingon | SectorContent::KlingonCommander => {
+                if dodge(galaxy, check_pos, output) {
+                    continue; // The torpedo flies on
+                }
                 handle_klingon_hit(galaxy, check_pos, output)?;
                 return Ok(());
             }
+            SectorContent::Romulan => {
+                handle_romulan_hit(galaxy, check_pos, output);
+                return Ok(());
+            }
+            SectorContent::Tholian => {
+                handle_tholian_hit(galaxy, check_pos, output);
+                return Ok(());
+            }
+            SectorContent::Web => {
+                output.writeln("TORPEDO CAUGHT IN THE THOLIAN WEB");
+                return Ok(());
+            }
+            SectorContent::Debris => {
+                if galaxy.rng_mut().unit("torpedo.debris") < DEBRIS_TORPEDO_BLOCK_CHANCE {
+                    output.writeln("TORPEDO LOST IN THE DEBRIS");
+                    return Ok(());
+                }
+                continue;
+            }
             SectorContent::Star => {
                 output.writeln("YOU CAN'T DESTROY STARS SILLY");
                 return Ok(());
             }
+            SectorContent::BlackHole => {
+                output.writeln("TORPEDO SWALLOWED BY A BLACK HOLE");
+                return Ok(());
+            }
             SectorContent::Starbase => {
                 handle_starbase_hit(galaxy, check_pos, output);
                 return Ok(());
@@ -128,7 +316,8 @@ fn fire_torpedo_trajectory(galaxy: &mut Galaxy, course: Course, output: &mut dyn
 
 /// Fires a photon torpedo in a specified direction (Command 4)
 ///
-/// Prompts the player for a course direction (1-9) and launches a photon torpedo
+/// Prompts the player for a course direction (1-9), or a target sector
+/// ("X,Y") for the computer to plot a course to, and launches a photon torpedo
 /// that travels in a straight line until it hits a target (Klingon, star, or starbase)
 /// or exits the sector. Klingons are destroyed on hit, stars block the torpedo,
 /// and hitting a starbase is heavily penalized.
@@ -158,33 +347,99 @@ pub fn fire_torpedoes(
     }
 
     // Phase 2: Get course input (0 = cancel)
-    let course = match read_torpedo_course(io)? {
+    let course = match read_torpedo_course(galaxy, io, output)? {
         Some(c) => c,
         None => return Ok(()),
     };
 
-    // Phase 3: Deduct torpedo BEFORE firing (spec step 2)
+    // Phase 3: Fire, and the Klingons fire back (spec 8.1)
+    turn::resolve(galaxy, &PlayerAction::Torpedo { course }, output)
+}
+
+/// Whether `count` torpedoes can be fired: the tubes are working and
+/// there are that many left, one for a single torpedo.
+pub fn can_fire_torpedoes(galaxy: &Galaxy, count: i32, output: &mut dyn OutputWriter) -> bool {
+    check_torpedo_readiness(galaxy, output)
+        && (1..=SALVO_MAX_TORPEDOES.min(galaxy.enterprise().torpedoes())).contains(&count)
+}
+
+/// Launch one torpedo on `course` and track it to whatever it hits.
+pub fn launch_torpedo(galaxy: &mut Galaxy, course: Course, output: &mut dyn OutputWriter) -> GameResult<()> {
+    // Deduct torpedo BEFORE firing (spec step 2)
     let _ = galaxy.enterprise_mut().consume_torpedo();
+    fire_torpedo_trajectory(galaxy, course, output)
+}
+
+/// Fires a spread of photon torpedoes (Command T)
+///
+/// Prompts for a course, as for a single torpedo, and how many torpedoes
+/// to fire (up to `SALVO_MAX_TORPEDOES`). The first flies on the course and
+/// the others `SALVO_SPREAD` to either side of it, each tracked on its own
+/// and each using up a torpedo. The salvo stops early once the quadrant is
+/// clear of Klingons, and the Klingons return fire once, after the last.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for the course and the number of torpedoes
+/// * `output` - Output writer for displaying results
+///
+/// # Returns
+///
+/// * `Ok(())` on successful execution, including a cancelled salvo
+/// * `Err` if I/O operations fail
+pub fn fire_salvo(galaxy: &mut Galaxy, io: &mut dyn InputReader, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if !check_torpedo_readiness(galaxy, output) {
+        return Ok(());
+    }
+    let Some(course) = read_torpedo_course(galaxy, io, output)? else {
+        return Ok(());
+    };
+    let most = SALVO_MAX_TORPEDOES.min(galaxy.enterprise().torpedoes());
+    let input = io.read_line(&format!("TORPEDOES IN SALVO (1-{})", most))?;
+    let count = match input.trim().parse::<i32>() {
+        Ok(n) if (1..=most).contains(&n) => n,
+        _ => return Ok(()),
+    };
 
-    // Phase 4: Fire along trajectory
-    fire_torpedo_trajectory(galaxy, course, output)?;
+    turn::resolve(galaxy, &PlayerAction::Salvo { course, count }, output)
+}
 
-    // Phase 5: Klingons fire back (after torpedo resolution, spec 8.1)
-    if klingons_fire(galaxy, output) {
-        return Ok(()); // Enterprise destroyed
+/// Launch a salvo of `count` torpedoes fanned out around `course`,
+/// stopping early once the quadrant is clear of Klingons.
+pub fn launch_salvo(galaxy: &mut Galaxy, course: Course, count: i32, output: &mut dyn OutputWriter) -> GameResult<()> {
+    let klingons_present = !galaxy.sector_map().klingons.is_empty();
+    for offset in [0.0, -SALVO_SPREAD, SALVO_SPREAD].into_iter().take(count as usize) {
+        if klingons_present && galaxy.sector_map().klingons.is_empty() {
+            break;
+        }
+        launch_torpedo(galaxy, spread_course(course, offset), output)?;
     }
     Ok(())
 }
 
+/// `course` turned by `offset`, wrapping around past 1 and 9.
+fn spread_course(course: Course, offset: f64) -> Course {
+    let value = course.value() + offset;
+    let value = if value < 1.0 {
+        value + 8.0
+    } else if value > 9.0 {
+        value - 8.0
+    } else {
+        value
+    };
+    Course::new(value).unwrap_or(course)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::io::test_utils::MockOutput;
+    use crate::io::testing::MockOutput;
     use crate::models::constants::SectorContent;
     use crate::models::galaxy::Galaxy;
     use crate::models::klingon::Klingon;
     use crate::models::position::SectorPosition;
-    use crate::models::sector_map::SectorMap;
+    use crate::models::sector_map::{SectorChange, SectorMap};
 
     /// Helper: Set up a combat scenario with specified parameters.
     fn setup_combat_scenario(
@@ -256,14 +511,174 @@ mod tests {
         galaxy.sector_map_mut().klingons.push(klingon);
 
         // Fire torpedo east (course 1.0)
+        let before = galaxy.sector_map().clone();
         let _ = fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut MockOutput::new());
 
         // Verify Klingon destroyed
         assert_eq!(galaxy.sector_map().klingons.len(), 0);
+        let changes = before.diff(galaxy.sector_map());
+        assert_eq!(
+            changes,
+            vec![SectorChange { pos: klingon_pos, from: SectorContent::Klingon, to: SectorContent::Empty }]
+        );
         assert_eq!(galaxy.sector_map().get(klingon_pos), SectorContent::Empty);
         assert_eq!(galaxy.total_klingons(), 0);
     }
 
+    #[test]
+    fn computer_plots_the_course_to_a_target_sector() {
+        use crate::io::testing::MockInput;
+
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 0.0, 200.0);
+        galaxy.set_total_klingons(1);
+        let mut output = MockOutput::new();
+        fire_torpedoes(&mut galaxy, &mut MockInput::new(vec!["2,2"]), &mut output).unwrap();
+        output.expect_line_containing("COMPUTER COURSE = 4.00");
+        assert!(galaxy.sector_map().klingons.is_empty());
+
+        // The Enterprise's own sector is no target; a damaged computer
+        // leaves the course to the player
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 5.0);
+        let mut output = MockOutput::new();
+        let mut input = MockInput::new(vec!["4,4", "2,2", "0"]);
+        fire_torpedoes(&mut galaxy, &mut input, &mut output).unwrap();
+        output.expect_line_containing("COMPUTER DISABLED, ENTER A COURSE");
+        assert_eq!(input.prompts().len(), 3);
+        assert_eq!(galaxy.sector_map().klingons.len(), 1);
+    }
+
+    #[test]
+    fn salvo_spreads_torpedoes_and_stops_once_the_quadrant_is_clear() {
+        use crate::io::testing::MockInput;
+
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.sector_map_mut().klingons.clear();
+        galaxy.sector_map_mut().set(SectorPosition { x: 2, y: 2 }, SectorContent::Empty);
+        let torpedoes = galaxy.enterprise().torpedoes();
+        let mut output = MockOutput::new();
+        let mut input = MockInput::new(vec!["1", "3"]);
+        fire_salvo(&mut galaxy, &mut input, &mut output).unwrap();
+        assert_eq!(input.prompts()[1], "TORPEDOES IN SALVO (1-3)");
+        assert_eq!(galaxy.enterprise().torpedoes(), torpedoes - 3);
+        let fired = output.messages.iter().filter(|m| m.contains("TORPEDO TRACK")).count();
+        assert_eq!(fired, 3);
+
+        // One hit clears the quadrant, saving the other two
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.set_total_klingons(1);
+        let mut input = MockInput::new(vec!["4", "3"]);
+        fire_salvo(&mut galaxy, &mut input, &mut MockOutput::new()).unwrap();
+        assert!(galaxy.sector_map().klingons.is_empty());
+        assert_eq!(galaxy.enterprise().torpedoes(), torpedoes - 1);
+    }
+
+    #[test]
+    fn salvo_courses_wrap_around() {
+        let course = |v: f64| Course::new(v).unwrap();
+        assert!((spread_course(course(1.0), -0.1).value() - 8.9).abs() < 1e-9);
+        assert!((spread_course(course(8.95), 0.1).value() - 1.05).abs() < 1e-9);
+        assert_eq!(spread_course(course(4.0), 0.1).value(), 4.1);
+    }
+
+    #[test]
+    fn distant_klingons_can_dodge_and_near_ones_cannot() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::rng::SequenceRng;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { torpedo_dodging: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 1, y: 4 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 1, y: 4 }, SectorContent::Enterprise);
+        let (near, far) = (SectorPosition { x: 3, y: 4 }, SectorPosition { x: 8, y: 4 });
+        galaxy.sector_map_mut().set(far, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(Klingon::new(far));
+
+        // Seven sectors away: a 35% chance, taken by the 0.1 draw
+        galaxy.rng_mut().set_source(SequenceRng::new([0.1, 0.0]));
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut output).unwrap();
+        output.expect_line_containing("KLINGON AT SECTOR 8,4 DODGES TO SECTOR 7,3");
+        output.expect_line_containing("TORPEDO MISSED");
+        let dodged = SectorPosition { x: 7, y: 3 };
+        assert_eq!(galaxy.sector_map().klingons[0].sector, dodged);
+        assert_eq!(galaxy.sector_map().get(dodged), SectorContent::Klingon);
+        assert_eq!(galaxy.sector_map().get(far), SectorContent::Empty);
+
+        galaxy.sector_map_mut().set(near, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(Klingon::new(near));
+        galaxy.set_total_klingons(2);
+        let draws = galaxy.rng_mut().draws();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut MockOutput::new()).unwrap();
+        assert_eq!(galaxy.rng_mut().draws(), draws);
+        assert_eq!(galaxy.sector_map().get(near), SectorContent::Empty);
+    }
+
+    #[test]
+    fn debris_stops_half_the_torpedoes() {
+        use crate::models::rng::SequenceRng;
+
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 1, y: 4 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 1, y: 4 }, SectorContent::Enterprise);
+        galaxy.sector_map_mut().set(SectorPosition { x: 3, y: 4 }, SectorContent::Debris);
+        let klingon = SectorPosition { x: 6, y: 4 };
+        galaxy.sector_map_mut().set(klingon, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(Klingon::new(klingon));
+        galaxy.set_total_klingons(2);
+
+        galaxy.rng_mut().set_source(SequenceRng::new([0.4, 0.6]));
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut output).unwrap();
+        output.expect_line_containing("TORPEDO LOST IN THE DEBRIS");
+        assert_eq!(galaxy.sector_map().get(klingon), SectorContent::Klingon);
+
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut MockOutput::new()).unwrap();
+        assert!(galaxy.sector_map().klingons.is_empty());
+    }
+
+    #[test]
+    fn torpedo_splash_damages_only_adjacent_klingons() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { torpedo_splash: Some(100.0), ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 4 }, SectorContent::Enterprise);
+        galaxy.set_total_klingons(3);
+
+        // Target east at (6,4); one neighbour weak, one strong, one out of reach
+        let target = SectorPosition { x: 6, y: 4 };
+        let weak = SectorPosition { x: 7, y: 5 };
+        let strong = SectorPosition { x: 6, y: 3 };
+        for (pos, shields) in [(target, 200.0), (weak, 50.0), (strong, 150.0)] {
+            let mut klingon = Klingon::new(pos);
+            klingon.shields = shields;
+            galaxy.sector_map_mut().set(pos, SectorContent::Klingon);
+            galaxy.sector_map_mut().klingons.push(klingon);
+        }
+
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut output).unwrap();
+
+        let klingons = &galaxy.sector_map().klingons;
+        assert_eq!(klingons.len(), 1);
+        assert_eq!((klingons[0].sector, klingons[0].shields), (strong, 50.0));
+        assert_eq!(galaxy.sector_map().get(weak), SectorContent::Empty);
+        assert_eq!(galaxy.total_klingons(), 1);
+        let text = output.messages.concat();
+        assert!(text.contains("100 UNIT BLAST HIT ON KLINGON AT SECTOR 7,5"));
+        assert!(text.contains("100 UNIT BLAST HIT ON KLINGON AT SECTOR 6,3\n   (50 LEFT)"));
+    }
+
     #[test]
     fn torpedo_blocked_by_star() {
         let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
@@ -308,6 +723,23 @@ mod tests {
         assert_eq!(galaxy.sector_map().starbase, None);
         assert_eq!(galaxy.sector_map().get(starbase_pos), SectorContent::Empty);
         assert_eq!(galaxy.total_starbases(), 0);
+        assert_eq!(galaxy.starbases_destroyed_by_enterprise(), 1);
+    }
+
+    #[test]
+    fn destroying_a_starbase_brings_a_warning_while_others_remain() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.set_total_starbases(2);
+        let starbase_pos = SectorPosition { x: 5, y: 4 };
+        galaxy.sector_map_mut().set(starbase_pos, SectorContent::Starbase);
+        galaxy.sector_map_mut().starbase = Some(starbase_pos);
+
+        let mut output = MockOutput::new();
+        handle_starbase_hit(&mut galaxy, starbase_pos, &mut output);
+        assert_eq!(
+            output.messages.last().unwrap(),
+            "STARFLEET COMMAND REVIEWING YOUR RECORD TO CONSIDER COURT MARTIAL!\n"
+        );
     }
 
     #[test]
@@ -322,6 +754,50 @@ mod tests {
         // Can't verify output but should not panic
     }
 
+    #[test]
+    fn stray_torpedoes_cause_incidents_only_when_a_mod_allows_them() {
+        use crate::models::constants::DIPLOMATIC_INCIDENT_PENALTY;
+        use crate::models::hooks::{ModHooks, ModRegistry};
+
+        /// Puts an inhabited world beyond every quadrant edge.
+        struct Planets;
+
+        impl ModHooks for Planets {
+            fn event_weight(&self, event: EventKind, weight: f64) -> f64 {
+                match event {
+                    EventKind::StrayTorpedo => 1.0,
+                    _ => weight,
+                }
+            }
+        }
+
+        let open_quadrant = |mods| {
+            let mut galaxy = Galaxy::new_with_mods(42, mods);
+            *galaxy.sector_map_mut() = SectorMap::new();
+            let quadrant = galaxy.enterprise().quadrant();
+            galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+            galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 4 }, SectorContent::Enterprise);
+            galaxy
+        };
+
+        let mut stock = open_quadrant(ModRegistry::new());
+        let draws = stock.rng_mut().draws();
+        fire_torpedo_trajectory(&mut stock, Course::new(3.0).unwrap(), &mut MockOutput::new()).unwrap();
+        assert_eq!(stock.diplomatic_incidents(), 0);
+        assert_eq!(stock.rng_mut().draws(), draws);
+
+        let mut mods = ModRegistry::new();
+        mods.register(Planets);
+        let mut expanded = open_quadrant(mods);
+        expanded.advance_time(1.0);
+        let rating = expanded.efficiency_rating();
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut expanded, Course::new(3.0).unwrap(), &mut output).unwrap();
+        output.expect_line_containing("STRUCK AN INHABITED WORLD");
+        assert_eq!(expanded.diplomatic_incidents(), 1);
+        assert_eq!(expanded.efficiency_rating(), rating - DIPLOMATIC_INCIDENT_PENALTY);
+    }
+
     #[test]
     fn torpedo_travels_through_empty_sectors() {
         let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
@@ -471,4 +947,101 @@ mod tests {
         // Klingon should be destroyed
         assert_eq!(galaxy.sector_map().klingons.len(), 0);
     }
+
+    #[test]
+    fn commanders_can_survive_a_torpedo() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        galaxy.set_total_klingons(1);
+        let klingon_pos = SectorPosition { x: 6, y: 4 };
+        galaxy.sector_map_mut().set(klingon_pos, SectorContent::KlingonCommander);
+        galaxy.sector_map_mut().klingons.push(Klingon::commander(klingon_pos, 400.0));
+
+        let course = Course::new(1.0).unwrap();
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, course, &mut output).unwrap();
+        assert!(output.messages.concat().contains("300 UNIT HIT ON KLINGON AT SECTOR 6,4"));
+        assert_eq!(galaxy.sector_map().klingons[0].shields, 100.0);
+        assert_eq!(galaxy.total_klingons(), 1);
+
+        fire_torpedo_trajectory(&mut galaxy, course, &mut MockOutput::new()).unwrap();
+        assert!(galaxy.sector_map().klingons.is_empty());
+        assert_eq!(galaxy.sector_map().get(klingon_pos), SectorContent::Empty);
+        assert_eq!(galaxy.total_klingons(), 0);
+    }
+
+    #[test]
+    fn torpedoes_destroy_cloaked_romulans() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::romulan::Romulan;
+        use crate::models::ruleset::Ruleset;
+
+        // A galaxy whose starting quadrant hides a Romulan
+        let ruleset = Ruleset { romulans: true, ..Default::default() };
+        let mut galaxy = (0..)
+            .map(|seed| Galaxy::with_options(seed, GalaxyOptions { ruleset, ..Default::default() }))
+            .find(|galaxy| !galaxy.sector_map().romulans.is_empty())
+            .unwrap();
+        let klingons = galaxy.total_klingons();
+        let q = galaxy.enterprise().quadrant();
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let sector = SectorPosition { x: 4, y: 4 };
+        galaxy.enterprise_mut().move_to(q, sector);
+        galaxy.sector_map_mut().set(sector, SectorContent::Enterprise);
+        let pos = SectorPosition { x: 4, y: 7 };
+        galaxy.sector_map_mut().set(pos, SectorContent::Romulan);
+        galaxy.sector_map_mut().romulans.push(Romulan::new(pos, 300.0));
+
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(7.0).unwrap(), &mut output).unwrap();
+        assert!(output.messages.concat().contains("*** ROMULAN WARBIRD DESTROYED ***"));
+        assert!(galaxy.sector_map().romulans.is_empty());
+        assert_eq!(galaxy.quadrants()[q].romulans, 0);
+        assert_eq!(galaxy.total_klingons(), klingons);
+    }
+
+    #[test]
+    fn black_holes_swallow_torpedoes() {
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        galaxy.set_total_klingons(1);
+        galaxy.sector_map_mut().set(SectorPosition { x: 3, y: 3 }, SectorContent::BlackHole);
+
+        // Course 4 runs from (4,4) through the black hole toward the Klingon at (2,2)
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(4.0).unwrap(), &mut output).unwrap();
+        assert!(output.messages.concat().ends_with("TORPEDO SWALLOWED BY A BLACK HOLE\n"));
+        assert_eq!(galaxy.sector_map().klingons.len(), 1);
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 3, y: 3 }), SectorContent::BlackHole);
+    }
+
+    #[test]
+    fn torpedoes_stop_in_the_web_and_destroy_its_spinner() {
+        use crate::models::tholian::Tholian;
+
+        let mut galaxy = setup_combat_scenario(42, 3000.0, 500.0, 200.0);
+        let tholian = SectorPosition { x: 8, y: 4 };
+        galaxy.sector_map_mut().set(tholian, SectorContent::Tholian);
+        galaxy.sector_map_mut().tholian = Some(Tholian::new(tholian));
+        // Spun along the top row and down to the Tholian
+        for x in 1..=8 {
+            galaxy.sector_map_mut().set(SectorPosition { x, y: 1 }, SectorContent::Web);
+        }
+        for y in 2..=3 {
+            galaxy.sector_map_mut().set(SectorPosition { x: 8, y }, SectorContent::Web);
+        }
+
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(2.0).unwrap(), &mut output).unwrap();
+        assert!(output.messages.concat().ends_with("TORPEDO CAUGHT IN THE THOLIAN WEB\n"));
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 7, y: 1 }), SectorContent::Web);
+
+        let mut output = MockOutput::new();
+        fire_torpedo_trajectory(&mut galaxy, Course::new(1.0).unwrap(), &mut output).unwrap();
+        assert!(output.messages.concat().ends_with("*** THOLIAN DESTROYED ***\nTHE THOLIAN WEB DISSOLVES\n"));
+        assert_eq!(galaxy.sector_map().tholian, None);
+        assert!(galaxy
+            .sector_map()
+            .iter()
+            .all(|(_, content)| !matches!(content, SectorContent::Tholian | SectorContent::Web)));
+    }
 }
@@ -0,0 +1,628 @@
+//! Command registry
+//!
+//! Built-in commands are described as data in [`COMMANDS`]; the dispatcher,
+//! the command menu, and the instructions all read that one table. Embedding
+//! applications can also add house-ruled commands (e.g. "SCAN PROBE") that
+//! appear in the command menu and dispatcher without modifying
+//! `services::game`.
+
+use std::fmt;
+
+use crate::game_engine::GameEngine;
+use crate::io::{InputReader, OutputWriter};
+use crate::models::config::CommandCosts;
+use crate::models::constants::{Condition, Device};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::services::{combat, computer, dilithium, navigation, probe, repair, scan};
+use crate::ui::presenters::EnterprisePresenter;
+
+/// Identifies a built-in command for the dispatcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandId {
+    SetCourse,
+    Impulse,
+    Autopilot,
+    ShortRangeScan,
+    LongRangeScan,
+    FirePhasers,
+    FireTorpedoes,
+    FireSalvo,
+    ShieldControl,
+    ShieldsUp,
+    ShieldsDown,
+    DamageReport,
+    LibraryComputer,
+    StarbaseRepair,
+    LaunchProbe,
+    BurnDilithium,
+    AssignRepairCrews,
+    Quit,
+}
+
+/// A built-in command and the rules for when it can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub id: CommandId,
+    /// What the player types, and the key a frontend binds it to.
+    pub key: &'static str,
+    /// Name shown in the command menu.
+    pub name: &'static str,
+    /// One-line description for the instructions.
+    pub help: &'static str,
+    /// Other accepted spellings, matched case-insensitively.
+    pub aliases: &'static [&'static str],
+    /// Devices that must be working for the command to run at all.
+    pub required_devices: &'static [Device],
+    /// Conditions the command may be used in; empty means any.
+    pub allowed_conditions: &'static [Condition],
+    /// Shown instead of running the command when a required device is damaged.
+    pub unavailable_message: &'static str,
+    /// Whether answers may be typed after the command on one line, as in
+    /// `AUTO 3 4`; see [`parse_command_line`].
+    pub takes_arguments: bool,
+}
+
+impl CommandSpec {
+    /// Check whether player input selects this command.
+    pub fn matches(&self, input: &str) -> bool {
+        let input = input.trim();
+        input.eq_ignore_ascii_case(self.key)
+            || self.aliases.iter().any(|alias| input.eq_ignore_ascii_case(alias))
+    }
+
+    /// Check the availability rules, returning the message to show if the
+    /// command cannot be used right now.
+    pub fn availability(&self, galaxy: &Galaxy) -> Result<(), String> {
+        let enterprise = galaxy.enterprise();
+        if self.required_devices.iter().any(|&d| enterprise.is_damaged(d)) {
+            return Err(self.unavailable_message.to_string());
+        }
+        let condition = galaxy.evaluate_condition();
+        if !self.allowed_conditions.is_empty() && !self.allowed_conditions.contains(&condition) {
+            return Err(format!("{} IS NOT AVAILABLE AT CONDITION {}", self.name, condition.label()));
+        }
+        Ok(())
+    }
+
+    /// The command's line in the command menu.
+    pub fn menu_line(&self) -> String {
+        format!("   {} = {}", self.key, self.name)
+    }
+}
+
+/// Every built-in command, in menu order.
+pub const COMMANDS: [CommandSpec; 18] = [
+    CommandSpec {
+        id: CommandId::SetCourse,
+        key: "0",
+        name: "SET COURSE",
+        help: "Navigate to a new location",
+        aliases: &["NAV"],
+        // Damaged engines still allow warp 0.2 (spec 6.0)
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::Impulse,
+        key: "I",
+        name: "IMPULSE",
+        help: "Move within the quadrant without warp",
+        aliases: &["IMP"],
+        required_devices: &[Device::ImpulseEngines],
+        allowed_conditions: &[],
+        unavailable_message: "IMPULSE ENGINES ARE DAMAGED",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::Autopilot,
+        key: "AUTO",
+        name: "AUTOPILOT",
+        help: "Fly to a quadrant, e.g. AUTO 3 4",
+        aliases: &["AP"],
+        required_devices: &[Device::WarpEngines],
+        allowed_conditions: &[],
+        unavailable_message: "WARP ENGINES ARE DAMAGED, AUTOPILOT UNAVAILABLE",
+        takes_arguments: true,
+    },
+    CommandSpec {
+        id: CommandId::ShortRangeScan,
+        key: "1",
+        name: "SHORT RANGE SENSOR SCAN",
+        help: "View current quadrant",
+        aliases: &["SRS"],
+        // Docking is still checked when the sensors are out (spec 6.1)
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::LongRangeScan,
+        key: "2",
+        name: "LONG RANGE SENSOR SCAN",
+        help: "View surrounding quadrants",
+        aliases: &["LRS"],
+        required_devices: &[Device::LongRangeSensors],
+        allowed_conditions: &[],
+        unavailable_message: "LONG RANGE SENSORS ARE INOPERABLE",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::FirePhasers,
+        key: "3",
+        name: "FIRE PHASERS",
+        help: "Attack with phasers",
+        aliases: &["PHA"],
+        required_devices: &[Device::PhaserControl],
+        allowed_conditions: &[],
+        unavailable_message: "PHASER CONTROL IS DISABLED",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::FireTorpedoes,
+        key: "4",
+        name: "FIRE PHOTON TORPEDOES",
+        help: "Attack with photon torpedoes",
+        aliases: &["TOR"],
+        required_devices: &[Device::PhotonTubes],
+        allowed_conditions: &[],
+        unavailable_message: "PHOTON TUBES ARE NOT OPERATIONAL",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::FireSalvo,
+        key: "T",
+        name: "TORPEDO SALVO",
+        help: "Fire up to three torpedoes in a spread",
+        aliases: &["SALVO"],
+        required_devices: &[Device::PhotonTubes],
+        allowed_conditions: &[],
+        unavailable_message: "PHOTON TUBES ARE NOT OPERATIONAL",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::ShieldControl,
+        key: "5",
+        name: "SHIELD CONTROL",
+        help: "Transfer energy to/from shields",
+        aliases: &["SHE"],
+        required_devices: &[Device::ShieldControl],
+        allowed_conditions: &[],
+        unavailable_message: "SHIELD CONTROL IS NON-OPERATIONAL",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::ShieldsUp,
+        key: "SHIELDS UP",
+        name: "RAISE SHIELDS TO PRESET",
+        help: "Raise shields without the prompt",
+        aliases: &["SU"],
+        required_devices: &[Device::ShieldControl],
+        allowed_conditions: &[],
+        unavailable_message: "SHIELD CONTROL IS NON-OPERATIONAL",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::ShieldsDown,
+        key: "SHIELDS DOWN",
+        name: "RESTORE PREVIOUS SHIELDS",
+        help: "Undo the last SHIELDS UP",
+        aliases: &["SD"],
+        required_devices: &[Device::ShieldControl],
+        allowed_conditions: &[],
+        unavailable_message: "SHIELD CONTROL IS NON-OPERATIONAL",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::DamageReport,
+        key: "6",
+        name: "DAMAGE CONTROL REPORT",
+        help: "View status of ship systems",
+        aliases: &["DAM"],
+        required_devices: &[Device::DamageControl],
+        allowed_conditions: &[],
+        unavailable_message: "DAMAGE CONTROL REPORT IS NOT AVAILABLE",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::LibraryComputer,
+        key: "7",
+        name: "CALL ON LIBRARY COMPUTER",
+        help: "Access computer functions",
+        aliases: &["COM"],
+        // A partially damaged computer stays online (see services::computer)
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::StarbaseRepair,
+        key: "8",
+        name: "STARBASE REPAIR",
+        help: "Prioritize a device repair while docked",
+        aliases: &["REP"],
+        required_devices: &[],
+        allowed_conditions: &[Condition::Docked],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    // Key 8 was already taken by STARBASE REPAIR when the probe arrived
+    CommandSpec {
+        id: CommandId::LaunchProbe,
+        key: "9",
+        name: "LAUNCH PROBE",
+        help: "Chart distant quadrants with a long-range probe (key 9; 8 is STARBASE REPAIR)",
+        aliases: &["PRO"],
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::BurnDilithium,
+        key: "D",
+        name: "BURN DILITHIUM RESERVE",
+        help: "Risk the dilithium crystals for 1000 units of energy",
+        aliases: &["DIL"],
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::AssignRepairCrews,
+        key: "R",
+        name: "ASSIGN REPAIR CREWS",
+        help: "Repair one device faster and the others slower",
+        aliases: &["CREWS"],
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+    CommandSpec {
+        id: CommandId::Quit,
+        key: "Q",
+        name: "QUIT",
+        help: "Abandon the mission",
+        aliases: &["QUIT"],
+        required_devices: &[],
+        allowed_conditions: &[],
+        unavailable_message: "",
+        takes_arguments: false,
+    },
+];
+
+/// Look up the built-in command selected by player input.
+pub fn find_command(input: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|spec| spec.matches(input))
+}
+
+impl CommandId {
+    /// The command's entry in [`COMMANDS`].
+    pub fn spec(self) -> &'static CommandSpec {
+        COMMANDS
+            .iter()
+            .find(|spec| spec.id == self)
+            .expect("every built-in command is in the registry")
+    }
+}
+
+/// Split a command line into the built-in command and the answer typed
+/// after it, e.g. "AUTO 3 4" into AUTOPILOT and "3 4". The whole line is
+/// tried first, so commands spelled with a space such as "SHIELDS UP" still
+/// match; the answer is empty when nothing follows the command. Only
+/// commands that take arguments are split, so a stray "3 200" is not read
+/// as an order to fire phasers.
+pub fn parse_command_line(input: &str) -> Option<(&'static CommandSpec, &str)> {
+    let input = input.trim();
+    if let Some(spec) = find_command(input) {
+        return Some((spec, ""));
+    }
+    let (word, rest) = input.split_once(char::is_whitespace)?;
+    find_command(word)
+        .filter(|spec| spec.takes_arguments)
+        .map(|spec| (spec, rest.trim()))
+}
+
+/// Run a built-in command against the galaxy, charging the stardates the
+/// config's command costs give it. Commands whose prompts can be cancelled
+/// charge only once carried out; the rest charge here. Quit does nothing
+/// here; ending the game is up to the caller.
+pub fn execute(
+    id: CommandId,
+    galaxy: &mut Galaxy,
+    quick_shield_units: f64,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let result = match id {
+        CommandId::SetCourse => navigation::navigate(galaxy, io, output),
+        CommandId::Impulse => navigation::impulse(galaxy, io, output),
+        CommandId::Autopilot => navigation::autopilot(galaxy, io, output),
+        CommandId::ShortRangeScan => scan::short_range_scan(galaxy, output),
+        CommandId::LongRangeScan => scan::long_range_scan(galaxy, output),
+        CommandId::FirePhasers => combat::fire_phasers(galaxy, io, output),
+        CommandId::FireTorpedoes => combat::fire_torpedoes(galaxy, io, output),
+        CommandId::FireSalvo => combat::fire_salvo(galaxy, io, output),
+        CommandId::ShieldControl => combat::shield_control(galaxy, io, output),
+        CommandId::ShieldsUp => combat::shields_up(galaxy, quick_shield_units, output),
+        CommandId::ShieldsDown => combat::shields_down(galaxy, output),
+        CommandId::DamageReport => {
+            EnterprisePresenter::show_damage_report(galaxy, output);
+            Ok(())
+        }
+        CommandId::LibraryComputer => computer::library_computer(galaxy, io, output),
+        CommandId::StarbaseRepair => repair::starbase_repair(galaxy, io, output),
+        CommandId::LaunchProbe => probe::launch_probe(galaxy, io, output),
+        CommandId::BurnDilithium => dilithium::burn_dilithium(galaxy, io, output),
+        CommandId::AssignRepairCrews => repair::assign_repair_crews(galaxy, io, output),
+        CommandId::Quit => Ok(()),
+    };
+    result?;
+    if matches!(
+        id,
+        CommandId::ShortRangeScan | CommandId::LongRangeScan | CommandId::DamageReport | CommandId::LibraryComputer
+    ) {
+        charge_time(galaxy, id);
+    }
+    Ok(())
+}
+
+/// Advance the clock by what `id` costs under the galaxy's config.
+pub fn charge_time(galaxy: &mut Galaxy, id: CommandId) {
+    let cost = time_cost(id, &galaxy.config().command_costs);
+    galaxy.charge_command_time(cost);
+}
+
+/// Stardates a command takes on top of any travel. Moves keep their own
+/// timing, and commands without an entry in the table are free.
+pub fn time_cost(id: CommandId, costs: &CommandCosts) -> f64 {
+    match id {
+        CommandId::ShortRangeScan => costs.short_range_scan,
+        CommandId::LongRangeScan => costs.long_range_scan,
+        CommandId::FirePhasers => costs.phasers,
+        CommandId::FireTorpedoes | CommandId::FireSalvo => costs.torpedoes,
+        CommandId::ShieldControl | CommandId::ShieldsUp | CommandId::ShieldsDown => costs.shields,
+        CommandId::DamageReport => costs.damage_report,
+        CommandId::LibraryComputer => costs.library_computer,
+        CommandId::LaunchProbe => costs.probe,
+        CommandId::SetCourse
+        | CommandId::Impulse
+        | CommandId::Autopilot
+        | CommandId::StarbaseRepair
+        | CommandId::BurnDilithium
+        | CommandId::AssignRepairCrews
+        | CommandId::Quit => 0.0,
+    }
+}
+
+/// Handler invoked when a custom command is entered at the command prompt.
+pub type CommandHandler = Box<
+    dyn FnMut(&mut GameEngine, &mut dyn InputReader, &mut dyn OutputWriter) -> GameResult<()>
+        + Send,
+>;
+
+/// A command registered by an embedding application.
+pub struct CustomCommand {
+    name: String,
+    help: String,
+    handler: CommandHandler,
+}
+
+impl CustomCommand {
+    /// Create a custom command. The name is what the player types at the
+    /// command prompt (matched case-insensitively); the help text is shown
+    /// next to it in the command menu.
+    pub fn new<F>(name: &str, help: &str, handler: F) -> Self
+    where
+        F: FnMut(&mut GameEngine, &mut dyn InputReader, &mut dyn OutputWriter) -> GameResult<()>
+            + Send
+            + 'static,
+    {
+        CustomCommand {
+            name: name.trim().to_uppercase(),
+            help: help.to_uppercase(),
+            handler: Box::new(handler),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn help(&self) -> &str {
+        &self.help
+    }
+
+    /// Check whether player input selects this command.
+    pub fn matches(&self, input: &str) -> bool {
+        input.trim().eq_ignore_ascii_case(&self.name)
+    }
+
+    /// Run the command against the engine.
+    pub fn execute(
+        &mut self,
+        engine: &mut GameEngine,
+        io: &mut dyn InputReader,
+        output: &mut dyn OutputWriter,
+    ) -> GameResult<()> {
+        (self.handler)(engine, io, output)
+    }
+
+    /// The command's line in the command menu.
+    pub fn menu_line(&self) -> String {
+        format!("   {} = {}", self.name, self.help)
+    }
+}
+
+impl fmt::Debug for CustomCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CustomCommand")
+            .field("name", &self.name)
+            .field("help", &self.help)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+
+    #[test]
+    fn built_ins_are_found_by_key_or_alias() {
+        assert_eq!(find_command("0").map(|s| s.id), Some(CommandId::SetCourse));
+        assert_eq!(find_command(" lrs ").map(|s| s.id), Some(CommandId::LongRangeScan));
+        assert_eq!(find_command("q").map(|s| s.id), Some(CommandId::Quit));
+        assert_eq!(find_command("QUIT").map(|s| s.id), Some(CommandId::Quit));
+        assert_eq!(find_command("shields up").map(|s| s.id), Some(CommandId::ShieldsUp));
+        assert_eq!(find_command("SD").map(|s| s.id), Some(CommandId::ShieldsDown));
+        assert_eq!(find_command("pro").map(|s| s.id), Some(CommandId::LaunchProbe));
+        assert_eq!(find_command("d").map(|s| s.id), Some(CommandId::BurnDilithium));
+        assert_eq!(find_command("crews").map(|s| s.id), Some(CommandId::AssignRepairCrews));
+        assert!(find_command("X").is_none());
+    }
+
+    #[test]
+    fn command_lines_split_into_command_and_answer() {
+        let (spec, answer) = parse_command_line(" auto  3 4 ").unwrap();
+        assert_eq!((spec.id, answer), (CommandId::Autopilot, "3 4"));
+        let (spec, answer) = parse_command_line("shields up").unwrap();
+        assert_eq!((spec.id, answer), (CommandId::ShieldsUp, ""));
+        assert_eq!(parse_command_line("ap 5 1").map(|(s, a)| (s.id, a)), Some((CommandId::Autopilot, "5 1")));
+        assert!(parse_command_line("3 200").is_none());
+        assert!(parse_command_line("scan probe").is_none());
+    }
+
+    #[test]
+    fn registry_keys_and_aliases_are_unique() {
+        let mut seen: Vec<String> = Vec::new();
+        for spec in &COMMANDS {
+            for word in std::iter::once(&spec.key).chain(spec.aliases) {
+                let word = word.to_uppercase();
+                assert!(!seen.contains(&word), "{} is bound twice", word);
+                seen.push(word);
+            }
+            assert_eq!(spec.id.spec().name, spec.name);
+            if !spec.required_devices.is_empty() {
+                assert!(!spec.unavailable_message.is_empty(), "{} needs a message", spec.name);
+            }
+        }
+    }
+
+    #[test]
+    fn damaged_required_device_makes_command_unavailable() {
+        let mut galaxy = Galaxy::new(42);
+        let phasers = find_command("3").unwrap();
+        assert_eq!(phasers.availability(&galaxy), Ok(()));
+
+        galaxy.enterprise_mut().damage_device(Device::PhaserControl, 1.0);
+        assert_eq!(phasers.availability(&galaxy), Err("PHASER CONTROL IS DISABLED".to_string()));
+    }
+
+    #[test]
+    fn allowed_conditions_restrict_availability() {
+        let galaxy = Galaxy::new(42);
+        let condition = galaxy.evaluate_condition();
+        assert_ne!(condition, Condition::Docked);
+
+        let undocked_only = CommandSpec {
+            allowed_conditions: &[Condition::Green, Condition::Yellow, Condition::Red],
+            ..COMMANDS[0]
+        };
+        let docked_only = CommandSpec {
+            allowed_conditions: &[Condition::Docked],
+            ..COMMANDS[0]
+        };
+        assert_eq!(undocked_only.availability(&galaxy), Ok(()));
+        assert_eq!(
+            docked_only.availability(&galaxy),
+            Err(format!("SET COURSE IS NOT AVAILABLE AT CONDITION {}", condition.label()))
+        );
+    }
+
+    #[test]
+    fn starbase_repair_requires_docking() {
+        let galaxy = Galaxy::new(42);
+        let repair = find_command("rep").unwrap();
+        assert_eq!(repair.id, CommandId::StarbaseRepair);
+        assert!(repair.availability(&galaxy).is_err());
+    }
+
+    #[test]
+    fn commands_take_the_time_the_config_gives_them() {
+        use crate::models::config::GameConfig;
+
+        let mut galaxy = Galaxy::new(42);
+        let stardate = galaxy.stardate();
+        execute(CommandId::LongRangeScan, &mut galaxy, 0.0, &mut MockInput::new(vec![]), &mut MockOutput::new()).unwrap();
+        assert_eq!(galaxy.stardate(), stardate);
+
+        let config = GameConfig { command_costs: CommandCosts::STRICT, ..Default::default() };
+        let mut galaxy = Galaxy::new_with_config(42, config);
+        for id in [CommandId::LongRangeScan, CommandId::ShieldsDown, CommandId::DamageReport] {
+            execute(id, &mut galaxy, 0.0, &mut MockInput::new(vec![]), &mut MockOutput::new()).unwrap();
+        }
+        let spent = CommandCosts::STRICT.long_range_scan + CommandCosts::STRICT.shields;
+        assert!((galaxy.stardate() - stardate - spent).abs() < 1e-9);
+        assert_eq!(galaxy.command_time(), spent);
+    }
+
+    #[test]
+    fn cancelled_commands_take_no_time() {
+        use crate::models::config::GameConfig;
+
+        let config = GameConfig { command_costs: CommandCosts::STRICT, ..Default::default() };
+        let mut galaxy = Galaxy::new_with_config(42, config);
+        let cancels = [
+            (CommandId::FireTorpedoes, vec!["0"]),
+            (CommandId::FirePhasers, vec!["0"]),
+            (CommandId::FireSalvo, vec!["1", "9"]),
+            (CommandId::ShieldControl, vec!["0"]),
+            (CommandId::LaunchProbe, vec!["0"]),
+        ];
+        for (id, answers) in cancels {
+            execute(id, &mut galaxy, 0.0, &mut MockInput::new(answers), &mut MockOutput::new()).unwrap();
+            assert_eq!(galaxy.command_time(), 0.0, "{:?}", id);
+        }
+
+        let mut input = MockInput::new(vec!["100"]);
+        execute(CommandId::ShieldControl, &mut galaxy, 0.0, &mut input, &mut MockOutput::new()).unwrap();
+        assert_eq!(galaxy.command_time(), CommandCosts::STRICT.shields);
+    }
+
+    #[test]
+    fn names_are_normalized_and_matched_case_insensitively() {
+        let command = CustomCommand::new(" scan probe ", "Launch a probe", |_, _, _| Ok(()));
+        assert_eq!(command.name(), "SCAN PROBE");
+        assert!(command.matches("scan probe"));
+        assert!(command.matches("  SCAN PROBE\n"));
+        assert!(!command.matches("scan"));
+        assert_eq!(command.menu_line(), "   SCAN PROBE = LAUNCH A PROBE");
+    }
+
+    #[test]
+    fn handler_receives_engine_and_io() {
+        let mut command = CustomCommand::new("REFUEL", "Top up energy", |engine, io, output| {
+            let amount: f64 = io.read_line("UNITS")?.trim().parse()?;
+            engine.galaxy_mut().enterprise_mut().add_energy(amount);
+            output.writeln("REFUELED");
+            Ok(())
+        });
+
+        let mut engine = GameEngine::new(42);
+        let before = engine.galaxy().enterprise().energy();
+        let mut output = MockOutput::new();
+        command
+            .execute(&mut engine, &mut MockInput::new(vec!["250"]), &mut output)
+            .unwrap();
+
+        assert_eq!(engine.galaxy().enterprise().energy(), before + 250.0);
+        assert_eq!(output.messages, vec!["REFUELED\n".to_string()]);
+    }
+}
@@ -1,16 +1,35 @@
 use crate::io::{InputReader, OutputWriter};
-use crate::models::constants::{Device, GALAXY_SIZE};
+use crate::models::constants::{Device, COMPUTER_PARTIAL_DAMAGE_LIMIT, GALAXY_SIZE};
+use crate::models::combat_odds::CombatOdds;
 use crate::models::errors::GameResult;
 use crate::models::galaxy::Galaxy;
-use crate::models::position::SectorPosition;
-use crate::ui::presenters::EnterprisePresenter;
+use crate::models::grid::QuadrantGrid;
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::region;
+use crate::models::status::StatusReport;
+use crate::services::navigation::{calculate_direction_and_distance, plan_route};
+use crate::ui::presenters::{CombatPresenter, EnterprisePresenter, GalaxyPresenter, RecordCell, WarPresenter};
 
 /// Accesses the ship's library computer functions (Command 7)
 ///
-/// Provides access to three computer functions:
+/// Provides access to these computer functions:
 /// - Option 0: Cumulative Galactic Record - Shows scanned quadrant data
 /// - Option 1: Status Report - Shows mission status and damage report
 /// - Option 2: Photon Torpedo Data - Calculates targeting information
+/// - Option 3: Starbase Nav Data - Direction and distance to the
+///   starbase in the current quadrant
+/// - Option 5: Region Names - Names the current quadrant and any other
+///   the player asks about
+/// - Option 6: War Status - Shows how the war is going (an extension;
+///   option 4 is left for the original game's distance calculator)
+/// - Option 7: Combat Odds - Expected damage and advice for the current
+///   quadrant (an extension)
+/// - Option 8: Quadrant Journal - Recorded events for a chosen quadrant
+///   (an extension)
+/// - Option 9: Galaxy Map - The galactic record with the Enterprise and
+///   region boundaries marked (an extension)
+/// - Option 10: Route Planner - Course, warp factor, energy, and time to
+///   reach a chosen quadrant (an extension)
 ///
 /// # Arguments
 ///
@@ -26,12 +45,16 @@ use crate::ui::presenters::EnterprisePresenter;
 /// # Specification
 ///
 /// See spec section 6.7 for full details on computer functions.
+///
+/// Unlike the original, a partially damaged computer (damage no worse than
+/// `COMPUTER_PARTIAL_DAMAGE_LIMIT`) stays online, but its galactic record
+/// shows garbled cells.
 pub fn library_computer(
     galaxy: &mut Galaxy,
     io: &mut dyn InputReader,
     output: &mut dyn OutputWriter,
 ) -> GameResult<()> {
-    if galaxy.enterprise().is_damaged(Device::Computer) {
+    if !computer_online(galaxy) {
         output.writeln("COMPUTER DISABLED");
         return Ok(());
     }
@@ -44,49 +67,87 @@ pub fn library_computer(
         "0" => cumulative_galactic_record(galaxy, output),
         "1" => status_report(galaxy, output),
         "2" => photon_torpedo_data(galaxy, io, output)?,
+        "3" => starbase_nav_data(galaxy, output),
+        "5" => region_names(galaxy, io, output)?,
+        "6" => WarPresenter::show_war_status(&galaxy.war_status(), output),
+        "7" => CombatPresenter::show_combat_odds(&CombatOdds::from_galaxy(galaxy), output),
+        "8" => quadrant_journal(galaxy, io, output)?,
+        "9" => galaxy_map(galaxy, output),
+        "10" => route_planner(galaxy, io, output)?,
         _ => print_computer_menu(output),
     }
     Ok(())
 }
 
+/// How badly the computer is damaged, as a positive number (0 = working).
+fn computer_damage(galaxy: &Galaxy) -> f64 {
+    (-galaxy.enterprise().devices()[Device::Computer as usize]).max(0.0)
+}
+
+/// Whether the computer is working well enough to use: undamaged, or no
+/// worse than `COMPUTER_PARTIAL_DAMAGE_LIMIT`.
+pub fn computer_online(galaxy: &Galaxy) -> bool {
+    computer_damage(galaxy) <= COMPUTER_PARTIAL_DAMAGE_LIMIT
+}
+
 /// Option 0 — Cumulative Galactic Record (spec section 6.7).
-fn cumulative_galactic_record(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
-    let qx = galaxy.enterprise().quadrant().x;
-    let qy = galaxy.enterprise().quadrant().y;
-    output.writeln(&format!("COMPUTER RECORD OF GALAXY FOR QUADRANT {},{}", qx, qy));
-
-    let border = "-------------------------------------------------";
-    for y in 0..GALAXY_SIZE {
-        output.writeln(border);
-        let mut cells: Vec<String> = Vec::new();
-        for x in 0..GALAXY_SIZE {
-            let val = galaxy.computer_memory()[y][x];
-            match val {
-                None => cells.push("???".to_string()),
-                Some(data) => cells.push(format!("{:03}", data.encoded())),
+/// With a partially damaged computer, each known cell is garbled to random
+/// digits with a chance that grows with the damage (up to one in two).
+fn cumulative_galactic_record(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let cells = recorded_cells(galaxy);
+    GalaxyPresenter::show_galactic_record(galaxy.enterprise().quadrant(), &cells, output);
+}
+
+/// Option 9 — Galaxy Map.
+/// The same record drawn as a map, with the Enterprise and the regions
+/// marked.
+fn galaxy_map(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let cells = recorded_cells(galaxy);
+    GalaxyPresenter::show_galaxy_map(galaxy.enterprise().quadrant(), &cells, output);
+}
+
+/// The encoded contents of each quadrant the computer has recorded, as
+/// a partially damaged computer reads them back, marked stale when old.
+fn recorded_cells(galaxy: &mut Galaxy) -> QuadrantGrid<Option<RecordCell>> {
+    let garble_chance = computer_damage(galaxy) / COMPUTER_PARTIAL_DAMAGE_LIMIT * 0.5;
+    let memory = *galaxy.computer_memory();
+    QuadrantGrid::from_fn(|pos| {
+        let encoded = match memory[pos] {
+            None => return None,
+            Some(_) if garble_chance > 0.0 && galaxy.rng_mut().unit("computer.garble") < garble_chance => {
+                galaxy.rng_mut().range("computer.garble_digits", 0..=999)
             }
-        }
-        output.writeln(&format!(
-            "| {} | {} | {} | {} | {} | {} | {} | {} |",
-            cells[0], cells[1], cells[2], cells[3], cells[4], cells[5], cells[6], cells[7]
-        ));
-    }
-    output.writeln(border);
+            Some(data) => data.encoded(),
+        };
+        Some(RecordCell { encoded, stale: galaxy.is_record_stale(pos) })
+    })
 }
 
 /// Option 1 — Status Report (spec section 6.7).
 /// Prints status info then falls through to the damage control report.
 fn status_report(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+    let report = StatusReport::from_galaxy(galaxy);
     output.writeln("   STATUS REPORT");
     output.writeln("");
-    output.writeln(&format!("NUMBER OF KLINGONS LEFT  = {}", galaxy.total_klingons()));
-    let stardates_left =
-        (galaxy.starting_stardate() + galaxy.mission_duration()) - galaxy.stardate();
-    output.writeln(&format!("NUMBER OF STARDATES LEFT = {}", stardates_left as i32));
-    output.writeln(&format!("NUMBER OF STARBASES LEFT = {}", galaxy.total_starbases()));
+    output.writeln(&format!("NUMBER OF KLINGONS LEFT  = {}", report.klingons_left));
+    output.writeln(&format!("NUMBER OF STARDATES LEFT = {}", report.stardates_left as i32));
+    output.writeln(&format!("NUMBER OF STARBASES LEFT = {}", report.starbases_left));
+    output.writeln(&format!("QUADRANTS EXPLORED       = {}%", report.exploration_percent as i32));
+    output.writeln(&format!("NUMBER OF TURNS TAKEN    = {}", report.turns));
+    if report.command_time > 0.0 {
+        output.writeln(&format!("TIME SPENT ON ORDERS     = {:.1}", report.command_time));
+    }
+    for (quadrant, stardates_left) in &report.starbases_under_attack {
+        output.writeln(&format!(
+            "STARBASE IN QUADRANT {},{} UNDER ATTACK, {} STARDATES LEFT",
+            quadrant.x,
+            quadrant.y,
+            stardates_left.max(0.0) as i32
+        ));
+    }
 
     // Falls through to damage control report (spec section 6.7)
-    EnterprisePresenter::show_damage_report(galaxy.enterprise(), output);
+    EnterprisePresenter::show_damage_report(galaxy, output);
 }
 
 /// Option 2 — Photon Torpedo Data (spec section 6.7).
@@ -168,57 +229,106 @@ fn use_calculator(
     Ok(())
 }
 
-/// Direction and distance calculation (spec section 7.4).
-/// Uses the original ratio-based algorithm from the spec.
-fn calculate_direction_and_distance(
-    source: SectorPosition,
-    target: SectorPosition,
-) -> (f64, f64) {
-    let delta_x = (target.x - source.x) as f64;
-    let delta_y = (source.y - target.y) as f64; // Inverted per spec
-
-    let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
-
-    // Direction calculation (spec section 7.4)
-    let direction = if delta_x >= 0.0 && delta_y >= 0.0 {
-        // Case 1: right and/or up
-        let base = if delta_x > 0.0 || delta_y > 0.0 {
-            1.0
-        } else {
-            5.0
-        };
-        if delta_y.abs() <= delta_x.abs() {
-            base + delta_y.abs() / delta_x.abs()
-        } else {
-            base + (delta_y.abs() - delta_x.abs() + delta_y.abs()) / delta_y.abs()
-        }
-    } else if delta_x < 0.0 && delta_y > 0.0 {
-        // Case 2: left and up
-        let base = 3.0;
-        if delta_y.abs() >= delta_x.abs() {
-            base + delta_x.abs() / delta_y.abs()
-        } else {
-            base + (delta_x.abs() - delta_y.abs() + delta_x.abs()) / delta_x.abs()
-        }
-    } else if delta_x >= 0.0 && delta_y < 0.0 {
-        // Case 3: right and down
-        let base = 7.0;
-        if delta_y.abs() >= delta_x.abs() {
-            base + delta_x.abs() / delta_y.abs()
-        } else {
-            base + (delta_x.abs() - delta_y.abs() + delta_x.abs()) / delta_x.abs()
-        }
-    } else {
-        // Case 4: left and down
-        let base = 5.0;
-        if delta_y.abs() <= delta_x.abs() {
-            base + delta_y.abs() / delta_x.abs()
-        } else {
-            base + (delta_y.abs() - delta_x.abs() + delta_y.abs()) / delta_y.abs()
+/// Option 3 — Starbase Nav Data.
+/// Direction and distance from the Enterprise to this quadrant's starbase.
+fn starbase_nav_data(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+    let Some(starbase) = galaxy.sector_map().starbase else {
+        output.writeln("MR. SPOCK REPORTS,  'SENSORS SHOW NO STARBASES IN THIS QUADRANT.'");
+        return;
+    };
+    let (direction, distance) = calculate_direction_and_distance(galaxy.enterprise().sector(), starbase);
+    output.writeln("FROM ENTERPRISE TO STARBASE:");
+    output.writeln(&format!("DIRECTION = {:.2}", direction));
+    output.writeln(&format!("DISTANCE  = {:.2}", distance));
+}
+
+/// Option 5 — Region Names.
+/// Names the Enterprise's quadrant, then any quadrant the player enters.
+fn region_names(
+    galaxy: &Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let here = galaxy.enterprise().quadrant();
+    if let Some(name) = region::quadrant_name(here) {
+        output.writeln(&format!("YOU ARE IN QUADRANT {},{}: {}", here.x, here.y, name));
+    }
+    if let Some(pos) = read_quadrant(io)? {
+        if let Some(name) = region::quadrant_name(pos) {
+            output.writeln(&format!("QUADRANT {},{} IS {}", pos.x, pos.y, name));
         }
+    }
+    Ok(())
+}
+
+/// Option 10 — Route Planner.
+/// Course, warp factor, energy, and stardates to reach a quadrant the
+/// player enters.
+fn route_planner(
+    galaxy: &Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let Some(target) = read_quadrant(io)? else {
+        return Ok(());
     };
+    if target == galaxy.enterprise().quadrant() {
+        output.writeln(&format!("YOU ARE ALREADY IN QUADRANT {},{}", target.x, target.y));
+        return Ok(());
+    }
+
+    let route = plan_route(galaxy, target);
+    output.writeln(&format!("ROUTE TO QUADRANT {},{}", target.x, target.y));
+    output.writeln(&format!("COURSE      = {:.2}", route.course));
+    output.writeln(&format!("WARP FACTOR = {}", route.warp));
+    output.writeln(&format!("ENERGY      = {}", route.energy));
+    output.writeln(&format!("STARDATES   = {}", route.stardates));
+    if galaxy.enterprise().is_damaged(Device::WarpEngines) {
+        output.writeln("WARP ENGINES ARE DAMAGED, MAXIMUM SPEED = WARP .2");
+    } else if route.energy > galaxy.enterprise().energy() {
+        output.writeln("INSUFFICIENT ENERGY FOR THIS ROUTE");
+    }
+    Ok(())
+}
+
+/// Ask for a quadrant as "X,Y". Blank, malformed, or off-galaxy answers
+/// give `None`.
+fn read_quadrant(io: &mut dyn InputReader) -> GameResult<Option<QuadrantPosition>> {
+    let input = io.read_line("QUADRANT (X,Y)")?;
+    let coords: Vec<i32> = input
+        .trim()
+        .split(',')
+        .filter_map(|c| c.trim().parse().ok())
+        .collect();
+    let range = 1..=GALAXY_SIZE as i32;
+    let [x, y] = coords[..] else {
+        return Ok(None);
+    };
+    if !range.contains(&x) || !range.contains(&y) {
+        return Ok(None);
+    }
+    Ok(Some(QuadrantPosition { x, y }))
+}
 
-    (direction, distance)
+/// Option 8 — Quadrant Journal.
+/// Lists what the computer has recorded about a quadrant, oldest first.
+fn quadrant_journal(
+    galaxy: &Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let Some(pos) = read_quadrant(io)? else {
+        return Ok(());
+    };
+    output.writeln(&format!("JOURNAL FOR QUADRANT {},{}", pos.x, pos.y));
+    let entries = galaxy.journal().entries(pos);
+    if entries.is_empty() {
+        output.writeln("NO ENTRIES");
+    }
+    for entry in entries {
+        output.writeln(&format!("STARDATE {:.1}  {}", entry.stardate, entry.event.describe()));
+    }
+    Ok(())
 }
 
 fn print_computer_menu(output: &mut dyn OutputWriter) {
@@ -226,12 +336,19 @@ fn print_computer_menu(output: &mut dyn OutputWriter) {
     output.writeln("   0 = CUMULATIVE GALACTIC RECORD");
     output.writeln("   1 = STATUS REPORT");
     output.writeln("   2 = PHOTON TORPEDO DATA");
+    output.writeln("   3 = STARBASE NAV DATA");
+    output.writeln("   5 = REGION NAMES");
+    output.writeln("   6 = WAR STATUS");
+    output.writeln("   7 = COMBAT ODDS");
+    output.writeln("   8 = QUADRANT JOURNAL");
+    output.writeln("   9 = GALAXY MAP");
+    output.writeln("  10 = ROUTE PLANNER");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::io::test_utils::MockOutput;
+    use crate::io::testing::{MockInput, MockOutput};
     use crate::models::constants::Device;
     use crate::models::galaxy::Galaxy;
 
@@ -239,14 +356,7 @@ mod tests {
     fn galactic_record_shows_unscanned_as_negative() {
         let galaxy = Galaxy::new(42);
         // Most quadrants should still be None (unscanned) except the starting one
-        let mut unscanned_count = 0;
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                if galaxy.computer_memory()[y][x].is_none() {
-                    unscanned_count += 1;
-                }
-            }
-        }
+        let unscanned_count = galaxy.computer_memory().iter().filter(|m| m.is_none()).count();
         // 64 total minus the starting quadrant = 63 unscanned
         assert_eq!(unscanned_count, 63);
     }
@@ -254,10 +364,9 @@ mod tests {
     #[test]
     fn starting_quadrant_is_recorded() {
         let galaxy = Galaxy::new(42);
-        let qx = galaxy.enterprise().quadrant().x;
-        let qy = galaxy.enterprise().quadrant().y;
-        let mem = galaxy.computer_memory()[(qy - 1) as usize][(qx - 1) as usize];
-        let actual = galaxy.quadrants()[(qy - 1) as usize][(qx - 1) as usize];
+        let here = galaxy.enterprise().quadrant();
+        let mem = galaxy.computer_memory()[here];
+        let actual = galaxy.quadrants()[here];
         assert_eq!(mem, Some(actual));
     }
 
@@ -270,19 +379,14 @@ mod tests {
         let qx = galaxy.enterprise().quadrant().x;
         let qy = galaxy.enterprise().quadrant().y;
         let target_x = if qx < 8 { qx + 1 } else { qx - 1 };
+        let target = QuadrantPosition { x: target_x, y: qy };
 
         // Should still be None (unscanned)
-        assert_eq!(
-            galaxy.computer_memory()[(qy - 1) as usize][(target_x - 1) as usize],
-            None
-        );
+        assert_eq!(galaxy.computer_memory()[target], None);
 
         // Try to record — should be blocked
         galaxy.record_quadrant_to_memory(target_x, qy);
-        assert_eq!(
-            galaxy.computer_memory()[(qy - 1) as usize][(target_x - 1) as usize],
-            None
-        );
+        assert_eq!(galaxy.computer_memory()[target], None);
     }
 
     #[test]
@@ -304,6 +408,95 @@ mod tests {
         assert_eq!((initial_remaining - after_remaining) as i32, 5);
     }
 
+    #[test]
+    fn war_status_option_shows_progress() {
+        let mut galaxy = Galaxy::new(42);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["6"]), &mut output).unwrap();
+
+        let text = output.messages.concat();
+        assert!(text.contains("WAR STATUS"));
+        assert!(text.contains(&format!(
+            "KLINGONS DESTROYED       = 0 OF {}",
+            galaxy.initial_klingons()
+        )));
+        assert!(text.contains("MISSION TIME REMAINING   = 100%"));
+    }
+
+    #[test]
+    fn combat_odds_option_gives_a_recommendation() {
+        let mut galaxy = Galaxy::new(42);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["7"]), &mut output).unwrap();
+
+        let text = output.messages.concat();
+        assert!(text.contains("COMBAT ODDS"));
+        let odds = CombatOdds::from_galaxy(&galaxy);
+        assert!(text.contains(odds.recommendation().label()));
+    }
+
+    #[test]
+    fn starbase_nav_data_points_at_the_starbase() {
+        let mut galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().sector();
+        let starbase = SectorPosition { x: here.x, y: if here.y > 1 { here.y - 1 } else { here.y + 1 } };
+        galaxy.sector_map_mut().starbase = Some(starbase);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["3"]), &mut output).unwrap();
+        let (direction, _) = calculate_direction_and_distance(here, starbase);
+        assert_eq!(
+            &output.messages[1..],
+            &[
+                "FROM ENTERPRISE TO STARBASE:\n".to_string(),
+                format!("DIRECTION = {:.2}\n", direction),
+                "DISTANCE  = 1.00\n".to_string(),
+            ]
+        );
+
+        galaxy.sector_map_mut().starbase = None;
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["3"]), &mut output).unwrap();
+        assert!(output.messages.last().unwrap().contains("NO STARBASES IN THIS QUADRANT"));
+    }
+
+    #[test]
+    fn region_names_option_names_here_and_the_asked_quadrant() {
+        let mut galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().quadrant();
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["5", "5,1"]), &mut output).unwrap();
+        assert_eq!(
+            &output.messages[1..],
+            &[
+                format!("YOU ARE IN QUADRANT {},{}: {}\n", here.x, here.y, region::quadrant_name(here).unwrap()),
+                "QUADRANT 5,1 IS SIRIUS I\n".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn quadrant_journal_lists_entries() {
+        let mut galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().quadrant();
+        let input = format!("{},{}", here.x, here.y);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["8", &input]), &mut output).unwrap();
+
+        let (stardate, klingons) = galaxy.journal().last_sighting(here).unwrap();
+        assert_eq!(
+            &output.messages[1..],
+            &[
+                format!("JOURNAL FOR QUADRANT {},{}\n", here.x, here.y),
+                format!("STARDATE {:.1}  KLINGONS SIGHTED: {}\n", stardate, klingons),
+            ]
+        );
+
+        let far = format!("{},{}", here.x % 8 + 1, here.y % 8 + 1);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["8", &far]), &mut output).unwrap();
+        assert_eq!(output.messages.last().unwrap(), "NO ENTRIES\n");
+    }
+
     #[test]
     fn status_report_displays_without_panic() {
         let galaxy = Galaxy::new(99);
@@ -311,6 +504,22 @@ mod tests {
         status_report(&galaxy, &mut MockOutput::new());
     }
 
+    #[test]
+    fn status_report_shows_turns_and_time_spent_on_orders() {
+        let mut galaxy = Galaxy::new(99);
+        galaxy.complete_turn();
+        galaxy.complete_turn();
+        let mut output = MockOutput::new();
+        status_report(&galaxy, &mut output);
+        output.expect_line_containing("NUMBER OF TURNS TAKEN    = 2");
+        assert!(!output.messages.concat().contains("TIME SPENT ON ORDERS"));
+
+        galaxy.charge_command_time(0.25);
+        let mut output = MockOutput::new();
+        status_report(&galaxy, &mut output);
+        output.expect_line_containing("TIME SPENT ON ORDERS     = 0.2");
+    }
+
     #[test]
     fn status_report_falls_through_to_damage_report() {
         let mut galaxy = Galaxy::new(99);
@@ -419,4 +628,112 @@ mod tests {
             distance
         );
     }
+
+    // ========== Partial damage tests ==========
+
+    /// Galaxy with every quadrant known to the computer.
+    fn fully_scanned_galaxy(seed: u64, computer_damage: f64) -> Galaxy {
+        let mut galaxy = Galaxy::new(seed);
+        let known = galaxy.quadrants().map(|&q| Some(q));
+        *galaxy.computer_memory_mut() = known;
+        galaxy.enterprise_mut().damage_device(Device::Computer, computer_damage);
+        galaxy
+    }
+
+    fn record_cells(galaxy: &mut Galaxy) -> Vec<String> {
+        let mut output = MockOutput::new();
+        cumulative_galactic_record(galaxy, &mut output);
+        output
+            .messages
+            .iter()
+            .filter(|line| line.starts_with('|'))
+            .flat_map(|line| line.split('|').map(str::trim).filter(|c| !c.is_empty()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn expected_cells(galaxy: &Galaxy) -> Vec<String> {
+        galaxy.quadrants().iter().map(|q| format!("{:03}", q.encoded())).collect()
+    }
+
+    #[test]
+    fn undamaged_record_is_exact() {
+        let mut galaxy = fully_scanned_galaxy(42, 0.0);
+        assert_eq!(record_cells(&mut galaxy), expected_cells(&galaxy));
+    }
+
+    #[test]
+    fn partially_damaged_record_garbles_some_cells_deterministically() {
+        let mut galaxy = fully_scanned_galaxy(42, COMPUTER_PARTIAL_DAMAGE_LIMIT);
+        let cells = record_cells(&mut galaxy);
+        let expected = expected_cells(&galaxy);
+        let garbled = cells.iter().zip(&expected).filter(|(a, b)| a != b).count();
+        assert!(garbled > 0 && garbled < 64, "garbled {} of 64 cells", garbled);
+
+        let mut again = fully_scanned_galaxy(42, COMPUTER_PARTIAL_DAMAGE_LIMIT);
+        assert_eq!(record_cells(&mut again), cells);
+    }
+
+    #[test]
+    fn heavier_damage_garbles_more_cells() {
+        let garbled = |damage: f64| {
+            (0..20)
+                .map(|seed| {
+                    let mut galaxy = fully_scanned_galaxy(seed, damage);
+                    let expected = expected_cells(&galaxy);
+                    record_cells(&mut galaxy).iter().zip(&expected).filter(|(a, b)| a != b).count()
+                })
+                .sum::<usize>()
+        };
+        assert!(garbled(0.5) < garbled(COMPUTER_PARTIAL_DAMAGE_LIMIT));
+    }
+
+    #[test]
+    fn galaxy_map_option_draws_the_record() {
+        let mut galaxy = fully_scanned_galaxy(42, 0.0);
+        let here = galaxy.enterprise().quadrant();
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["9"]), &mut output).unwrap();
+        output.expect_line_containing(&format!("GALAXY MAP FOR QUADRANT {},{}", here.x, here.y));
+        output.expect_line_containing(&format!("[{:03}]", galaxy.quadrants()[here].encoded()));
+        output.expect_line_containing("ANTARES / SIRIUS");
+    }
+
+    #[test]
+    fn route_planner_option_reports_the_jump() {
+        let mut galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().quadrant();
+        let target = QuadrantPosition { x: if here.x > 4 { here.x - 3 } else { here.x + 3 }, y: here.y };
+        let mut output = MockOutput::new();
+        let answer = format!("{},{}", target.x, target.y);
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["10", &answer]), &mut output).unwrap();
+        let course = if target.x > here.x { "1.00" } else { "5.00" };
+        output.expect_line_containing(&format!("COURSE      = {}", course));
+        output.expect_line_containing("WARP FACTOR = 3");
+        output.expect_line_containing("ENERGY      = 19");
+        output.expect_line_containing("STARDATES   = 1");
+
+        let mut output = MockOutput::new();
+        let answer = format!("{},{}", here.x, here.y);
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["10", &answer]), &mut output).unwrap();
+        output.expect_line_containing("YOU ARE ALREADY IN QUADRANT");
+    }
+
+    #[test]
+    fn computer_disabled_beyond_partial_damage() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::Computer, COMPUTER_PARTIAL_DAMAGE_LIMIT + 1.0);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["0"]), &mut output).unwrap();
+        assert_eq!(output.messages, vec!["COMPUTER DISABLED\n".to_string()]);
+    }
+
+    #[test]
+    fn partially_damaged_computer_stays_online() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 1.0);
+        let mut output = MockOutput::new();
+        library_computer(&mut galaxy, &mut MockInput::new(vec!["0"]), &mut output).unwrap();
+        assert_eq!(output.messages[0], "COMPUTER ACTIVE AND AWAITING COMMAND\n");
+    }
 }
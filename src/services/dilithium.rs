@@ -0,0 +1,124 @@
+//! Emergency dilithium burn
+//!
+//! The Enterprise carries one reserve of dilithium crystals that can be
+//! burned for emergency energy. The burn is risky: the crystals may explode
+//! instead, damaging devices, and more often without damage control to
+//! watch over it.
+
+use crate::io::{InputReader, OutputWriter};
+use crate::models::constants::{
+    Device, DILITHIUM_ENERGY, DILITHIUM_EXPLOSION_CHANCE, DILITHIUM_MAX_DEVICES_DAMAGED,
+    DILITHIUM_UNSUPERVISED_EXPLOSION_CHANCE,
+};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::ui::presenters::Emit;
+
+/// Chance the crystals explode, given the state of damage control.
+pub fn explosion_chance(galaxy: &Galaxy) -> f64 {
+    if galaxy.enterprise().is_damaged(Device::DamageControl) {
+        DILITHIUM_UNSUPERVISED_EXPLOSION_CHANCE
+    } else {
+        DILITHIUM_EXPLOSION_CHANCE
+    }
+}
+
+/// Burns the dilithium reserve for emergency energy (Command D)
+///
+/// Damage control estimates the risk if it is working, and the player
+/// confirms the burn. On success the ship gains `DILITHIUM_ENERGY` units;
+/// otherwise the crystals explode and damage one to
+/// `DILITHIUM_MAX_DEVICES_DAMAGED` devices. Either way the reserve is gone.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for the confirmation
+/// * `output` - Output writer for displaying results
+///
+/// # Returns
+///
+/// * `Ok(())` on success, including a cancelled burn
+/// * `Err` if I/O operations fail
+pub fn burn_dilithium(galaxy: &mut Galaxy, io: &mut dyn InputReader, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if !galaxy.enterprise().has_dilithium_reserve() {
+        output.writeln("THE DILITHIUM RESERVE HAS BEEN USED");
+        return Ok(());
+    }
+
+    let chance = explosion_chance(galaxy);
+    if galaxy.enterprise().is_damaged(Device::DamageControl) {
+        output.writeln("DAMAGE CONTROL CANNOT ESTIMATE THE RISK");
+    } else {
+        output.writeln(&format!("DAMAGE CONTROL ESTIMATES A {}% CHANCE OF EXPLOSION", (chance * 100.0).round()));
+    }
+    if !io.confirm("BURN THE DILITHIUM CRYSTALS (Y/N)?")? {
+        return Ok(());
+    }
+
+    galaxy.enterprise_mut().take_dilithium_reserve();
+    if galaxy.rng_mut().unit("dilithium.explosion") >= chance {
+        galaxy.enterprise_mut().add_energy(DILITHIUM_ENERGY);
+        output.writeln(&format!("DILITHIUM BURN COMPLETE. {} UNITS ADDED TO MAIN ENERGY", DILITHIUM_ENERGY));
+        return Ok(());
+    }
+
+    output.writeln("*** DILITHIUM CRYSTALS EXPLODE ***");
+    let devices = galaxy.ruleset().devices();
+    let count = galaxy.rng_mut().range("dilithium.devices", 1..=DILITHIUM_MAX_DEVICES_DAMAGED);
+    for _ in 0..count {
+        let index = (galaxy.rng_mut().unit("dilithium.device") * devices.len() as f64).floor() as usize;
+        let severity = (galaxy.rng_mut().unit("dilithium.severity") * 5.0).floor() + 1.0;
+        let device = devices[index];
+        galaxy.enterprise_mut().damage_device(device, severity);
+        output.emit(OutputEvent::DeviceDamaged { device, severity });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::rng::SequenceRng;
+
+    #[test]
+    fn a_clean_burn_adds_energy_once() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.rng_mut().set_source(SequenceRng::new([0.5]));
+        let energy = galaxy.enterprise().energy();
+        let mut output = MockOutput::new();
+
+        burn_dilithium(&mut galaxy, &mut MockInput::new(vec!["N"]), &mut output).unwrap();
+        output.expect_line_containing("10% CHANCE OF EXPLOSION");
+        assert!(galaxy.enterprise().has_dilithium_reserve());
+
+        burn_dilithium(&mut galaxy, &mut MockInput::new(vec!["Y"]), &mut output).unwrap();
+        assert_eq!(galaxy.enterprise().energy(), energy + DILITHIUM_ENERGY);
+        assert!(!galaxy.enterprise().has_dilithium_reserve());
+
+        burn_dilithium(&mut galaxy, &mut MockInput::new(vec![]), &mut output).unwrap();
+        output.expect_line_containing("RESERVE HAS BEEN USED");
+        assert_eq!(galaxy.enterprise().energy(), energy + DILITHIUM_ENERGY);
+    }
+
+    #[test]
+    fn explosions_damage_devices_and_are_likelier_without_damage_control() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::DamageControl, 1.0);
+        assert_eq!(explosion_chance(&galaxy), DILITHIUM_UNSUPERVISED_EXPLOSION_CHANCE);
+        // Explode (0.2 < 0.3), damage two devices (0.4 of 1..=3)
+        galaxy.rng_mut().set_source(SequenceRng::new([0.2, 0.4, 0.0, 0.0, 0.95, 0.99]));
+        let energy = galaxy.enterprise().energy();
+        let mut output = MockOutput::new();
+
+        burn_dilithium(&mut galaxy, &mut MockInput::new(vec!["Y"]), &mut output).unwrap();
+        output.expect_line_containing("CANNOT ESTIMATE");
+        output.expect_line_containing("CRYSTALS EXPLODE");
+        assert_eq!(galaxy.enterprise().energy(), energy);
+        assert!(galaxy.enterprise().is_damaged(Device::WarpEngines));
+        assert!(galaxy.enterprise().is_damaged(Device::SubspaceRadio));
+        assert!(!galaxy.enterprise().has_dilithium_reserve());
+    }
+}
@@ -1,102 +1,661 @@
-use crate::game_engine::{GameEngine, GameState, DefeatReason};
-use crate::io::{InputReader, OutputWriter, TerminalIO};
-use crate::models::errors::GameResult;
+use std::collections::VecDeque;
+
+use crate::game_engine::GameEngine;
+use crate::io::{self, InputReader, OutputWriter, RecordingReader, TerminalIO, TypeAhead};
+use crate::models::achievements::Achievement;
+use crate::models::compat::CompatMode;
+use crate::models::constants::DEFAULT_QUICK_SHIELD_UNITS;
+use crate::models::errors::{GameError, GameResult};
+use crate::models::galaxy::Galaxy;
+use crate::models::turn::TurnSummary;
+use crate::services::battle_log::BattleLog;
 use crate::services::combat;
-use crate::services::computer;
-use crate::services::navigation;
+use crate::services::commands::{self, CommandId, CustomCommand, COMMANDS};
 use crate::services::scan;
-use crate::ui::presenters::{EnterprisePresenter, CombatPresenter};
+use crate::ui::presenters::{BriefingPresenter, CombatPresenter, Emit, MapPresenter, WarPresenter};
+
+/// Callback that receives a summary at the end of every turn.
+pub type TurnObserver = Box<dyn FnMut(&TurnSummary) + Send>;
+
+/// Commands UNDO can take back: navigation and shield control.
+const UNDOABLE_COMMANDS: [CommandId; 6] = [
+    CommandId::SetCourse,
+    CommandId::Impulse,
+    CommandId::Autopilot,
+    CommandId::ShieldControl,
+    CommandId::ShieldsUp,
+    CommandId::ShieldsDown,
+];
 
 pub struct Game {
     game_engine: GameEngine,
-    io: TerminalIO,
-    output: TerminalIO,
+    io: RecordingReader<Box<dyn InputReader + Send>>,
+    output: Box<dyn OutputWriter + Send>,
+    custom_commands: Vec<CustomCommand>,
+    turn_observers: Vec<TurnObserver>,
+    battle_log: BattleLog,
+    compat: CompatMode,
+    quick_shield_units: f64,
+    /// Commands UNDO can take back; 0 disables it.
+    undo_depth: usize,
+    /// The game before each undoable command, oldest first.
+    history: VecDeque<UndoPoint>,
+    /// The player paused the game with Ctrl-C and chose to save it.
+    save_requested: bool,
+}
+
+/// The game before an undoable command, which UNDO goes back to.
+struct UndoPoint {
+    galaxy: Galaxy,
+    battle_log: BattleLog,
+}
+
+/// The game at the start of a turn, which Ctrl-C goes back to.
+struct Checkpoint {
+    galaxy: Galaxy,
+    battle_log: BattleLog,
+    inputs: usize,
+    history: usize,
 }
 
 impl Game {
     pub fn new(seed: u64) -> Self {
+        Self::from_engine(GameEngine::new(seed))
+    }
+
+    /// Run a terminal game on a pre-built engine, e.g. one created with mods.
+    pub fn from_engine(game_engine: GameEngine) -> Self {
+        Self::with_input(game_engine, Box::new(TerminalIO))
+    }
+
+    /// Run a game that reads its input from `input`, e.g. a replay script,
+    /// and writes to the terminal.
+    pub fn with_input(game_engine: GameEngine, input: Box<dyn InputReader + Send>) -> Self {
         Game {
-            game_engine: GameEngine::new(seed),
-            io: TerminalIO,
-            output: TerminalIO,
+            game_engine,
+            io: RecordingReader::new(input),
+            output: Box::new(TerminalIO),
+            custom_commands: Vec::new(),
+            turn_observers: Vec::new(),
+            battle_log: BattleLog::default(),
+            compat: CompatMode::default(),
+            quick_shield_units: DEFAULT_QUICK_SHIELD_UNITS,
+            undo_depth: 0,
+            history: VecDeque::new(),
+            save_requested: false,
+        }
+    }
+
+    /// Send the game's output somewhere other than the terminal, e.g. a
+    /// file for golden tests.
+    pub fn set_output(&mut self, output: Box<dyn OutputWriter + Send>) {
+        self.output = output;
+    }
+
+    /// Choose between modern and original wording.
+    pub fn set_compat_mode(&mut self, mode: CompatMode) {
+        self.compat = mode;
+    }
+
+    /// Shield level the SHIELDS UP shortcut sets.
+    pub fn set_quick_shield_units(&mut self, units: f64) {
+        self.quick_shield_units = units;
+    }
+
+    /// Allow UNDO to take back the last `depth` navigation or shield
+    /// commands made with no enemy ships present. Fails in tournament games.
+    pub fn enable_undo(&mut self, depth: usize) -> GameResult<()> {
+        if self.game_engine.is_tournament() {
+            return Err(GameError::InvalidInput("undo is not allowed in tournament games".to_string()));
         }
+        self.undo_depth = depth;
+        self.history.clear();
+        Ok(())
+    }
+
+    pub fn engine(&self) -> &GameEngine {
+        &self.game_engine
+    }
+
+    /// The most recent input lines, oldest first.
+    pub fn command_history(&self) -> impl Iterator<Item = &str> {
+        self.io.history()
+    }
+
+    /// Every input line entered so far, for recording a replay.
+    pub fn input_transcript(&self) -> &[String] {
+        self.io.transcript()
+    }
+
+    /// Digest of all input entered so far. Together with the seed it
+    /// identifies the game for score validation.
+    pub fn replay_hash(&self) -> u64 {
+        self.io.digest()
+    }
+
+    /// Whether the player paused the game and asked to save it; the
+    /// frontend saves the input transcript as a replay to resume from.
+    pub fn save_requested(&self) -> bool {
+        self.save_requested
+    }
+
+    /// Events of the game so far, for exporting after it ends.
+    pub fn battle_log(&self) -> &BattleLog {
+        &self.battle_log
+    }
+
+    /// Register a custom command that appears in the command menu.
+    /// Fails if the name is empty or already taken by another command, or
+    /// if this is a tournament game.
+    pub fn register_command(&mut self, command: CustomCommand) -> GameResult<()> {
+        if self.game_engine.is_tournament() {
+            return Err(GameError::InvalidInput(
+                "custom commands are not allowed in tournament games".to_string(),
+            ));
+        }
+        let name = command.name();
+        if name.is_empty() {
+            return Err(GameError::InvalidInput("command name is empty".to_string()));
+        }
+        if commands::parse_command_line(name).is_some()
+            || self.custom_commands.iter().any(|c| c.matches(name))
+        {
+            return Err(GameError::InvalidInput(format!(
+                "command {} is already registered",
+                name
+            )));
+        }
+        self.custom_commands.push(command);
+        Ok(())
+    }
+
+    /// Register a callback for the [`TurnSummary`] emitted after every
+    /// built-in or custom command, e.g. to feed a monitoring dashboard.
+    pub fn on_turn<F>(&mut self, observer: F)
+    where
+        F: FnMut(&TurnSummary) + Send + 'static,
+    {
+        self.turn_observers.push(Box::new(observer));
     }
 
     pub fn run(&mut self) -> GameResult<()> {
-        self.print_mission_briefing();
+        combat::red_alert(self.game_engine.galaxy(), &mut self.output);
+        BriefingPresenter::show_mission_briefing(self.game_engine.galaxy(), self.compat, &mut self.output);
         scan::short_range_scan(self.game_engine.galaxy_mut(), &mut self.output)?;
+        combat::auto_shields(self.game_engine.galaxy_mut(), &mut self.output);
+        self.battle_log = BattleLog::begin(self.game_engine.galaxy());
 
         loop {
-            let input = self.io.read_line("COMMAND")?;
-            let input = input.trim();
+            // Ctrl-C during the turn takes it back to here
+            let checkpoint = self.checkpoint();
 
-            let result = match input {
-                "0" => navigation::navigate(self.game_engine.galaxy_mut(), &mut self.io, &mut self.output),
-                "1" => scan::short_range_scan(self.game_engine.galaxy_mut(), &mut self.output),
-                "2" => scan::long_range_scan(self.game_engine.galaxy_mut(), &mut self.output),
-                "3" => combat::fire_phasers(self.game_engine.galaxy_mut(), &mut self.io, &mut self.output),
-                "4" => combat::fire_torpedoes(self.game_engine.galaxy_mut(), &mut self.io, &mut self.output),
-                "5" => combat::shield_control(self.game_engine.galaxy_mut(), &mut self.io, &mut self.output),
-                "6" => {
-                    EnterprisePresenter::show_damage_report(self.game_engine.galaxy().enterprise(), &mut self.output);
-                    Ok(())
-                }
-                "7" => computer::library_computer(self.game_engine.galaxy_mut(), &mut self.io, &mut self.output),
-                "q" | "Q" => {
-                    self.output.writeln("GOODBYE, CAPTAIN.");
-                    break;
+            // Running out of input (the end of a script) ends the game
+            let input = match self.io.read_line("COMMAND") {
+                Ok(input) => input,
+                Err(e) => {
+                    let e = GameError::from(e);
+                    if io::is_end_of_input(&e) {
+                        self.output.writeln("END OF INPUT");
+                        break;
+                    }
+                    if io::is_interrupted(&e) {
+                        if self.pause()? {
+                            break;
+                        }
+                        continue;
+                    }
+                    return Err(e);
                 }
-                _ => {
-                    Self::print_command_menu(&mut self.output);
-                    Ok(())
+            };
+            let input = input.trim();
+            if self.undo_depth > 0 && input.eq_ignore_ascii_case("UNDO") {
+                if let Err(e) = self.undo() {
+                    self.output.writeln(&format!("Error: {}", e));
                 }
+                continue;
+            }
+            let start = self.game_engine.begin_turn();
+            let mut command = None;
+
+            let result = match commands::parse_command_line(input) {
+                Some((spec, answer)) => match spec.availability(self.game_engine.galaxy()) {
+                    Err(message) => {
+                        self.output.writeln(&message);
+                        Ok(())
+                    }
+                    Ok(()) if spec.id == CommandId::Quit => {
+                        match io::confirm_with(&mut self.io, "ABANDON THE MISSION (Y/N)?", self.compat) {
+                            Ok(true) => {
+                                self.output.writeln("GOODBYE, CAPTAIN.");
+                                break;
+                            }
+                            Ok(false) => Ok(()),
+                            Err(e) => Err(e),
+                        }
+                    }
+                    Ok(()) => {
+                        command = Some(spec.name.to_string());
+                        self.remember(spec.id);
+                        self.execute(spec.id, answer)
+                    }
+                },
+                None => match self.dispatch_custom(input) {
+                    Some(result) => {
+                        self.history.clear();
+                        command = Some(input.to_uppercase());
+                        result
+                    }
+                    None => {
+                        Self::print_command_menu(&self.custom_commands, self.undo_depth > 0, &mut self.output);
+                        Ok(())
+                    }
+                },
             };
 
             // Handle errors from commands - for now just print and continue
             if let Err(e) = result {
+                if io::is_end_of_input(&e) {
+                    self.output.writeln("END OF INPUT");
+                    break;
+                }
+                if io::is_interrupted(&e) {
+                    self.rewind(checkpoint);
+                    if self.pause()? {
+                        break;
+                    }
+                    continue;
+                }
                 self.output.writeln(&format!("Error: {}", e));
             }
 
-            // Check for game over after each command
-            if let Some(state) = self.game_engine.check_game_over() {
-                match state {
-                    GameState::Victory { rating } => {
-                        CombatPresenter::show_victory(rating, &mut self.output);
-                        break;
-                    }
-                    GameState::Defeat { reason } => {
-                        let message = match reason {
-                            DefeatReason::ShipDestroyed => "SHIP DESTROYED",
-                            DefeatReason::TimeExpired => "TIME EXPIRED",
-                            DefeatReason::DeadInSpace => "DEAD IN SPACE",
-                        };
-                        CombatPresenter::show_defeat(message, &mut self.output);
-                        break;
+            // No command ran, so no time passed
+            let Some(command) = command else {
+                continue;
+            };
+            let summary =
+                match self.game_engine.finish_turn(&start, &command, Some(&mut self.io), &mut self.output) {
+                    Ok(summary) => summary,
+                    Err(_) => {
+                        self.rewind(checkpoint);
+                        if self.pause()? {
+                            break;
+                        }
+                        continue;
                     }
-                    GameState::Playing => {} // Continue playing
+                };
+            self.notify_turn(&summary);
+
+            // Check for game over after each command, showing any escape
+            // from a destroyed ship
+            let game_over = self.game_engine.game_over_report().is_some();
+            for event in self.game_engine.take_events() {
+                self.output.emit(event);
+            }
+            if game_over {
+                if let Some(report) = self.game_engine.game_over_report() {
+                    self.battle_log.finish(&report, self.game_engine.galaxy());
+                    CombatPresenter::show_epilogue(&report, &mut self.output);
+                    Self::show_final_summary(self.game_engine.galaxy(), &mut self.output);
                 }
+                break;
             }
         }
         Ok(())
     }
 
-    fn print_mission_briefing(&mut self) {
-        let g = self.game_engine.galaxy();
-        let plural = if g.total_starbases() != 1 { "S" } else { "" };
-        self.output.writeln(&format!(
-            "YOU MUST DESTROY {} KLINGONS IN {} STARDATES WITH {} STARBASE{}",
-            g.total_klingons(), g.mission_duration() as i32, g.total_starbases(), plural,
+    /// Run a built-in command, answering its first prompt with anything
+    /// typed after it. Quit is handled by the game loop.
+    fn execute(&mut self, id: CommandId, answer: &str) -> GameResult<()> {
+        let galaxy = self.game_engine.galaxy_mut();
+        let mut io = TypeAhead::new(answer, &mut self.io);
+        commands::execute(id, galaxy, self.quick_shield_units, &mut io, &mut self.output)
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            galaxy: self.game_engine.galaxy().clone(),
+            battle_log: self.battle_log.clone(),
+            inputs: self.io.transcript().len(),
+            history: self.history.len(),
+        }
+    }
+
+    /// Take the turn back to `checkpoint`, forgetting its input, so the
+    /// recorded input still replays the game exactly.
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        *self.game_engine.galaxy_mut() = checkpoint.galaxy;
+        self.battle_log = checkpoint.battle_log;
+        self.io.rewind(checkpoint.inputs);
+        self.history.truncate(checkpoint.history);
+    }
+
+    /// Ctrl-C: offer to resume, save and quit, or quit. Returns whether the
+    /// game ends. The answer is read past the recording, so replays never
+    /// see it.
+    fn pause(&mut self) -> GameResult<bool> {
+        self.output.writeln("");
+        self.output.writeln("GAME PAUSED");
+        loop {
+            let answer = match self.io.inner_mut().read_line("RESUME, SAVE AND QUIT, OR QUIT (R/S/Q)") {
+                Ok(answer) => answer,
+                Err(e) => {
+                    let e = GameError::from(e);
+                    if io::is_interrupted(&e) {
+                        continue;
+                    }
+                    if io::is_end_of_input(&e) {
+                        return Ok(true);
+                    }
+                    return Err(e);
+                }
+            };
+            match answer.trim().to_uppercase().as_str() {
+                "" | "R" => {
+                    self.output.writeln("GAME RESUMED");
+                    return Ok(false);
+                }
+                "S" => {
+                    self.save_requested = true;
+                    return Ok(true);
+                }
+                "Q" => {
+                    self.output.writeln("GOODBYE, CAPTAIN.");
+                    return Ok(true);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Save the game before an undoable command with no enemy ships
+    /// present; any other command ends the chain of undoable moves.
+    fn remember(&mut self, id: CommandId) {
+        if self.undo_depth == 0 {
+            return;
+        }
+        if !UNDOABLE_COMMANDS.contains(&id) || self.game_engine.galaxy().sector_map().hostiles_present() {
+            self.history.clear();
+            return;
+        }
+        if self.history.len() == self.undo_depth {
+            self.history.pop_front();
+        }
+        self.history.push_back(UndoPoint {
+            galaxy: self.game_engine.galaxy().clone(),
+            battle_log: self.battle_log.clone(),
+        });
+    }
+
+    /// Restore the game, battle log included, from before the last
+    /// undoable command.
+    fn undo(&mut self) -> GameResult<()> {
+        if self.game_engine.galaxy().sector_map().hostiles_present() {
+            self.output.writeln("CANNOT UNDO WITH ENEMY SHIPS IN THE QUADRANT");
+            return Ok(());
+        }
+        let Some(point) = self.history.pop_back() else {
+            self.output.writeln("NOTHING TO UNDO");
+            return Ok(());
+        };
+        *self.game_engine.galaxy_mut() = point.galaxy;
+        self.battle_log = point.battle_log;
+        self.output.writeln("LAST COMMAND UNDONE");
+        scan::short_range_scan(self.game_engine.galaxy_mut(), &mut self.output)
+    }
+
+    /// Run the custom command matching the input, if any.
+    fn dispatch_custom(&mut self, input: &str) -> Option<GameResult<()>> {
+        let command = self.custom_commands.iter_mut().find(|c| c.matches(input))?;
+        Some(command.execute(&mut self.game_engine, &mut self.io, &mut self.output))
+    }
+
+    /// Complete the turn and pass its summary to every observer.
+    fn notify_turn(&mut self, summary: &TurnSummary) {
+        self.battle_log.record_turn(summary, self.game_engine.galaxy());
+        for observer in &mut self.turn_observers {
+            observer(summary);
+        }
+    }
+
+    /// Final summary of the war, the revealed galaxy and achievements,
+    /// shown after the victory or defeat message.
+    pub fn show_final_summary(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+        output.writeln("");
+        WarPresenter::show_war_status(&galaxy.war_status(), output);
+        output.writeln("");
+        MapPresenter::show_galaxy_reveal(galaxy, output);
+        MapPresenter::show_achievements(&Achievement::earned(galaxy), output);
+    }
+
+    fn print_command_menu(custom_commands: &[CustomCommand], undo: bool, output: &mut dyn OutputWriter) {
+        for spec in &COMMANDS {
+            output.writeln(&spec.menu_line());
+        }
+        for command in custom_commands {
+            output.writeln(&command.menu_line());
+        }
+        if undo {
+            output.writeln("   UNDO = TAKE BACK THE LAST MOVE OR SHIELD CHANGE");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::turn::TurnSnapshot;
+
+    fn probe_command() -> CustomCommand {
+        CustomCommand::new("SCAN PROBE", "Launch a sensor probe", |engine, _, _| {
+            engine.galaxy_mut().advance_time(0.5);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn turn_observers_receive_numbered_summaries() {
+        use std::sync::{Arc, Mutex};
+
+        let mut game = Game::new(42);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        game.on_turn(move |summary| sink.lock().unwrap().push(summary.clone()));
+
+        for _ in 0..2 {
+            let before = TurnSnapshot::capture(game.engine().galaxy());
+            game.game_engine.galaxy_mut().advance_time(0.5);
+            let summary = game.game_engine.end_turn("SCAN PROBE", &before);
+            game.notify_turn(&summary);
+        }
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.iter().map(|s| s.turn).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(seen[1].command, "SCAN PROBE");
+        assert_eq!(seen[1].time_delta, 0.5);
+    }
+
+    #[test]
+    fn registered_command_appears_in_menu() {
+        let mut game = Game::new(42);
+        game.register_command(probe_command()).unwrap();
+
+        let mut output = MockOutput::new();
+        Game::print_command_menu(&game.custom_commands, false, &mut output);
+
+        assert_eq!(
+            output.messages.last().map(String::as_str),
+            Some("   SCAN PROBE = LAUNCH A SENSOR PROBE\n")
+        );
+    }
+
+    #[test]
+    fn registered_command_is_dispatched() {
+        let mut game = Game::new(42);
+        game.register_command(probe_command()).unwrap();
+        let before = game.game_engine.galaxy().stardate();
+
+        assert!(game.dispatch_custom("scan probe").unwrap().is_ok());
+        assert_eq!(game.game_engine.galaxy().stardate(), before + 0.5);
+        assert!(game.dispatch_custom("9").is_none());
+    }
+
+    #[test]
+    fn tournament_games_refuse_custom_commands() {
+        let mut game = Game::from_engine(GameEngine::new_tournament(42));
+        assert!(game.register_command(probe_command()).is_err());
+        assert!(game.custom_commands.is_empty());
+    }
+
+    #[test]
+    fn duplicate_and_built_in_names_are_rejected() {
+        let mut game = Game::new(42);
+        game.register_command(probe_command()).unwrap();
+
+        assert!(game.register_command(probe_command()).is_err());
+        assert!(game
+            .register_command(CustomCommand::new("q", "Not quit", |_, _, _| Ok(())))
+            .is_err());
+        assert!(game
+            .register_command(CustomCommand::new("srs", "Alias clash", |_, _, _| Ok(())))
+            .is_err());
+        assert!(game
+            .register_command(CustomCommand::new("  ", "Blank", |_, _, _| Ok(())))
+            .is_err());
+    }
+
+    #[test]
+    fn undo_restores_the_galaxy_before_a_move() {
+        let mut game = Game::new(42);
+        game.enable_undo(1).unwrap();
+        game.game_engine.galaxy_mut().sector_map_mut().klingons.clear();
+        game.battle_log = BattleLog::begin(game.game_engine.galaxy());
+        let start = game.game_engine.galaxy().stardate();
+        let logged = game.battle_log().events().len();
+
+        game.remember(CommandId::SetCourse);
+        let before = TurnSnapshot::capture(game.game_engine.galaxy());
+        game.game_engine.galaxy_mut().advance_time(1.0);
+        let summary = game.game_engine.end_turn("SET COURSE", &before);
+        game.notify_turn(&summary);
+        game.undo().unwrap();
+        assert_eq!(game.game_engine.galaxy().stardate(), start);
+        assert_eq!(game.battle_log().events().len(), logged);
+        assert!(game.history.is_empty());
+
+        // Only one level is kept, and other commands end the chain
+        game.remember(CommandId::ShieldsUp);
+        game.remember(CommandId::ShieldsDown);
+        assert_eq!(game.history.len(), 1);
+        game.remember(CommandId::LongRangeScan);
+        assert!(game.history.is_empty());
+    }
+
+    #[test]
+    fn undo_is_not_offered_with_enemies_present_or_in_tournaments() {
+        let mut game = Game::new(42);
+        game.enable_undo(1).unwrap();
+        let galaxy = game.game_engine.galaxy_mut();
+        galaxy.sector_map_mut().klingons.push(crate::models::klingon::Klingon::new(
+            crate::models::position::SectorPosition { x: 1, y: 1 },
         ));
+        game.remember(CommandId::SetCourse);
+        assert!(game.history.is_empty());
+
+        // A cloaked Romulan counts as much as a Klingon
+        let galaxy = game.game_engine.galaxy_mut();
+        galaxy.sector_map_mut().klingons.clear();
+        let mut romulan = crate::models::romulan::Romulan::new(crate::models::position::SectorPosition { x: 2, y: 2 }, 200.0);
+        romulan.cloaked = true;
+        galaxy.sector_map_mut().romulans.push(romulan);
+        game.remember(CommandId::SetCourse);
+        assert!(game.history.is_empty());
+
+        let mut tournament = Game::from_engine(GameEngine::new_tournament(42));
+        assert!(tournament.enable_undo(1).is_err());
+        assert_eq!(tournament.undo_depth, 0);
+    }
+
+    /// Answers prompts in order, pressing Ctrl-C at each `None`.
+    struct Interrupting(VecDeque<Option<&'static str>>);
+
+    impl InputReader for Interrupting {
+        fn read_line(&mut self, _prompt: &str) -> Result<String, std::io::Error> {
+            match self.0.pop_front() {
+                Some(Some(line)) => Ok(line.to_string()),
+                Some(None) => Err(std::io::ErrorKind::Interrupted.into()),
+                None => Err(std::io::ErrorKind::UnexpectedEof.into()),
+            }
+        }
     }
 
-    fn print_command_menu(output: &mut dyn OutputWriter) {
-        output.writeln("   0 = SET COURSE");
-        output.writeln("   1 = SHORT RANGE SENSOR SCAN");
-        output.writeln("   2 = LONG RANGE SENSOR SCAN");
-        output.writeln("   3 = FIRE PHASERS");
-        output.writeln("   4 = FIRE PHOTON TORPEDOES");
-        output.writeln("   5 = SHIELD CONTROL");
-        output.writeln("   6 = DAMAGE CONTROL REPORT");
-        output.writeln("   7 = CALL ON LIBRARY COMPUTER");
+    #[test]
+    fn ctrl_c_takes_the_turn_back_and_pauses() {
+        let lines = [Some("5"), None, Some("R"), Some("5"), Some("100"), None, Some("S")];
+        let mut game = Game::with_input(GameEngine::new(42), Box::new(Interrupting(lines.into())));
+        game.set_output(Box::new(io::FileOutput::new(std::io::sink())));
+        game.game_engine.galaxy_mut().sector_map_mut().klingons.clear();
+
+        assert!(game.run().is_ok());
+        assert!(game.save_requested());
+        assert_eq!(game.input_transcript(), ["5", "100"]);
+        assert_eq!(game.engine().galaxy().enterprise().shields(), 100.0);
+
+        let lines = [Some("5"), None, Some("Q")];
+        let mut game = Game::with_input(GameEngine::new(42), Box::new(Interrupting(lines.into())));
+        game.set_output(Box::new(io::FileOutput::new(std::io::sink())));
+        assert!(game.run().is_ok());
+        assert!(!game.save_requested());
+        assert!(game.input_transcript().is_empty());
+    }
+
+    #[test]
+    fn rewinding_a_turn_takes_it_out_of_the_battle_log() {
+        let mut game = Game::new(42);
+        game.battle_log = BattleLog::begin(game.game_engine.galaxy());
+        let logged = game.battle_log().events().len();
+        let checkpoint = game.checkpoint();
+
+        let before = TurnSnapshot::capture(game.game_engine.galaxy());
+        let summary = game.game_engine.end_turn("SHORT RANGE SENSOR SCAN", &before);
+        game.notify_turn(&summary);
+        assert_eq!(game.battle_log().events().len(), logged + 1);
+        game.rewind(checkpoint);
+        assert_eq!(game.battle_log().events().len(), logged);
+    }
+
+    #[test]
+    fn commands_that_do_not_run_take_no_turn() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { shield_drain: true, ..Default::default() };
+        let mut engine = GameEngine::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        engine.galaxy_mut().enterprise_mut().set_shields(100.0);
+        engine.galaxy_mut().advance_time(1.0);
+        let mut game = Game::with_input(engine, Box::new(MockInput::new(vec!["XYZ", "QUIT", "N"])));
+        game.set_output(Box::new(MockOutput::new()));
+
+        assert!(game.run().is_ok());
+        assert_eq!(game.engine().turns(), 0);
+        // Upkeep would have reported the drain
+        assert!(game.game_engine.galaxy_mut().take_shield_drain() > 0.0);
+    }
+
+    #[test]
+    fn scripted_game_ends_cleanly_when_the_script_runs_out() {
+        let path = std::env::temp_dir().join(format!("startrek-script-{}.out", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let script = std::io::Cursor::new("1\n5\n");
+        let input = io::FileInput::new(script, file.try_clone().unwrap());
+        let mut game = Game::with_input(GameEngine::new(42), Box::new(input));
+        game.set_output(Box::new(io::FileOutput::new(file)));
+
+        assert!(game.run().is_ok());
+        let transcript = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(transcript.contains("COMMAND 1\n"));
+        assert!(transcript.contains("NUMBER OF UNITS TO SHIELDS\n"), "{}", transcript);
+        assert!(transcript.ends_with("END OF INPUT\n"), "{}", transcript);
     }
 }
@@ -3,8 +3,21 @@
 //! This module contains business logic for game operations including
 //! combat, navigation, scanning, and computer functions.
 
+pub mod ai;
+pub mod battle_log;
 pub mod combat;
+pub mod commands;
 pub mod computer;
+pub mod dilithium;
+#[cfg(feature = "terminal")]
 pub mod game;
 pub mod navigation;
+pub mod pending;
+pub mod preview;
+pub mod probe;
+pub mod repair;
 pub mod scan;
+pub mod scoring;
+pub mod session;
+pub mod simulation;
+pub mod turn;
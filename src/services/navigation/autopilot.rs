@@ -0,0 +1,203 @@
+use crate::io::{InputReader, OutputWriter};
+use crate::models::constants::{Device, AUTOPILOT_ENERGY_RESERVE, AUTOPILOT_MAX_JUMPS, GALAXY_SIZE};
+use crate::models::errors::{GameError, GameResult};
+use crate::models::galaxy::Galaxy;
+use crate::models::navigation_types::{Course, WarpFactor};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+
+use super::course::calculate_direction_and_distance;
+use super::movement::execute_move;
+
+/// A single warp jump to the same sector of another quadrant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Route {
+    pub course: f64,
+    pub warp: f64,
+    pub energy: f64,
+    pub stardates: f64,
+}
+
+/// Plan the jump from the Enterprise to `target`. The course comes from
+/// the same direction math as the torpedo data, over absolute galactic
+/// sector coordinates; a quadrant is eight sectors, so the warp factor is
+/// the number of quadrants crossed along the longer axis. The energy and
+/// time follow the warp move's rules: eight sectors per warp factor less
+/// five units, and a stardate for leaving the quadrant.
+pub fn plan_route(galaxy: &Galaxy, target: QuadrantPosition) -> Route {
+    let here = galaxy.enterprise().quadrant();
+    let sector = galaxy.enterprise().sector();
+    let absolute = |q: QuadrantPosition| SectorPosition { x: q.x * 8 + sector.x, y: q.y * 8 + sector.y };
+    let (course, _) = calculate_direction_and_distance(absolute(here), absolute(target));
+    let warp = (target.x - here.x).abs().max((target.y - here.y).abs()) as f64;
+    Route { course, warp, energy: warp * 8.0 - 5.0, stardates: 1.0 }
+}
+
+/// Flies the Enterprise to a quadrant on autopilot (Command AUTO)
+///
+/// Prompts for the destination, which can also be typed after the command
+/// as `AUTO 3 4`, then flies there one quadrant at a time, each leg a warp
+/// 1 jump plotted by `plan_route`. The autopilot disengages early in a
+/// quadrant with Klingons, before a jump that would leave less than
+/// `AUTOPILOT_ENERGY_RESERVE` units of energy, or if the course is blocked.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for the destination
+/// * `output` - Output writer for displaying navigation results
+///
+/// # Returns
+///
+/// * `Ok(())` on success, including an early stop or a cancelled command
+/// * `Err` if I/O operations fail
+pub fn autopilot(galaxy: &mut Galaxy, io: &mut dyn InputReader, output: &mut dyn OutputWriter) -> GameResult<()> {
+    let Some(target) = read_destination(io)? else {
+        return Ok(());
+    };
+    for _ in 0..AUTOPILOT_MAX_JUMPS {
+        let here = galaxy.enterprise().quadrant();
+        if here == target {
+            output.writeln(&format!("AUTOPILOT: ARRIVED AT QUADRANT {},{}", target.x, target.y));
+            return Ok(());
+        }
+        // Damage events on the way can take the engines out too
+        if galaxy.enterprise().is_damaged(Device::WarpEngines) {
+            output.writeln("AUTOPILOT DISENGAGED: WARP ENGINES DAMAGED");
+            return Ok(());
+        }
+        if !galaxy.sector_map().klingons.is_empty() {
+            output.writeln(&format!("AUTOPILOT DISENGAGED: KLINGONS IN QUADRANT {},{}", here.x, here.y));
+            return Ok(());
+        }
+        let next = QuadrantPosition {
+            x: here.x + (target.x - here.x).signum(),
+            y: here.y + (target.y - here.y).signum(),
+        };
+        let route = plan_route(galaxy, next);
+        if galaxy.enterprise().energy() - route.energy < AUTOPILOT_ENERGY_RESERVE {
+            output.writeln("AUTOPILOT DISENGAGED: ENERGY RESERVE REACHED");
+            return Ok(());
+        }
+
+        // Plotted the way a player would type it
+        let course = Course::new((route.course * 100.0).round() / 100.0)
+            .map_err(|e| GameError::NavigationError(e.to_string()))?;
+        let warp = WarpFactor::new(route.warp).map_err(|e| GameError::NavigationError(e.to_string()))?;
+        execute_move(galaxy, course, warp, output);
+        if galaxy.enterprise().is_destroyed() || galaxy.is_time_expired() {
+            return Ok(());
+        }
+        if galaxy.enterprise().quadrant() == here {
+            output.writeln("AUTOPILOT DISENGAGED: COURSE BLOCKED");
+            return Ok(());
+        }
+    }
+    output.writeln("AUTOPILOT DISENGAGED: DESTINATION NOT REACHED");
+    Ok(())
+}
+
+/// Ask for the destination as "X,Y" or "X Y". Blank, malformed, or
+/// off-galaxy answers give `None`.
+fn read_destination(io: &mut dyn InputReader) -> GameResult<Option<QuadrantPosition>> {
+    let input = io.read_line("DESTINATION QUADRANT (X,Y)")?;
+    let coords: Vec<i32> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|c| !c.is_empty())
+        .filter_map(|c| c.parse().ok())
+        .collect();
+    let range = 1..=GALAXY_SIZE as i32;
+    match coords[..] {
+        [x, y] if range.contains(&x) && range.contains(&y) => Ok(Some(QuadrantPosition { x, y })),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::constants::SectorContent;
+    use crate::models::grid::QuadrantGrid;
+    use crate::models::sector_map::SectorMap;
+    use crate::services::navigation::{calculate_direction, calculate_quadrant_crossing};
+
+    /// A row of three quadrants without Klingons, with the Enterprise in
+    /// the middle of the westernmost and nothing else in its way there.
+    fn quiet_galaxy() -> (Galaxy, QuadrantPosition) {
+        let mut galaxy = Galaxy::new(42);
+        let quiet = |q: QuadrantPosition| galaxy.quadrants()[q].klingons == 0;
+        let start = QuadrantGrid::<()>::positions()
+            .find(|&q| q.x <= 6 && (0..3).all(|i| quiet(QuadrantPosition { x: q.x + i, y: q.y })))
+            .unwrap();
+        galaxy.enterprise_mut().move_to(start, SectorPosition { x: 4, y: 4 });
+        galaxy.enter_quadrant();
+        *galaxy.sector_map_mut() = SectorMap::new();
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 4 }, SectorContent::Enterprise);
+        (galaxy, start)
+    }
+
+    #[test]
+    fn planned_routes_arrive_in_the_target_quadrant() {
+        let galaxy = Galaxy::new(42);
+        let here = galaxy.enterprise().quadrant();
+        let sector = galaxy.enterprise().sector();
+        for target in QuadrantGrid::<()>::positions().filter(|&q| q != here) {
+            let route = plan_route(&galaxy, target);
+            // Players type the course to two decimal places
+            let course = (route.course * 100.0).round() / 100.0;
+            let (dx, dy) = calculate_direction(course);
+            let n = (route.warp * 8.0).floor() as i32;
+            let (arrival, _) = calculate_quadrant_crossing(here.x, here.y, sector.x, sector.y, dx, dy, n);
+            assert_eq!(arrival, target, "course {} warp {}", course, route.warp);
+            assert!(route.warp <= 7.0);
+        }
+    }
+
+    #[test]
+    fn autopilot_flies_to_the_destination() {
+        let (mut galaxy, start) = quiet_galaxy();
+        let target = QuadrantPosition { x: start.x + 2, y: start.y };
+        let (energy, stardate) = (galaxy.enterprise().energy(), galaxy.stardate());
+        let mut output = MockOutput::new();
+
+        let answer = format!("{} {}", target.x, target.y);
+        autopilot(&mut galaxy, &mut MockInput::new(vec![answer.as_str()]), &mut output).unwrap();
+        output.expect_line_containing(&format!("ARRIVED AT QUADRANT {},{}", target.x, target.y));
+        assert_eq!(galaxy.enterprise().quadrant(), target);
+        assert!(galaxy.enterprise().energy() < energy);
+        assert_eq!(galaxy.stardate(), stardate + 2.0);
+    }
+
+    #[test]
+    fn autopilot_stops_for_klingons_and_the_energy_reserve() {
+        let (mut galaxy, start) = quiet_galaxy();
+        let target = format!("{},{}", start.x + 2, start.y);
+        let spare = galaxy.enterprise().energy() - AUTOPILOT_ENERGY_RESERVE;
+        galaxy.enterprise_mut().subtract_energy(spare);
+        let mut output = MockOutput::new();
+        autopilot(&mut galaxy, &mut MockInput::new(vec![target.as_str()]), &mut output).unwrap();
+        output.expect_line_containing("ENERGY RESERVE REACHED");
+        assert_eq!(galaxy.enterprise().quadrant(), start);
+
+        let (mut galaxy, start) = quiet_galaxy();
+        let middle = QuadrantPosition { x: start.x + 1, y: start.y };
+        galaxy.reinforce_klingons(middle);
+        let mut output = MockOutput::new();
+        autopilot(&mut galaxy, &mut MockInput::new(vec![target.as_str()]), &mut output).unwrap();
+        output.expect_line_containing(&format!("KLINGONS IN QUADRANT {},{}", middle.x, middle.y));
+        assert_eq!(galaxy.enterprise().quadrant(), middle);
+    }
+
+    #[test]
+    fn autopilot_needs_a_destination_and_working_engines() {
+        let (mut galaxy, start) = quiet_galaxy();
+        let mut output = MockOutput::new();
+        autopilot(&mut galaxy, &mut MockInput::new(vec!["9,1"]), &mut output).unwrap();
+        assert!(output.messages.is_empty());
+
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 1.0);
+        autopilot(&mut galaxy, &mut MockInput::new(vec!["8 8"]), &mut output).unwrap();
+        output.expect_line_containing("WARP ENGINES DAMAGED");
+        assert_eq!(galaxy.enterprise().quadrant(), start);
+    }
+}
@@ -61,6 +61,59 @@ pub fn calculate_quadrant_crossing(
     )
 }
 
+/// Direction and distance calculation (spec section 7.4).
+/// Uses the original ratio-based algorithm from the spec.
+pub fn calculate_direction_and_distance(
+    source: SectorPosition,
+    target: SectorPosition,
+) -> (f64, f64) {
+    let delta_x = (target.x - source.x) as f64;
+    let delta_y = (source.y - target.y) as f64; // Inverted per spec
+
+    let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+    // Direction calculation (spec section 7.4)
+    let direction = if delta_x >= 0.0 && delta_y >= 0.0 {
+        // Case 1: right and/or up
+        let base = if delta_x > 0.0 || delta_y > 0.0 {
+            1.0
+        } else {
+            5.0
+        };
+        if delta_y.abs() <= delta_x.abs() {
+            base + delta_y.abs() / delta_x.abs()
+        } else {
+            base + (delta_y.abs() - delta_x.abs() + delta_y.abs()) / delta_y.abs()
+        }
+    } else if delta_x < 0.0 && delta_y > 0.0 {
+        // Case 2: left and up
+        let base = 3.0;
+        if delta_y.abs() >= delta_x.abs() {
+            base + delta_x.abs() / delta_y.abs()
+        } else {
+            base + (delta_x.abs() - delta_y.abs() + delta_x.abs()) / delta_x.abs()
+        }
+    } else if delta_x >= 0.0 && delta_y < 0.0 {
+        // Case 3: right and down
+        let base = 7.0;
+        if delta_y.abs() >= delta_x.abs() {
+            base + delta_x.abs() / delta_y.abs()
+        } else {
+            base + (delta_x.abs() - delta_y.abs() + delta_x.abs()) / delta_x.abs()
+        }
+    } else {
+        // Case 4: left and down
+        let base = 5.0;
+        if delta_y.abs() <= delta_x.abs() {
+            base + delta_y.abs() / delta_x.abs()
+        } else {
+            base + (delta_y.abs() - delta_x.abs() + delta_y.abs()) / delta_y.abs()
+        }
+    };
+
+    (direction, distance)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,49 +1,78 @@
 use crate::io::OutputWriter;
 use crate::models::constants::Device;
 use crate::models::galaxy::Galaxy;
+use crate::models::hooks::EventKind;
+use crate::models::output_event::OutputEvent;
+use crate::models::repair::transit_repair_rate;
+use crate::ui::presenters::Emit;
 
 /// Automatic device repair on navigation moves (spec section 5.2).
-/// Each damaged device (value < 0) is incremented by 1.
+/// Each damaged device (value < 0) is incremented by 1, or less by a
+/// depleted crew under the crew morale rule. Assigning the repair crews to
+/// one device speeds its repair and slows the others.
 pub fn auto_repair_devices(galaxy: &mut Galaxy) {
-    for device in Device::ALL.iter() {
+    let rates = Device::ALL.map(|device| transit_repair_rate(galaxy, device));
+    for (device, rate) in Device::ALL.iter().zip(rates) {
         if galaxy.enterprise().is_damaged(*device) {
-            galaxy.enterprise_mut().repair_device(*device, 1.0);
+            galaxy.enterprise_mut().repair_device(*device, rate);
         }
     }
 }
 
 /// Random damage/repair events on navigation moves (spec section 5.3).
-/// 20% chance of event affecting a random device.
+/// 20% chance of event affecting a random device, adjustable by mods.
 /// FIXED: Now uses galaxy.rng instead of thread_rng() for determinism
 pub fn random_damage_event(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
-    use rand::Rng;
-
     // 20% chance of event - FIXED: using galaxy.rng for determinism!
-    if galaxy.rng_mut().gen::<f64>() > 0.2 {
+    let chance = galaxy
+        .mods()
+        .event_weight(
+            EventKind::DeviceMalfunction,
+            galaxy.config().device_event_chance * galaxy.ruleset().difficulty.device_event_factor(),
+        );
+    if galaxy.rng_mut().unit("damage.chance") > chance {
         return;
     }
 
-    // Select random device (0-7 index)
-    let device_index = (galaxy.rng_mut().gen::<f64>() * 8.0).floor() as usize;
+    // Select a random device the ship carries
+    let devices = galaxy.ruleset().devices();
+    let device_index = (galaxy.rng_mut().unit("damage.device") * devices.len() as f64).floor() as usize;
 
     // Determine severity (1-5)
-    let severity = (galaxy.rng_mut().gen::<f64>() * 5.0).floor() + 1.0;
+    let severity = (galaxy.rng_mut().unit("damage.severity") * 5.0).floor() + 1.0;
 
-    // 50% chance of damage vs repair
-    let is_repair = galaxy.rng_mut().gen::<f64>() >= 0.5;
+    // Damage vs repair, 50/50 by default
+    let repair_chance = galaxy.config().device_repair_chance;
+    let is_repair = galaxy.rng_mut().unit("damage.repair") >= 1.0 - repair_chance;
 
-    let device = Device::ALL[device_index];
+    let device = devices[device_index];
 
-    output.writeln("");
     if is_repair {
         galaxy.enterprise_mut().repair_device(device, severity);
-        output.writeln(&format!(
-            "DAMAGE CONTROL REPORT: {} STATE OF REPAIR IMPROVED",
-            device.name()
-        ));
+        output.emit(OutputEvent::DeviceRepaired { device, severity });
     } else {
         galaxy.enterprise_mut().damage_device(device, severity);
-        output.writeln(&format!("DAMAGE CONTROL REPORT: {} DAMAGED", device.name()));
+        output.emit(OutputEvent::DeviceDamaged { device, severity });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assigned_crews_speed_one_repair_until_it_is_done() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 2.0);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 2.0);
+        galaxy.enterprise_mut().set_repair_priority(Some(Device::PhotonTubes));
+
+        auto_repair_devices(&mut galaxy);
+        assert_eq!(galaxy.enterprise().devices()[Device::WarpEngines as usize], -1.5);
+        assert_eq!(galaxy.enterprise().devices()[Device::PhotonTubes as usize], 0.0);
+        assert_eq!(galaxy.enterprise().repair_priority(), None);
+
+        auto_repair_devices(&mut galaxy);
+        assert_eq!(galaxy.enterprise().devices()[Device::WarpEngines as usize], -0.5);
     }
-    output.writeln("");
 }
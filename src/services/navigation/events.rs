@@ -0,0 +1,340 @@
+use crate::io::OutputWriter;
+use crate::models::constants::{
+    Device, GALAXY_SIZE, KLINGON_MIGRATION_INTERVAL, MAX_KLINGONS_PER_QUADRANT, REINFORCEMENT_WAVE_INTERVAL,
+};
+use crate::models::director::DirectorSettings;
+use crate::models::galaxy::Galaxy;
+use crate::models::hooks::EventKind;
+use crate::models::output_event::OutputEvent;
+use crate::models::position::QuadrantPosition;
+use crate::ui::presenters::Emit;
+
+/// Base chance per navigation move that Starfleet completes a new starbase.
+const STARBASE_CONSTRUCTION_CHANCE: f64 = 0.05;
+
+/// Quadrants the computer must have on record before Starfleet trusts the
+/// charts enough to build (half the galaxy).
+const STARBASE_CONSTRUCTION_MIN_SCANNED: usize = GALAXY_SIZE * GALAXY_SIZE / 2;
+
+/// Starbase construction on navigation moves.
+/// While no starbase has been lost and the computer has charted at least
+/// half the galaxy, there is a chance (adjustable by mods) that a new
+/// starbase is completed in a scanned quadrant with no Klingons or
+/// starbase, announced over subspace radio. Nothing is drawn from the RNG
+/// until the player qualifies.
+pub fn starbase_construction_event(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    if galaxy.starbases_lost() > 0 {
+        return;
+    }
+    let scanned = galaxy.scanned_quadrants().count();
+    if scanned < STARBASE_CONSTRUCTION_MIN_SCANNED {
+        return;
+    }
+
+    let sites = construction_sites(galaxy);
+    if sites.is_empty() {
+        return;
+    }
+
+    let chance = galaxy
+        .mods()
+        .event_weight(EventKind::StarbaseConstruction, STARBASE_CONSTRUCTION_CHANCE);
+    if galaxy.rng_mut().unit("events.starbase_construction") > chance {
+        return;
+    }
+
+    let index = galaxy
+        .rng_mut()
+        .range("events.starbase_site", 0..=sites.len() as i32 - 1) as usize;
+    let site = sites[index];
+    galaxy.construct_starbase(site);
+
+    output.writeln("");
+    output.writeln(&format!(
+        "SUBSPACE RADIO: STARFLEET HAS COMPLETED A NEW STARBASE IN QUADRANT {},{}",
+        site.x, site.y
+    ));
+    output.writeln("");
+}
+
+/// Klingon migration on navigation moves, when the ruleset enables it.
+/// Once a migration interval has passed since the last step, groups take
+/// one step toward the nearest starbase; starbases they reach call for help.
+pub fn klingon_migration_event(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    if !galaxy.ruleset().klingon_migration {
+        return;
+    }
+    if galaxy.stardate() - galaxy.last_klingon_migration() < KLINGON_MIGRATION_INTERVAL {
+        return;
+    }
+    for base in galaxy.migrate_klingons() {
+        output.writeln(&format!(
+            "STARBASE IN QUADRANT {},{} REPORTS KLINGONS CLOSING IN",
+            base.x, base.y
+        ));
+    }
+}
+
+/// Klingon reinforcements on navigation moves, when the adaptive director
+/// is on. The director sets the chance from the player's pace; a new
+/// Klingon joins a random quadrant, other than the Enterprise's, without a
+/// starbase and with room for it. Nothing is drawn from the RNG when the
+/// director is off or the war is already won.
+pub fn klingon_reinforcement_event(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    let Some(bounds) = galaxy.ruleset().director else {
+        return;
+    };
+    if galaxy.total_klingons() == 0 {
+        return;
+    }
+    let sites = reinforcement_sites(galaxy);
+    if sites.is_empty() {
+        return;
+    }
+
+    let chance = DirectorSettings::assess(galaxy, &bounds).reinforcement_chance;
+    if galaxy.rng_mut().unit("events.reinforcement") >= chance {
+        return;
+    }
+    let index = galaxy
+        .rng_mut()
+        .range("events.reinforcement_site", 0..=sites.len() as i32 - 1) as usize;
+    galaxy.reinforce_klingons(sites[index]);
+    output.writeln("");
+    output.writeln("STARFLEET INTELLIGENCE REPORTS KLINGON REINFORCEMENTS ENTERING THE GALAXY");
+    output.writeln("");
+}
+
+/// Klingon reinforcement waves on navigation moves, when the ruleset
+/// enables them. Each interval that has passed brings one Klingon to a
+/// random quadrant, as for the director's reinforcements. A working subspace
+/// radio picks up where it arrived.
+pub fn klingon_reinforcement_wave(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    if !galaxy.ruleset().reinforcement_waves {
+        return;
+    }
+    while galaxy.stardate() - galaxy.last_reinforcement_wave() >= REINFORCEMENT_WAVE_INTERVAL {
+        galaxy.record_reinforcement_wave();
+        let sites = reinforcement_sites(galaxy);
+        if galaxy.total_klingons() == 0 || sites.is_empty() {
+            continue;
+        }
+        let index = galaxy
+            .rng_mut()
+            .range("events.reinforcement_wave_site", 0..=sites.len() as i32 - 1) as usize;
+        galaxy.reinforce_klingons(sites[index]);
+        if !galaxy.enterprise().is_damaged(Device::SubspaceRadio) {
+            output.emit(OutputEvent::ReinforcementsArrived { quadrant: sites[index] });
+        }
+    }
+}
+
+/// Quadrants a reinforcement can arrive in, in row-major order.
+fn reinforcement_sites(galaxy: &Galaxy) -> Vec<QuadrantPosition> {
+    let here = galaxy.enterprise().quadrant();
+    galaxy
+        .quadrants_iter()
+        .filter(|&(pos, data)| {
+            pos != here && data.starbases == 0 && data.klingons < MAX_KLINGONS_PER_QUADRANT as i32
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// Scanned quadrants, other than the Enterprise's, with no Klingons and no
+/// starbase, in row-major order.
+fn construction_sites(galaxy: &Galaxy) -> Vec<QuadrantPosition> {
+    let here = galaxy.enterprise().quadrant();
+    galaxy
+        .quadrants_iter()
+        .filter(|&(pos, data)| {
+            pos != here
+                && galaxy.computer_memory()[pos].is_some()
+                && data.klingons == 0
+                && data.starbases == 0
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::hooks::{ModHooks, ModRegistry};
+
+    /// Makes every eligible move build a starbase.
+    struct Builders;
+
+    impl ModHooks for Builders {
+        fn event_weight(&self, event: EventKind, weight: f64) -> f64 {
+            match event {
+                EventKind::StarbaseConstruction => 1.0,
+                _ => weight,
+            }
+        }
+    }
+
+    fn charted_galaxy(mods: ModRegistry) -> Galaxy {
+        let mut galaxy = Galaxy::new_with_mods(42, mods);
+        for y in 1..=GALAXY_SIZE as i32 {
+            for x in 1..=GALAXY_SIZE as i32 {
+                galaxy.record_quadrant_to_memory(x, y);
+            }
+        }
+        galaxy
+    }
+
+    fn builders() -> ModRegistry {
+        let mut mods = ModRegistry::new();
+        mods.register(Builders);
+        mods
+    }
+
+    #[test]
+    fn construction_adds_base_to_counts_and_memory() {
+        let mut galaxy = charted_galaxy(builders());
+        let before = galaxy.total_starbases();
+        let mut output = MockOutput::new();
+
+        starbase_construction_event(&mut galaxy, &mut output);
+
+        assert_eq!(galaxy.total_starbases(), before + 1);
+        let announcement = output.messages.concat();
+        assert!(announcement.contains("SUBSPACE RADIO: STARFLEET HAS COMPLETED A NEW STARBASE"));
+
+        let built: Vec<_> = galaxy.scanned_quadrants().filter(|(_, q)| q.starbases > 0).collect();
+        assert_eq!(built.len() as i32, galaxy.total_starbases());
+        let total: i32 = galaxy.quadrants().iter().map(|q| q.starbases).sum();
+        assert_eq!(total, galaxy.total_starbases());
+    }
+
+    #[test]
+    fn no_construction_after_a_base_is_lost() {
+        let mut galaxy = charted_galaxy(builders());
+        galaxy.destroy_starbase(galaxy.enterprise().sector());
+        let before = galaxy.total_starbases();
+        let draws = galaxy.rng().draws();
+
+        starbase_construction_event(&mut galaxy, &mut MockOutput::new());
+
+        assert_eq!(galaxy.total_starbases(), before);
+        assert_eq!(galaxy.rng().draws(), draws);
+    }
+
+    #[test]
+    fn no_construction_without_heavy_scanning() {
+        let mut galaxy = Galaxy::new_with_mods(42, builders());
+        let before = galaxy.total_starbases();
+        let draws = galaxy.rng().draws();
+
+        starbase_construction_event(&mut galaxy, &mut MockOutput::new());
+
+        assert_eq!(galaxy.total_starbases(), before);
+        assert_eq!(galaxy.rng().draws(), draws);
+    }
+
+    #[test]
+    fn migration_waits_for_the_interval_and_is_off_by_default() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { klingon_migration: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let mut classic = Galaxy::new(42);
+        let start = galaxy.quadrants().to_owned();
+
+        galaxy.advance_time(KLINGON_MIGRATION_INTERVAL - 1.0);
+        klingon_migration_event(&mut galaxy, &mut MockOutput::new());
+        assert_eq!(galaxy.quadrants(), &start);
+
+        galaxy.advance_time(KLINGON_MIGRATION_INTERVAL * 2.0);
+        classic.advance_time(KLINGON_MIGRATION_INTERVAL * 3.0);
+        klingon_migration_event(&mut galaxy, &mut MockOutput::new());
+        klingon_migration_event(&mut classic, &mut MockOutput::new());
+        assert_ne!(galaxy.quadrants(), &start);
+        assert_eq!(classic.quadrants(), &start);
+        assert_eq!(galaxy.last_klingon_migration(), galaxy.stardate());
+        let total: i32 = galaxy.quadrants().iter().map(|q| q.klingons).sum();
+        assert_eq!(total, galaxy.total_klingons());
+    }
+
+    #[test]
+    fn reinforcements_follow_the_director() {
+        use crate::models::director::DirectorBounds;
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let certain = DirectorBounds { reinforcement: (1.0, 1.0), ..Default::default() };
+        let ruleset = Ruleset { director: Some(certain), ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let before = galaxy.total_klingons();
+        let mut output = MockOutput::new();
+
+        klingon_reinforcement_event(&mut galaxy, &mut output);
+
+        assert_eq!(galaxy.total_klingons(), before + 1);
+        assert_eq!(galaxy.klingons_faced(), galaxy.initial_klingons() + 1);
+        let total: i32 = galaxy.quadrants().iter().map(|q| q.klingons).sum();
+        assert_eq!(total, galaxy.total_klingons());
+        assert!(output.messages.concat().contains("KLINGON REINFORCEMENTS"));
+
+        // Off by default, without touching the RNG
+        let mut classic = Galaxy::new(42);
+        let draws = classic.rng().draws();
+        klingon_reinforcement_event(&mut classic, &mut MockOutput::new());
+        assert_eq!(classic.total_klingons(), before);
+        assert_eq!(classic.rng().draws(), draws);
+        assert_eq!(classic.klingon_aggression(), 1.0);
+    }
+
+    #[test]
+    fn reinforcement_waves_arrive_once_per_interval() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { reinforcement_waves: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        let before = galaxy.total_klingons();
+        let mut output = MockOutput::new();
+
+        galaxy.advance_time(REINFORCEMENT_WAVE_INTERVAL - 1.0);
+        klingon_reinforcement_wave(&mut galaxy, &mut output);
+        assert_eq!(galaxy.total_klingons(), before);
+
+        galaxy.advance_time(REINFORCEMENT_WAVE_INTERVAL + 1.0);
+        klingon_reinforcement_wave(&mut galaxy, &mut output);
+        assert_eq!(galaxy.total_klingons(), before + 2);
+        let total: i32 = galaxy.quadrants().iter().map(|q| q.klingons).sum();
+        assert_eq!(total, galaxy.total_klingons());
+        assert_eq!(output.messages.len(), 2);
+        assert!(output.messages[0].starts_with("SUBSPACE MESSAGE: KLINGON REINFORCEMENTS HAVE WARPED INTO QUADRANT"));
+
+        // A damaged radio hears nothing, though the Klingons still come
+        galaxy.enterprise_mut().damage_device(Device::SubspaceRadio, 1.0);
+        galaxy.advance_time(REINFORCEMENT_WAVE_INTERVAL);
+        klingon_reinforcement_wave(&mut galaxy, &mut output);
+        assert_eq!(galaxy.total_klingons(), before + 3);
+        assert_eq!(output.messages.len(), 2);
+
+        let mut classic = Galaxy::new(42);
+        classic.advance_time(REINFORCEMENT_WAVE_INTERVAL * 3.0);
+        let draws = classic.rng().draws();
+        klingon_reinforcement_wave(&mut classic, &mut MockOutput::new());
+        assert_eq!(classic.total_klingons(), before);
+        assert_eq!(classic.rng().draws(), draws);
+    }
+
+    #[test]
+    fn sites_are_empty_scanned_quadrants_elsewhere() {
+        let galaxy = charted_galaxy(ModRegistry::new());
+        let here = galaxy.enterprise().quadrant();
+        let sites = construction_sites(&galaxy);
+        assert!(!sites.is_empty());
+        for site in sites {
+            assert_ne!(site, here);
+            let data = galaxy.quadrants()[site];
+            assert_eq!((data.klingons, data.starbases), (0, 0));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::io::{InputReader, OutputWriter};
+use crate::models::constants::{SectorContent, IMPULSE_ENERGY_PER_SECTOR, IMPULSE_STARDATES_PER_SECTOR, SECTOR_SIZE};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::navigation_types::Course;
+use crate::models::position::SectorPosition;
+use crate::services::turn::{self, PlayerAction};
+
+use super::course::calculate_direction;
+use super::movement::{hit_mine, ram_klingon, rams, read_course, slow_down};
+
+/// Moves the Enterprise on impulse power (Command I)
+///
+/// Prompts the player for a course (1-9) and a distance in sectors (1-8).
+/// Impulse travel costs one unit of energy and a tenth of a stardate per
+/// sector. It never leaves the quadrant, and unlike warp travel it brings
+/// no device damage or repair events.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for getting course and distance
+/// * `output` - Output writer for displaying navigation results
+///
+/// # Returns
+///
+/// * `Ok(())` on successful navigation (complete, stopped, or cancelled)
+/// * `Err` if I/O operations fail
+pub fn impulse(galaxy: &mut Galaxy, io: &mut dyn InputReader, output: &mut dyn OutputWriter) -> GameResult<()> {
+    let Some(course) = read_course(io)? else {
+        return Ok(());
+    };
+    let input = io.read_line("SECTORS (1-8)")?;
+    let sectors = match input.trim().parse::<i32>() {
+        Ok(n) if (1..=SECTOR_SIZE as i32).contains(&n) => n,
+        _ => return Ok(()),
+    };
+    if !can_impulse(galaxy, sectors, output) {
+        return Ok(());
+    }
+
+    // Klingons fire before the move, as for warp travel (spec section 8.1)
+    turn::resolve(galaxy, &PlayerAction::Impulse { course, sectors }, output)
+}
+
+/// Whether the Enterprise can move `sectors` (1-8) on impulse with the
+/// energy it has.
+pub fn can_impulse(galaxy: &Galaxy, sectors: i32, output: &mut dyn OutputWriter) -> bool {
+    if !(1..=SECTOR_SIZE as i32).contains(&sectors) {
+        return false;
+    }
+    if galaxy.enterprise().energy() < sectors as f64 * IMPULSE_ENERGY_PER_SECTOR {
+        output.writeln("INSUFFICIENT ENERGY FOR IMPULSE TRAVEL");
+        return false;
+    }
+    true
+}
+
+/// Step up to `sectors` sectors along the course, stopping short of the
+/// quadrant's edge or anything in the way.
+pub fn execute_impulse(galaxy: &mut Galaxy, course: Course, sectors: i32, output: &mut dyn OutputWriter) {
+    let (dx, dy) = calculate_direction(course.value());
+    let start = galaxy.enterprise().sector();
+    let (mut sx, mut sy) = (start.x as f64, start.y as f64);
+    let mut end = start;
+    let mut moved = 0;
+    let mut debris = 0;
+
+    for _ in 0..sectors {
+        let (nx, ny) = (sx + dx, sy + dy);
+        if !(0.5..8.5).contains(&nx) || !(0.5..8.5).contains(&ny) {
+            output.writeln(&format!("IMPULSE ENGINES STOP AT SECTOR {},{}, THE EDGE OF THE QUADRANT", end.x, end.y));
+            break;
+        }
+        let next = SectorPosition { x: (nx + 0.5).floor() as i32, y: (ny + 0.5).floor() as i32 };
+        if galaxy.sector_map().get(next) == SectorContent::Mine {
+            hit_mine(galaxy, next, output);
+        }
+        if rams(galaxy, next) {
+            ram_klingon(galaxy, next, output);
+            end = next;
+            moved += 1;
+            break;
+        }
+        if galaxy.sector_map().get(next) == SectorContent::Debris {
+            debris += 1;
+        } else if !galaxy.sector_map().is_empty(next) {
+            output.writeln(&format!("IMPULSE ENGINES STOP AT SECTOR {},{} TO AVOID A COLLISION", end.x, end.y));
+            break;
+        }
+        (sx, sy) = (nx, ny);
+        end = next;
+        moved += 1;
+    }
+
+    if moved == 0 {
+        return;
+    }
+    let quadrant = galaxy.enterprise().quadrant();
+    galaxy.sector_map_mut().set(start, SectorContent::Empty);
+    galaxy.sector_map_mut().set(end, SectorContent::Enterprise);
+    galaxy.enterprise_mut().move_to(quadrant, end);
+    galaxy.enterprise_mut().subtract_energy(moved as f64 * IMPULSE_ENERGY_PER_SECTOR);
+    galaxy.advance_time(moved as f64 * IMPULSE_STARDATES_PER_SECTOR);
+    slow_down(galaxy, debris, output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::position::QuadrantPosition;
+    use crate::models::sector_map::SectorMap;
+
+    fn empty_quadrant() -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let sector = SectorPosition { x: 4, y: 4 };
+        galaxy.enterprise_mut().move_to(QuadrantPosition { x: 4, y: 4 }, sector);
+        galaxy.sector_map_mut().set(sector, SectorContent::Enterprise);
+        galaxy
+    }
+
+    #[test]
+    fn impulse_costs_a_unit_and_a_tenth_of_a_stardate_per_sector() {
+        let mut galaxy = empty_quadrant();
+        let (energy, stardate) = (galaxy.enterprise().energy(), galaxy.stardate());
+        let mut input = MockInput::new(vec!["1", "3"]);
+        impulse(&mut galaxy, &mut input, &mut MockOutput::new()).unwrap();
+
+        let end = SectorPosition { x: 7, y: 4 };
+        assert_eq!(galaxy.enterprise().sector(), end);
+        assert_eq!(galaxy.sector_map().get(end), SectorContent::Enterprise);
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 4, y: 4 }), SectorContent::Empty);
+        assert_eq!(galaxy.enterprise().energy(), energy - 3.0);
+        assert!((galaxy.stardate() - stardate - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn impulse_stops_at_the_edge_and_short_of_obstacles() {
+        let mut galaxy = empty_quadrant();
+        let mut output = MockOutput::new();
+        execute_impulse(&mut galaxy, Course::new(3.0).unwrap(), 8, &mut output);
+        assert_eq!(galaxy.enterprise().quadrant(), QuadrantPosition { x: 4, y: 4 });
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 4, y: 1 });
+        assert_eq!(output.messages.concat(), "IMPULSE ENGINES STOP AT SECTOR 4,1, THE EDGE OF THE QUADRANT\n");
+
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 3 }, SectorContent::Star);
+        let mut output = MockOutput::new();
+        execute_impulse(&mut galaxy, Course::new(7.0).unwrap(), 5, &mut output);
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 4, y: 2 });
+        assert_eq!(output.messages.concat(), "IMPULSE ENGINES STOP AT SECTOR 4,2 TO AVOID A COLLISION\n");
+    }
+}
@@ -1,14 +1,19 @@
 //! Navigation system
 //!
-//! Handles ship movement, course plotting, warp travel,
-//! and device damage/repair during navigation.
+//! Handles ship movement, course plotting, warp and impulse travel,
+//! device damage/repair, and random events during navigation.
 
 mod course;
 mod movement;
+mod impulse;
 mod damage;
+mod events;
+mod autopilot;
 
 // Re-export main navigation function
-pub use movement::navigate;
+pub use movement::{can_warp, engage_warp, navigate};
+pub use impulse::{can_impulse, execute_impulse, impulse};
+pub use autopilot::{autopilot, plan_route, Route};
 
-// Re-export calculate_direction for use by combat module
-pub use course::calculate_direction;
+// Re-export the course math for use by combat and previews
+pub use course::{calculate_direction, calculate_direction_and_distance, calculate_quadrant_crossing};
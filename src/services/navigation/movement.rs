@@ -1,13 +1,23 @@
 use crate::io::{InputReader, OutputWriter};
-use crate::models::constants::{Device, SectorContent};
+use crate::models::constants::{
+    Device, SectorContent, BLACK_HOLE_HULL_DAMAGE, COLLISION_HULL_DAMAGE, DEBRIS_STARDATES_PER_SECTOR, MINE_SHIELD_DAMAGE, RAMMING_IMPACT,
+    RAMMING_MAX_DEVICES_DAMAGED,
+};
 use crate::models::errors::GameResult;
 use crate::models::galaxy::Galaxy;
 use crate::models::navigation_types::{Course, WarpFactor};
+use crate::models::output_event::OutputEvent;
 use crate::models::position::SectorPosition;
 use crate::services::combat;
+use crate::services::pending;
+use crate::services::turn::{self, PlayerAction};
+use crate::ui::presenters::Emit;
 
 use super::course::{calculate_direction, calculate_quadrant_crossing};
 use super::damage::{auto_repair_devices, random_damage_event};
+use super::events::{
+    klingon_migration_event, klingon_reinforcement_event, klingon_reinforcement_wave, starbase_construction_event,
+};
 
 /// Engages warp engines to move the Enterprise (Command 0)
 ///
@@ -35,18 +45,28 @@ pub fn navigate(
     io: &mut dyn InputReader,
     output: &mut dyn OutputWriter,
 ) -> GameResult<()> {
-    let (course, warp_factor) = match read_course_and_warp(galaxy, io, output)? {
+    let (course, warp) = match read_course_and_warp(galaxy, io, output)? {
         Some(values) => values,
         None => return Ok(()),
     };
 
     // If Klingons present, they fire before warp move (spec section 8.1)
-    if !galaxy.sector_map().klingons.is_empty()
-        && combat::klingons_fire(galaxy, output)
-    {
-        return Ok(()); // Enterprise destroyed, game ended
+    turn::resolve(galaxy, &PlayerAction::Warp { course, warp }, output)
+}
+
+/// Whether the warp engines can make `warp_factor`; damaged engines
+/// manage warp 0.2 at most.
+pub fn can_warp(galaxy: &Galaxy, warp_factor: WarpFactor, output: &mut dyn OutputWriter) -> bool {
+    if galaxy.enterprise().is_damaged(Device::WarpEngines) && warp_factor.value() > 0.2 {
+        output.writeln("WARP ENGINES ARE DAMAGED, MAXIMUM SPEED = WARP .2");
+        return false;
     }
+    true
+}
 
+/// Make a warp move once any Klingons have fired, unless the Enterprise
+/// is out of energy, in which case it may be dead in space.
+pub fn engage_warp(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, output: &mut dyn OutputWriter) {
     // Energy/shields check (no-Klingons path, spec section 10.4)
     if galaxy.enterprise().energy() <= 0.0 {
         if galaxy.enterprise().shields() < 1.0 {
@@ -55,7 +75,10 @@ pub fn navigate(
 
             // Klingons fire repeatedly until Enterprise destroyed or survives (spec 10.4)
             combat::dead_in_space_loop(galaxy, output);
-            return Ok(()); // Game ended (either destroyed or demoted)
+            if !galaxy.enterprise().is_destroyed() {
+                galaxy.mark_dead_in_space();
+            }
+            return; // The engine ends the game either way
         } else {
             output.writeln(&format!(
                 "YOU HAVE {} UNITS OF ENERGY",
@@ -65,12 +88,11 @@ pub fn navigate(
                 "SUGGEST YOU GET SOME FROM YOUR SHIELDS WHICH HAVE {} UNITS LEFT",
                 galaxy.enterprise().shields() as i32
             ));
-            return Ok(()); // Prevent movement
+            return; // Prevent movement
         }
     }
 
     execute_move(galaxy, course, warp_factor, output);
-    Ok(())
 }
 
 /// Prompt the player for course and warp factor. Returns None if the player
@@ -80,20 +102,8 @@ fn read_course_and_warp(
     io: &mut dyn InputReader,
     output: &mut dyn OutputWriter,
 ) -> GameResult<Option<(Course, WarpFactor)>> {
-    // Course input loop
-    let course: Course = loop {
-        let input = io.read_line("COURSE (1-9)")?;
-        let value: f64 = match input.trim().parse() {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        if value == 0.0 {
-            return Ok(None);
-        }
-        match Course::new(value) {
-            Ok(c) => break c,
-            Err(_) => continue, // Invalid range — re-prompt
-        }
+    let Some(course) = read_course(io)? else {
+        return Ok(None);
     };
 
     // Warp factor input
@@ -108,17 +118,88 @@ fn read_course_and_warp(
     };
 
     // Check for damaged warp engines
-    if galaxy.enterprise().is_damaged(Device::WarpEngines) && warp_factor.value() > 0.2 {
-        output.writeln("WARP ENGINES ARE DAMAGED, MAXIMUM SPEED = WARP .2");
+    if !can_warp(galaxy, warp_factor, output) {
         return Ok(None);
     }
 
     Ok(Some((course, warp_factor)))
 }
 
+/// Prompt for a course until a valid one is given. Returns None if the
+/// player cancels with course 0.
+pub(super) fn read_course(io: &mut dyn InputReader) -> GameResult<Option<Course>> {
+    loop {
+        let input = io.read_line("COURSE (1-9)")?;
+        let value: f64 = match input.trim().parse() {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if value == 0.0 {
+            return Ok(None);
+        }
+        match Course::new(value) {
+            Ok(c) => return Ok(Some(c)),
+            Err(_) => continue, // Invalid range — re-prompt
+        }
+    }
+}
+
+/// A mine the Enterprise runs into goes off against its shields, leaving
+/// the way clear.
+pub(super) fn hit_mine(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) {
+    galaxy.detonate_mine(pos);
+    let shields = (galaxy.enterprise().shields() - MINE_SHIELD_DAMAGE).max(0.0);
+    galaxy.enterprise_mut().set_shields(shields);
+    output.writeln(&format!("*** MINE DETONATES AT SECTOR {},{} ***", pos.x, pos.y));
+    output.writeln(&format!("SHIELDS DOWN TO {} UNITS", shields as i32));
+}
+
+/// Whether the Enterprise rams whatever is at `pos` instead of stopping:
+/// only a Klingon, and only under the ramming rule.
+pub(super) fn rams(galaxy: &Galaxy, pos: SectorPosition) -> bool {
+    galaxy.ruleset().ramming
+        && matches!(galaxy.sector_map().get(pos), SectorContent::Klingon | SectorContent::KlingonCommander)
+}
+
+/// The Enterprise rams the Klingon at `pos`, destroying it. The ship takes
+/// a `RAMMING_IMPACT` hit, which the shields absorb as far as they can,
+/// and damage to one to `RAMMING_MAX_DEVICES_DAMAGED` devices.
+pub(super) fn ram_klingon(galaxy: &mut Galaxy, pos: SectorPosition, output: &mut dyn OutputWriter) {
+    output.writeln(&format!("*** THE ENTERPRISE RAMS THE KLINGON AT SECTOR {},{} ***", pos.x, pos.y));
+    output.emit(OutputEvent::KlingonDestroyed);
+    // The count is kept in step with the sector map, so this cannot fail
+    let _ = galaxy.destroy_klingon(pos);
+    galaxy.sector_map_mut().klingons.retain(|k| k.sector != pos);
+
+    galaxy.enterprise_mut().absorb_hit(RAMMING_IMPACT);
+    output.writeln(&format!(
+        "SHIELDS {}  HULL INTEGRITY {}%",
+        galaxy.enterprise().shields() as i32,
+        galaxy.enterprise().hull().ceil() as i32
+    ));
+    let devices = galaxy.ruleset().devices();
+    let count = galaxy.rng_mut().range("ramming.devices", 1..=RAMMING_MAX_DEVICES_DAMAGED);
+    for _ in 0..count {
+        let index = (galaxy.rng_mut().unit("ramming.device") * devices.len() as f64).floor() as usize;
+        let severity = (galaxy.rng_mut().unit("ramming.severity") * 5.0).floor() + 1.0;
+        let device = devices[index];
+        galaxy.enterprise_mut().damage_device(device, severity);
+        output.emit(OutputEvent::DeviceDamaged { device, severity });
+    }
+}
+
+/// Debris the Enterprise flew through costs `DEBRIS_STARDATES_PER_SECTOR`
+/// for each sector of it.
+pub(super) fn slow_down(galaxy: &mut Galaxy, sectors: i32, output: &mut dyn OutputWriter) {
+    if sectors > 0 {
+        output.writeln("DEBRIS SLOWS THE ENTERPRISE");
+        galaxy.advance_time(sectors as f64 * DEBRIS_STARDATES_PER_SECTOR);
+    }
+}
+
 /// Execute the warp move: step through sectors, handle collisions and
 /// quadrant boundary crossings, update energy and stardate.
-fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, output: &mut dyn OutputWriter) {
+pub(super) fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, output: &mut dyn OutputWriter) {
     let (dx, dy) = calculate_direction(course.value());
     let n = (warp_factor.value() * 8.0).floor() as i32;
 
@@ -132,6 +213,8 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
     let mut sx = galaxy.enterprise().sector().x as f64;
     let mut sy = galaxy.enterprise().sector().y as f64;
     let mut crossed_boundary = false;
+    let mut thrown_to = None;
+    let mut debris = 0;
 
     // Remove Enterprise from current position before moving
     galaxy
@@ -155,6 +238,33 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
             x: check_x,
             y: check_y,
         };
+        if galaxy.sector_map().get(check_pos) == SectorContent::Mine {
+            hit_mine(galaxy, check_pos, output);
+        }
+        if galaxy.sector_map().get(check_pos) == SectorContent::BlackHole {
+            let sector = galaxy.random_empty_sector();
+            output.writeln(&format!(
+                "THE ENTERPRISE IS CAUGHT BY A BLACK HOLE AT SECTOR {},{}",
+                check_x, check_y
+            ));
+            output.writeln(&format!("AND THROWN TO SECTOR {},{}", sector.x, sector.y));
+            galaxy.enterprise_mut().damage_hull(BLACK_HOLE_HULL_DAMAGE);
+            output.writeln(&format!(
+                "HULL INTEGRITY {}%",
+                galaxy.enterprise().hull().ceil() as i32
+            ));
+            thrown_to = Some(sector);
+            break;
+        }
+        if rams(galaxy, check_pos) {
+            // The Enterprise ends up where the Klingon was
+            ram_klingon(galaxy, check_pos, output);
+            break;
+        }
+        if galaxy.sector_map().get(check_pos) == SectorContent::Debris {
+            debris += 1;
+            continue;
+        }
         if galaxy.sector_map().get(check_pos) != SectorContent::Empty {
             // Back up one step
             sx -= dx;
@@ -165,10 +275,17 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
                 "WARP ENGINES SHUTDOWN AT SECTOR {},{} DUE TO BAD NAVIGATION",
                 stop_x, stop_y
             ));
+            galaxy.enterprise_mut().damage_hull(COLLISION_HULL_DAMAGE);
+            output.writeln(&format!(
+                "HULL INTEGRITY {}%",
+                galaxy.enterprise().hull().ceil() as i32
+            ));
             break;
         }
     }
 
+    slow_down(galaxy, debris, output);
+
     if crossed_boundary {
         // Quadrant boundary crossing
         let (new_quadrant, new_sector) = calculate_quadrant_crossing(
@@ -181,8 +298,10 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
             n,
         );
 
+        galaxy.leave_quadrant();
         galaxy.enterprise_mut().move_to(new_quadrant, new_sector);
         galaxy.enter_quadrant();
+        combat::red_alert(galaxy, output);
 
         // Record the new quadrant to computer memory
         galaxy.record_quadrant_to_memory(
@@ -192,15 +311,19 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
 
         // Boundary crossing always advances stardate by 1
         galaxy.advance_time(1.0);
+        output.emit(OutputEvent::QuadrantEntered {
+            quadrant: new_quadrant,
+            stardate: galaxy.stardate(),
+        });
         check_time_limit(galaxy, output);
     } else {
         // Intra-quadrant move: update sector map
         let final_x = (sx + 0.5).floor() as i32;
         let final_y = (sy + 0.5).floor() as i32;
-        let new_sector = SectorPosition {
+        let new_sector = thrown_to.unwrap_or(SectorPosition {
             x: final_x,
             y: final_y,
-        };
+        });
 
         let quadrant = galaxy.enterprise().quadrant();
         galaxy
@@ -228,6 +351,21 @@ fn execute_move(galaxy: &mut Galaxy, course: Course, warp_factor: WarpFactor, ou
 
     // Random damage/repair events - 20% chance (spec section 5.3)
     random_damage_event(galaxy, output);
+
+    // Positive events for a careful captain
+    starbase_construction_event(galaxy, output);
+
+    // Harder variant: Klingons close in on the starbases over time
+    klingon_migration_event(galaxy, output);
+
+    // Adaptive difficulty: reinforcements follow the player's pace
+    klingon_reinforcement_event(galaxy, output);
+
+    // Harder variant: reinforcements arrive on a timer
+    klingon_reinforcement_wave(galaxy, output);
+
+    // Harder variant: Klingons besiege a starbase
+    pending::starbase_attack_event(galaxy, output);
 }
 
 /// Check if the time limit has been exceeded (spec section 10.3).
@@ -239,7 +377,8 @@ fn check_time_limit(_galaxy: &Galaxy, _output: &mut dyn OutputWriter) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::io::test_utils::MockOutput;
+    use crate::io::testing::MockOutput;
+    use crate::models::constants::INITIAL_HULL;
     use crate::models::galaxy::Galaxy;
     use crate::models::position::QuadrantPosition;
 
@@ -381,6 +520,94 @@ mod tests {
         execute_move(&mut galaxy, Course::new(1.0).unwrap(), WarpFactor::new(0.5).unwrap(), &mut MockOutput::new());
         assert_eq!(galaxy.enterprise().sector().x, 3);
         assert_eq!(galaxy.enterprise().sector().y, 4);
+        assert_eq!(galaxy.enterprise().hull(), INITIAL_HULL - COLLISION_HULL_DAMAGE);
+    }
+
+    #[test]
+    fn black_holes_throw_the_ship_elsewhere() {
+        let mut galaxy = Galaxy::new(42);
+        place_enterprise_for_test(&mut galaxy, 4, 4, 1, 4);
+        let black_hole = SectorPosition { x: 3, y: 4 };
+        galaxy.sector_map_mut().set(black_hole, SectorContent::BlackHole);
+
+        let mut output = MockOutput::new();
+        execute_move(&mut galaxy, Course::new(1.0).unwrap(), WarpFactor::new(0.5).unwrap(), &mut output);
+        let sector = galaxy.enterprise().sector();
+        assert!(output.messages[0].starts_with("THE ENTERPRISE IS CAUGHT BY A BLACK HOLE AT SECTOR 3,4"));
+        assert_eq!(output.messages[1], format!("AND THROWN TO SECTOR {},{}\n", sector.x, sector.y));
+        assert_ne!(sector, black_hole);
+        assert_eq!(galaxy.sector_map().get(sector), SectorContent::Enterprise);
+        let start = SectorPosition { x: 1, y: 4 };
+        if sector != start {
+            assert_eq!(galaxy.sector_map().get(start), SectorContent::Empty);
+        }
+        assert_eq!(galaxy.enterprise().hull(), INITIAL_HULL - BLACK_HOLE_HULL_DAMAGE);
+    }
+
+    #[test]
+    fn mines_drain_the_shields_and_clear_the_way() {
+        let mut galaxy = Galaxy::new(42);
+        place_enterprise_for_test(&mut galaxy, 4, 4, 1, 4);
+        let mine = SectorPosition { x: 3, y: 4 };
+        galaxy.sector_map_mut().set(mine, SectorContent::Mine);
+        galaxy.enterprise_mut().set_shields(200.0);
+
+        let mut output = MockOutput::new();
+        execute_move(&mut galaxy, Course::new(1.0).unwrap(), WarpFactor::new(0.5).unwrap(), &mut output);
+        assert_eq!(output.messages[0], "*** MINE DETONATES AT SECTOR 3,4 ***\n");
+        assert_eq!(output.messages[1], "SHIELDS DOWN TO 50 UNITS\n");
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 5, y: 4 });
+        assert_eq!(galaxy.sector_map().get(mine), SectorContent::Empty);
+    }
+
+    #[test]
+    fn ramming_destroys_the_klingon_only_under_the_rule() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::klingon::Klingon;
+        use crate::models::ruleset::Ruleset;
+
+        let klingon = SectorPosition { x: 6, y: 4 };
+        for ramming in [false, true] {
+            let ruleset = Ruleset { ramming, ..Default::default() };
+            let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+            place_enterprise_for_test(&mut galaxy, 4, 4, 4, 4);
+            galaxy.sector_map_mut().set(klingon, SectorContent::Klingon);
+            galaxy.sector_map_mut().klingons.push(Klingon::new(klingon));
+            galaxy.set_total_klingons(2);
+            galaxy.enterprise_mut().set_shields(500.0);
+
+            let mut output = MockOutput::new();
+            execute_move(&mut galaxy, Course::new(1.0).unwrap(), WarpFactor::new(0.25).unwrap(), &mut output);
+            if !ramming {
+                assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 5, y: 4 });
+                assert_eq!(galaxy.sector_map().klingons.len(), 1);
+                continue;
+            }
+            output.expect_line_containing("*** THE ENTERPRISE RAMS THE KLINGON AT SECTOR 6,4 ***");
+            assert_eq!(galaxy.enterprise().sector(), klingon);
+            assert_eq!(galaxy.sector_map().get(klingon), SectorContent::Enterprise);
+            assert!(galaxy.sector_map().klingons.is_empty());
+            assert_eq!(galaxy.total_klingons(), 1);
+            assert!(galaxy.enterprise().shields() < 500.0);
+            assert!(galaxy.ruleset().devices().iter().any(|&d| galaxy.enterprise().is_damaged(d)));
+        }
+    }
+
+    #[test]
+    fn debris_slows_the_enterprise_without_stopping_it() {
+        let mut galaxy = Galaxy::new(42);
+        place_enterprise_for_test(&mut galaxy, 4, 4, 1, 4);
+        for x in [3, 4] {
+            galaxy.sector_map_mut().set(SectorPosition { x, y: 4 }, SectorContent::Debris);
+        }
+        let stardate = galaxy.stardate();
+
+        let mut output = MockOutput::new();
+        execute_move(&mut galaxy, Course::new(1.0).unwrap(), WarpFactor::new(0.5).unwrap(), &mut output);
+        output.expect_line_containing("DEBRIS SLOWS THE ENTERPRISE");
+        assert_eq!(galaxy.enterprise().sector(), SectorPosition { x: 5, y: 4 });
+        assert!((galaxy.stardate() - stardate - 2.0 * DEBRIS_STARDATES_PER_SECTOR).abs() < 1e-9);
+        assert_eq!(galaxy.sector_map().get(SectorPosition { x: 3, y: 4 }), SectorContent::Debris);
     }
 
     // --- Quadrant boundary crossing integration test ---
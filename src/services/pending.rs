@@ -0,0 +1,169 @@
+//! Pending events
+//!
+//! Events the galaxy schedules for a later stardate, such as Klingon attacks
+//! on starbases. They start on navigation moves and are resolved once per
+//! turn, so clearing an attack in combat counts before the deadline passes.
+
+use crate::io::OutputWriter;
+use crate::models::constants::{Device, STARBASE_ATTACK_CHANCE, STARBASE_ATTACK_DEADLINE};
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::models::pending::PendingEvent;
+use crate::models::position::QuadrantPosition;
+use crate::ui::presenters::Emit;
+
+/// Starbase attacks on navigation moves, when the ruleset enables them.
+/// While no attack is under way, there is a chance that Klingons attack a
+/// starbase elsewhere in the galaxy, bringing one of their own if none are
+/// there already. Nothing is drawn from the RNG when the rule is off.
+pub fn starbase_attack_event(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    if !galaxy.ruleset().starbase_attacks || !galaxy.pending_events().is_empty() {
+        return;
+    }
+    let sites = attack_sites(galaxy);
+    if sites.is_empty() || galaxy.rng_mut().unit("events.starbase_attack") >= STARBASE_ATTACK_CHANCE {
+        return;
+    }
+    let index = galaxy
+        .rng_mut()
+        .range("events.starbase_attack_site", 0..=sites.len() as i32 - 1) as usize;
+    let quadrant = sites[index];
+    if galaxy.quadrants()[quadrant].klingons == 0 {
+        galaxy.reinforce_klingons(quadrant);
+    }
+    let deadline = galaxy.stardate() + STARBASE_ATTACK_DEADLINE;
+    galaxy.schedule(PendingEvent::StarbaseAttack { quadrant, deadline });
+    radio(galaxy, output, OutputEvent::StarbaseUnderAttack { quadrant, stardates_left: STARBASE_ATTACK_DEADLINE });
+}
+
+/// Resolve every pending event that has come due. A starbase under attack
+/// is saved once its quadrant is clear of Klingons, and falls at the
+/// deadline otherwise; an attack on a starbase already gone is dropped.
+/// Called once per turn.
+pub fn resolve_pending_events(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    for event in galaxy.pending_events().to_vec() {
+        match event {
+            PendingEvent::StarbaseAttack { quadrant, deadline } => {
+                if galaxy.quadrants()[quadrant].klingons == 0 {
+                    galaxy.unschedule(&event);
+                    radio(galaxy, output, OutputEvent::StarbaseRelieved { quadrant });
+                } else if galaxy.stardate() >= deadline {
+                    galaxy.unschedule(&event);
+                    if galaxy.overrun_starbase(quadrant) {
+                        radio(galaxy, output, OutputEvent::StarbaseOverrun { quadrant });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pass on a subspace message, if the radio is working to hear it.
+fn radio(galaxy: &Galaxy, output: &mut dyn OutputWriter, event: OutputEvent) {
+    if !galaxy.enterprise().is_damaged(Device::SubspaceRadio) {
+        output.emit(event);
+    }
+}
+
+/// Starbase quadrants other than the Enterprise's, in row-major order.
+fn attack_sites(galaxy: &Galaxy) -> Vec<QuadrantPosition> {
+    let here = galaxy.enterprise().quadrant();
+    galaxy
+        .quadrants_iter()
+        .filter(|&(pos, data)| pos != here && data.starbases > 0)
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::galaxy::GalaxyOptions;
+    use crate::models::ruleset::Ruleset;
+
+    fn galaxy_under_attack() -> Galaxy {
+        let ruleset = Ruleset { starbase_attacks: true, ..Default::default() };
+        let mut galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        while galaxy.pending_events().is_empty() {
+            starbase_attack_event(&mut galaxy, &mut MockOutput::new());
+        }
+        galaxy
+    }
+
+    #[test]
+    fn an_attack_brings_klingons_to_a_starbase_and_is_off_by_default() {
+        let galaxy = galaxy_under_attack();
+        let PendingEvent::StarbaseAttack { quadrant, deadline } = galaxy.pending_events()[0];
+        assert_eq!(galaxy.quadrants()[quadrant].starbases, 1);
+        assert!(galaxy.quadrants()[quadrant].klingons > 0);
+        assert_eq!(deadline, galaxy.stardate() + STARBASE_ATTACK_DEADLINE);
+
+        let mut classic = Galaxy::new(42);
+        let draws = classic.rng().draws();
+        starbase_attack_event(&mut classic, &mut MockOutput::new());
+        assert!(classic.pending_events().is_empty());
+        assert_eq!(classic.rng().draws(), draws);
+    }
+
+    #[test]
+    fn starbases_fall_at_the_deadline() {
+        let mut galaxy = galaxy_under_attack();
+        let PendingEvent::StarbaseAttack { quadrant, .. } = galaxy.pending_events()[0];
+        let starbases = galaxy.total_starbases();
+        let mut output = MockOutput::new();
+
+        galaxy.advance_time(STARBASE_ATTACK_DEADLINE - 1.0);
+        resolve_pending_events(&mut galaxy, &mut output);
+        assert_eq!(galaxy.pending_events().len(), 1);
+
+        galaxy.advance_time(1.0);
+        resolve_pending_events(&mut galaxy, &mut output);
+        assert!(galaxy.pending_events().is_empty());
+        assert_eq!(galaxy.total_starbases(), starbases - 1);
+        assert_eq!(galaxy.starbases_lost(), 1);
+        assert_eq!(galaxy.quadrants()[quadrant].starbases, 0);
+        assert_eq!(
+            output.messages.concat(),
+            format!("SUBSPACE MESSAGE: STARBASE IN QUADRANT {},{} HAS BEEN DESTROYED\n", quadrant.x, quadrant.y)
+        );
+    }
+
+    #[test]
+    fn clearing_the_quadrant_saves_the_starbase() {
+        let mut galaxy = galaxy_under_attack();
+        let PendingEvent::StarbaseAttack { quadrant, .. } = galaxy.pending_events()[0];
+        let starbases = galaxy.total_starbases();
+        let sector = galaxy.enterprise().sector();
+        galaxy.enterprise_mut().move_to(quadrant, sector);
+        galaxy.enter_quadrant();
+        for klingon in galaxy.sector_map().klingons.clone() {
+            galaxy.destroy_klingon(klingon.sector).unwrap();
+        }
+        let mut output = MockOutput::new();
+        resolve_pending_events(&mut galaxy, &mut output);
+        assert!(galaxy.pending_events().is_empty());
+        assert_eq!(galaxy.total_starbases(), starbases);
+        assert!(output.messages.concat().contains("REPORTS THE ATTACK BROKEN"));
+    }
+
+    #[test]
+    fn a_starbase_already_gone_is_not_lost_twice() {
+        let mut galaxy = galaxy_under_attack();
+        let PendingEvent::StarbaseAttack { quadrant, .. } = galaxy.pending_events()[0];
+        let sector = galaxy.enterprise().sector();
+        galaxy.enterprise_mut().move_to(quadrant, sector);
+        galaxy.enter_quadrant();
+        let starbase = galaxy.sector_map().starbase.unwrap();
+        galaxy.destroy_starbase_by_torpedo(starbase);
+        let (starbases, lost) = (galaxy.total_starbases(), galaxy.starbases_lost());
+
+        let mut output = MockOutput::new();
+        galaxy.advance_time(STARBASE_ATTACK_DEADLINE);
+        resolve_pending_events(&mut galaxy, &mut output);
+        assert!(galaxy.pending_events().is_empty());
+        assert_eq!((galaxy.total_starbases(), galaxy.starbases_lost()), (starbases, lost));
+        assert!(output.messages.is_empty());
+        assert!(!galaxy.overrun_starbase(quadrant));
+    }
+}
@@ -0,0 +1,360 @@
+//! Command previews
+//!
+//! Projects the outcome of a command without running it, so a front end can
+//! show the energy cost, where a warp ends, and the fire expected in return
+//! before the player commits. Every random factor is taken at its mean and
+//! nothing is drawn from the game's RNG, so previewing never changes the game.
+//! Previews see only what the sensors see: a cloaked Romulan is empty space.
+
+use crate::models::constants::{
+    Device, SectorContent, BLACK_HOLE_HULL_DAMAGE, COLLISION_HULL_DAMAGE, DEBRIS_STARDATES_PER_SECTOR,
+    TORPEDO_DAMAGE_TO_COMMANDER,
+};
+use crate::models::galaxy::Galaxy;
+use crate::models::navigation_types::{Course, WarpFactor};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+
+use super::combat::calculate_distance;
+use super::navigation::{calculate_direction, calculate_quadrant_crossing};
+
+/// A command with its answers already given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Warp { course: Course, warp: WarpFactor },
+    Phasers { energy: f64 },
+    Torpedo { course: Course },
+    Shields { units: f64 },
+}
+
+/// Projected outcome of a command.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PreviewReport {
+    /// Energy the command spends; negative when a short warp gains energy.
+    pub energy_cost: f64,
+    pub torpedoes_used: i32,
+    pub stardates: f64,
+    /// Where a warp leaves the Enterprise; `None` when a black hole will
+    /// throw it somewhere random.
+    pub endpoint: Option<(QuadrantPosition, SectorPosition)>,
+    /// True when a warp stops short against an obstacle.
+    pub blocked: bool,
+    /// Hull integrity lost to a collision or a black hole.
+    pub hull_damage: f64,
+    /// Mean damage the command deals to Klingons.
+    pub expected_damage_dealt: f64,
+    pub expected_klingons_destroyed: usize,
+    /// Mean damage of the Klingon fire the command draws.
+    pub expected_damage_taken: f64,
+}
+
+/// Project the outcome of `command` against the current state.
+pub fn preview(galaxy: &Galaxy, command: Command) -> PreviewReport {
+    let here = galaxy.enterprise().sector();
+    // (shields, distance) of each Klingon that will shoot
+    let klingons: Vec<(f64, f64)> = galaxy
+        .sector_map()
+        .klingons
+        .iter()
+        .filter(|k| k.is_alive())
+        .map(|k| (k.shields, calculate_distance(here, k.sector)))
+        .collect();
+
+    match command {
+        Command::Warp { course, warp } => {
+            let mut report = preview_warp(galaxy, course, warp);
+            // Klingons in the quadrant fire before the ship moves
+            report.expected_damage_taken = expected_fire(galaxy, &klingons);
+            report
+        }
+        Command::Phasers { energy } => {
+            let mut hit_energy = energy;
+            if galaxy.enterprise().is_damaged(Device::Computer) {
+                hit_energy *= 0.5;
+            }
+            let count = klingons.len() as f64;
+            let mut dealt = 0.0;
+            let mut destroyed = 0;
+            let mut survivors = Vec::new();
+            for &(shields, distance) in &klingons {
+                let hit = hit_energy / count / distance;
+                dealt += hit.min(shields);
+                if hit >= shields {
+                    destroyed += 1;
+                } else {
+                    survivors.push((shields - hit, distance));
+                }
+            }
+            PreviewReport {
+                energy_cost: energy,
+                expected_damage_dealt: dealt,
+                expected_klingons_destroyed: destroyed,
+                expected_damage_taken: expected_fire(galaxy, &survivors),
+                ..Default::default()
+            }
+        }
+        Command::Torpedo { course } => {
+            let mut report = PreviewReport {
+                torpedoes_used: 1,
+                ..Default::default()
+            };
+            let mut survivors = klingons.clone();
+            if let Some(target) = torpedo_target(galaxy, course) {
+                let alive: Vec<_> = galaxy.sector_map().klingons.iter().filter(|k| k.is_alive()).collect();
+                if let Some(index) = alive.iter().position(|k| k.sector == target) {
+                    let (shields, distance) = survivors[index];
+                    // A commander's shields can outlast the torpedo
+                    if alive[index].is_commander() && shields > TORPEDO_DAMAGE_TO_COMMANDER {
+                        survivors[index] = (shields - TORPEDO_DAMAGE_TO_COMMANDER, distance);
+                        report.expected_damage_dealt = TORPEDO_DAMAGE_TO_COMMANDER;
+                    } else {
+                        survivors.remove(index);
+                        report.expected_damage_dealt = shields;
+                        report.expected_klingons_destroyed = 1;
+                    }
+                }
+            }
+            report.expected_damage_taken = expected_fire(galaxy, &survivors);
+            report
+        }
+        Command::Shields { units } => PreviewReport {
+            energy_cost: units - galaxy.enterprise().shields(),
+            ..Default::default()
+        },
+    }
+}
+
+/// Follow the warp path the way `navigation` does, without moving.
+fn preview_warp(galaxy: &Galaxy, course: Course, warp: WarpFactor) -> PreviewReport {
+    let (dx, dy) = calculate_direction(course.value());
+    let n = (warp.value() * 8.0).floor() as i32;
+    let quadrant = galaxy.enterprise().quadrant();
+    let start = galaxy.enterprise().sector();
+    if n == 0 {
+        return PreviewReport {
+            endpoint: Some((quadrant, start)),
+            ..Default::default()
+        };
+    }
+
+    let mut report = PreviewReport {
+        energy_cost: (n - 5) as f64,
+        ..Default::default()
+    };
+    let (mut sx, mut sy) = (start.x as f64, start.y as f64);
+    for _ in 0..n {
+        sx += dx;
+        sy += dy;
+        if !(0.5..8.5).contains(&sx) || !(0.5..8.5).contains(&sy) {
+            report.endpoint = Some(calculate_quadrant_crossing(
+                quadrant.x, quadrant.y, start.x, start.y, dx, dy, n,
+            ));
+            report.stardates = 1.0;
+            return report;
+        }
+        let next = SectorPosition {
+            x: (sx + 0.5).floor() as i32,
+            y: (sy + 0.5).floor() as i32,
+        };
+        if galaxy.sector_map().visible(next) == SectorContent::BlackHole {
+            report.blocked = true;
+            report.hull_damage = BLACK_HOLE_HULL_DAMAGE;
+            if warp.is_warp() {
+                report.stardates = 1.0;
+            }
+            return report;
+        }
+        if galaxy.sector_map().visible(next) == SectorContent::Debris {
+            report.stardates += DEBRIS_STARDATES_PER_SECTOR;
+            continue;
+        }
+        if !matches!(galaxy.sector_map().visible(next), SectorContent::Empty | SectorContent::Enterprise) {
+            sx -= dx;
+            sy -= dy;
+            report.blocked = true;
+            report.hull_damage = COLLISION_HULL_DAMAGE;
+            break;
+        }
+    }
+    let end = SectorPosition {
+        x: (sx + 0.5).floor() as i32,
+        y: (sy + 0.5).floor() as i32,
+    };
+    report.endpoint = Some((quadrant, end));
+    if warp.is_warp() {
+        report.stardates = 1.0;
+    }
+    report
+}
+
+/// The first occupied sector a torpedo on `course` would reach.
+fn torpedo_target(galaxy: &Galaxy, course: Course) -> Option<SectorPosition> {
+    let (dx, dy) = calculate_direction(course.value());
+    let start = galaxy.enterprise().sector();
+    let (mut x, mut y) = (start.x as f64, start.y as f64);
+    loop {
+        x += dx;
+        y += dy;
+        if !(0.5..8.5).contains(&x) || !(0.5..8.5).contains(&y) {
+            return None;
+        }
+        let pos = SectorPosition {
+            x: (x + 0.5).floor() as i32,
+            y: (y + 0.5).floor() as i32,
+        };
+        // Debris may or may not stop the torpedo, so look past it
+        if !matches!(galaxy.sector_map().visible(pos), SectorContent::Empty | SectorContent::Debris) {
+            return Some(pos);
+        }
+    }
+}
+
+/// Mean damage of one round of fire from `klingons`, none when docked.
+fn expected_fire(galaxy: &Galaxy, klingons: &[(f64, f64)]) -> f64 {
+    if galaxy
+        .enterprise()
+        .is_adjacent_to_starbase(galaxy.sector_map().starbase)
+    {
+        return 0.0;
+    }
+    // Klingon hit = shields / distance * 2 * rnd * aggression
+    let mean: f64 = klingons.iter().map(|&(shields, distance)| shields / distance).sum();
+    mean * galaxy.klingon_aggression()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::klingon::Klingon;
+    use crate::models::sector_map::SectorMap;
+
+    /// Enterprise at (4,4) in an otherwise empty quadrant.
+    fn empty_quadrant() -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let sector = SectorPosition { x: 4, y: 4 };
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, sector);
+        galaxy.sector_map_mut().set(sector, SectorContent::Enterprise);
+        galaxy
+    }
+
+    fn add_klingon(galaxy: &mut Galaxy, x: i32, y: i32, shields: f64) {
+        let pos = SectorPosition { x, y };
+        let mut klingon = Klingon::new(pos);
+        klingon.shields = shields;
+        galaxy.sector_map_mut().set(pos, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+    }
+
+    fn warp(course: f64, warp: f64) -> Command {
+        Command::Warp {
+            course: Course::new(course).unwrap(),
+            warp: WarpFactor::new(warp).unwrap(),
+        }
+    }
+
+    #[test]
+    fn warp_within_the_quadrant() {
+        let galaxy = empty_quadrant();
+        let quadrant = galaxy.enterprise().quadrant();
+        let report = preview(&galaxy, warp(1.0, 0.25));
+        assert_eq!(report.endpoint, Some((quadrant, SectorPosition { x: 6, y: 4 })));
+        assert_eq!(report.energy_cost, -3.0);
+        assert_eq!(report.stardates, 0.0);
+        assert!(!report.blocked);
+    }
+
+    #[test]
+    fn warp_stops_short_of_an_obstacle() {
+        let mut galaxy = empty_quadrant();
+        galaxy.sector_map_mut().set(SectorPosition { x: 7, y: 4 }, SectorContent::Star);
+        let report = preview(&galaxy, warp(1.0, 0.5));
+        assert_eq!(report.endpoint.map(|(_, sector)| sector), Some(SectorPosition { x: 6, y: 4 }));
+        assert!(report.blocked);
+        assert_eq!(report.hull_damage, COLLISION_HULL_DAMAGE);
+    }
+
+    #[test]
+    fn warp_into_a_black_hole_has_no_known_endpoint() {
+        let mut galaxy = empty_quadrant();
+        galaxy.sector_map_mut().set(SectorPosition { x: 6, y: 4 }, SectorContent::BlackHole);
+        let report = preview(&galaxy, warp(1.0, 1.0));
+        assert_eq!(report.endpoint, None);
+        assert!(report.blocked);
+        assert_eq!(report.hull_damage, BLACK_HOLE_HULL_DAMAGE);
+        assert_eq!(report.stardates, 1.0);
+    }
+
+    #[test]
+    fn warp_across_a_boundary_matches_the_move() {
+        let galaxy = empty_quadrant();
+        let quadrant = galaxy.enterprise().quadrant();
+        let report = preview(&galaxy, warp(1.0, 1.0));
+        let expected = calculate_quadrant_crossing(quadrant.x, quadrant.y, 4, 4, 1.0, 0.0, 8);
+        assert_eq!(report.endpoint, Some(expected));
+        assert_eq!(report.stardates, 1.0);
+        assert_eq!(report.energy_cost, 3.0);
+    }
+
+    #[test]
+    fn phasers_project_kills_and_return_fire() {
+        let mut galaxy = empty_quadrant();
+        add_klingon(&mut galaxy, 6, 4, 100.0);
+        add_klingon(&mut galaxy, 4, 8, 300.0);
+        // 1000 split two ways: 250 at distance 2, 125 at distance 4
+        let report = preview(&galaxy, Command::Phasers { energy: 1000.0 });
+        assert_eq!(report.energy_cost, 1000.0);
+        assert_eq!(report.expected_klingons_destroyed, 1);
+        assert_eq!(report.expected_damage_dealt, 225.0);
+        assert_eq!(report.expected_damage_taken, 175.0 / 4.0);
+    }
+
+    #[test]
+    fn torpedo_removes_its_target_from_the_return_fire() {
+        let mut galaxy = empty_quadrant();
+        add_klingon(&mut galaxy, 6, 4, 100.0);
+        add_klingon(&mut galaxy, 4, 8, 300.0);
+        let report = preview(&galaxy, Command::Torpedo { course: Course::new(1.0).unwrap() });
+        assert_eq!(report.torpedoes_used, 1);
+        assert_eq!(report.expected_klingons_destroyed, 1);
+        assert_eq!(report.expected_damage_taken, 75.0);
+
+        let miss = preview(&galaxy, Command::Torpedo { course: Course::new(5.0).unwrap() });
+        assert_eq!(miss.expected_klingons_destroyed, 0);
+        assert_eq!(miss.expected_damage_taken, 50.0 + 75.0);
+    }
+
+    #[test]
+    fn torpedo_on_a_commander_leaves_it_firing() {
+        let mut galaxy = empty_quadrant();
+        let pos = SectorPosition { x: 6, y: 4 };
+        galaxy.sector_map_mut().set(pos, SectorContent::KlingonCommander);
+        galaxy.sector_map_mut().klingons.push(Klingon::commander(pos, 400.0));
+        let report = preview(&galaxy, Command::Torpedo { course: Course::new(1.0).unwrap() });
+        assert_eq!(report.expected_klingons_destroyed, 0);
+        assert_eq!(report.expected_damage_dealt, 300.0);
+        assert_eq!(report.expected_damage_taken, 50.0);
+
+        galaxy.sector_map_mut().klingons[0].shields = 300.0;
+        let report = preview(&galaxy, Command::Torpedo { course: Course::new(1.0).unwrap() });
+        assert_eq!(report.expected_klingons_destroyed, 1);
+        assert_eq!(report.expected_damage_taken, 0.0);
+    }
+
+    #[test]
+    fn previews_never_draw_from_the_rng() {
+        let mut galaxy = empty_quadrant();
+        add_klingon(&mut galaxy, 6, 4, 100.0);
+        let draws = galaxy.rng().draws();
+        for command in [
+            warp(3.0, 2.0),
+            Command::Phasers { energy: 500.0 },
+            Command::Torpedo { course: Course::new(1.0).unwrap() },
+            Command::Shields { units: 200.0 },
+        ] {
+            preview(&galaxy, command);
+        }
+        assert_eq!(galaxy.rng().draws(), draws);
+        assert_eq!(galaxy.sector_map().klingons.len(), 1);
+    }
+}
@@ -0,0 +1,128 @@
+//! Long-range probes
+//!
+//! A probe is launched along a course and crosses one quadrant per stardate,
+//! recording each quadrant it enters in the computer's memory until it
+//! leaves the galaxy. One probe can be in flight at a time.
+
+use crate::io::{InputReader, OutputWriter};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::navigation_types::Course;
+use crate::models::output_event::OutputEvent;
+use crate::models::probe::Probe;
+use crate::services::commands::{self, CommandId};
+use crate::services::navigation;
+use crate::ui::presenters::Emit;
+
+/// Launches a long-range probe along a course (Command 9)
+///
+/// Prompts the player for a course (1-9, 0 cancels). The probe reports on
+/// later turns as the stardate advances; see [`advance_probe`].
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for getting the course
+/// * `output` - Output writer for displaying results
+///
+/// # Returns
+///
+/// * `Ok(())` on success, including a cancelled launch
+/// * `Err` if I/O operations fail
+pub fn launch_probe(galaxy: &mut Galaxy, io: &mut dyn InputReader, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if galaxy.probe().is_some() {
+        output.writeln("A PROBE IS ALREADY IN FLIGHT");
+        return Ok(());
+    }
+
+    let course = loop {
+        let input = io.read_line("PROBE COURSE (1-9)")?;
+        let value: f64 = match input.trim().parse() {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if value == 0.0 {
+            return Ok(());
+        }
+        match Course::new(value) {
+            Ok(c) => break c,
+            Err(_) => continue,
+        }
+    };
+
+    let direction = navigation::calculate_direction(course.value());
+    let probe = Probe::launch(galaxy.enterprise().quadrant(), direction, galaxy.stardate());
+    galaxy.set_probe(Some(probe));
+    commands::charge_time(galaxy, CommandId::LaunchProbe);
+    output.writeln("PROBE LAUNCHED. IT WILL REPORT ONE QUADRANT PER STARDATE");
+    Ok(())
+}
+
+/// Move a probe in flight into every quadrant it has reached by now,
+/// recording each one in computer memory. Called once per turn.
+pub fn advance_probe(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    while let Some(mut probe) = galaxy.probe().filter(|p| p.is_due(galaxy.stardate())) {
+        let quadrant = probe.advance();
+        let Some(&data) = galaxy.quadrants().get(quadrant) else {
+            galaxy.set_probe(None);
+            output.emit(OutputEvent::ProbeLeftGalaxy);
+            return;
+        };
+        galaxy.set_probe(Some(probe));
+        galaxy.record_quadrant_to_memory(quadrant.x, quadrant.y);
+        output.emit(OutputEvent::ProbeReport { quadrant, encoded: data.encoded() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::position::{QuadrantPosition, SectorPosition};
+
+    fn galaxy_at(quadrant: QuadrantPosition) -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        galaxy
+    }
+
+    #[test]
+    fn probes_chart_one_quadrant_per_stardate() {
+        let mut galaxy = galaxy_at(QuadrantPosition { x: 6, y: 2 });
+        let mut input = MockInput::new(vec!["1"]);
+        launch_probe(&mut galaxy, &mut input, &mut MockOutput::new()).unwrap();
+        assert!(galaxy.probe().is_some());
+
+        // Nothing to report until a stardate has passed
+        let mut output = MockOutput::new();
+        advance_probe(&mut galaxy, &mut output);
+        assert!(output.messages.is_empty());
+
+        galaxy.advance_time(2.0);
+        advance_probe(&mut galaxy, &mut output);
+        let text = output.messages.concat();
+        assert!(text.starts_with("PROBE REPORT FROM QUADRANT 7,2: "));
+        assert!(text.contains("PROBE REPORT FROM QUADRANT 8,2: "));
+        let memory = galaxy.computer_memory();
+        assert_eq!(memory[QuadrantPosition { x: 7, y: 2 }], Some(galaxy.quadrants()[QuadrantPosition { x: 7, y: 2 }]));
+        assert_eq!(memory[QuadrantPosition { x: 8, y: 2 }], Some(galaxy.quadrants()[QuadrantPosition { x: 8, y: 2 }]));
+
+        galaxy.advance_time(1.0);
+        let mut output = MockOutput::new();
+        advance_probe(&mut galaxy, &mut output);
+        assert_eq!(output.messages.concat(), "PROBE HAS LEFT THE GALAXY\n");
+        assert_eq!(galaxy.probe(), None);
+    }
+
+    #[test]
+    fn one_probe_at_a_time() {
+        let mut galaxy = galaxy_at(QuadrantPosition { x: 1, y: 1 });
+        launch_probe(&mut galaxy, &mut MockInput::new(vec!["0"]), &mut MockOutput::new()).unwrap();
+        assert_eq!(galaxy.probe(), None);
+
+        launch_probe(&mut galaxy, &mut MockInput::new(vec!["7"]), &mut MockOutput::new()).unwrap();
+        let mut output = MockOutput::new();
+        launch_probe(&mut galaxy, &mut MockInput::new(vec!["3"]), &mut output).unwrap();
+        assert_eq!(output.messages.concat(), "A PROBE IS ALREADY IN FLIGHT\n");
+    }
+}
@@ -0,0 +1,320 @@
+use crate::io::{InputReader, OutputWriter};
+use crate::models::constants::{Condition, Device};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::repair::{full_repair_time, repair_estimates};
+use crate::ui::presenters::RepairPresenter;
+
+/// Orders a priority repair while docked (Command 8)
+///
+/// Lists each damaged device with its repair time and lets the player pick
+/// one for the starbase crews to finish immediately. The chosen device is
+/// restored to working order and the stardate advances by the priority
+/// repair time. Entering 0, a blank line, or anything unrecognized leaves
+/// the ship as it was.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for getting the device selection
+/// * `output` - Output writer for displaying repair options and results
+///
+/// # Returns
+///
+/// * `Ok(())` on success, whether or not a repair was ordered
+/// * `Err` if I/O operations fail
+///
+/// # Specification
+///
+/// This is an extension; see spec section 9.3 for the original rule that
+/// docking does not repair devices.
+pub fn starbase_repair(
+    galaxy: &mut Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    if galaxy.evaluate_condition() != Condition::Docked {
+        output.writeln("REPAIR CREWS ARE ONLY AVAILABLE WHILE DOCKED");
+        return Ok(());
+    }
+
+    let estimates = repair_estimates(galaxy.enterprise());
+    if estimates.is_empty() {
+        output.writeln("ALL SYSTEMS ARE FULLY OPERATIONAL");
+        return Ok(());
+    }
+
+    RepairPresenter::show_repair_options(&estimates, output);
+    let input = io.read_line("WHICH DEVICE SHALL THE REPAIR CREWS PRIORITIZE?")?;
+    let choice = match input.trim().parse::<usize>() {
+        Ok(n) if (1..=estimates.len()).contains(&n) => estimates[n - 1],
+        _ => return Ok(()),
+    };
+
+    galaxy
+        .enterprise_mut()
+        .repair_device(choice.device, choice.repair_time);
+    galaxy.advance_time(choice.priority_time);
+    RepairPresenter::show_repair_complete(&choice, output);
+    Ok(())
+}
+
+/// Assigns the ship's repair crews to one device (Command R)
+///
+/// Lists each damaged device with its repair rate per move and lets the
+/// player pick one for the crews to work on first. Until it is working
+/// again, `auto_repair_devices` repairs it at twice the usual rate and every
+/// other device at half. Entering 0 releases the crews; a blank line or
+/// anything unrecognized leaves the assignment as it was.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for getting the device selection
+/// * `output` - Output writer for displaying the assignments
+///
+/// # Returns
+///
+/// * `Ok(())` on success, whether or not the assignment changed
+/// * `Err` if I/O operations fail
+pub fn assign_repair_crews(
+    galaxy: &mut Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let damaged: Vec<Device> = galaxy
+        .ruleset()
+        .devices()
+        .iter()
+        .copied()
+        .filter(|&d| galaxy.enterprise().is_damaged(d))
+        .collect();
+    if damaged.is_empty() {
+        output.writeln("ALL SYSTEMS ARE FULLY OPERATIONAL");
+        return Ok(());
+    }
+
+    RepairPresenter::show_crew_assignments(galaxy.enterprise(), &damaged, output);
+    let input = io.read_line("WHICH DEVICE SHALL THE REPAIR CREWS WORK ON FIRST?")?;
+    match input.trim().parse::<usize>() {
+        Ok(0) => {
+            galaxy.enterprise_mut().set_repair_priority(None);
+            output.writeln("REPAIR CREWS RETURNED TO GENERAL DUTIES");
+        }
+        Ok(n) if n <= damaged.len() => {
+            let device = damaged[n - 1];
+            galaxy.enterprise_mut().set_repair_priority(Some(device));
+            output.writeln(&format!("REPAIR CREWS ASSIGNED TO {}", device.name()));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Offers a full repair on arriving at a starbase
+///
+/// Under the docking repairs rule, a ship that has just docked with damaged
+/// devices is offered repairs to all of them at once. Accepting restores
+/// every device and advances the stardate by the sum of their priority
+/// repair times. Does nothing when the rule is off, the ship is not docked,
+/// or nothing is damaged.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `io` - Input reader for the player's answer
+/// * `output` - Output writer for the offer and its result
+///
+/// # Returns
+///
+/// * `Ok(())` whether or not the offer was accepted
+/// * `Err` if I/O operations fail
+pub fn offer_docking_repairs(
+    galaxy: &mut Galaxy,
+    io: &mut dyn InputReader,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    if !galaxy.ruleset().docking_repairs || galaxy.evaluate_condition() != Condition::Docked {
+        return Ok(());
+    }
+    let estimates = repair_estimates(galaxy.enterprise());
+    if estimates.is_empty() {
+        return Ok(());
+    }
+
+    let time = full_repair_time(&estimates);
+    output.writeln("TECHNICIANS STANDING BY TO EFFECT REPAIRS TO YOUR SHIP;");
+    output.writeln(&format!("ESTIMATED TIME TO REPAIR: {:.1} STARDATES", time));
+    if !io.confirm("WILL YOU AUTHORIZE THE REPAIR ORDER (Y/N)?")? {
+        return Ok(());
+    }
+    galaxy.enterprise_mut().repair_all_devices();
+    galaxy.advance_time(time);
+    output.writeln("ALL REPAIRS COMPLETE");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::{MockInput, MockOutput};
+    use crate::models::constants::SectorContent;
+    use crate::models::position::SectorPosition;
+    use crate::models::sector_map::SectorMap;
+
+    fn docked_galaxy() -> Galaxy {
+        dock(Galaxy::new(42))
+    }
+
+    /// Put the Enterprise next to a starbase in an otherwise empty quadrant.
+    fn dock(mut galaxy: Galaxy) -> Galaxy {
+        let enterprise = SectorPosition { x: 4, y: 4 };
+        let starbase = SectorPosition { x: 5, y: 4 };
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, enterprise);
+        let map = galaxy.sector_map_mut();
+        *map = SectorMap::new();
+        map.set(enterprise, SectorContent::Enterprise);
+        map.set(starbase, SectorContent::Starbase);
+        map.starbase = Some(starbase);
+        galaxy
+    }
+
+    #[test]
+    fn chosen_device_is_repaired_at_a_cost_in_stardates() {
+        let mut galaxy = docked_galaxy();
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 1.0);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 4.0);
+        let before = galaxy.stardate();
+
+        let mut output = MockOutput::new();
+        starbase_repair(&mut galaxy, &mut MockInput::new(vec!["2"]), &mut output).unwrap();
+
+        assert!(!galaxy.enterprise().is_damaged(Device::PhotonTubes));
+        assert!(galaxy.enterprise().is_damaged(Device::WarpEngines));
+        assert_eq!(galaxy.stardate(), before + 2.0);
+        assert!(output.messages.concat().contains("REPAIRS TO PHOTON TUBES COMPLETE"));
+    }
+
+    #[test]
+    fn cancelled_order_changes_nothing() {
+        let mut galaxy = docked_galaxy();
+        galaxy.enterprise_mut().damage_device(Device::Computer, 3.0);
+        let before = galaxy.stardate();
+
+        for answer in ["0", "", "9", "X"] {
+            let mut output = MockOutput::new();
+            starbase_repair(&mut galaxy, &mut MockInput::new(vec![answer]), &mut output).unwrap();
+        }
+
+        assert!(galaxy.enterprise().is_damaged(Device::Computer));
+        assert_eq!(galaxy.stardate(), before);
+    }
+
+    #[test]
+    fn nothing_to_repair_skips_the_prompt() {
+        let mut galaxy = docked_galaxy();
+        let mut output = MockOutput::new();
+        starbase_repair(&mut galaxy, &mut MockInput::new(vec![]), &mut output).unwrap();
+        assert_eq!(output.messages, vec!["ALL SYSTEMS ARE FULLY OPERATIONAL\n".to_string()]);
+    }
+
+    fn docking_repairs_galaxy() -> Galaxy {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { docking_repairs: true, ..Default::default() };
+        dock(Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() }))
+    }
+
+    #[test]
+    fn accepted_docking_repairs_fix_everything_for_the_total_time() {
+        let mut galaxy = docking_repairs_galaxy();
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 1.0);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 4.0);
+        let before = galaxy.stardate();
+
+        let mut output = MockOutput::new();
+        offer_docking_repairs(&mut galaxy, &mut MockInput::new(vec!["Y"]), &mut output).unwrap();
+
+        assert!(Device::ALL.iter().all(|&d| !galaxy.enterprise().is_damaged(d)));
+        assert_eq!(galaxy.stardate(), before + 2.5);
+        assert_eq!(output.messages[1], "ESTIMATED TIME TO REPAIR: 2.5 STARDATES\n");
+        assert_eq!(output.messages.last().unwrap(), "ALL REPAIRS COMPLETE\n");
+    }
+
+    #[test]
+    fn docking_repairs_can_be_declined_and_are_off_by_default() {
+        let mut galaxy = docking_repairs_galaxy();
+        galaxy.enterprise_mut().damage_device(Device::Computer, 3.0);
+        let before = galaxy.stardate();
+        offer_docking_repairs(&mut galaxy, &mut MockInput::new(vec!["N"]), &mut MockOutput::new()).unwrap();
+        assert!(galaxy.enterprise().is_damaged(Device::Computer));
+        assert_eq!(galaxy.stardate(), before);
+
+        let mut classic = docked_galaxy();
+        classic.enterprise_mut().damage_device(Device::Computer, 3.0);
+        let mut output = MockOutput::new();
+        offer_docking_repairs(&mut classic, &mut MockInput::new(vec![]), &mut output).unwrap();
+        assert!(output.messages.is_empty());
+    }
+
+    #[test]
+    fn repairs_require_docking() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 3.0);
+        let mut output = MockOutput::new();
+        starbase_repair(&mut galaxy, &mut MockInput::new(vec![]), &mut output).unwrap();
+        assert!(galaxy.enterprise().is_damaged(Device::Computer));
+        assert_eq!(
+            output.messages,
+            vec!["REPAIR CREWS ARE ONLY AVAILABLE WHILE DOCKED\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn crews_are_assigned_to_the_chosen_damaged_device() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::WarpEngines, 2.0);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 2.0);
+        let mut output = MockOutput::new();
+        assign_repair_crews(&mut galaxy, &mut MockInput::new(vec!["2"]), &mut output).unwrap();
+        output.expect_line_containing("REPAIR CREWS ASSIGNED TO PHOTON TUBES");
+        assert_eq!(galaxy.enterprise().repair_priority(), Some(Device::PhotonTubes));
+        assert_eq!(galaxy.enterprise().repair_rate(Device::WarpEngines), 0.5);
+
+        let mut output = MockOutput::new();
+        assign_repair_crews(&mut Galaxy::new(42), &mut MockInput::new(vec![]), &mut output).unwrap();
+        assert_eq!(output.messages, vec!["ALL SYSTEMS ARE FULLY OPERATIONAL\n".to_string()]);
+    }
+
+    #[test]
+    fn crew_assignments_can_be_listed_and_released() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::Computer, 3.0);
+        galaxy.enterprise_mut().set_repair_priority(Some(Device::Computer));
+
+        let mut output = MockOutput::new();
+        assign_repair_crews(&mut galaxy, &mut MockInput::new(vec![""]), &mut output).unwrap();
+        assert_eq!(output.expect_line_containing("COMPUTER"), " 1  COMPUTER           -3     2.0  ASSIGNED");
+        assert_eq!(galaxy.enterprise().repair_priority(), Some(Device::Computer));
+
+        assign_repair_crews(&mut galaxy, &mut MockInput::new(vec!["0"]), &mut output).unwrap();
+        assert_eq!(galaxy.enterprise().repair_priority(), None);
+        assert_eq!(galaxy.enterprise().repair_rate(Device::Computer), 1.0);
+    }
+
+    #[test]
+    fn docked_damage_report_quotes_starbase_repair_times() {
+        use crate::models::repair::estimated_repair_time;
+        use crate::ui::presenters::EnterprisePresenter;
+
+        let mut galaxy = docked_galaxy();
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 4.0);
+        assert_eq!(estimated_repair_time(&galaxy, Device::PhotonTubes), 2.0);
+        let mut output = MockOutput::new();
+        EnterprisePresenter::show_damage_report(&galaxy, &mut output);
+        assert_eq!(output.expect_line_containing("PHOTON TUBES"), "PHOTON TUBES  -4              2.0");
+        output.expect_line_containing("(REPAIR TIMES ARE FOR STARBASE CREWS)");
+    }
+}
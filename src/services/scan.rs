@@ -7,12 +7,16 @@ use crate::io::OutputWriter;
 use crate::models::constants::{Device, GALAXY_SIZE, SECTOR_SIZE};
 use crate::models::errors::GameResult;
 use crate::models::galaxy::Galaxy;
+use crate::models::output_event::{OutputEvent, SensorReadout};
+use crate::models::position::QuadrantPosition;
+use crate::ui::presenters::Emit;
 
 /// Performs a long-range sensor scan of surrounding quadrants (Command 2)
 ///
-/// Scans a 3x3 grid centered on the Enterprise's current quadrant and
-/// displays the encoded contents (Klingons, Starbases, Stars) of each
-/// quadrant. Also records the scanned quadrants in the ship's computer memory.
+/// Scans the grid centered on the Enterprise's current quadrant (3x3, or
+/// 5x5 with the fleet HQ upgrade) and displays the encoded contents
+/// (Klingons, Starbases, Stars) of each quadrant. Also records the scanned
+/// quadrants in the ship's computer memory.
 ///
 /// # Arguments
 ///
@@ -33,31 +37,37 @@ pub fn long_range_scan(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> Ga
         return Ok(());
     }
 
+    let radius = galaxy.enterprise().long_range_scan_radius();
+    let grid = scan_neighborhood(galaxy, radius);
+    output.emit(OutputEvent::LongRangeScan { quadrant: galaxy.enterprise().quadrant(), grid });
+    Ok(())
+}
+
+/// Scan the quadrants within `radius` of the Enterprise, recording each one
+/// in computer memory. Returns rows of encoded quadrant values, top to
+/// bottom, with `None` for positions outside the galaxy.
+pub fn scan_neighborhood(galaxy: &mut Galaxy, radius: i32) -> Vec<Vec<Option<i32>>> {
     let qx = galaxy.enterprise().quadrant().x;
     let qy = galaxy.enterprise().quadrant().y;
-    output.writeln(&format!("LONG RANGE SENSOR SCAN FOR QUADRANT {},{}", qx, qy));
+    let in_galaxy = 1..=GALAXY_SIZE as i32;
 
-    let border = "-------------------";
-    for dy in -1..=1_i32 {
-        output.writeln(border);
-        let mut cells: Vec<String> = Vec::new();
-        for dx in -1..=1_i32 {
+    let mut grid = Vec::new();
+    for dy in -radius..=radius {
+        let mut row = Vec::new();
+        for dx in -radius..=radius {
             let scan_x = qx + dx;
             let scan_y = qy + dy;
-            if scan_x < 1 || scan_x > GALAXY_SIZE as i32 || scan_y < 1 || scan_y > GALAXY_SIZE as i32
-            {
-                cells.push("xxx".to_string());
-            } else {
-                let encoded = galaxy.quadrants()[(scan_y - 1) as usize][(scan_x - 1) as usize]
-                    .encoded();
-                cells.push(format!("{:03}", encoded));
+            if in_galaxy.contains(&scan_x) && in_galaxy.contains(&scan_y) {
+                let pos = QuadrantPosition { x: scan_x, y: scan_y };
+                row.push(Some(galaxy.quadrants()[pos].encoded()));
                 galaxy.record_quadrant_to_memory(scan_x, scan_y);
+            } else {
+                row.push(None);
             }
         }
-        output.writeln(&format!("| {} | {} | {} |", cells[0], cells[1], cells[2]));
+        grid.push(row);
     }
-    output.writeln(border);
-    Ok(())
+    grid
 }
 
 /// Performs a short-range sensor scan of the current sector (Command 1)
@@ -80,7 +90,12 @@ pub fn long_range_scan(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> Ga
 ///
 /// See spec section 6.1 for full details on short-range scanning.
 pub fn short_range_scan(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> GameResult<()> {
-    galaxy.check_docking();
+    if galaxy.check_docking() {
+        output.writeln("SHIELDS DROPPED FOR DOCKING PURPOSES");
+    }
+    if galaxy.install_fleet_hq_upgrade() {
+        output.writeln("FLEET HQ HAS UPGRADED YOUR LONG RANGE SENSORS TO 5X5 COVERAGE");
+    }
     let condition = galaxy.evaluate_condition();
 
     if galaxy.enterprise().is_damaged(Device::ShortRangeSensors) {
@@ -88,50 +103,65 @@ pub fn short_range_scan(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) -> G
         return Ok(());
     }
 
-    let border = "-=--=--=--=--=--=--=--=-";
     let e = galaxy.enterprise();
-    let status: [String; SECTOR_SIZE] = [
-        format!("STARDATE  {}", galaxy.stardate() as i32),
-        format!("CONDITION {}", condition.label()),
-        format!("QUADRANT  {},{}", e.quadrant().x, e.quadrant().y),
-        format!("SECTOR    {},{}", e.sector().x, e.sector().y),
-        format!("ENERGY    {}", e.energy() as i32),
-        format!("SHIELDS   {}", e.shields() as i32),
-        format!("PHOTON TORPEDOES {}", e.torpedoes()),
-        String::new(),
-    ];
-
-    output.writeln(border);
-    for y in 1..=SECTOR_SIZE as i32 {
-        let row = galaxy.sector_map().render_row(y);
-        let idx = (y - 1) as usize;
-        if !status[idx].is_empty() {
-            output.writeln(&format!("{}        {}", row, status[idx]));
-        } else {
-            output.writeln(&row);
-        }
-    }
-    output.writeln(border);
+    let show_mines = e.sensors_repaired();
+    let readout = SensorReadout {
+        rows: (1..=SECTOR_SIZE as i32).map(|y| galaxy.sector_map().visible_row(y, show_mines)).collect(),
+        symbols: galaxy.symbols(),
+        stardate: galaxy.stardate(),
+        condition,
+        quadrant: e.quadrant(),
+        sector: e.sector(),
+        energy: e.energy(),
+        shields: e.shields(),
+        torpedoes: e.torpedoes(),
+        hull: e.hull(),
+        crew: e.crew(),
+        phaser_heat: galaxy.ruleset().phaser_heat.then(|| e.phaser_heat_percent()),
+    };
+    output.emit(OutputEvent::ShortRangeScan(Box::new(readout)));
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::constants::{Device, GALAXY_SIZE};
+    use crate::io::ansi::{highlight, Highlight};
+    use crate::models::constants::{Device, SectorContent};
+    use crate::models::grid::QuadrantGrid;
 
     #[test]
     fn short_range_scan_does_not_panic() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
         // Just verify it runs without panicking
         short_range_scan(&mut galaxy, &mut output).unwrap();
     }
 
+    #[test]
+    fn scans_reach_event_queues_as_events() {
+        use crate::io::EventQueue;
+        let mut galaxy = Galaxy::new(42);
+        let mut queue = EventQueue::default();
+        short_range_scan(&mut galaxy, &mut queue).unwrap();
+        long_range_scan(&mut galaxy, &mut queue).unwrap();
+
+        let events = queue.drain();
+        let OutputEvent::ShortRangeScan(readout) = &events[events.len() - 2] else {
+            panic!("expected a short range scan, got {:?}", events);
+        };
+        assert_eq!(readout.sector, galaxy.enterprise().sector());
+        assert_eq!(readout.rows.len(), SECTOR_SIZE);
+        assert!(matches!(
+            events.last(),
+            Some(OutputEvent::LongRangeScan { quadrant, grid }) if *quadrant == galaxy.enterprise().quadrant() && grid.len() == 3
+        ));
+    }
+
     #[test]
     fn short_range_scan_blocked_when_sensors_damaged() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
         galaxy.enterprise_mut().damage_device(Device::ShortRangeSensors, 1.0);
@@ -139,9 +169,45 @@ mod tests {
         short_range_scan(&mut galaxy, &mut output).unwrap();
     }
 
+    #[test]
+    fn compact_scan_narrows_the_grid_and_border() {
+        use crate::io::testing::MockOutput;
+        use crate::models::constants::SymbolSet;
+        let mut galaxy = Galaxy::new(42);
+        galaxy.set_symbols(SymbolSet::Compact);
+        let mut output = MockOutput::new();
+        short_range_scan(&mut galaxy, &mut output).unwrap();
+        let text = output.text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "========");
+        assert!(lines[1].starts_with(&galaxy.sector_map().render_row(1, true, SymbolSet::Compact)));
+        assert!(lines[2].ends_with(&format!("CONDITION {}", galaxy.evaluate_condition().label())));
+        assert_eq!(lines[9], "========");
+    }
+
+    #[test]
+    fn color_writers_get_klingons_and_the_condition_painted() {
+        use crate::io::testing::MockOutput;
+        use crate::models::position::SectorPosition;
+        let mut galaxy = Galaxy::new(42);
+        galaxy.sector_map_mut().set(SectorPosition { x: 1, y: 1 }, SectorContent::Klingon);
+        let condition = galaxy.evaluate_condition();
+
+        let mut output = MockOutput::with_color();
+        short_range_scan(&mut galaxy, &mut output).unwrap();
+        let text = output.text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[1].starts_with(&highlight("+++", Highlight::Klingon, true)));
+        assert!(lines[2].ends_with(&highlight(condition.label(), Highlight::Condition(condition), true)));
+
+        let mut plain = MockOutput::new();
+        short_range_scan(&mut galaxy, &mut plain).unwrap();
+        assert!(!plain.contains("\x1b["));
+    }
+
     #[test]
     fn long_range_scan_does_not_panic() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
         long_range_scan(&mut galaxy, &mut output).unwrap();
@@ -149,7 +215,7 @@ mod tests {
 
     #[test]
     fn long_range_scan_blocked_when_sensors_damaged() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
         galaxy.enterprise_mut().damage_device(Device::LongRangeSensors, 1.0);
@@ -159,11 +225,11 @@ mod tests {
 
     #[test]
     fn long_range_scan_updates_computer_memory() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
         // Reset computer memory to verify LRS populates it
-        *galaxy.computer_memory_mut() = [[None; GALAXY_SIZE]; GALAXY_SIZE];
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(None);
 
         long_range_scan(&mut galaxy, &mut output).unwrap();
 
@@ -176,9 +242,9 @@ mod tests {
                 let sx = qx + dx;
                 let sy = qy + dy;
                 if (1..=8).contains(&sx) && (1..=8).contains(&sy) {
-                    let mem = galaxy.computer_memory()[(sy - 1) as usize][(sx - 1) as usize];
-                    let actual =
-                        galaxy.quadrants()[(sy - 1) as usize][(sx - 1) as usize];
+                    let pos = QuadrantPosition { x: sx, y: sy };
+                    let mem = galaxy.computer_memory()[pos];
+                    let actual = galaxy.quadrants()[pos];
                     assert_eq!(mem, Some(actual), "memory at ({},{}) should match quadrant data", sx, sy);
                 }
             }
@@ -187,19 +253,71 @@ mod tests {
 
     #[test]
     fn long_range_scan_does_not_record_when_computer_damaged() {
-        use crate::io::test_utils::MockOutput;
+        use crate::io::testing::MockOutput;
         let mut galaxy = Galaxy::new(42);
         let mut output = MockOutput::new();
-        *galaxy.computer_memory_mut() = [[None; GALAXY_SIZE]; GALAXY_SIZE];
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(None);
         galaxy.enterprise_mut().damage_device(Device::Computer, 1.0);
 
         long_range_scan(&mut galaxy, &mut output).unwrap();
 
         // All memory should remain unscanned (None)
-        for y in 0..GALAXY_SIZE {
-            for x in 0..GALAXY_SIZE {
-                assert_eq!(galaxy.computer_memory()[y][x], None);
-            }
-        }
+        assert!(galaxy.computer_memory().iter().all(|m| m.is_none()));
+    }
+
+    #[test]
+    fn neighborhood_grid_matches_radius() {
+        let mut galaxy = Galaxy::new(42);
+        let q = galaxy.enterprise().quadrant();
+
+        let grid = scan_neighborhood(&mut galaxy, 2);
+        assert_eq!(grid.len(), 5);
+        assert!(grid.iter().all(|row| row.len() == 5));
+        let centre = galaxy.quadrants()[q].encoded();
+        assert_eq!(grid[2][2], Some(centre));
+    }
+
+    #[test]
+    fn extended_sensors_record_wider_area() {
+        use crate::io::testing::MockOutput;
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.computer_memory_mut() = QuadrantGrid::filled(None);
+        galaxy.enterprise_mut().upgrade_long_range_sensors();
+
+        long_range_scan(&mut galaxy, &mut MockOutput::new()).unwrap();
+
+        let q = galaxy.enterprise().quadrant();
+        let expected = |c: i32| ((c - 2).max(1)..=(c + 2).min(8)).count();
+        let recorded = galaxy.computer_memory().iter().filter(|m| m.is_some()).count();
+        assert_eq!(recorded, expected(q.x) * expected(q.y));
+    }
+
+    #[test]
+    fn docking_at_fleet_hq_upgrades_sensors_once() {
+        use crate::io::testing::MockOutput;
+        use crate::models::constants::SectorContent;
+        use crate::models::position::SectorPosition;
+        use crate::models::sector_map::SectorMap;
+
+        let mut galaxy = Galaxy::new(42);
+        let hq = galaxy.fleet_hq().expect("every galaxy has a starbase");
+        let sector = SectorPosition { x: 4, y: 4 };
+        let starbase = SectorPosition { x: 5, y: 4 };
+        galaxy.enterprise_mut().move_to(hq, sector);
+        let map = galaxy.sector_map_mut();
+        *map = SectorMap::new();
+        map.set(sector, SectorContent::Enterprise);
+        map.set(starbase, SectorContent::Starbase);
+        map.starbase = Some(starbase);
+
+        let mut output = MockOutput::new();
+        short_range_scan(&mut galaxy, &mut output).unwrap();
+        assert!(galaxy.enterprise().has_extended_long_range_sensors());
+        assert_eq!(output.messages[0], "SHIELDS DROPPED FOR DOCKING PURPOSES\n");
+        assert!(output.messages[1].starts_with("FLEET HQ HAS UPGRADED"));
+
+        let mut output = MockOutput::new();
+        short_range_scan(&mut galaxy, &mut output).unwrap();
+        assert!(!output.messages.concat().contains("FLEET HQ"));
     }
 }
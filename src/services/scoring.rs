@@ -0,0 +1,301 @@
+//! Scoring
+//!
+//! A final rank from the player's whole record rather than the efficiency
+//! rating alone: Klingons destroyed add to the score, while starbases lost,
+//! torpedoes fired, and crew casualties take away from it. Victories are
+//! kept in a local high score table.
+
+use crate::models::errors::{GameError, GameResult};
+use crate::models::galaxy::Galaxy;
+
+/// Points for each Klingon destroyed.
+const KLINGON_POINTS: i32 = 10;
+
+/// Points lost for each starbase destroyed.
+const STARBASE_PENALTY: i32 = 100;
+
+/// Points lost for each torpedo fired.
+const TORPEDO_PENALTY: i32 = 2;
+
+/// Points lost for each crew member killed.
+const CASUALTY_PENALTY: i32 = 1;
+
+/// Entries kept in the high score table.
+pub const HIGH_SCORE_LIMIT: usize = 10;
+
+/// First line of a saved high score table.
+const HIGH_SCORE_MAGIC: &str = "STARTREK HIGH SCORES";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rank {
+    Cadet,
+    Ensign,
+    Lieutenant,
+    Commander,
+    Captain,
+    Commodore,
+    Admiral,
+}
+
+impl Rank {
+    /// Every rank from lowest to highest.
+    pub const ALL: [Rank; 7] = [
+        Rank::Cadet,
+        Rank::Ensign,
+        Rank::Lieutenant,
+        Rank::Commander,
+        Rank::Captain,
+        Rank::Commodore,
+        Rank::Admiral,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Rank::Cadet => "CADET",
+            Rank::Ensign => "ENSIGN",
+            Rank::Lieutenant => "LIEUTENANT",
+            Rank::Commander => "COMMANDER",
+            Rank::Captain => "CAPTAIN",
+            Rank::Commodore => "COMMODORE",
+            Rank::Admiral => "ADMIRAL",
+        }
+    }
+
+    /// Lowest score that earns the rank.
+    pub fn threshold(&self) -> i32 {
+        match self {
+            Rank::Cadet => i32::MIN,
+            Rank::Ensign => 200,
+            Rank::Lieutenant => 400,
+            Rank::Commander => 600,
+            Rank::Captain => 800,
+            Rank::Commodore => 1000,
+            Rank::Admiral => 1300,
+        }
+    }
+
+    /// The rank with the given label, ignoring case.
+    pub fn parse(label: &str) -> Option<Rank> {
+        Rank::ALL.into_iter().find(|rank| rank.label().eq_ignore_ascii_case(label.trim()))
+    }
+
+    /// The highest rank a score reaches.
+    pub fn for_score(score: i32) -> Rank {
+        Rank::ALL
+            .into_iter()
+            .rev()
+            .find(|rank| score >= rank.threshold())
+            .unwrap_or(Rank::Cadet)
+    }
+}
+
+/// Everything that went into a final score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreCard {
+    /// Efficiency rating after mods.
+    pub rating: i32,
+    pub klingons_destroyed: i32,
+    pub starbases_lost: i32,
+    pub torpedoes_used: i32,
+    pub casualties: i32,
+    pub score: i32,
+    pub rank: Rank,
+}
+
+impl ScoreCard {
+    /// Score a game that ended with `rating`.
+    pub fn tally(galaxy: &Galaxy, rating: i32) -> Self {
+        let klingons_destroyed = galaxy.klingons_faced() - galaxy.total_klingons();
+        let starbases_lost = galaxy.starbases_lost();
+        let torpedoes_used = galaxy.enterprise().torpedoes_fired();
+        let casualties = galaxy.enterprise().casualties();
+        // Saturating: a game won on its first stardate rates i32::MAX
+        let score = rating
+            .saturating_add(klingons_destroyed * KLINGON_POINTS)
+            .saturating_sub(starbases_lost * STARBASE_PENALTY)
+            .saturating_sub(torpedoes_used * TORPEDO_PENALTY)
+            .saturating_sub(casualties * CASUALTY_PENALTY);
+        ScoreCard {
+            rating,
+            klingons_destroyed,
+            starbases_lost,
+            torpedoes_used,
+            casualties,
+            score,
+            rank: Rank::for_score(score),
+        }
+    }
+}
+
+/// One victory in the high score table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighScore {
+    pub name: String,
+    pub seed: u64,
+    pub rating: i32,
+    pub rank: Rank,
+    /// Day the game was won, as YYYY-MM-DD
+    pub date: String,
+    pub turns: u32,
+}
+
+/// The best victories, highest rating first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighScoreTable {
+    entries: Vec<HighScore>,
+}
+
+impl HighScoreTable {
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// Whether a victory with `rating` would make the table.
+    pub fn qualifies(&self, rating: i32) -> bool {
+        self.entries.len() < HIGH_SCORE_LIMIT
+            || self.entries.last().is_some_and(|last| rating > last.rating)
+    }
+
+    /// Add a score, dropping the lowest if the table is full.
+    ///
+    /// Returns the entry's place (0-based), or `None` if it did not make the
+    /// table. Ties go to the earlier score.
+    pub fn insert(&mut self, score: HighScore) -> Option<usize> {
+        let place = self
+            .entries
+            .iter()
+            .position(|entry| score.rating > entry.rating)
+            .unwrap_or(self.entries.len());
+        if place >= HIGH_SCORE_LIMIT {
+            return None;
+        }
+        self.entries.insert(place, score);
+        self.entries.truncate(HIGH_SCORE_LIMIT);
+        Some(place)
+    }
+
+    /// One tab-separated line per entry under a header line.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n", HIGH_SCORE_MAGIC);
+        for entry in &self.entries {
+            // Names are the only free text; keep them on one field
+            let name: String =
+                entry.name.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                entry.rating,
+                entry.rank.label(),
+                entry.seed,
+                entry.date,
+                entry.turns,
+                name.trim(),
+            ));
+        }
+        out
+    }
+
+    pub fn parse(text: &str) -> GameResult<HighScoreTable> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HIGH_SCORE_MAGIC) {
+            return Err(GameError::InvalidInput("not a high score file".to_string()));
+        }
+        let mut table = HighScoreTable::default();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let fields: Vec<&str> = line.splitn(6, '\t').collect();
+            let [rating, rank, seed, date, turns, name] = fields[..] else {
+                return Err(GameError::InvalidInput(format!("bad high score entry: {}", line)));
+            };
+            let rank = Rank::parse(rank)
+                .ok_or_else(|| GameError::InvalidInput(format!("unknown rank: {}", rank)))?;
+            table.insert(HighScore {
+                name: name.to_string(),
+                seed: seed.parse()?,
+                rating: rating.parse()?,
+                rank,
+                date: date.to_string(),
+                turns: turns.parse()?,
+            });
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_rise_with_score() {
+        assert_eq!(Rank::for_score(-50), Rank::Cadet);
+        assert_eq!(Rank::for_score(199), Rank::Cadet);
+        assert_eq!(Rank::for_score(200), Rank::Ensign);
+        assert_eq!(Rank::for_score(850), Rank::Captain);
+        assert_eq!(Rank::for_score(5000), Rank::Admiral);
+        assert!(Rank::ALL.windows(2).all(|pair| pair[0].threshold() < pair[1].threshold()));
+    }
+
+    #[test]
+    fn losses_cost_points() {
+        let mut galaxy = Galaxy::new(42);
+        let clean = ScoreCard::tally(&galaxy, 700);
+        assert_eq!(clean.score, 700);
+        assert_eq!(clean.rank, Rank::Commander);
+
+        galaxy.enterprise_mut().consume_torpedo().unwrap();
+        galaxy.enterprise_mut().damage_hull(10.0);
+        galaxy.enterprise_mut().dock();
+        let card = ScoreCard::tally(&galaxy, 700);
+        assert_eq!(card.torpedoes_used, 1);
+        assert_eq!(card.casualties, 43);
+        assert_eq!(card.score, 700 - 2 - 43);
+        assert_eq!(card.rank, Rank::Commander);
+    }
+
+    fn score(name: &str, rating: i32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            seed: 42,
+            rating,
+            rank: Rank::for_score(rating),
+            date: "2026-10-16".to_string(),
+            turns: 80,
+        }
+    }
+
+    #[test]
+    fn high_scores_stay_sorted_and_bounded() {
+        let mut table = HighScoreTable::default();
+        assert_eq!(table.insert(score("KIRK", 500)), Some(0));
+        assert_eq!(table.insert(score("SPOCK", 900)), Some(0));
+        assert_eq!(table.insert(score("SULU", 500)), Some(2));
+        for i in 0..7 {
+            table.insert(score("REDSHIRT", 600 + i));
+        }
+        assert_eq!(table.entries().len(), HIGH_SCORE_LIMIT);
+        assert!(!table.qualifies(500));
+        assert!(table.qualifies(501));
+        assert_eq!(table.insert(score("CHEKOV", 100)), None);
+        assert_eq!(table.insert(score("UHURA", 700)), Some(1));
+        assert_eq!(table.entries().len(), HIGH_SCORE_LIMIT);
+        assert_eq!(table.entries()[0].name, "SPOCK");
+        assert_eq!(table.entries().last().unwrap().name, "KIRK");
+        assert!(table.entries().windows(2).all(|pair| pair[0].rating >= pair[1].rating));
+    }
+
+    #[test]
+    fn high_score_text_round_trips() {
+        let mut table = HighScoreTable::default();
+        table.insert(score("JAMES T. KIRK", 812));
+        table.insert(score("BAD\tNAME\n", 300));
+        let text = table.to_text();
+        assert!(text.starts_with("STARTREK HIGH SCORES\n812\tCAPTAIN\t42\t2026-10-16\t80\tJAMES T. KIRK\n"));
+        let parsed = HighScoreTable::parse(&text).unwrap();
+        assert_eq!(parsed.entries()[0], table.entries()[0]);
+        assert_eq!(parsed.entries()[1].name, "BAD NAME");
+
+        assert!(HighScoreTable::parse("hello").is_err());
+        assert!(HighScoreTable::parse("STARTREK HIGH SCORES\n812\tCAPTAIN\n").is_err());
+        assert!(HighScoreTable::parse("STARTREK HIGH SCORES\n812\tMIDSHIPMAN\t1\td\t1\tX\n").is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! Game sessions
+//!
+//! [`SessionManager`] owns any number of games at once, keyed by id, for
+//! servers and bindings that drive the engine one command at a time instead
+//! of running the interactive loop in `services::game`. Each session has its
+//! own lock, so commands for different games run concurrently while commands
+//! for the same game are applied in order.
+//!
+//! Sessions for head-to-head play can be given a chess clock: the player's
+//! budget runs down between a step's result and their next command, and a
+//! player who overruns it forfeits.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::game_engine::{GameEngine, GameState};
+use crate::io::{BufferedOutput, InputReader, OutputWriter, TypeAhead};
+use crate::models::clock::{ChessClock, Clock};
+use crate::models::compat::CompatMode;
+use crate::models::constants::DEFAULT_QUICK_SHIELD_UNITS;
+use crate::models::errors::{GameError, GameResult};
+use crate::models::status::StatusReport;
+use crate::models::turn::TurnSummary;
+use crate::services::commands::{self, CommandId};
+use crate::services::{combat, scan};
+use crate::ui::presenters::{BriefingPresenter, CombatPresenter, Emit};
+
+/// Identifies a session within its manager.
+pub type SessionId = u64;
+
+/// What one step of a session produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult {
+    /// Everything the game printed during the step.
+    pub output: String,
+    /// Game state after the step.
+    pub state: GameState,
+    /// The completed turn, if the step ran a command.
+    pub turn: Option<TurnSummary>,
+}
+
+/// Point-in-time view of a session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSnapshot {
+    pub id: SessionId,
+    pub seed: u64,
+    pub state: GameState,
+    pub turns: u32,
+    pub stardate: f64,
+    pub status: StatusReport,
+    /// `Galaxy::state_digest`, for checking two sessions agree.
+    pub state_digest: u64,
+    /// Thinking time left on the session's chess clock, if it has one.
+    pub clock_remaining: Option<Duration>,
+}
+
+/// Most prompts a step may answer with blank lines once its input runs
+/// out; a command still asking after that is stuck re-prompting.
+const MAX_BLANK_ANSWERS: usize = 16;
+
+/// One game and everything entered into it.
+struct Session {
+    engine: GameEngine,
+    /// Every input line the game has read, as a replay would feed them.
+    transcript: Vec<String>,
+    turns: Vec<TurnSummary>,
+    clock: Option<ChessClock>,
+}
+
+impl Session {
+    /// Forfeit the game if its chess clock has run out.
+    fn enforce_clock(&mut self, now: Duration) {
+        if self.clock.is_some_and(|clock| clock.is_flagged(now)) {
+            self.engine.forfeit_on_time();
+        }
+    }
+}
+
+/// A session, locked while a command runs in it.
+type SharedSession = Arc<Mutex<Session>>;
+
+/// Owns concurrent games keyed by id.
+pub struct SessionManager {
+    sessions: Mutex<HashMap<SessionId, SharedSession>>,
+    next_id: AtomicU64,
+    clock: Arc<dyn Clock>,
+}
+
+/// Reads the system clock, which browser builds do not have; they use
+/// [`SessionManager::with_clock`].
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Default for SessionManager {
+    fn default() -> Self {
+        use crate::models::clock::SystemClock;
+
+        Self::with_clock(Arc::new(SystemClock::default()))
+    }
+}
+
+impl SessionManager {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A manager whose chess clocks read `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        SessionManager {
+            sessions: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            clock,
+        }
+    }
+
+    /// Start a session on `engine`. The result holds the mission briefing
+    /// and opening scan.
+    pub fn create(&self, engine: GameEngine) -> (SessionId, StepResult) {
+        self.create_session(engine, None)
+    }
+
+    /// Start a session whose player has `budget` of thinking time for the
+    /// whole game. The clock starts running at once.
+    pub fn create_timed(&self, engine: GameEngine, budget: Duration) -> (SessionId, StepResult) {
+        self.create_session(engine, Some(ChessClock::new(budget)))
+    }
+
+    fn create_session(
+        &self,
+        mut engine: GameEngine,
+        mut clock: Option<ChessClock>,
+    ) -> (SessionId, StepResult) {
+        let mut output = BufferedOutput::default();
+        let galaxy = engine.galaxy_mut();
+        BriefingPresenter::show_mission_briefing(galaxy, CompatMode::default(), &mut output);
+        // The opening scan cannot fail: the sensors start undamaged
+        let _ = scan::short_range_scan(galaxy, &mut output);
+        combat::auto_shields(galaxy, &mut output);
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let result = StepResult {
+            output: output.take(),
+            state: engine.state().clone(),
+            turn: None,
+        };
+        if let Some(clock) = &mut clock {
+            clock.start(self.clock.now());
+        }
+        let session = Session {
+            engine,
+            transcript: Vec::new(),
+            turns: Vec::new(),
+            clock,
+        };
+        self.lock_sessions().insert(id, Arc::new(Mutex::new(session)));
+        (id, result)
+    }
+
+    /// Run one command in a session. `input` is the command followed by
+    /// the answers to any prompts it asks. Prompts left unanswered get a
+    /// blank line, as a terminal at end of input would, and lines left over
+    /// are ignored. Unknown commands and QUIT are rejected without a turn.
+    ///
+    /// A command that arrives after the session's chess clock has run out
+    /// is not run; the game is forfeited instead.
+    pub fn step(&self, id: SessionId, input: &[&str]) -> GameResult<StepResult> {
+        let session = self.session(id)?;
+        let mut session = session.lock().unwrap_or_else(PoisonError::into_inner);
+        if *session.engine.state() != GameState::Playing {
+            return Err(GameError::InvalidInput(format!("session {} has ended", id)));
+        }
+        let now = self.clock.now();
+        session.enforce_clock(now);
+        if let Some(report) = session.engine.game_over_report() {
+            let mut output = BufferedOutput::default();
+            CombatPresenter::show_epilogue(&report, &mut output);
+            return Ok(StepResult {
+                output: output.take(),
+                state: report.state,
+                turn: None,
+            });
+        }
+
+        let mut io = QueuedInput::new(input);
+        let mut output = BufferedOutput::default();
+        let command = io.read_line("COMMAND")?;
+        let (spec, answer) = match commands::parse_command_line(&command) {
+            Some((spec, answer)) if spec.id != CommandId::Quit => (spec, answer),
+            Some(_) => {
+                return Err(GameError::InvalidInput(
+                    "end a session by destroying it".to_string(),
+                ))
+            }
+            None => {
+                return Err(GameError::InvalidInput(format!(
+                    "unknown command {}",
+                    command.trim()
+                )))
+            }
+        };
+
+        let Session { engine, transcript, turns, clock } = &mut *session;
+        let start = engine.begin_turn();
+        let mut turn = None;
+        match spec.availability(engine.galaxy()) {
+            Err(message) => output.writeln(&message),
+            Ok(()) => {
+                if let Err(e) = commands::execute(
+                    spec.id,
+                    engine.galaxy_mut(),
+                    DEFAULT_QUICK_SHIELD_UNITS,
+                    &mut TypeAhead::new(answer, &mut io),
+                    &mut output,
+                ) {
+                    output.writeln(&format!("Error: {}", e));
+                }
+                let summary = engine.finish_turn(&start, spec.name, Some(&mut io), &mut output)?;
+                turns.push(summary.clone());
+                turn = Some(summary);
+            }
+        }
+        transcript.extend(io.read);
+        if let Some(clock) = clock {
+            // The player's time runs from the command arriving until the
+            // next one does; the step itself is not charged
+            clock.stop(now);
+            clock.start(self.clock.now());
+        }
+
+        let game_over = engine.game_over_report().is_some();
+        for event in engine.take_events() {
+            output.emit(event);
+        }
+        if game_over {
+            if let Some(report) = engine.game_over_report() {
+                CombatPresenter::show_epilogue(&report, &mut output);
+            }
+        }
+        Ok(StepResult {
+            output: output.take(),
+            state: engine.state().clone(),
+            turn,
+        })
+    }
+
+    /// Current view of a session. A session whose chess clock has run out
+    /// is forfeited first.
+    pub fn snapshot(&self, id: SessionId) -> GameResult<SessionSnapshot> {
+        let session = self.session(id)?;
+        let mut session = session.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = self.clock.now();
+        session.enforce_clock(now);
+        let engine = &session.engine;
+        let galaxy = engine.galaxy();
+        Ok(SessionSnapshot {
+            id,
+            seed: galaxy.seed(),
+            state: engine.state().clone(),
+            turns: engine.turns(),
+            stardate: galaxy.stardate(),
+            status: StatusReport::from_galaxy(galaxy),
+            state_digest: galaxy.state_digest(),
+            clock_remaining: session.clock.map(|clock| clock.remaining(now)),
+        })
+    }
+
+    /// Summaries of every turn played in a session, oldest first.
+    pub fn turns(&self, id: SessionId) -> GameResult<Vec<TurnSummary>> {
+        let session = self.session(id)?;
+        let turns = session.lock().unwrap_or_else(PoisonError::into_inner).turns.clone();
+        Ok(turns)
+    }
+
+    /// Every input line a session has read, oldest first. Fed to a game
+    /// with the same seed and rules, it replays the session.
+    pub fn transcript(&self, id: SessionId) -> GameResult<Vec<String>> {
+        let session = self.session(id)?;
+        let transcript = session.lock().unwrap_or_else(PoisonError::into_inner).transcript.clone();
+        Ok(transcript)
+    }
+
+    /// Whether a session with this id is live.
+    pub fn contains(&self, id: SessionId) -> bool {
+        self.lock_sessions().contains_key(&id)
+    }
+
+    /// End a session. Returns false if there was no such session.
+    pub fn destroy(&self, id: SessionId) -> bool {
+        self.lock_sessions().remove(&id).is_some()
+    }
+
+    /// Ids of the live sessions, in creation order.
+    pub fn ids(&self) -> Vec<SessionId> {
+        let mut ids: Vec<SessionId> = self.lock_sessions().keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock_sessions().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Look up a session. The manager lock is released before the session
+    /// is locked, so a long step never blocks other sessions.
+    fn session(&self, id: SessionId) -> GameResult<SharedSession> {
+        self.lock_sessions()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| GameError::InvalidInput(format!("no session {}", id)))
+    }
+
+    fn lock_sessions(&self) -> MutexGuard<'_, HashMap<SessionId, SharedSession>> {
+        self.sessions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("sessions", &self.ids())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Input for one step. Unlike a terminal it never waits: once the lines
+/// run out it answers blank, then fails.
+struct QueuedInput {
+    queued: VecDeque<String>,
+    /// Lines handed to the game, blanks included.
+    read: Vec<String>,
+    blanks: usize,
+}
+
+impl QueuedInput {
+    fn new(input: &[&str]) -> Self {
+        QueuedInput {
+            queued: input.iter().map(|line| line.to_string()).collect(),
+            read: Vec::new(),
+            blanks: 0,
+        }
+    }
+}
+
+impl InputReader for QueuedInput {
+    fn read_line(&mut self, _prompt: &str) -> Result<String, io::Error> {
+        let line = match self.queued.pop_front() {
+            Some(line) => line,
+            None if self.blanks < MAX_BLANK_ANSWERS => {
+                self.blanks += 1;
+                String::new()
+            }
+            None => {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "step needs more input"))
+            }
+        };
+        self.read.push(line.clone());
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn sessions_are_created_stepped_and_destroyed() {
+        let manager = SessionManager::new();
+        let (a, opening) = manager.create(GameEngine::new(42));
+        let (b, _) = manager.create(GameEngine::new(7));
+        assert_ne!(a, b);
+        assert_eq!(manager.ids(), vec![a, b]);
+        assert!(opening.output.starts_with("YOU MUST DESTROY"));
+        assert_eq!(opening.state, GameState::Playing);
+
+        let result = manager.step(a, &["SRS"]).unwrap();
+        assert!(result.output.contains("STARDATE"));
+        assert_eq!(result.turn.map(|t| t.turn), Some(1));
+        assert_eq!(manager.snapshot(a).unwrap().turns, 1);
+        assert_eq!(manager.snapshot(b).unwrap().turns, 0);
+
+        assert!(manager.destroy(a));
+        assert!(!manager.destroy(a));
+        assert!(manager.step(a, &["SRS"]).is_err());
+        assert_eq!(manager.len(), 1);
+    }
+
+    #[test]
+    fn bad_steps_are_rejected() {
+        let manager = SessionManager::new();
+        let (id, _) = manager.create(GameEngine::new(42));
+        assert!(manager.step(id, &[]).is_err());
+        assert!(manager.step(id, &["X"]).is_err());
+        assert!(manager.step(id, &["QUIT"]).is_err());
+        assert_eq!(manager.snapshot(id).unwrap().turns, 0);
+    }
+
+    #[test]
+    fn transcript_records_answers_as_the_game_read_them() {
+        let manager = SessionManager::new();
+        let (id, _) = manager.create(GameEngine::new(42));
+        manager.step(id, &["SRS", "ignored"]).unwrap();
+        manager.step(id, &["5"]).unwrap();
+        assert!(manager.step(id, &["X"]).is_err());
+
+        assert_eq!(manager.transcript(id).unwrap(), vec!["SRS", "5", ""]);
+        let commands: Vec<String> = manager.turns(id).unwrap().into_iter().map(|t| t.command).collect();
+        assert_eq!(commands, vec!["SHORT RANGE SENSOR SCAN", "SHIELD CONTROL"]);
+    }
+
+    #[test]
+    fn finished_sessions_report_their_epilogue() {
+        let manager = SessionManager::new();
+        let mut engine = GameEngine::new(42);
+        engine.galaxy_mut().enterprise_mut().set_hull(0.0);
+        let (id, _) = manager.create(engine);
+
+        let result = manager.step(id, &["6"]).unwrap();
+        assert!(matches!(result.state, GameState::Defeat { .. }));
+        assert!(result.output.contains("*** SHIP DESTROYED"));
+        assert!(manager.step(id, &["SRS"]).is_err());
+    }
+
+    #[test]
+    fn chess_clock_charges_thinking_time_and_forfeits() {
+        use crate::game_engine::DefeatReason;
+        use crate::models::clock::ManualClock;
+
+        let time = Arc::new(ManualClock::default());
+        let manager = SessionManager::with_clock(time.clone());
+        let (id, _) = manager.create_timed(GameEngine::new(42), Duration::from_secs(60));
+        let (untimed, _) = manager.create(GameEngine::new(42));
+
+        time.advance(Duration::from_secs(20));
+        manager.step(id, &["SRS"]).unwrap();
+        assert_eq!(manager.snapshot(id).unwrap().clock_remaining, Some(Duration::from_secs(40)));
+        assert_eq!(manager.snapshot(untimed).unwrap().clock_remaining, None);
+
+        time.advance(Duration::from_secs(41));
+        let result = manager.step(id, &["SRS"]).unwrap();
+        assert_eq!(result.state, GameState::Defeat { reason: DefeatReason::ClockExpired });
+        assert!(result.output.contains("*** CLOCK EXPIRED"));
+        assert_eq!(result.turn, None);
+        assert_eq!(manager.snapshot(id).unwrap().clock_remaining, Some(Duration::ZERO));
+        assert!(manager.step(untimed, &["SRS"]).is_ok());
+    }
+
+    #[test]
+    fn sessions_step_concurrently() {
+        let manager = Arc::new(SessionManager::new());
+        let ids: Vec<SessionId> = (0..4).map(|seed| manager.create(GameEngine::new(seed)).0).collect();
+
+        let handles: Vec<_> = ids
+            .iter()
+            .map(|&id| {
+                let manager = Arc::clone(&manager);
+                thread::spawn(move || {
+                    for _ in 0..5 {
+                        manager.step(id, &["LRS"]).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        for id in ids {
+            assert_eq!(manager.snapshot(id).unwrap().turns, 5);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Balance simulation
+//!
+//! Plays games with the autoplayer and gathers what a balance pass looks
+//! at: wins, efficiency ratings, stardates used, and how often each device
+//! breaks down. Samples are plain data, so batches can be played on
+//! separate threads and summed afterwards in seed order.
+
+use crate::game_engine::GameEngine;
+use crate::models::constants::{Device, NUM_DEVICES};
+use crate::services::ai;
+
+/// One autoplayed game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSample {
+    pub seed: u64,
+    /// `None` while the game was still going at the turn limit.
+    pub outcome: Option<&'static str>,
+    pub victory: bool,
+    /// Efficiency rating; `None` unless won.
+    pub rating: Option<i32>,
+    /// Stardates from the start to the end of the game, or to the turn
+    /// limit.
+    pub stardates_used: f64,
+    pub turns: u32,
+    pub klingons_destroyed: i32,
+    /// Times each device went from working to damaged, by `Device` index.
+    pub breakdowns: [u32; NUM_DEVICES],
+}
+
+/// Autoplay `engine`, seeded with `seed`, for at most `max_turns` turns.
+pub fn simulate(seed: u64, engine: &mut GameEngine, max_turns: u32) -> GameSample {
+    let mut breakdowns = [0; NUM_DEVICES];
+    let mut damaged = Device::ALL.map(|d| engine.galaxy().enterprise().is_damaged(d));
+    let report = ai::autoplay_observed(engine, max_turns, |galaxy| {
+        for device in Device::ALL {
+            let now = galaxy.enterprise().is_damaged(device);
+            if now && !damaged[device as usize] {
+                breakdowns[device as usize] += 1;
+            }
+            damaged[device as usize] = now;
+        }
+    });
+    let galaxy = engine.galaxy();
+    GameSample {
+        seed,
+        outcome: report.as_ref().map(|r| r.outcome_label()),
+        victory: report.as_ref().is_some_and(|r| r.is_victory()),
+        rating: report.as_ref().and_then(|r| r.rating),
+        stardates_used: report.as_ref().map_or(galaxy.stardate() - galaxy.starting_stardate(), |r| r.stardates_used),
+        turns: engine.turns(),
+        klingons_destroyed: report.as_ref().map_or(0, |r| r.klingons_destroyed()),
+        breakdowns,
+    }
+}
+
+/// Totals over a batch of samples. Unfinished games are only counted:
+/// the win rate and the averages are over the games that ended.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationSummary {
+    pub games: u32,
+    pub victories: u32,
+    /// Games still going at the turn limit, or resigned by the captain.
+    pub unfinished: u32,
+    /// Klingons destroyed over all finished games.
+    pub klingons_destroyed: i64,
+    rating_total: i64,
+    turns_total: u64,
+    stardates_total: f64,
+    breakdowns: [u64; NUM_DEVICES],
+    /// Games in which each device broke down at least once.
+    games_damaged: [u32; NUM_DEVICES],
+}
+
+impl SimulationSummary {
+    pub fn add(&mut self, sample: &GameSample) {
+        self.games += 1;
+        if sample.outcome.is_none() {
+            self.unfinished += 1;
+            return;
+        }
+        self.victories += sample.victory as u32;
+        self.klingons_destroyed += sample.klingons_destroyed as i64;
+        self.rating_total += sample.rating.unwrap_or(0) as i64;
+        self.turns_total += sample.turns as u64;
+        self.stardates_total += sample.stardates_used;
+        for (i, &count) in sample.breakdowns.iter().enumerate() {
+            self.breakdowns[i] += count as u64;
+            self.games_damaged[i] += (count > 0) as u32;
+        }
+    }
+
+    /// Games that ended in victory or defeat.
+    pub fn finished(&self) -> u32 {
+        self.games - self.unfinished
+    }
+
+    /// Percentage of finished games won.
+    pub fn win_rate(&self) -> f64 {
+        self.per_game(self.victories as f64) * 100.0
+    }
+
+    /// Mean efficiency rating of the games won.
+    pub fn average_rating(&self) -> f64 {
+        if self.victories == 0 {
+            return 0.0;
+        }
+        self.rating_total as f64 / self.victories as f64
+    }
+
+    pub fn average_turns(&self) -> f64 {
+        self.per_game(self.turns_total as f64)
+    }
+
+    pub fn average_stardates(&self) -> f64 {
+        self.per_game(self.stardates_total)
+    }
+
+    /// Mean number of times `device` broke down in a game.
+    pub fn breakdowns_per_game(&self, device: Device) -> f64 {
+        self.per_game(self.breakdowns[device as usize] as f64)
+    }
+
+    /// Percentage of games in which `device` broke down at least once.
+    pub fn damaged_share(&self, device: Device) -> f64 {
+        self.per_game(self.games_damaged[device as usize] as f64) * 100.0
+    }
+
+    /// `total` spread over the finished games.
+    fn per_game(&self, total: f64) -> f64 {
+        if self.finished() == 0 {
+            return 0.0;
+        }
+        total / self.finished() as f64
+    }
+}
+
+impl<'a> FromIterator<&'a GameSample> for SimulationSummary {
+    fn from_iter<I: IntoIterator<Item = &'a GameSample>>(samples: I) -> Self {
+        let mut summary = SimulationSummary::default();
+        for sample in samples {
+            summary.add(sample);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(victory: bool, rating: Option<i32>, breakdowns: &[(Device, u32)]) -> GameSample {
+        let mut counts = [0; NUM_DEVICES];
+        for &(device, count) in breakdowns {
+            counts[device as usize] = count;
+        }
+        GameSample {
+            seed: 1,
+            outcome: Some(if victory { "VICTORY" } else { "SHIP DESTROYED" }),
+            victory,
+            rating,
+            stardates_used: 20.0,
+            turns: 50,
+            klingons_destroyed: 0,
+            breakdowns: counts,
+        }
+    }
+
+    #[test]
+    fn summary_averages_ratings_over_victories_and_damage_over_games() {
+        let samples = [
+            sample(true, Some(800), &[(Device::WarpEngines, 3)]),
+            sample(true, Some(400), &[]),
+            sample(false, None, &[(Device::WarpEngines, 1), (Device::Computer, 2)]),
+            sample(false, None, &[]),
+        ];
+        let summary: SimulationSummary = samples.iter().collect();
+        assert_eq!((summary.games, summary.victories, summary.unfinished), (4, 2, 0));
+        assert_eq!(summary.win_rate(), 50.0);
+        assert_eq!(summary.average_rating(), 600.0);
+        assert_eq!(summary.average_stardates(), 20.0);
+        assert_eq!(summary.average_turns(), 50.0);
+        assert_eq!(summary.breakdowns_per_game(Device::WarpEngines), 1.0);
+        assert_eq!(summary.damaged_share(Device::WarpEngines), 50.0);
+        assert_eq!(summary.damaged_share(Device::Computer), 25.0);
+        assert_eq!(SimulationSummary::default().average_rating(), 0.0);
+    }
+
+    #[test]
+    fn unfinished_games_stay_out_of_the_rates_and_averages() {
+        let stalled = GameSample { outcome: None, turns: 1000, stardates_used: 30.0, ..sample(false, None, &[(Device::Computer, 4)]) };
+        let samples = [sample(true, Some(800), &[]), sample(false, None, &[]), stalled];
+        let summary: SimulationSummary = samples.iter().collect();
+        assert_eq!((summary.games, summary.finished(), summary.unfinished), (3, 2, 1));
+        assert_eq!(summary.win_rate(), 50.0);
+        assert_eq!(summary.average_turns(), 50.0);
+        assert_eq!(summary.average_stardates(), 20.0);
+        assert_eq!(summary.breakdowns_per_game(Device::Computer), 0.0);
+    }
+
+    #[test]
+    fn simulated_games_are_reproducible() {
+        let play = || simulate(7, &mut GameEngine::new(7), 60);
+        let sample = play();
+        assert_eq!(sample, play());
+        assert!(sample.turns <= 60);
+        assert_eq!(sample.victory, sample.outcome == Some("VICTORY"));
+    }
+}
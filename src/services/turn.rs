@@ -0,0 +1,235 @@
+//! Turn order
+//!
+//! Every turn runs in the same order: the player's action, with the
+//! Klingons returning fire before or after it as the action calls for,
+//! then the enemy phase, then end-of-turn upkeep. The interactive commands
+//! resolve their actions here, and every frontend ends its turns with
+//! [`GameEngine::finish_turn`], so the order is written down once. A command
+//! that does not run takes no turn.
+//!
+//! [`GameEngine::finish_turn`]: crate::game_engine::GameEngine::finish_turn
+
+use crate::io::{OutputWriter, PendingInput};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::navigation_types::{Course, WarpFactor};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::services::commands::{self, CommandId};
+use crate::services::{combat, navigation, pending, probe};
+
+/// A player's action with its answers already given.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerAction {
+    Warp { course: Course, warp: WarpFactor },
+    Impulse { course: Course, sectors: i32 },
+    Phasers { energy: f64 },
+    Torpedo { course: Course },
+    Salvo { course: Course, count: i32 },
+    /// Any other built-in command, its prompts answered from `answers`.
+    Command { id: CommandId, answers: Vec<String> },
+}
+
+/// When the enemies in the quadrant return fire on an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnFire {
+    /// As the order is given, before it takes effect: moves and phasers.
+    /// Only if there are Klingons in the quadrant.
+    BeforeAction,
+    /// Once the action has played out: torpedoes.
+    AfterAction,
+}
+
+impl PlayerAction {
+    /// The built-in command the action is an answered form of.
+    pub fn command(&self) -> CommandId {
+        match self {
+            PlayerAction::Warp { .. } => CommandId::SetCourse,
+            PlayerAction::Impulse { .. } => CommandId::Impulse,
+            PlayerAction::Phasers { .. } => CommandId::FirePhasers,
+            PlayerAction::Torpedo { .. } => CommandId::FireTorpedoes,
+            PlayerAction::Salvo { .. } => CommandId::FireSalvo,
+            PlayerAction::Command { id, .. } => *id,
+        }
+    }
+
+    /// When the enemies return fire on the action (spec section 8.1);
+    /// `None` for commands they let pass.
+    pub fn return_fire(&self) -> Option<ReturnFire> {
+        match self {
+            PlayerAction::Warp { .. } | PlayerAction::Impulse { .. } | PlayerAction::Phasers { .. } => {
+                Some(ReturnFire::BeforeAction)
+            }
+            PlayerAction::Torpedo { .. } | PlayerAction::Salvo { .. } => Some(ReturnFire::AfterAction),
+            PlayerAction::Command { .. } => None,
+        }
+    }
+}
+
+/// Take the player's action: check it can be done, as the command's own
+/// prompts would have, then resolve it.
+///
+/// # Arguments
+///
+/// * `galaxy` - The game galaxy state
+/// * `action` - The action, with its answers
+/// * `quick_shield_units` - Units the SHIELDS UP command raises
+/// * `output` - Output writer for displaying results
+///
+/// # Returns
+///
+/// * `Ok(())` on success, including an action that could not be taken
+/// * `Err` if a `Command` action runs out of answers
+pub fn act(
+    galaxy: &mut Galaxy,
+    action: &PlayerAction,
+    quick_shield_units: f64,
+    output: &mut dyn OutputWriter,
+) -> GameResult<()> {
+    let possible = match *action {
+        PlayerAction::Warp { warp, .. } => navigation::can_warp(galaxy, warp, output),
+        PlayerAction::Impulse { sectors, .. } => navigation::can_impulse(galaxy, sectors, output),
+        PlayerAction::Phasers { energy } => combat::can_fire_phasers(galaxy, energy, output),
+        PlayerAction::Torpedo { .. } => combat::can_fire_torpedoes(galaxy, 1, output),
+        PlayerAction::Salvo { count, .. } => combat::can_fire_torpedoes(galaxy, count, output),
+        PlayerAction::Command { id, ref answers } => {
+            let mut input = PendingInput::new(answers.iter().cloned());
+            return commands::execute(id, galaxy, quick_shield_units, &mut input, output);
+        }
+    };
+    if possible {
+        resolve(galaxy, action, output)?;
+    }
+    Ok(())
+}
+
+/// Resolve an action that has been checked, with the enemies' return fire
+/// in its place, and charge its command cost. A `Command` action does
+/// nothing here; see [`act`].
+pub fn resolve(galaxy: &mut Galaxy, action: &PlayerAction, output: &mut dyn OutputWriter) -> GameResult<()> {
+    if let PlayerAction::Phasers { energy } = *action {
+        combat::charge_phasers(galaxy, energy);
+    }
+    if action.return_fire() == Some(ReturnFire::BeforeAction)
+        && galaxy.sector_map().hostiles_present()
+        && combat::enemies_fire(galaxy, output)
+    {
+        return Ok(()); // Enterprise destroyed
+    }
+
+    match *action {
+        PlayerAction::Warp { course, warp } => navigation::engage_warp(galaxy, course, warp, output),
+        PlayerAction::Impulse { course, sectors } => navigation::execute_impulse(galaxy, course, sectors, output),
+        PlayerAction::Phasers { energy } => combat::phaser_volley(galaxy, energy, output)?,
+        PlayerAction::Torpedo { course } => combat::launch_torpedo(galaxy, course, output)?,
+        PlayerAction::Salvo { course, count } => combat::launch_salvo(galaxy, course, count, output)?,
+        PlayerAction::Command { .. } => {}
+    }
+
+    if action.return_fire() == Some(ReturnFire::AfterAction) {
+        combat::enemies_fire(galaxy, output);
+    }
+    commands::charge_time(galaxy, action.command());
+    Ok(())
+}
+
+/// The enemy's turn, given where the Enterprise was before the player
+/// acted. Entering a quadrant, the enemy fires on the next command and
+/// only automatic shields react; staying, the enemy takes its turn.
+pub fn enemy_phase(
+    galaxy: &mut Galaxy,
+    quadrant: QuadrantPosition,
+    sector: SectorPosition,
+    output: &mut dyn OutputWriter,
+) {
+    if galaxy.enterprise().quadrant() != quadrant {
+        combat::auto_shields(galaxy, output);
+    } else {
+        combat::enemy_phase(galaxy, sector, output);
+    }
+}
+
+/// End-of-turn upkeep: a probe in flight reports the quadrants it reached,
+/// raised shields report what they drained, and starbases under attack
+/// hold out or fall. Checking for the end of the game is left to the
+/// engine.
+pub fn upkeep(galaxy: &mut Galaxy, output: &mut dyn OutputWriter) {
+    probe::advance_probe(galaxy, output);
+    combat::report_shield_drain(galaxy, output);
+    pending::resolve_pending_events(galaxy, output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::models::constants::SectorContent;
+    use crate::models::klingon::Klingon;
+    use crate::models::sector_map::SectorMap;
+
+    /// The Enterprise at sector 4,4 with one Klingon two sectors south.
+    fn standoff() -> Galaxy {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        let here = SectorPosition { x: 4, y: 4 };
+        galaxy.enterprise_mut().move_to(quadrant, here);
+        galaxy.enterprise_mut().set_shields(500.0);
+        galaxy.sector_map_mut().set(here, SectorContent::Enterprise);
+        let klingon = Klingon::new(SectorPosition { x: 4, y: 6 });
+        galaxy.sector_map_mut().set(klingon.sector, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+        galaxy
+    }
+
+    fn line_of(output: &MockOutput, text: &str) -> usize {
+        output.messages.iter().position(|m| m.contains(text)).unwrap()
+    }
+
+    #[test]
+    fn romulans_fire_without_klingons_and_then_take_phaser_fire() {
+        use crate::models::constants::Condition;
+        use crate::models::rng::SequenceRng;
+        use crate::models::romulan::Romulan;
+
+        let mut galaxy = standoff();
+        let pos = galaxy.sector_map().klingons[0].sector;
+        galaxy.sector_map_mut().klingons.clear();
+        galaxy.sector_map_mut().set(pos, SectorContent::Romulan);
+        galaxy.sector_map_mut().romulans.push(Romulan::new(pos, 300.0));
+        galaxy.rng_mut().set_source(SequenceRng::new([0.4]));
+
+        let mut output = MockOutput::new();
+        resolve(&mut galaxy, &PlayerAction::Phasers { energy: 50.0 }, &mut output).unwrap();
+        assert!(line_of(&output, "DECLOAKS") < line_of(&output, "HIT ON ENTERPRISE"));
+        assert!(line_of(&output, "HIT ON ENTERPRISE") < line_of(&output, "HIT ON ROMULAN"));
+        assert!(galaxy.sector_map().romulans[0].shields < 300.0);
+        assert_eq!(galaxy.evaluate_condition(), Condition::Red);
+    }
+
+    #[test]
+    fn klingons_fire_before_phasers_and_after_torpedoes() {
+        let mut galaxy = standoff();
+        let mut output = MockOutput::new();
+        act(&mut galaxy, &PlayerAction::Phasers { energy: 50.0 }, 0.0, &mut output).unwrap();
+        assert!(line_of(&output, "HIT ON ENTERPRISE") < line_of(&output, "HIT ON KLINGON"));
+
+        let mut galaxy = standoff();
+        let mut output = MockOutput::new();
+        let course = Course::new(1.0).unwrap();
+        act(&mut galaxy, &PlayerAction::Torpedo { course }, 0.0, &mut output).unwrap();
+        assert!(line_of(&output, "TORPEDO MISSED") < line_of(&output, "HIT ON ENTERPRISE"));
+    }
+
+    #[test]
+    fn impossible_actions_change_nothing() {
+        let mut galaxy = standoff();
+        let energy = galaxy.enterprise().energy();
+        let mut output = MockOutput::new();
+        act(&mut galaxy, &PlayerAction::Phasers { energy: energy + 1.0 }, 0.0, &mut output).unwrap();
+        let course = Course::new(1.0).unwrap();
+        act(&mut galaxy, &PlayerAction::Impulse { course, sectors: 9 }, 0.0, &mut output).unwrap();
+        assert_eq!(galaxy.enterprise().energy(), energy);
+        assert_eq!(galaxy.enterprise().shields(), 500.0);
+        assert!(output.messages.is_empty());
+    }
+}
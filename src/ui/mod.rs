@@ -4,3 +4,5 @@
 //! game information to the player, separating presentation from business logic.
 
 pub mod presenters;
+#[cfg(feature = "tui")]
+pub mod tui;
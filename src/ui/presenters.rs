@@ -1,52 +1,862 @@
+use crate::io::ansi::{highlight, Highlight};
 use crate::io::OutputWriter;
-use crate::models::constants::Device;
+use crate::game_engine::{DefeatReason, GameOverReport, GameState};
+use crate::models::achievements::Achievement;
+use crate::models::combat_odds::CombatOdds;
+use crate::models::compat::CompatMode;
+use crate::models::constants::{Condition, Device, SectorContent, GALAXY_SIZE, SECTOR_SIZE};
 use crate::models::enterprise::Enterprise;
-use crate::models::position::SectorPosition;
+use crate::models::galaxy::Galaxy;
+use crate::models::grid::QuadrantGrid;
+use crate::models::output_event::{OutputEvent, SensorReadout};
+use crate::models::position::{QuadrantPosition, SectorPosition};
+use crate::models::region;
+use crate::models::repair::{estimated_repair_time, repair_estimates, RepairEstimate};
+use crate::models::war::WarStatus;
+use crate::models::difficulty::Difficulty;
+use crate::models::ruleset::MissionDuration;
+use crate::services::scoring::HighScoreTable;
+
+pub struct BriefingPresenter;
+
+impl BriefingPresenter {
+    /// Print the mission briefing shown after galaxy generation (spec 3.6).
+    pub fn show_mission_briefing(galaxy: &Galaxy, mode: CompatMode, output: &mut dyn OutputWriter) {
+        let klingons = galaxy.initial_klingons();
+        let stardates = galaxy.mission_duration() as i32;
+        let starbases = galaxy.total_starbases();
+        let plural = if starbases != 1 { "S" } else { "" };
+
+        match mode {
+            CompatMode::Modern => {
+                output.writeln(&format!(
+                    "YOU MUST DESTROY {} KLINGONS IN {} STARDATES WITH {} STARBASE{}",
+                    klingons, stardates, starbases, plural,
+                ));
+            }
+            CompatMode::Classic => {
+                let deadline = (galaxy.starting_stardate() + galaxy.mission_duration()) as i32;
+                let verb = if starbases != 1 { "ARE" } else { "IS" };
+                output.writeln("YOUR ORDERS ARE AS FOLLOWS:");
+                output.writeln(&format!(
+                    "   DESTROY THE {} KLINGON WARSHIPS WHICH HAVE INVADED",
+                    klingons
+                ));
+                output.writeln(" THE GALAXY BEFORE THEY CAN ATTACK FEDERATION HEADQUARTERS");
+                output.writeln(&format!(
+                    " ON STARDATE {}.  THIS GIVES YOU {} DAYS.  THERE {}",
+                    deadline, stardates, verb
+                ));
+                output.writeln(&format!(
+                    " {} STARBASE{} IN THE GALAXY FOR RESUPPLYING YOUR SHIP.",
+                    starbases, plural
+                ));
+                output.writeln(" EVERY WARP AND EVERY BATTLE DEPLETES YOUR ENERGY, AS IT");
+                output.writeln(" DEPLETES THE TIME LEFT TO SAVE THE FEDERATION.");
+                output.writeln(" TO DOCK, MANEUVER INTO A SECTOR ADJACENT TO A STARBASE;");
+                output.writeln(" THE LIBRARY COMPUTER'S GALACTIC RECORD SHOWS WHERE THE");
+                output.writeln(" STARBASES YOU HAVE SCANNED LIE, SO PLOT YOUR COURSE THERE.");
+            }
+        }
+
+        let duration = galaxy.ruleset().mission_duration;
+        if duration != MissionDuration::default() {
+            output.writeln(&format!("(MISSION TIME RULE: {})", duration.formula()));
+        }
+        let difficulty = galaxy.ruleset().difficulty;
+        if difficulty != Difficulty::default() {
+            output.writeln(&format!("(DIFFICULTY: {})", difficulty));
+        }
+    }
+}
+
+/// A quadrant in the computer's record: its three-digit code, and whether
+/// the record is old enough to be out of date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordCell {
+    pub encoded: i32,
+    pub stale: bool,
+}
+
+pub struct GalaxyPresenter;
+
+impl GalaxyPresenter {
+    /// The library computer's record of every quadrant as a plain grid
+    /// (spec 6.7). Quadrants it has no record of show as `???`, and old
+    /// records in parentheses.
+    pub fn show_galactic_record(
+        quadrant: QuadrantPosition,
+        cells: &QuadrantGrid<Option<RecordCell>>,
+        output: &mut dyn OutputWriter,
+    ) {
+        output.writeln(&format!("COMPUTER RECORD OF GALAXY FOR QUADRANT {},{}", quadrant.x, quadrant.y));
+        let rows: Vec<Vec<String>> = cells.rows().map(|row| row.iter().map(|&cell| record_cell(cell)).collect()).collect();
+        write_grid(&rows, output);
+        if cells.iter().flatten().any(|cell| cell.stale) {
+            output.writeln("( ) = OLD RECORD");
+        }
+    }
+
+    /// The computer's record as a map: the Enterprise's quadrant in
+    /// brackets, a divider between the western and eastern regions, and
+    /// each row's region names alongside.
+    pub fn show_galaxy_map(
+        quadrant: QuadrantPosition,
+        cells: &QuadrantGrid<Option<RecordCell>>,
+        output: &mut dyn OutputWriter,
+    ) {
+        let half = GALAXY_SIZE / 2;
+        let columns: Vec<String> = (1..=GALAXY_SIZE).map(|x| format!("{:^5}", x)).collect();
+        let border = format!("   +{}+{}+", "-".repeat(half * 5), "-".repeat(half * 5));
+
+        output.writeln(&format!("GALAXY MAP FOR QUADRANT {},{}", quadrant.x, quadrant.y));
+        let header = format!("    {} {}", columns[..half].concat(), columns[half..].concat());
+        output.writeln(header.trim_end());
+        output.writeln(&border);
+        for (y, row) in (1..).zip(cells.rows()) {
+            let cells: Vec<String> = (1..)
+                .zip(row)
+                .map(|(x, &cell)| {
+                    if (QuadrantPosition { x, y }) == quadrant {
+                        format!("[{}]", record_cell(cell.map(|c| RecordCell { stale: false, ..c })))
+                    } else {
+                        format!("{:^5}", record_cell(cell))
+                    }
+                })
+                .collect();
+            let west = region::region_name(QuadrantPosition { x: 1, y }).unwrap_or_default();
+            let east = region::region_name(QuadrantPosition { x: GALAXY_SIZE as i32, y }).unwrap_or_default();
+            output.writeln(&format!(
+                "{:>2} |{}|{}| {} / {}",
+                y,
+                cells[..half].concat(),
+                cells[half..].concat(),
+                west,
+                east
+            ));
+        }
+        output.writeln(&border);
+        output.writeln("[ ] = ENTERPRISE   ??? = NO RECORD   ( ) = OLD RECORD");
+    }
+}
+
+/// A recorded quadrant as three digits, in parentheses when old, or
+/// `???` for no record.
+fn record_cell(cell: Option<RecordCell>) -> String {
+    match cell {
+        Some(RecordCell { encoded, stale: true }) => format!("({:03})", encoded),
+        Some(RecordCell { encoded, .. }) => format!("{:03}", encoded),
+        None => "???".to_string(),
+    }
+}
+
+pub struct MapPresenter;
+
+impl MapPresenter {
+    /// End-of-game reveal: the true contents of every quadrant, whether or
+    /// not the computer ever recorded it. Quadrants the Enterprise entered
+    /// are marked `.` and its final quadrant `E`.
+    pub fn show_galaxy_reveal(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+        output.writeln("FINAL MAP OF THE GALAXY");
+
+        let here = galaxy.enterprise().quadrant();
+        let cells: Vec<Vec<String>> = (1..=GALAXY_SIZE as i32)
+            .map(|y| {
+                (1..=GALAXY_SIZE as i32)
+                    .map(|x| {
+                        let pos = QuadrantPosition { x, y };
+                        let marker = if pos == here {
+                            'E'
+                        } else if galaxy.has_visited(pos) {
+                            '.'
+                        } else {
+                            ' '
+                        };
+                        let data = galaxy.quadrants()[pos];
+                        format!("{}{:03}", marker, data.encoded())
+                    })
+                    .collect()
+            })
+            .collect();
+        write_grid(&cells, output);
+        output.writeln("E = FINAL POSITION OF THE ENTERPRISE   . = QUADRANT VISITED");
+        output.writeln(&format!(
+            "QUADRANTS EXPLORED: {} OF {} ({}%)",
+            galaxy.quadrants_visited(),
+            GALAXY_SIZE * GALAXY_SIZE,
+            galaxy.exploration_percent() as i32
+        ));
+    }
+
+    pub fn show_achievements(achievements: &[Achievement], output: &mut dyn OutputWriter) {
+        if achievements.is_empty() {
+            return;
+        }
+        output.writeln("ACHIEVEMENTS:");
+        for achievement in achievements {
+            output.writeln(&format!("   {}", achievement.label()));
+        }
+    }
+}
+
+/// Write rows of cells as a bordered grid, as in the long range scan and
+/// galactic record. Cells are right-aligned to the widest, which is wider
+/// than 3 only when a quadrant holds a Klingon commander.
+fn write_grid(rows: &[Vec<String>], output: &mut dyn OutputWriter) {
+    for line in grid_lines(rows) {
+        output.writeln(&line);
+    }
+}
+
+/// Rows of cells between ruled borders, right-aligned to the widest cell.
+fn grid_lines(rows: &[Vec<String>]) -> Vec<String> {
+    let width = rows.first().map_or(0, Vec::len);
+    let cell_width = rows.iter().flatten().map(String::len).max().unwrap_or(3);
+    let border = "-".repeat(width * (cell_width + 3) + 1);
+    let mut lines = Vec::new();
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| format!("{:>w$}", cell, w = cell_width)).collect();
+        lines.push(border.clone());
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.push(border);
+    lines
+}
 
 pub struct EnterprisePresenter;
 
 impl EnterprisePresenter {
-    pub fn show_damage_report(enterprise: &Enterprise, output: &mut dyn OutputWriter) {
+    /// The state of repair of each device the ship carries, with the
+    /// stardates each damaged one needs to come back online.
+    pub fn show_damage_report(galaxy: &Galaxy, output: &mut dyn OutputWriter) {
+        let enterprise = galaxy.enterprise();
         if enterprise.is_damaged(Device::DamageControl) {
             output.writeln("DAMAGE CONTROL REPORT IS NOT AVAILABLE");
             return;
         }
 
-        output.writeln(&format!("{:<14}{}", "DEVICE", "STATE OF REPAIR"));
-        for device in Device::ALL.iter() {
-            let state = enterprise.devices()[*device as usize] as i32;
-            output.writeln(&format!("{:<14}{}", device.name(), state));
+        output.writeln(&format!("{:<14}{:<16}{}", "DEVICE", "STATE OF REPAIR", "REPAIR TIME"));
+        for &device in galaxy.ruleset().devices() {
+            let state = enterprise.devices()[device as usize] as i32;
+            if enterprise.is_damaged(device) {
+                let time = estimated_repair_time(galaxy, device);
+                output.writeln(&format!("{:<14}{:<16}{:.1}", device.name(), state, time));
+            } else {
+                output.writeln(&format!("{:<14}{}", device.name(), state));
+            }
+        }
+        if galaxy.evaluate_condition() == Condition::Docked && !repair_estimates(enterprise).is_empty() {
+            output.writeln("(REPAIR TIMES ARE FOR STARBASE CREWS)");
         }
     }
 }
 
-pub struct CombatPresenter;
+pub struct RepairPresenter;
 
-impl CombatPresenter {
-    pub fn show_klingon_hit(hit: f64, pos: SectorPosition, remaining: f64, output: &mut dyn OutputWriter) {
+impl RepairPresenter {
+    /// List damaged devices as numbered choices for a priority repair.
+    pub fn show_repair_options(estimates: &[RepairEstimate], output: &mut dyn OutputWriter) {
+        output.writeln("STARBASE REPAIR CREWS STANDING BY");
+        output.writeln(&format!("    {:<14}{:>8}{:>12}", "DEVICE", "IN FLIGHT", "PRIORITY"));
+        for (i, estimate) in estimates.iter().enumerate() {
+            output.writeln(&format!(
+                "{:>2}  {:<14}{:>8.1}{:>12.1}",
+                i + 1,
+                estimate.device.name(),
+                estimate.repair_time,
+                estimate.priority_time
+            ));
+        }
+        output.writeln("(REPAIR TIMES IN STARDATES; 0 TO CANCEL)");
+    }
+
+    /// List damaged devices as numbered choices for the ship's repair
+    /// crews, with each one's repair per move and the current assignment.
+    pub fn show_crew_assignments(enterprise: &Enterprise, damaged: &[Device], output: &mut dyn OutputWriter) {
+        output.writeln("REPAIR CREW ASSIGNMENTS");
+        output.writeln(&format!("    {:<14}{:>7}{:>8}", "DEVICE", "STATE", "RATE"));
+        for (i, &device) in damaged.iter().enumerate() {
+            let assigned = if enterprise.repair_priority() == Some(device) { "  ASSIGNED" } else { "" };
+            output.writeln(&format!(
+                "{:>2}  {:<14}{:>7}{:>8.1}{}",
+                i + 1,
+                device.name(),
+                enterprise.devices()[device as usize] as i32,
+                enterprise.repair_rate(device),
+                assigned
+            ));
+        }
+        output.writeln("(REPAIR PER MOVE; 0 TO RELEASE THE CREWS)");
+    }
+
+    pub fn show_repair_complete(estimate: &RepairEstimate, output: &mut dyn OutputWriter) {
         output.writeln(&format!(
-            "{} UNIT HIT ON KLINGON AT SECTOR {},{}",
-            hit as i32, pos.x, pos.y
+            "REPAIRS TO {} COMPLETE IN {:.1} STARDATES",
+            estimate.device.name(),
+            estimate.priority_time
         ));
-        output.writeln(&format!("   ({} LEFT)", remaining.max(0.0) as i32));
     }
+}
 
-    pub fn show_klingon_destroyed(output: &mut dyn OutputWriter) {
-        output.writeln("*** KLINGON DESTROYED ***");
-    }
+pub struct WarPresenter;
 
-    pub fn show_victory(rating: i32, output: &mut dyn OutputWriter) {
+impl WarPresenter {
+    pub fn show_war_status(status: &WarStatus, output: &mut dyn OutputWriter) {
+        output.writeln("   WAR STATUS");
         output.writeln("");
-        output.writeln("THE LAST KLINGON BATTLE CRUISER IN THE GALAXY HAS BEEN DESTROYED");
-        output.writeln("THE FEDERATION HAS BEEN SAVED !!!");
+        output.writeln(&format!(
+            "KLINGONS DESTROYED       = {} OF {}",
+            status.klingons_destroyed, status.klingons_initial
+        ));
+        output.writeln(&format!("QUADRANTS CLEARED        = {}", status.quadrants_cleared));
+        output.writeln(&format!("QUADRANTS UNDER KLINGONS = {}", status.quadrants_occupied));
+        output.writeln(&format!("STARBASES LOST           = {}", status.starbases_lost));
+        output.writeln(&format!(
+            "MISSION TIME REMAINING   = {}%",
+            (status.time_remaining_ratio * 100.0).round() as i32
+        ));
+        output.writeln(&format!("OUTLOOK: {}", status.outlook().label()));
+    }
+}
+
+/// Reporting structured events to any writer: a writer that keeps events
+/// ([`OutputWriter::accept`]) gets the event itself, and any other is
+/// written its lines from [`EventPresenter::lines`].
+pub trait Emit {
+    fn emit(&mut self, event: OutputEvent);
+}
+
+impl<W: OutputWriter + ?Sized> Emit for W {
+    fn emit(&mut self, event: OutputEvent) {
+        match self.accept(event) {
+            None => {}
+            Some(OutputEvent::Text(text)) => self.write(&text),
+            Some(event) => {
+                for line in EventPresenter::lines(&event, self.color()) {
+                    self.writeln(&line);
+                }
+            }
+        }
+    }
+}
+
+pub struct EventPresenter;
+
+impl EventPresenter {
+    /// The lines of text an event is shown as, with hits highlighted when
+    /// `color` is on. Some events, like entering a quadrant, are shown by
+    /// the scan that follows and have none.
+    pub fn lines(event: &OutputEvent, color: bool) -> Vec<String> {
+        match event {
+            OutputEvent::KlingonHit { from, damage, shields_left, hull }
+            | OutputEvent::RomulanHit { from, damage, shields_left, hull } => {
+                let mut lines = vec![
+                    highlight(
+                        &format!("{} UNIT HIT ON ENTERPRISE FROM SECTOR {},{}", *damage as i32, from.x, from.y),
+                        Highlight::EnterpriseHit,
+                        color,
+                    ),
+                    format!("   ({} LEFT)", shields_left.max(0.0) as i32),
+                ];
+                if let Some(hull) = hull {
+                    lines.push(format!("   HULL INTEGRITY {}%", hull.ceil() as i32));
+                }
+                lines
+            }
+            OutputEvent::CommanderTorpedo { from } => {
+                vec![format!("PHOTON TORPEDO FIRED BY KLINGON COMMANDER AT SECTOR {},{}", from.x, from.y)]
+            }
+            OutputEvent::CommanderMoved { to, .. } => {
+                vec![format!("KLINGON COMMANDER MOVES TO SECTOR {},{}", to.x, to.y)]
+            }
+            OutputEvent::TractorBeam { from, to, hull } => {
+                let mut lines = vec![format!(
+                    "KLINGON COMMANDER AT SECTOR {},{} LOCKS A TRACTOR BEAM ON THE ENTERPRISE",
+                    from.x, from.y
+                )];
+                match hull {
+                    None => lines.push(format!("ENTERPRISE DRAGGED TO SECTOR {},{}", to.x, to.y)),
+                    Some(hull) => {
+                        lines.push(format!("ENTERPRISE DRAGGED INTO AN OBSTACLE AT SECTOR {},{}", to.x, to.y));
+                        lines.push(format!("   HULL INTEGRITY {}%", hull.ceil() as i32));
+                    }
+                }
+                lines
+            }
+            OutputEvent::RomulanDecloaked { at } => {
+                vec![format!("A ROMULAN WARBIRD DECLOAKS AT SECTOR {},{}", at.x, at.y)]
+            }
+            OutputEvent::WebSpun { at } => vec![format!("THOLIAN WEB SPUN AT SECTOR {},{}", at.x, at.y)],
+            OutputEvent::ReinforcementsArrived { quadrant } => vec![format!(
+                "SUBSPACE MESSAGE: KLINGON REINFORCEMENTS HAVE WARPED INTO QUADRANT {},{}",
+                quadrant.x, quadrant.y
+            )],
+            OutputEvent::StarbaseUnderAttack { quadrant, stardates_left } => vec![
+                format!("SUBSPACE MESSAGE: STARBASE IN QUADRANT {},{} IS UNDER ATTACK", quadrant.x, quadrant.y),
+                format!("   IT CAN HOLD OUT FOR {} STARDATES", *stardates_left as i32),
+            ],
+            OutputEvent::StarbaseRelieved { quadrant } => vec![format!(
+                "SUBSPACE MESSAGE: STARBASE IN QUADRANT {},{} REPORTS THE ATTACK BROKEN",
+                quadrant.x, quadrant.y
+            )],
+            OutputEvent::StarbaseOverrun { quadrant } => vec![format!(
+                "SUBSPACE MESSAGE: STARBASE IN QUADRANT {},{} HAS BEEN DESTROYED",
+                quadrant.x, quadrant.y
+            )],
+            OutputEvent::ProbeReport { quadrant, encoded } => {
+                vec![format!("PROBE REPORT FROM QUADRANT {},{}: {:03}", quadrant.x, quadrant.y, encoded)]
+            }
+            OutputEvent::ProbeLeftGalaxy => vec!["PROBE HAS LEFT THE GALAXY".to_string()],
+            OutputEvent::ShipAbandoned { energy } => vec![
+                "THE ENTERPRISE HAS BEEN DESTROYED. ABANDON SHIP!".to_string(),
+                format!("THE CREW ESCAPES IN THE SHUTTLECRAFT WITH {} UNITS OF ENERGY", *energy as i32),
+                "STARFLEET WILL ISSUE A NEW SHIP AT ANY STARBASE".to_string(),
+            ],
+            OutputEvent::StarbaseShieldsProtect => vec!["STAR BASE SHIELDS PROTECT THE ENTERPRISE".to_string()],
+            OutputEvent::TorpedoFired => vec!["TORPEDO TRACK:".to_string()],
+            OutputEvent::TorpedoTrack { x, y } => vec![format!("{},{}", x, y)],
+            OutputEvent::TorpedoMissed => vec!["TORPEDO MISSED".to_string()],
+            OutputEvent::SplashHit { at, damage, shields_left } => vec![
+                highlight(
+                    &format!("{} UNIT BLAST HIT ON KLINGON AT SECTOR {},{}", *damage as i32, at.x, at.y),
+                    Highlight::EnemyHit,
+                    color,
+                ),
+                format!("   ({} LEFT)", shields_left.max(0.0) as i32),
+            ],
+            OutputEvent::HitOnKlingon { at, damage, shields_left } => {
+                Self::enemy_hit("KLINGON", *at, *damage, *shields_left, color)
+            }
+            OutputEvent::HitOnRomulan { at, damage, shields_left } => {
+                Self::enemy_hit("ROMULAN", *at, *damage, *shields_left, color)
+            }
+            OutputEvent::KlingonDestroyed => vec!["*** KLINGON DESTROYED ***".to_string()],
+            OutputEvent::RomulanDestroyed => vec!["*** ROMULAN WARBIRD DESTROYED ***".to_string()],
+            OutputEvent::ShortRangeScan(readout) => Self::short_range_scan(readout, color),
+            OutputEvent::LongRangeScan { quadrant, grid } => {
+                let cells: Vec<Vec<String>> = grid
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|cell| match cell {
+                                Some(encoded) => format!("{:03}", encoded),
+                                None => "xxx".to_string(),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let mut lines = vec![format!("LONG RANGE SENSOR SCAN FOR QUADRANT {},{}", quadrant.x, quadrant.y)];
+                lines.extend(grid_lines(&cells));
+                lines
+            }
+            OutputEvent::QuadrantEntered { .. } => Vec::new(),
+            OutputEvent::DeviceDamaged { device, .. } => vec![
+                String::new(),
+                format!("DAMAGE CONTROL REPORT: {} DAMAGED", device.name()),
+                String::new(),
+            ],
+            OutputEvent::DeviceRepaired { device, .. } => vec![
+                String::new(),
+                format!("DAMAGE CONTROL REPORT: {} STATE OF REPAIR IMPROVED", device.name()),
+                String::new(),
+            ],
+            OutputEvent::Message(line) | OutputEvent::Text(line) => vec![line.clone()],
+        }
+    }
+
+    fn enemy_hit(enemy: &str, at: SectorPosition, damage: f64, shields_left: f64, color: bool) -> Vec<String> {
+        let line = format!("{} UNIT HIT ON {} AT SECTOR {},{}", damage as i32, enemy, at.x, at.y);
+        vec![
+            highlight(&line, Highlight::EnemyHit, color),
+            format!("   ({} LEFT)", shields_left.max(0.0) as i32),
+        ]
+    }
+
+    /// The sector grid between borders, with the status read out down the
+    /// right-hand side (spec 6.1).
+    fn short_range_scan(readout: &SensorReadout, color: bool) -> Vec<String> {
+        let symbols = readout.symbols;
+        let border = match symbols.cell_width() {
+            3 => "-=-".repeat(SECTOR_SIZE),
+            width => "=".repeat(SECTOR_SIZE * width),
+        };
+        let condition = readout.condition;
+        let mut energy = format!("ENERGY    {}", readout.energy as i32);
+        if let Some(heat) = readout.phaser_heat {
+            energy = format!("{}  PHASER HEAT {}%", energy, heat);
+        }
+        let status = [
+            format!("STARDATE  {}", readout.stardate as i32),
+            format!("CONDITION {}", highlight(condition.label(), Highlight::Condition(condition), color)),
+            format!("QUADRANT  {},{}", readout.quadrant.x, readout.quadrant.y),
+            format!("SECTOR    {},{}", readout.sector.x, readout.sector.y),
+            energy,
+            format!("SHIELDS   {}", readout.shields as i32),
+            format!("PHOTON TORPEDOES {}", readout.torpedoes),
+            format!("HULL      {}%  CREW {}", readout.hull.ceil() as i32, readout.crew),
+        ];
+
+        let mut lines = vec![border.clone()];
+        for (row, status) in readout.rows.iter().zip(status) {
+            let row: String = row
+                .iter()
+                .map(|&content| match content {
+                    SectorContent::Klingon | SectorContent::KlingonCommander => {
+                        highlight(content.symbol_in(symbols), Highlight::Klingon, color)
+                    }
+                    _ => content.symbol_in(symbols).to_string(),
+                })
+                .collect();
+            lines.push(format!("{}        {}", row, status));
+        }
+        lines.push(border);
+        lines
+    }
+
+    /// Show queued events in order.
+    pub fn render(events: &[OutputEvent], output: &mut dyn OutputWriter) {
+        for event in events {
+            output.emit(event.clone());
+        }
+    }
+}
+
+pub struct CombatPresenter;
+
+impl CombatPresenter {
+    pub fn show_combat_odds(odds: &CombatOdds, output: &mut dyn OutputWriter) {
+        output.writeln("   COMBAT ODDS");
         output.writeln("");
-        output.writeln(&format!("YOUR EFFICIENCY RATING = {}", rating));
+        if odds.klingons == 0 {
+            output.writeln(odds.recommendation().label());
+            return;
+        }
+        let exchanges = |n: Option<f64>| n.map_or("-".to_string(), |n| format!("{:.1}", n));
+        output.writeln(&format!("KLINGONS IN QUADRANT     = {}", odds.klingons));
+        output.writeln(&format!("KLINGON SHIELDS (TOTAL)  = {}", odds.klingon_shields as i32));
+        if odds.protected {
+            output.writeln("STAR BASE SHIELDS PROTECT THE ENTERPRISE");
+        }
+        output.writeln(&format!(
+            "DAMAGE PER ROUND (MEAN)  = {} (UP TO {})",
+            odds.expected_damage as i32, odds.worst_case_damage as i32
+        ));
+        output.writeln(&format!(
+            "ROUNDS TO SHIELD FAILURE = {}",
+            exchanges(odds.exchanges_to_shield_failure)
+        ));
+        output.writeln(&format!(
+            "ROUNDS TO DESTRUCTION    = {}",
+            exchanges(odds.exchanges_to_destruction)
+        ));
+        output.writeln(&format!(
+            "PHASER ENERGY TO WIN     = {} (YOU HAVE {})",
+            odds.phaser_energy_needed.ceil() as i32, odds.available_energy as i32
+        ));
+        output.writeln(&format!("RECOMMENDATION: {}", odds.recommendation().label()));
     }
 
-    pub fn show_defeat(reason: &str, output: &mut dyn OutputWriter) {
+    /// End screen for a finished game (spec section 10).
+    pub fn show_epilogue(report: &GameOverReport, output: &mut dyn OutputWriter) {
         output.writeln("");
-        output.writeln(&format!("*** {}", reason));
-        output.writeln("THE FEDERATION WILL BE CONQUERED");
+        match report.rating {
+            Some(rating) => {
+                output.writeln("THE LAST KLINGON BATTLE CRUISER IN THE GALAXY HAS BEEN DESTROYED");
+                output.writeln("THE FEDERATION HAS BEEN SAVED !!!");
+                output.writeln("");
+                output.writeln(&format!("YOUR EFFICIENCY RATING = {}", rating));
+                if let Some(rank) = report.rank {
+                    output.writeln(&format!("YOU ARE PROMOTED TO THE RANK OF {}", rank.label()));
+                }
+            }
+            None => {
+                output.writeln(&format!("*** {}", report.outcome_label()));
+                if matches!(report.state, GameState::Defeat { reason: DefeatReason::CourtMartial }) {
+                    output.writeln("YOU ARE RELIEVED OF COMMAND FOR DESTROYING FEDERATION STARBASES");
+                } else {
+                    output.writeln("THE FEDERATION WILL BE CONQUERED");
+                }
+                output.writeln("");
+                output.writeln(&format!("IT IS STARDATE {}", report.stardate as i32));
+                output.writeln(&format!(
+                    "THERE WERE {} KLINGON BATTLE CRUISERS LEFT AT THE END OF YOUR MISSION",
+                    report.klingons_remaining
+                ));
+            }
+        }
+        output.writeln(&format!(
+            "{} STARDATES AND {} COMMANDS USED",
+            report.stardates_used as i32, report.turns
+        ));
+    }
+}
+
+pub struct HighScorePresenter;
+
+impl HighScorePresenter {
+    pub fn show_high_scores(table: &HighScoreTable, output: &mut dyn OutputWriter) {
+        output.writeln("                    HIGH SCORES");
         output.writeln("");
+        if table.entries().is_empty() {
+            output.writeln("NO VICTORIES RECORDED YET");
+            return;
+        }
+        output.writeln(" #  NAME             RATING  RANK        TURNS  DATE        SEED");
+        for (place, entry) in table.entries().iter().enumerate() {
+            let name: String = entry.name.chars().take(16).collect();
+            output.writeln(&format!(
+                "{:>2}  {:<16} {:>6}  {:<10} {:>6}  {:<10}  {}",
+                place + 1,
+                name,
+                entry.rating,
+                entry.rank.label(),
+                entry.turns,
+                entry.date,
+                entry.seed
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::testing::MockOutput;
+    use crate::io::{BufferedOutput, EventQueue};
+
+    #[test]
+    fn long_range_scan_grid_scales_with_width() {
+        let q = QuadrantPosition { x: 1, y: 1 };
+        let mut output = MockOutput::new();
+        let grid = vec![
+            vec![None, None, None],
+            vec![None, Some(5), Some(112)],
+            vec![None, Some(7), Some(0)],
+        ];
+        output.emit(OutputEvent::LongRangeScan { quadrant: q, grid });
+        assert_eq!(
+            output.messages.concat(),
+            concat!(
+                "LONG RANGE SENSOR SCAN FOR QUADRANT 1,1\n",
+                "-------------------\n",
+                "| xxx | xxx | xxx |\n",
+                "-------------------\n",
+                "| xxx | 005 | 112 |\n",
+                "-------------------\n",
+                "| xxx | 007 | 000 |\n",
+                "-------------------\n",
+            )
+        );
+
+        let mut output = MockOutput::new();
+        output.emit(OutputEvent::LongRangeScan { quadrant: q, grid: vec![vec![Some(1); 5]; 5] });
+        assert_eq!(output.messages.len(), 12);
+        assert_eq!(output.messages[1], format!("{}\n", "-".repeat(31)));
+        assert_eq!(output.messages[2], "| 001 | 001 | 001 | 001 | 001 |\n");
+    }
+
+    #[test]
+    fn commander_codes_widen_the_scan_grid() {
+        let mut output = MockOutput::new();
+        let grid = vec![vec![Some(5), Some(1217)], vec![None, Some(0)]];
+        output.emit(OutputEvent::LongRangeScan { quadrant: QuadrantPosition { x: 1, y: 1 }, grid });
+        assert_eq!(output.messages[1], format!("{}\n", "-".repeat(15)));
+        assert_eq!(output.messages[2], "|  005 | 1217 |\n");
+        assert_eq!(output.messages[4], "|  xxx |  000 |\n");
+    }
+
+    #[test]
+    fn galaxy_reveal_marks_visited_and_final_quadrants() {
+        let mut galaxy = Galaxy::new(42);
+        let start = galaxy.enterprise().quadrant();
+        let next = QuadrantPosition { x: start.x % 8 + 1, y: start.y };
+        let sector = galaxy.enterprise().sector();
+        galaxy.enterprise_mut().move_to(next, sector);
+        galaxy.enter_quadrant();
+
+        let mut output = MockOutput::new();
+        MapPresenter::show_galaxy_reveal(&galaxy, &mut output);
+        assert_eq!(output.messages.len(), 20);
+        assert_eq!(output.messages[19], "QUADRANTS EXPLORED: 2 OF 64 (3%)\n");
+        assert_eq!(output.messages[1], format!("{}\n", "-".repeat(57)));
+
+        let row = &output.messages[start.y as usize * 2];
+        let cells: Vec<&str> = row.trim_end().trim_matches('|').split(" | ").collect();
+        let cell = |x: i32| cells[(x - 1) as usize].trim_start();
+        let encoded = |x: i32| galaxy.quadrants()[QuadrantPosition { x, y: start.y }].encoded();
+        assert_eq!(cell(start.x), format!(".{:03}", encoded(start.x)));
+        assert_eq!(cell(next.x), format!("E{:03}", encoded(next.x)));
+    }
+
+    fn briefing(galaxy: &Galaxy, mode: CompatMode) -> String {
+        let mut output = MockOutput::new();
+        BriefingPresenter::show_mission_briefing(galaxy, mode, &mut output);
+        output.messages.concat()
+    }
+
+    #[test]
+    fn modern_briefing_snapshot() {
+        assert_eq!(
+            briefing(&Galaxy::new(42), CompatMode::Modern),
+            "YOU MUST DESTROY 16 KLINGONS IN 30 STARDATES WITH 4 STARBASES\n"
+        );
+    }
+
+    #[test]
+    fn classic_briefing_snapshot() {
+        assert_eq!(
+            briefing(&Galaxy::new(42), CompatMode::Classic),
+            "YOUR ORDERS ARE AS FOLLOWS:\n\
+             \x20  DESTROY THE 16 KLINGON WARSHIPS WHICH HAVE INVADED\n\
+             \x20THE GALAXY BEFORE THEY CAN ATTACK FEDERATION HEADQUARTERS\n\
+             \x20ON STARDATE 3030.  THIS GIVES YOU 30 DAYS.  THERE ARE\n\
+             \x204 STARBASES IN THE GALAXY FOR RESUPPLYING YOUR SHIP.\n\
+             \x20EVERY WARP AND EVERY BATTLE DEPLETES YOUR ENERGY, AS IT\n\
+             \x20DEPLETES THE TIME LEFT TO SAVE THE FEDERATION.\n\
+             \x20TO DOCK, MANEUVER INTO A SECTOR ADJACENT TO A STARBASE;\n\
+             \x20THE LIBRARY COMPUTER'S GALACTIC RECORD SHOWS WHERE THE\n\
+             \x20STARBASES YOU HAVE SCANNED LIE, SO PLOT YOUR COURSE THERE.\n"
+        );
+    }
+
+    #[test]
+    fn briefings_agree_on_single_starbase() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.set_total_starbases(1);
+        assert!(briefing(&galaxy, CompatMode::Modern).ends_with("WITH 1 STARBASE\n"));
+        assert!(briefing(&galaxy, CompatMode::Classic)
+            .contains("THERE IS\n 1 STARBASE IN THE GALAXY"));
+    }
+
+    #[test]
+    fn briefing_stamps_scaled_mission_duration() {
+        use crate::models::galaxy::GalaxyOptions;
+        use crate::models::ruleset::Ruleset;
+
+        let ruleset = Ruleset { mission_duration: MissionDuration::SCALED, ..Default::default() };
+        let galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        for mode in [CompatMode::Modern, CompatMode::Classic] {
+            assert!(briefing(&galaxy, mode)
+                .ends_with("(MISSION TIME RULE: MAX(KLINGONS + 1, 30) STARDATES)\n"));
+        }
+        let ruleset = Ruleset { difficulty: Difficulty::Admiral, ..Default::default() };
+        let galaxy = Galaxy::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+        assert!(briefing(&galaxy, CompatMode::Modern).ends_with("(DIFFICULTY: ADMIRAL)\n"));
+        assert!(!briefing(&Galaxy::new(42), CompatMode::Modern).contains("DIFFICULTY"));
+    }
+
+    #[test]
+    fn defeat_epilogue_reports_what_was_left() {
+        use crate::game_engine::{DefeatReason, GameState};
+
+        let report = GameOverReport {
+            state: GameState::Defeat { reason: DefeatReason::TimeExpired },
+            rating: None,
+            rank: None,
+            klingons_remaining: 4,
+            klingons_initial: 16,
+            stardate: 3031.5,
+            stardates_used: 31.5,
+            starbases_remaining: 2,
+            starbases_lost: 2,
+            hull: 60.0,
+            torpedoes_used: 6,
+            casualties: 172,
+            turns: 88,
+        };
+        let mut output = MockOutput::new();
+        CombatPresenter::show_epilogue(&report, &mut output);
+        assert_eq!(
+            output.messages.concat(),
+            "\n*** TIME EXPIRED\n\
+             THE FEDERATION WILL BE CONQUERED\n\n\
+             IT IS STARDATE 3031\n\
+             THERE WERE 4 KLINGON BATTLE CRUISERS LEFT AT THE END OF YOUR MISSION\n\
+             31 STARDATES AND 88 COMMANDS USED\n"
+        );
+    }
+
+    #[test]
+    fn high_score_table_lists_places() {
+        use crate::services::scoring::{HighScore, Rank};
+
+        let mut output = MockOutput::new();
+        let mut table = HighScoreTable::default();
+        HighScorePresenter::show_high_scores(&table, &mut output);
+        assert_eq!(output.messages.last().unwrap(), "NO VICTORIES RECORDED YET\n");
+
+        table.insert(HighScore {
+            name: "KIRK".to_string(),
+            seed: 42,
+            rating: 812,
+            rank: Rank::Captain,
+            date: "2026-10-16".to_string(),
+            turns: 88,
+        });
+        let mut output = MockOutput::new();
+        HighScorePresenter::show_high_scores(&table, &mut output);
+        assert_eq!(
+            output.messages[3],
+            " 1  KIRK                812  CAPTAIN        88  2026-10-16  42\n"
+        );
+    }
+
+    #[test]
+    fn damage_report_estimates_repair_times() {
+        let mut galaxy = Galaxy::new(42);
+        galaxy.enterprise_mut().damage_device(Device::PhotonTubes, 2.0);
+        let mut output = MockOutput::new();
+        EnterprisePresenter::show_damage_report(&galaxy, &mut output);
+        assert_eq!(output.messages[0], "DEVICE        STATE OF REPAIR REPAIR TIME\n");
+        assert_eq!(output.expect_line_containing("PHOTON TUBES"), "PHOTON TUBES  -2              2.0");
+        assert_eq!(output.expect_line_containing("WARP ENGINES"), "WARP ENGINES  0");
+        assert!(!output.contains("STARBASE CREWS"));
+    }
+
+    #[test]
+    fn galaxy_map_marks_the_enterprise_and_regions() {
+        let record = |encoded, stale| Some(RecordCell { encoded, stale });
+        let mut cells: QuadrantGrid<Option<RecordCell>> = QuadrantGrid::default();
+        cells[QuadrantPosition { x: 5, y: 2 }] = record(103, true);
+        cells[QuadrantPosition { x: 6, y: 2 }] = record(4, false);
+        cells[QuadrantPosition { x: 7, y: 2 }] = record(210, true);
+        let mut output = MockOutput::new();
+        GalaxyPresenter::show_galaxy_map(QuadrantPosition { x: 5, y: 2 }, &cells, &mut output);
+
+        assert_eq!(output.messages[0], "GALAXY MAP FOR QUADRANT 5,2\n");
+        assert_eq!(output.messages[1], "      1    2    3    4     5    6    7    8\n");
+        assert_eq!(output.messages[2], "   +--------------------+--------------------+\n");
+        assert_eq!(
+            output.messages[4],
+            " 2 | ???  ???  ???  ??? |[103] 004 (210) ??? | RIGEL / DENEB\n"
+        );
+        assert_eq!(output.messages.len(), 13);
+    }
+
+    #[test]
+    fn emit_queues_events_or_writes_their_lines() {
+        let hit = OutputEvent::KlingonHit {
+            from: SectorPosition { x: 2, y: 7 },
+            damage: 150.6,
+            shields_left: -20.0,
+            hull: Some(81.2),
+        };
+        let mut queue = EventQueue::default();
+        queue.emit(hit.clone());
+        assert_eq!(queue.drain(), vec![hit.clone()]);
+
+        let mut text = BufferedOutput::default();
+        text.emit(hit.clone());
+        text.emit(OutputEvent::Text("> ".to_string()));
+        assert_eq!(
+            text.take(),
+            "150 UNIT HIT ON ENTERPRISE FROM SECTOR 2,7\n   (0 LEFT)\n   HULL INTEGRITY 82%\n> "
+        );
+
+        // Only the hit itself is highlighted for a writer with color
+        let mut colored = MockOutput::with_color();
+        colored.emit(hit);
+        assert_eq!(colored.messages[0], "\x1b[1;31m150 UNIT HIT ON ENTERPRISE FROM SECTOR 2,7\x1b[0m\n");
+        assert_eq!(colored.messages[1], "   (0 LEFT)\n");
     }
 }
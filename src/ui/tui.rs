@@ -0,0 +1,334 @@
+//! Full-screen terminal frontend
+//!
+//! With the `tui` feature, `--tui` plays the game on a persistent screen:
+//! the short-range scan, a status panel, and the damage report stay drawn
+//! while command output scrolls in a log underneath. Commands run through
+//! [`GameEngine::execute`] and their events are rendered by
+//! [`EventPresenter`], so the log reads the same as the teletype.
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::game_engine::GameEngine;
+use crate::io::{self as game_io, BufferedOutput, EventQueue, InputReader, OutputWriter, RecordingReader};
+use crate::models::compat::CompatMode;
+use crate::models::constants::{Device, SECTOR_SIZE};
+use crate::models::errors::GameResult;
+use crate::models::galaxy::Galaxy;
+use crate::models::output_event::OutputEvent;
+use crate::services::battle_log::BattleLog;
+use crate::services::combat;
+use crate::services::commands::{CommandId, CommandSpec, COMMANDS};
+use crate::ui::presenters::{BriefingPresenter, CombatPresenter, EnterprisePresenter, EventPresenter};
+
+/// Log lines kept for scrolling back.
+const LOG_CAPACITY: usize = 500;
+
+/// The command a key runs: a command's own key from the registry
+/// (0-9, D, R, T, Q), or `+` and `-` for the shield shortcuts.
+pub fn shortcut(key: char) -> Option<&'static CommandSpec> {
+    let id = match key {
+        '+' => CommandId::ShieldsUp,
+        '-' => CommandId::ShieldsDown,
+        key => {
+            return COMMANDS
+                .iter()
+                .find(|spec| spec.key.len() == 1 && spec.key.eq_ignore_ascii_case(&key.to_string()))
+        }
+    };
+    COMMANDS.iter().find(|spec| spec.id == id)
+}
+
+/// The sector grid, without the status column the teletype scan adds.
+pub fn scan_lines(galaxy: &Galaxy) -> Vec<String> {
+    if galaxy.enterprise().is_damaged(Device::ShortRangeSensors) {
+        return vec!["*** SHORT RANGE SENSORS ARE OUT ***".to_string()];
+    }
+    let show_mines = galaxy.enterprise().sensors_repaired();
+    (1..=SECTOR_SIZE as i32).map(|y| galaxy.sector_map().render_row(y, show_mines, galaxy.symbols())).collect()
+}
+
+/// Ship and mission status for the side panel.
+pub fn status_lines(galaxy: &Galaxy) -> Vec<String> {
+    let e = galaxy.enterprise();
+    let stardates_left = galaxy.starting_stardate() + galaxy.mission_duration() - galaxy.stardate();
+    let mut lines = vec![
+        format!("STARDATE  {}", galaxy.stardate() as i32),
+        format!("CONDITION {}", galaxy.evaluate_condition().label()),
+        format!("QUADRANT  {},{}", e.quadrant().x, e.quadrant().y),
+        format!("SECTOR    {},{}", e.sector().x, e.sector().y),
+        format!("ENERGY    {}", e.energy() as i32),
+        format!("SHIELDS   {}", e.shields() as i32),
+        format!("PHOTON TORPEDOES {}", e.torpedoes()),
+        format!("HULL      {}%", e.hull().ceil() as i32),
+        format!("CREW      {}", e.crew()),
+        format!("KLINGONS  {}", galaxy.total_klingons()),
+        format!("TIME LEFT {:.1}", stardates_left),
+    ];
+    if galaxy.ruleset().phaser_heat {
+        lines.push(format!("PHASER HEAT {}%", e.phaser_heat_percent()));
+    }
+    lines
+}
+
+/// The damage control report, as the DAMAGE REPORT command prints it.
+pub fn damage_lines(galaxy: &Galaxy) -> Vec<String> {
+    let mut output = BufferedOutput::default();
+    EnterprisePresenter::show_damage_report(galaxy, &mut output);
+    output.take().lines().map(str::to_string).collect()
+}
+
+/// Help line listing the key bindings.
+fn key_help() -> String {
+    let keys: Vec<String> = COMMANDS
+        .iter()
+        .filter(|spec| spec.key.len() == 1)
+        .map(|spec| format!("{}={}", spec.key, spec.name))
+        .collect();
+    format!("{}  +=SHIELDS UP  -=SHIELDS DOWN", keys.join("  "))
+}
+
+/// Panels drawn from the galaxy before each command. Prompts during a
+/// command redraw these as they were, since the engine is busy.
+#[derive(Default)]
+struct Panels {
+    scan: Vec<String>,
+    status: Vec<String>,
+    damage: Vec<String>,
+}
+
+impl Panels {
+    fn from_galaxy(galaxy: &Galaxy) -> Self {
+        Panels {
+            scan: scan_lines(galaxy),
+            status: status_lines(galaxy),
+            damage: damage_lines(galaxy),
+        }
+    }
+}
+
+/// The terminal screen, which also answers the commands' prompts.
+struct Screen {
+    terminal: DefaultTerminal,
+    panels: Panels,
+    log: Vec<String>,
+    /// Prompt and the answer typed so far, while a command is waiting.
+    entry: Option<(String, String)>,
+}
+
+impl Screen {
+    fn log_events(&mut self, events: Vec<OutputEvent>) {
+        for event in events {
+            self.log.extend(EventPresenter::lines(&event, false).iter().map(|line| line.trim_end().to_string()));
+        }
+        let excess = self.log.len().saturating_sub(LOG_CAPACITY);
+        self.log.drain(..excess);
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let Screen { terminal, panels, log, entry } = self;
+        terminal.draw(|frame| render(frame, panels, log, entry.as_ref()))?;
+        Ok(())
+    }
+
+    /// Wait for a key press, ignoring releases and other events.
+    fn next_key(&mut self) -> io::Result<(KeyCode, KeyModifiers)> {
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok((key.code, key.modifiers));
+                }
+            }
+        }
+    }
+}
+
+impl InputReader for Screen {
+    /// Edit an answer on the bottom line. Escape cancels with an empty
+    /// answer, which every prompt treats as no or zero.
+    fn read_line(&mut self, prompt: &str) -> Result<String, io::Error> {
+        let mut answer = String::new();
+        let result = loop {
+            self.entry = Some((prompt.to_string(), answer.clone()));
+            if let Err(e) = self.draw() {
+                break Err(e);
+            }
+            match self.next_key() {
+                Ok((KeyCode::Enter, _)) => break Ok(()),
+                Ok((KeyCode::Esc, _)) => {
+                    answer.clear();
+                    break Ok(());
+                }
+                Ok((KeyCode::Char('c'), KeyModifiers::CONTROL)) => {
+                    break Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"))
+                }
+                Ok((KeyCode::Backspace, _)) => {
+                    answer.pop();
+                }
+                Ok((KeyCode::Char(c), _)) => answer.push(c),
+                Ok(_) => {}
+                Err(e) => break Err(e),
+            }
+        };
+        // The bottom line goes back to the key help whatever happened
+        self.entry = None;
+        result?;
+        self.log.push(format!("{} {}", prompt, answer));
+        Ok(answer)
+    }
+}
+
+fn render(frame: &mut Frame, panels: &Panels, log: &[String], entry: Option<&(String, String)>) {
+    let [top, log_area, bottom] = Layout::vertical([
+        Constraint::Length(SECTOR_SIZE as u16 + 4),
+        Constraint::Min(3),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [scan_area, status_area, damage_area] =
+        Layout::horizontal([Constraint::Length(28), Constraint::Length(26), Constraint::Min(24)]).areas(top);
+
+    let panel = |lines: &[String], title: &'static str| {
+        Paragraph::new(lines.join("\n")).block(Block::default().borders(Borders::ALL).title(title))
+    };
+    frame.render_widget(panel(&panels.scan, " SHORT RANGE SCAN "), scan_area);
+    frame.render_widget(panel(&panels.status, " STATUS "), status_area);
+    frame.render_widget(panel(&panels.damage, " DAMAGE CONTROL "), damage_area);
+
+    // Newest lines at the bottom of the log
+    let visible = log_area.height.saturating_sub(2) as usize;
+    let shown = &log[log.len().saturating_sub(visible)..];
+    frame.render_widget(panel(shown, " LOG "), log_area);
+
+    let line = match entry {
+        Some((prompt, answer)) => format!("{} {}_", prompt, answer),
+        None => key_help(),
+    };
+    frame.render_widget(Paragraph::new(line).block(Block::default().borders(Borders::ALL)), bottom);
+}
+
+/// What a finished full-screen game leaves behind for the frontend's
+/// post-game steps, as [`Game`](crate::services::game::Game) keeps it for
+/// the teletype.
+pub struct Played {
+    /// Every line of input, with each command key recorded as the command
+    /// line the teletype reads, so the game replays without the screen.
+    pub transcript: Vec<String>,
+    /// Digest of the transcript, for score validation.
+    pub replay_hash: u64,
+    pub battle_log: BattleLog,
+}
+
+/// Play a game on the full screen until it ends or the player quits.
+/// The terminal is restored before returning, even on error.
+pub fn run(engine: &mut GameEngine) -> GameResult<Played> {
+    let screen = Screen {
+        terminal: ratatui::init(),
+        panels: Panels::default(),
+        log: Vec::new(),
+        entry: None,
+    };
+    let mut input = RecordingReader::new(screen);
+    let mut battle_log = BattleLog::default();
+    let result = play(engine, &mut input, &mut battle_log);
+    ratatui::restore();
+    result?;
+    Ok(Played {
+        transcript: input.transcript().to_vec(),
+        replay_hash: input.digest(),
+        battle_log,
+    })
+}
+
+fn play(engine: &mut GameEngine, input: &mut RecordingReader<Screen>, battle_log: &mut BattleLog) -> GameResult<()> {
+    let mut queue = EventQueue::default();
+    combat::red_alert(engine.galaxy(), &mut queue);
+    BriefingPresenter::show_mission_briefing(engine.galaxy(), CompatMode::Modern, &mut queue);
+    if engine.galaxy_mut().check_docking() {
+        queue.writeln("SHIELDS DROPPED FOR DOCKING PURPOSES");
+    }
+    combat::auto_shields(engine.galaxy_mut(), &mut queue);
+    input.inner_mut().log_events(queue.drain());
+    *battle_log = BattleLog::begin(engine.galaxy());
+
+    loop {
+        let screen = input.inner_mut();
+        screen.panels = Panels::from_galaxy(engine.galaxy());
+        screen.draw()?;
+        let spec = match screen.next_key()? {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Esc, _) => return Ok(()),
+            (KeyCode::Char(key), _) => match shortcut(key) {
+                Some(spec) => spec,
+                None => continue,
+            },
+            _ => continue,
+        };
+        input.record(spec.key);
+        if let Err(message) = spec.availability(engine.galaxy()) {
+            input.inner_mut().log.push(message);
+            continue;
+        }
+        if spec.id == CommandId::Quit {
+            if game_io::confirm_with(input, "ABANDON THE MISSION (Y/N)?", CompatMode::Modern)? {
+                return Ok(());
+            }
+            continue;
+        }
+
+        let start = engine.begin_turn();
+        input.inner_mut().log.push(format!("> {}", spec.name));
+        if let Err(e) = engine.execute(spec.id, input) {
+            input.inner_mut().log.push(format!("Error: {}", e));
+        }
+        input.inner_mut().log_events(engine.take_events());
+        let summary = engine.finish_turn(&start, spec.name, Some(input), &mut queue)?;
+        battle_log.record_turn(&summary, engine.galaxy());
+
+        let game_over = engine.game_over_report().is_some();
+        let screen = input.inner_mut();
+        screen.log_events(queue.drain());
+        screen.log_events(engine.take_events());
+        if game_over {
+            if let Some(report) = engine.game_over_report() {
+                battle_log.finish(&report, engine.galaxy());
+                CombatPresenter::show_epilogue(&report, &mut queue);
+            }
+            screen.log_events(queue.drain());
+            screen.log.push("PRESS ANY KEY TO EXIT".to_string());
+            screen.panels = Panels::from_galaxy(engine.galaxy());
+            screen.draw()?;
+            screen.next_key()?;
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_follow_the_command_registry() {
+        assert_eq!(shortcut('0').map(|s| s.id), Some(CommandId::SetCourse));
+        assert_eq!(shortcut('q').map(|s| s.id), Some(CommandId::Quit));
+        assert_eq!(shortcut('+').map(|s| s.id), Some(CommandId::ShieldsUp));
+        assert_eq!(shortcut('-').map(|s| s.id), Some(CommandId::ShieldsDown));
+        assert!(shortcut('x').is_none());
+    }
+
+    #[test]
+    fn panels_show_the_scan_status_and_damage() {
+        let mut galaxy = Galaxy::new(42);
+        assert_eq!(scan_lines(&galaxy).len(), SECTOR_SIZE);
+        assert!(scan_lines(&galaxy).concat().contains("<*>"));
+        assert_eq!(status_lines(&galaxy)[0], format!("STARDATE  {}", galaxy.stardate() as i32));
+        assert_eq!(damage_lines(&galaxy).len(), galaxy.ruleset().devices().len() + 1);
+
+        galaxy.enterprise_mut().damage_device(Device::ShortRangeSensors, 1.0);
+        assert_eq!(scan_lines(&galaxy), vec!["*** SHORT RANGE SENSORS ARE OUT ***"]);
+    }
+}
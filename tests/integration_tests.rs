@@ -69,12 +69,26 @@ fn victory_condition_detected() {
     ), "Should detect victory when no Klingons remain");
 }
 
+#[test]
+fn built_galaxy_without_klingons_is_already_won() {
+    use startrek::models::galaxy::GalaxyBuilder;
+    use startrek::models::position::QuadrantPosition;
+
+    let galaxy = GalaxyBuilder::new(42).starbase(QuadrantPosition { x: 4, y: 4 }).build().unwrap();
+    let mut engine = GameEngine::from_galaxy(galaxy);
+
+    assert!(matches!(
+        engine.check_game_over(),
+        Some(GameState::Victory { .. })
+    ), "A galaxy built without Klingons should be won at once");
+}
+
 #[test]
 fn ship_destroyed_defeat_detected() {
     let mut engine = GameEngine::new(42);
 
-    // Manually set shields below 0 to simulate destruction
-    engine.galaxy_mut().enterprise_mut().set_shields(-1.0);
+    // Manually breach the hull to simulate destruction
+    engine.galaxy_mut().enterprise_mut().set_hull(0.0);
 
     // Check game over
     let state = engine.check_game_over();
@@ -84,7 +98,57 @@ fn ship_destroyed_defeat_detected() {
         Some(GameState::Defeat {
             reason: DefeatReason::ShipDestroyed
         })
-    ), "Should detect defeat when shields < 0");
+    ), "Should detect defeat when the hull is breached");
+}
+
+#[test]
+fn shuttlecraft_escapes_a_destroyed_ship_once() {
+    use startrek::models::galaxy::GalaxyOptions;
+    use startrek::models::output_event::OutputEvent;
+    use startrek::models::ruleset::Ruleset;
+
+    let ruleset = Ruleset { shuttlecraft: true, ..Default::default() };
+    let mut engine = GameEngine::with_options(42, GalaxyOptions { ruleset, ..Default::default() });
+    engine.galaxy_mut().enterprise_mut().set_hull(0.0);
+    assert_eq!(engine.check_game_over(), None);
+    assert_eq!(engine.take_events(), vec![OutputEvent::ShipAbandoned { energy: 300.0 }]);
+    let enterprise = engine.galaxy().enterprise();
+    assert!(enterprise.in_shuttlecraft());
+    assert_eq!(enterprise.torpedoes(), 0);
+
+    // There is no escaping the shuttlecraft
+    engine.galaxy_mut().enterprise_mut().set_hull(0.0);
+    assert_eq!(engine.check_game_over(), Some(GameState::Defeat { reason: DefeatReason::ShipDestroyed }));
+    assert!(engine.take_events().is_empty());
+}
+
+#[test]
+fn destroying_starbases_ends_in_court_martial() {
+    use startrek::models::constants::SectorContent;
+    use startrek::models::galaxy::GalaxyBuilder;
+    use startrek::models::position::QuadrantPosition;
+
+    let mut engine = GameEngine::new(42);
+    let (beside, _) = engine.galaxy().sector_map().iter().find(|&(_, c)| c == SectorContent::Empty).unwrap();
+
+    engine.galaxy_mut().destroy_starbase_by_torpedo(beside);
+    assert!(engine.galaxy().total_starbases() > 0);
+    assert_eq!(engine.check_game_over(), None);
+
+    engine.galaxy_mut().destroy_starbase_by_torpedo(beside);
+    assert_eq!(engine.check_game_over(), Some(GameState::Defeat { reason: DefeatReason::CourtMartial }));
+    assert_eq!(engine.game_over_report().unwrap().outcome_label(), "COURT MARTIAL");
+
+    // Destroying the last starbase is enough on its own
+    let galaxy = GalaxyBuilder::new(42)
+        .starbase(QuadrantPosition { x: 4, y: 4 })
+        .klingons(QuadrantPosition { x: 8, y: 8 }, 1)
+        .build()
+        .unwrap();
+    let mut engine = GameEngine::from_galaxy(galaxy);
+    let starbase = engine.galaxy().sector_map().starbase.unwrap();
+    engine.galaxy_mut().destroy_starbase_by_torpedo(starbase);
+    assert_eq!(engine.check_game_over(), Some(GameState::Defeat { reason: DefeatReason::CourtMartial }));
 }
 
 #[test]
@@ -113,6 +177,79 @@ fn time_expired_defeat_detected() {
     ), "Should detect defeat when time expires");
 }
 
+#[test]
+fn surviving_dead_in_space_ends_the_game() {
+    use startrek::io::PendingInput;
+    use startrek::services::commands::CommandId;
+
+    let mut engine = GameEngine::new(42);
+    let galaxy = engine.galaxy_mut();
+    galaxy.sector_map_mut().klingons.clear();
+    galaxy.enterprise_mut().set_energy(0.0);
+    galaxy.enterprise_mut().set_shields(0.0);
+    assert_eq!(engine.check_game_over(), None);
+
+    engine.execute(CommandId::SetCourse, &mut PendingInput::new(["1", "1"])).unwrap();
+    assert!(engine.galaxy().is_dead_in_space());
+    assert!(matches!(
+        engine.check_game_over(),
+        Some(GameState::Defeat { reason: DefeatReason::DeadInSpace })
+    ));
+    assert_eq!(engine.game_over_report().unwrap().outcome_label(), "DEAD IN SPACE");
+}
+
+#[test]
+fn game_over_report_describes_the_ending() {
+    let mut engine = GameEngine::new(42);
+    assert_eq!(engine.game_over_report(), None, "No epilogue while playing");
+
+    let initial = engine.galaxy().initial_klingons();
+    engine.galaxy_mut().advance_time(2.0);
+    engine.galaxy_mut().enterprise_mut().set_hull(0.0);
+    engine.check_game_over();
+
+    let report = engine.game_over_report().expect("game is over");
+    assert!(!report.is_victory());
+    assert_eq!(report.outcome_label(), "SHIP DESTROYED");
+    assert_eq!(report.rating, None);
+    assert_eq!(report.klingons_initial, initial);
+    assert_eq!(report.klingons_remaining, initial);
+    assert_eq!(report.klingons_destroyed(), 0);
+    assert_eq!(report.stardates_used, 2.0);
+    assert_eq!(report.stardate, engine.galaxy().stardate());
+    assert_eq!(report.hull, 0.0);
+
+    let mut engine = GameEngine::new(42);
+    engine.galaxy_mut().advance_time(1.0);
+    engine.galaxy_mut().set_total_klingons(0);
+    let Some(GameState::Victory { rating, rank }) = engine.check_game_over() else {
+        panic!("expected victory");
+    };
+    let report = engine.game_over_report().unwrap();
+    assert!(report.is_victory());
+    assert_eq!(report.outcome_label(), "VICTORY");
+    assert_eq!(report.rating, Some(rating));
+    assert_eq!(report.rank, Some(rank));
+    let card = startrek::services::scoring::ScoreCard::tally(engine.galaxy(), rating);
+    assert_eq!(card.score, rating + initial * 10);
+    assert_eq!(rank, card.rank);
+    assert_eq!(report.klingons_destroyed(), initial);
+}
+
+#[test]
+fn tournament_engine_plays_classic_rules_and_seals_results() {
+    let engine = GameEngine::new_tournament(42);
+    assert!(engine.is_tournament());
+    assert_eq!(engine.galaxy().state_digest(), GameEngine::new(42).galaxy().state_digest());
+    assert!(engine.galaxy().rng().log().is_none());
+
+    let seal = engine.tournament_seal(1).expect("tournament games are sealed");
+    assert_eq!(GameEngine::new_tournament(42).tournament_seal(1), Some(seal));
+    assert_ne!(engine.tournament_seal(2), Some(seal), "seal covers the input");
+    assert_ne!(GameEngine::new_tournament(43).tournament_seal(1), Some(seal));
+    assert_eq!(GameEngine::new(42).tournament_seal(1), None);
+}
+
 #[test]
 fn game_state_persists_after_check() {
     let mut engine = GameEngine::new(42);
@@ -129,8 +266,8 @@ fn game_state_persists_after_check() {
     assert!(matches!(state2, Some(GameState::Victory { .. })));
 
     // States should be identical
-    if let (Some(GameState::Victory { rating: r1 }),
-             Some(GameState::Victory { rating: r2 })) = (state1, state2) {
+    if let (Some(GameState::Victory { rating: r1, .. }),
+             Some(GameState::Victory { rating: r2, .. })) = (state1, state2) {
         assert_eq!(r1, r2);
     }
 }
@@ -146,7 +283,7 @@ fn efficiency_rating_calculated() {
     engine.galaxy_mut().set_total_klingons(0);
 
     // Check game over
-    if let Some(GameState::Victory { rating }) = engine.check_game_over() {
+    if let Some(GameState::Victory { rating, .. }) = engine.check_game_over() {
         // Rating should be reasonable (based on initial Klingons / time used)
         // The formula is: (initial_klingons / elapsed_time) * 1000
         // With seed 42 and 1.0 time unit elapsed, rating should be reasonable
@@ -326,10 +463,8 @@ fn quadrant_klingon_tracking() {
 
     // Count Klingons in all quadrants
     let mut quadrant_sum = 0;
-    for quadrant_row in engine.galaxy().quadrants() {
-        for quadrant in quadrant_row {
-            quadrant_sum += quadrant.klingons;
-        }
+    for (_, quadrant) in engine.galaxy().quadrants_iter() {
+        quadrant_sum += quadrant.klingons;
     }
 
     // Total should match sum of all quadrants
@@ -343,12 +478,245 @@ fn starbase_tracking() {
 
     // Count starbases in all quadrants
     let mut quadrant_sum = 0;
-    for quadrant_row in engine.galaxy().quadrants() {
-        for quadrant in quadrant_row {
-            quadrant_sum += quadrant.starbases;
-        }
+    for (_, quadrant) in engine.galaxy().quadrants_iter() {
+        quadrant_sum += quadrant.starbases;
     }
 
     // Total should match sum of all quadrants
     assert_eq!(total_starbases, quadrant_sum);
 }
+
+#[test]
+fn embedded_mods_shape_the_game() {
+    use startrek::models::hooks::{EventKind, ModHooks, ModRegistry};
+    use startrek::models::position::QuadrantPosition;
+    use startrek::models::quadrant::QuadrantData;
+
+    struct NoStarbasesInCorners;
+
+    impl ModHooks for NoStarbasesInCorners {
+        fn adjust_quadrant(&self, pos: QuadrantPosition, _seed: u64, data: &mut QuadrantData) {
+            if (pos.x == 1 || pos.x == 8) && (pos.y == 1 || pos.y == 8) {
+                data.starbases = 0;
+            }
+        }
+
+        fn event_weight(&self, _event: EventKind, _weight: f64) -> f64 {
+            0.0
+        }
+
+        fn adjust_score(&self, rating: i32) -> i32 {
+            rating * 2
+        }
+    }
+
+    let mut mods = ModRegistry::new();
+    mods.register(NoStarbasesInCorners);
+    let mut engine = GameEngine::new_with_mods(42, mods);
+
+    for (x, y) in [(1, 1), (1, 8), (8, 1), (8, 8)] {
+        assert_eq!(engine.galaxy().quadrants()[QuadrantPosition { x, y }].starbases, 0);
+    }
+
+    engine.galaxy_mut().advance_time(1.0);
+    engine.galaxy_mut().set_total_klingons(0);
+    let expected = engine.galaxy().efficiency_rating() * 2;
+    assert!(matches!(
+        engine.check_game_over(),
+        Some(GameState::Victory { rating, .. }) if rating == expected
+    ));
+}
+
+#[test]
+fn step_plays_a_whole_turn() {
+    use startrek::models::navigation_types::{Course, WarpFactor};
+    use startrek::{CommandId, GameEvent, PlayerAction};
+
+    let mut engine = GameEngine::new(42);
+    let events = engine.step(PlayerAction::Command { id: CommandId::LongRangeScan, answers: vec![] }).unwrap();
+    assert!(matches!(events.last(), Some(GameEvent::Turn(summary)) if summary.turn == 1));
+    assert!(!engine.take_events().is_empty());
+
+    // A command that runs out of answers takes no turn
+    let stardate = engine.galaxy().stardate();
+    let result = engine.step(PlayerAction::Command { id: CommandId::SetCourse, answers: vec![] });
+    assert!(result.is_err());
+    assert_eq!(engine.turns(), 1);
+
+    let warp = PlayerAction::Warp { course: Course::new(1.0).unwrap(), warp: WarpFactor::new(1.0).unwrap() };
+    engine.step(warp).unwrap();
+    assert_eq!(engine.turns(), 2);
+    assert!(engine.galaxy().stardate() > stardate);
+}
+
+#[test]
+fn end_turn_counts_turns_and_summarizes_changes() {
+    use startrek::models::turn::TurnSnapshot;
+
+    let mut engine = GameEngine::new(42);
+    assert_eq!(engine.turns(), 0);
+
+    let before = TurnSnapshot::capture(engine.galaxy());
+    engine.galaxy_mut().advance_time(1.0);
+    engine.galaxy_mut().enterprise_mut().subtract_energy(8.0);
+    let summary = engine.end_turn("SET COURSE", &before);
+
+    assert_eq!(engine.turns(), 1);
+    assert_eq!(summary.turn, 1);
+    assert_eq!(summary.command, "SET COURSE");
+    assert_eq!(summary.time_delta, 1.0);
+    assert_eq!(summary.energy_delta, -8.0);
+    assert_eq!(summary.stardate, engine.galaxy().stardate());
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn replay_compatibility_matrix() {
+    use startrek::cli::args::parse_from;
+    use startrek::cli::replay::{Incompatibility, Replay, ReplayHeader, LOGIC_VERSION};
+
+    let args = parse_from(["--scaled-duration", "--mirror", "v"].map(String::from));
+    let current = ReplayHeader::new(7, args.logic_flags(), &args.galaxy_options());
+    let recorded = Replay::parse(
+        &Replay { header: current.clone(), inputs: vec!["1".to_string()], seal: None }.to_text(),
+    )
+    .unwrap()
+    .header;
+
+    // Each drift and the incompatibility it must be refused for, if any
+    type Drift = fn(&mut ReplayHeader);
+    let matrix: [(Drift, Option<&str>); 5] = [
+        (|h| h.version = "0.0.1".to_string(), None),
+        (|h| h.logic_version = LOGIC_VERSION - 1, Some("LogicVersion")),
+        (|h| h.rng_stream = "xorshift".to_string(), Some("RngStream")),
+        (
+            |h| h.ruleset = format!("{:?}", startrek::models::ruleset::Ruleset::default()),
+            Some("Ruleset"),
+        ),
+        (|h| h.config_hash ^= 1, Some("ConfigHash")),
+    ];
+
+    assert_eq!(recorded.check_compatible(&current), Ok(()));
+    for (drift, expected) in matrix {
+        let mut header = recorded.clone();
+        drift(&mut header);
+        let refused = header.check_compatible(&current).err().map(|reason: Incompatibility| {
+            format!("{:?}", reason).split(' ').next().unwrap().to_string()
+        });
+        assert_eq!(refused.as_deref(), expected, "{:?}", header);
+    }
+}
+
+#[test]
+fn preview_matches_the_move_it_describes() {
+    use startrek::io::testing::MockInput;
+    use startrek::io::BufferedOutput;
+    use startrek::models::navigation_types::{Course, WarpFactor};
+    use startrek::services::commands::{self, CommandId};
+    use startrek::services::preview::Command;
+
+    let mut engine = GameEngine::new(42);
+    let draws = engine.galaxy().rng().draws();
+    let report = engine.preview(Command::Warp {
+        course: Course::new(7.0).unwrap(),
+        warp: WarpFactor::new(2.0).unwrap(),
+    });
+    assert_eq!(engine.galaxy().rng().draws(), draws);
+
+    let energy = engine.galaxy().enterprise().energy();
+    let galaxy = engine.galaxy_mut();
+    commands::execute(
+        CommandId::SetCourse,
+        galaxy,
+        0.0,
+        &mut MockInput::new(vec!["7", "2"]),
+        &mut BufferedOutput::default(),
+    )
+    .unwrap();
+    let enterprise = galaxy.enterprise();
+    assert_eq!(report.endpoint, Some((enterprise.quadrant(), enterprise.sector())));
+    assert_eq!(energy - enterprise.energy(), report.energy_cost);
+}
+
+#[test]
+fn engine_events_render_as_the_terminal_text() {
+    use startrek::io::testing::MockInput;
+    use startrek::io::BufferedOutput;
+    use startrek::models::output_event::OutputEvent;
+    use startrek::services::commands::{self, CommandId};
+    use startrek::ui::presenters::EventPresenter;
+
+    let mut engine = GameEngine::new(42);
+    engine.execute(CommandId::SetCourse, &mut MockInput::new(vec!["7"]).answer("WARP", "2")).unwrap();
+    let events = engine.take_events();
+    let quadrant = engine.galaxy().enterprise().quadrant();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, OutputEvent::QuadrantEntered { quadrant: q, .. } if *q == quadrant)));
+    assert!(engine.take_events().is_empty());
+
+    let mut rendered = BufferedOutput::default();
+    EventPresenter::render(&events, &mut rendered);
+    let mut direct = BufferedOutput::default();
+    let mut twin = GameEngine::new(42);
+    commands::execute(CommandId::SetCourse, twin.galaxy_mut(), 500.0, &mut MockInput::new(vec!["7", "2"]), &mut direct)
+        .unwrap();
+    assert_eq!(rendered.take(), direct.take());
+}
+
+#[test]
+fn try_execute_asks_for_answers_without_blocking() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+    use startrek::prelude::*;
+
+    let mut engine = GameEngine::new(42);
+    let digest = engine.galaxy().state_digest();
+    let mut answers = Vec::new();
+    let mut prompts = Vec::new();
+    while let Execution::NeedsInput { prompt } = engine.try_execute(CommandId::SetCourse, &answers).unwrap() {
+        // Nothing sticks until the command has every answer
+        assert_eq!(engine.galaxy().state_digest(), digest);
+        assert!(engine.take_events().is_empty());
+        prompts.push(prompt);
+        answers.push(["7", "2"][answers.len()].to_string());
+    }
+    assert_eq!(prompts.len(), 2);
+    assert!(prompts[0].contains("COURSE"));
+
+    let mut twin = GameEngine::new(42);
+    let mut asked = Vec::new();
+    {
+        let run = pin!(twin.execute_async(CommandId::SetCourse, |prompt| {
+            asked.push(prompt);
+            std::future::ready(["7", "2"][asked.len() - 1].to_string())
+        }));
+        let poll = run.poll(&mut Context::from_waker(Waker::noop()));
+        assert!(matches!(poll, Poll::Ready(Ok(()))));
+    }
+    assert_eq!(asked, prompts);
+    assert_eq!(twin.galaxy().state_digest(), engine.galaxy().state_digest());
+    assert_ne!(engine.galaxy().state_digest(), digest);
+}
+
+#[test]
+fn prelude_covers_a_frontend() {
+    use startrek::prelude::*;
+
+    let mut engine = GameEngine::new(42);
+    let mut output = BufferedOutput::default();
+    let status = StatusReport::from_galaxy(engine.galaxy());
+    assert_eq!(status.klingons_left, engine.galaxy().total_klingons());
+
+    engine.galaxy_mut().enterprise_mut().damage_hull(1000.0);
+    let outcome = match engine.check_game_over() {
+        Some(GameState::Defeat { reason: DefeatReason::ShipDestroyed }) => "destroyed",
+        // GameState and DefeatReason are non_exhaustive outside the crate
+        Some(_) => "other",
+        None => "playing",
+    };
+    assert_eq!(outcome, "destroyed");
+    output.emit(OutputEvent::Message(outcome.to_uppercase()));
+    assert_eq!(output.take(), "DESTROYED\n");
+}
@@ -2,8 +2,13 @@ use proptest::prelude::*;
 use startrek::{GameEngine, GameState};
 use startrek::models::galaxy::Galaxy;
 use startrek::models::quadrant::QuadrantData;
+use startrek::io::testing::MockOutput;
+use startrek::models::constants::SectorContent;
+use startrek::models::klingon::Klingon;
 use startrek::models::position::SectorPosition;
-use startrek::services::combat::calculate_distance;
+use startrek::models::rng::SequenceRng;
+use startrek::models::sector_map::SectorMap;
+use startrek::services::combat::{calculate_distance, enemies_fire};
 
 proptest! {
     /// Property: Total Klingons always equals sum of quadrant Klingons
@@ -11,10 +16,8 @@ proptest! {
     fn klingon_count_invariant(seed in any::<u64>()) {
         let galaxy = Galaxy::new(seed);
 
-        let quadrant_sum: i32 = galaxy.quadrants()
-            .iter()
-            .flatten()
-            .map(|q| q.klingons)
+        let quadrant_sum: i32 = galaxy.quadrants_iter()
+            .map(|(_, q)| q.klingons)
             .sum();
 
         prop_assert_eq!(
@@ -31,10 +34,8 @@ proptest! {
     fn starbase_count_invariant(seed in any::<u64>()) {
         let galaxy = Galaxy::new(seed);
 
-        let quadrant_sum: i32 = galaxy.quadrants()
-            .iter()
-            .flatten()
-            .map(|q| q.starbases)
+        let quadrant_sum: i32 = galaxy.quadrants_iter()
+            .map(|(_, q)| q.starbases)
             .sum();
 
         prop_assert_eq!(
@@ -101,7 +102,7 @@ proptest! {
         starbases in 0i32..2,
         stars in 0i32..10
     ) {
-        let data = QuadrantData { klingons, starbases, stars };
+        let data = QuadrantData { klingons, starbases, stars, commanders: 0, romulans: 0, mines: 0 };
         let encoded = data.encoded();
 
         // Decode by extracting digits
@@ -200,4 +201,26 @@ proptest! {
             "Initial Klingon count should equal total at start"
         );
     }
+
+    /// Property: A Klingon's hit never exceeds twice its shields over its
+    /// distance, whatever it draws. Draws come from a sequence, so a failure
+    /// shrinks to the draws behind it.
+    #[test]
+    fn klingon_hits_are_bounded(draws in prop::collection::vec(0.0f64..1.0, 1..8)) {
+        let mut galaxy = Galaxy::new(42);
+        *galaxy.sector_map_mut() = SectorMap::new();
+        let quadrant = galaxy.enterprise().quadrant();
+        galaxy.enterprise_mut().move_to(quadrant, SectorPosition { x: 4, y: 4 });
+        galaxy.enterprise_mut().set_shields(500.0);
+        galaxy.sector_map_mut().set(SectorPosition { x: 4, y: 4 }, SectorContent::Enterprise);
+        let klingon = Klingon::new(SectorPosition { x: 2, y: 2 });
+        let bound = 2.0 * klingon.shields / calculate_distance(SectorPosition { x: 4, y: 4 }, klingon.sector);
+        galaxy.sector_map_mut().set(klingon.sector, SectorContent::Klingon);
+        galaxy.sector_map_mut().klingons.push(klingon);
+        galaxy.rng_mut().set_source(SequenceRng::new(draws));
+
+        enemies_fire(&mut galaxy, &mut MockOutput::new());
+        let hit = 500.0 - galaxy.enterprise().shields();
+        prop_assert!((0.0..=bound).contains(&hit), "hit {} exceeds {}", hit, bound);
+    }
 }